@@ -1,6 +1,7 @@
 use crate::event::{ClickState, EventPayload, EventProxy, RioEvent, RioEventType};
 use crate::ime::Preedit;
 use crate::renderer::utils::update_colors_based_on_theme;
+use crate::router::alignment::{apply_animation_frame, AnimatedLayout};
 use crate::router::{routes::RoutePath, Router};
 use crate::scheduler::{Scheduler, TimerId, Topic};
 use crate::screen::touch::on_touch;
@@ -14,6 +15,8 @@ use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use raw_window_handle::HasDisplayHandle;
 use rio_backend::clipboard::{Clipboard, ClipboardType};
 use rio_backend::config::colors::{ColorRgb, NamedColor};
+use rio_backend::config::features::Feature;
+use rio_backend::config::watcher::ConfigWatcher;
 use rio_window::application::ApplicationHandler;
 use rio_window::event::{
     ElementState, Ime, MouseButton, MouseScrollDelta, StartCause, TouchPhase, WindowEvent,
@@ -25,8 +28,8 @@ use rio_window::event_loop::{DeviceEvents, EventLoop};
 use rio_window::platform::macos::ActiveEventLoopExtMacOS;
 #[cfg(target_os = "macos")]
 use rio_window::platform::macos::WindowExtMacOS;
+use rio_window::window::CursorIcon;
 use rio_window::window::WindowId;
-use rio_window::window::{CursorIcon, Fullscreen};
 use std::error::Error;
 use std::time::{Duration, Instant};
 
@@ -40,10 +43,19 @@ pub struct Application<'a> {
     /// When true, the next WindowEvent::Focused(true) should trigger align_windows().
     /// This is reset after the focus event is processed.
     keyboard_triggered_focus: bool,
+    /// Windows gliding to their aligned slots, see `window.align-animate`.
+    layout_animation: AnimatedLayout,
+    /// When `layout_animation` last advanced, `None` while it is idle.
+    layout_tick: Option<Instant>,
+    /// Reloads the config on change, watching stops when this is dropped.
+    _config_watcher: Option<ConfigWatcher>,
     #[cfg(feature = "sound-effects")]
     sound_manager: Option<crate::sound::SoundManager>,
 }
 
+/// Time between two frames of the window alignment animation
+const LAYOUT_FRAME: Duration = Duration::from_millis(16);
+
 impl Application<'_> {
     pub fn new<'app>(
         config: rio_backend::config::Config,
@@ -63,10 +75,16 @@ impl Application<'_> {
 
         let proxy = event_loop.create_proxy();
         let event_proxy = EventProxy::new(proxy.clone());
-        let _ = configuration_file_updates(
-            rio_backend::config::config_dir_path(),
+        let config_watcher = match configuration_file_updates(
+            rio_backend::config::config_file_path(),
             event_proxy.clone(),
-        );
+        ) {
+            Ok(watcher) => Some(watcher),
+            Err(err_message) => {
+                tracing::warn!("unable to watch config file {err_message:?}");
+                None
+            }
+        };
         let scheduler = Scheduler::new(proxy);
         event_loop.listen_device_events(DeviceEvents::Never);
 
@@ -75,6 +93,7 @@ impl Application<'_> {
 
         #[cfg(feature = "sound-effects")]
         let sound_manager = Self::build_sound_manager(&config);
+        let layout_animation = AnimatedLayout::new(config.window.align_animate_speed);
 
         Application {
             config,
@@ -83,6 +102,9 @@ impl Application<'_> {
             scheduler,
             app_id,
             keyboard_triggered_focus: false,
+            layout_animation,
+            layout_tick: None,
+            _config_watcher: config_watcher,
             #[cfg(feature = "sound-effects")]
             sound_manager,
         }
@@ -95,7 +117,7 @@ impl Application<'_> {
     fn build_sound_manager(
         config: &rio_backend::config::Config,
     ) -> Option<crate::sound::SoundManager> {
-        if !config.sound_effects.enabled {
+        if !config.feature_enabled(Feature::Sound) {
             return None;
         }
         let config_dir = rio_backend::config::config_dir_path();
@@ -105,9 +127,20 @@ impl Application<'_> {
         }
         crate::sound::SoundManager::new(
             mapping,
+            config.sound_effects.random_events(),
             config.sound_effects.volume,
+            config.sound_effects.pitch_variance,
             config.sound_effects.max_duration,
+            config.sound_effects.max_concurrent,
+            config.sound_effects.cooldowns(),
+            config.sound_effects.devices(),
         )
+        .map(|mut sound_manager| {
+            sound_manager.set_bell_escalation(config.sound_effects.bell_escalation());
+            sound_manager.set_typing_pitch(config.sound_effects.typing_pitch);
+            sound_manager.set_category_volumes(config.sound_effects.category_volumes());
+            sound_manager
+        })
     }
 
     fn skip_window_event(event: &WindowEvent) -> bool {
@@ -163,19 +196,57 @@ impl Application<'_> {
         }
     }
 
-    fn handle_audio_bell(&mut self) {
-        // If sound-effects feature is enabled and a bell sound is
-        // configured, use the SoundManager instead of the platform default.
-        #[cfg(feature = "sound-effects")]
-        {
-            if let Some(ref mut mgr) = self.sound_manager {
-                if mgr.has_sound(rio_backend::event::SoundEvent::Bell) {
-                    mgr.play(rio_backend::event::SoundEvent::Bell);
-                    return;
+    /// Play `sound-effects.bell` when one is configured, returns whether
+    /// it did.
+    #[cfg(feature = "sound-effects")]
+    fn play_bell_sound(&mut self) -> bool {
+        let Some(ref mut mgr) = self.sound_manager else {
+            return false;
+        };
+        if !mgr.has_sound(rio_backend::event::SoundEvent::Bell) {
+            return false;
+        }
+        mgr.play(rio_backend::event::SoundEvent::Bell);
+        true
+    }
+
+    /// Play `sound-effects.process-exit` when a foreground job finished
+    /// since the last check: the one of pane `route_id` of `window_id`
+    /// when given, otherwise the one of any pane.
+    #[cfg(all(feature = "sound-effects", not(target_os = "windows")))]
+    fn play_process_exit_sound(&mut self, pane: Option<(WindowId, usize)>) {
+        let Some(ref mut mgr) = self.sound_manager else {
+            return;
+        };
+        if !mgr.has_sound(rio_backend::event::SoundEvent::ProcessExit) {
+            return;
+        }
+        let finished = match pane {
+            Some((window_id, route_id)) => {
+                self.router.routes.get_mut(&window_id).is_some_and(|route| {
+                    route
+                        .window
+                        .screen
+                        .context_manager
+                        .poll_foreground_exit_of(route_id)
+                })
+            }
+            None => {
+                let mut finished = 0;
+                for route in self.router.routes.values_mut() {
+                    finished +=
+                        route.window.screen.context_manager.poll_foreground_exits();
                 }
+                finished > 0
             }
+        };
+        if finished {
+            mgr.play(rio_backend::event::SoundEvent::ProcessExit);
         }
+    }
 
+    /// Platform beep for `bell.audio`.
+    fn handle_audio_bell(&mut self) {
         #[cfg(target_os = "macos")]
         {
             // Use system bell sound on macOS
@@ -225,29 +296,16 @@ impl Application<'_> {
     /// If `override_focused` is Some, use that as the focused window instead of querying.
     /// Skips alignment when there's only 1 window (leaves it at user's position/size).
     fn align_windows_with(&mut self, override_focused: Option<WindowId>) {
-        // Skip alignment for 0 or 1 window - no need to align a single window
+        // Skip alignment for 0 or 1 window - the one left goes back to
+        // where it was before the layout moved it
         if self.router.window_order.len() < 2 {
+            self.restore_windows();
+            self.write_window_layout(override_focused);
             return;
         }
 
-        let focused_id = match override_focused
-            .or_else(|| self.router.get_focused_route())
-            .or_else(|| self.router.window_order.last().copied())
-        {
-            Some(id) => id,
-            None => return,
-        };
-
-        let screen = match self.router.routes.get(&focused_id) {
-            Some(route) => crate::router::alignment::get_available_screen_area(
-                &route.window.winit_window,
-            ),
-            None => return,
-        };
-
-        let screen = match screen {
-            Some(s) => s,
-            None => return,
+        let Some((focused_id, screen)) = self.layout_target(override_focused) else {
+            return;
         };
 
         crate::router::alignment::apply_layout(
@@ -255,9 +313,83 @@ impl Application<'_> {
             focused_id,
             &self.router.window_order,
             &screen,
-            self.config.window.peek_width,
-            self.config.window.align_gap,
-            self.config.window.align_width,
+            &self.config.window,
+            self.config
+                .window
+                .align_animate
+                .then_some(&mut self.layout_animation),
+        );
+        self.write_window_layout(Some(focused_id));
+    }
+
+    /// Window the layout is centered on and the screen area it fills.
+    fn layout_target(
+        &self,
+        override_focused: Option<WindowId>,
+    ) -> Option<(WindowId, crate::router::alignment::ScreenArea)> {
+        let focused_id = override_focused
+            .or_else(|| self.router.get_focused_route())
+            .or_else(|| self.router.window_order.last().copied())?;
+        let route = self.router.routes.get(&focused_id)?;
+        let screen = crate::router::alignment::get_available_screen_area(
+            &route.window.winit_window,
+        )?;
+        Some((focused_id, screen))
+    }
+
+    /// The layout `align_windows` applies as JSON, see `LayoutSnapshot`.
+    fn window_layout_json(&self, override_focused: Option<WindowId>) -> Option<String> {
+        let (focused_id, screen) = self.layout_target(override_focused)?;
+        let snapshot = crate::router::alignment::LayoutSnapshot::compute(
+            &self.router.routes,
+            focused_id,
+            &self.router.window_order,
+            &screen,
+            &self.config.window,
+        );
+        snapshot
+            .to_json()
+            .map_err(|err| tracing::error!("unable to serialize window layout: {err}"))
+            .ok()
+    }
+
+    /// Write the layout to `layout_snapshot_path`, so scripts can read it
+    /// with `rio --print-layout`.
+    fn write_window_layout(&self, override_focused: Option<WindowId>) {
+        let Some(path) = crate::router::alignment::layout_snapshot_path() else {
+            return;
+        };
+        let Some(json) = self.window_layout_json(override_focused) else {
+            return;
+        };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(&path, json));
+        if let Err(err) = written {
+            tracing::warn!("unable to write window layout to {}: {err}", path.display());
+        }
+    }
+
+    /// Copy the layout `align_windows` applies to the clipboard as JSON,
+    /// the same `rio --print-layout` prints.
+    fn copy_window_layout(&mut self) {
+        if let Some(json) = self.window_layout_json(None) {
+            self.router
+                .clipboard
+                .borrow_mut()
+                .set(ClipboardType::Clipboard, json);
+        }
+    }
+
+    /// Put windows moved by the layout back to their original geometry.
+    fn restore_windows(&mut self) {
+        crate::router::alignment::restore_layout(
+            &mut self.router.routes,
+            self.config
+                .window
+                .align_animate
+                .then_some(&mut self.layout_animation),
         );
     }
 
@@ -294,16 +426,93 @@ impl Application<'_> {
         self.keyboard_triggered_focus = true;
 
         let window_order = self.router.window_order.clone();
-        crate::router::alignment::cycle_focus(
+        let new_focused = crate::router::alignment::cycle_focus(
             &mut self.router.routes,
             &window_order,
             focused_id,
             &screen,
-            self.config.window.peek_width,
-            self.config.window.align_gap,
-            self.config.window.align_width,
+            &self.config.window,
             reverse,
+            self.config
+                .window
+                .align_animate
+                .then_some(&mut self.layout_animation),
         );
+        if new_focused.is_some() {
+            self.write_window_layout(new_focused);
+        }
+    }
+
+    /// Apply every window's opacity for the current window and app focus,
+    /// see `window.unfocused-opacity` and `window.inactive-app-opacity`.
+    /// The app counts as focused while any of its windows is.
+    fn update_window_opacities(&mut self) {
+        let app_focused = self
+            .router
+            .routes
+            .values()
+            .any(|route| route.window.is_focused);
+
+        for route in self.router.routes.values_mut() {
+            let opacity = self
+                .config
+                .window
+                .effective_opacity(route.window.is_focused, app_focused);
+            if !route.window.screen.set_window_opacity(opacity) {
+                continue;
+            }
+
+            #[cfg(target_os = "macos")]
+            {
+                let bg_color = self.config.colors.background.1;
+                route.window.winit_window.set_background_color(
+                    bg_color.r,
+                    bg_color.g,
+                    bg_color.b,
+                    opacity as f64,
+                );
+            }
+            route.request_redraw();
+        }
+    }
+
+    /// Advance the window alignment animation by one frame. Returns when
+    /// the next frame is due, `None` once every window has arrived.
+    fn tick_layout_animation(&mut self) -> Option<Instant> {
+        if !self.layout_animation.is_animating() {
+            self.layout_tick = None;
+            return None;
+        }
+
+        let now = Instant::now();
+        let dt = self.layout_tick.map_or(LAYOUT_FRAME, |last| now - last);
+        self.layout_tick = Some(now);
+
+        let frame = self.layout_animation.tick(dt.as_secs_f32());
+        apply_animation_frame(&mut self.router.routes, &frame);
+
+        if self.layout_animation.is_animating() {
+            Some(now + LAYOUT_FRAME)
+        } else {
+            self.layout_tick = None;
+            None
+        }
+    }
+
+    /// Close leader menus left idle past `leader.timeout-ms`. Returns the
+    /// earliest deadline among the menus still open.
+    fn tick_leader_timeouts(&mut self) -> Option<Instant> {
+        let now = Instant::now();
+        let mut next = None;
+        for route in self.router.routes.values_mut() {
+            let leader_state = &mut route.window.screen.leader_state;
+            if leader_state.close_if_expired(now) {
+                route.request_redraw();
+            } else if let Some(deadline) = leader_state.deadline() {
+                next = Some(next.map_or(deadline, |next: Instant| next.min(deadline)));
+            }
+        }
+        next
     }
 }
 
@@ -362,7 +571,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
         );
 
         // Auto-align the first window too
-        if self.config.window.auto_align {
+        if self.config.feature_enabled(Feature::AutoAlign) {
             self.align_windows_with(Some(new_id));
         }
 
@@ -467,6 +676,11 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
             }
 
             RioEventType::Rio(RioEvent::Wakeup(route_id)) => {
+                // The shell prints its prompt once a job exits, so checking
+                // on output notices the exit right away
+                #[cfg(all(feature = "sound-effects", not(target_os = "windows")))]
+                self.play_process_exit_sound(Some((window_id, route_id)));
+
                 if self.config.renderer.strategy.is_event_based() {
                     if let Some(route) = self.router.routes.get_mut(&window_id) {
                         // Skip rendering for unfocused windows if configured
@@ -559,12 +773,59 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     None
                 };
 
+                // Volume, pitch variance, bell escalation and a lower max
+                // duration can be applied in place, anything else needs the
+                // files decoded again
+                #[cfg(feature = "sound-effects")]
+                let keep_sound_cache = {
+                    let previous = &self.config.sound_effects;
+                    let next = &config.sound_effects;
+                    let mut adjusted = previous.clone();
+                    adjusted.volume = next.volume;
+                    adjusted.keyboard_volume = next.keyboard_volume;
+                    adjusted.navigation_volume = next.navigation_volume;
+                    adjusted.system_volume = next.system_volume;
+                    adjusted.pitch_variance = next.pitch_variance;
+                    adjusted.typing_pitch = next.typing_pitch;
+                    adjusted.max_duration = next.max_duration;
+                    adjusted.bell_escalate = next.bell_escalate;
+                    adjusted.bell_escalate_window_ms = next.bell_escalate_window_ms;
+                    adjusted == *next
+                        && next.max_duration <= previous.max_duration
+                        && self.config.features.sound == config.features.sound
+                };
+
+                let was_aligning = self.config.feature_enabled(Feature::AutoAlign);
                 self.config = config;
+                self.layout_animation
+                    .set_tau(self.config.window.align_animate_speed);
 
                 // Rebuild sound manager on config reload
                 #[cfg(feature = "sound-effects")]
-                {
-                    self.sound_manager = Self::build_sound_manager(&self.config);
+                match self.sound_manager.as_mut() {
+                    Some(sound_manager) if keep_sound_cache => {
+                        sound_manager.set_volume(self.config.sound_effects.volume);
+                        sound_manager
+                            .set_pitch_variance(self.config.sound_effects.pitch_variance);
+                        sound_manager
+                            .set_typing_pitch(self.config.sound_effects.typing_pitch);
+                        sound_manager
+                            .set_max_duration(self.config.sound_effects.max_duration);
+                        sound_manager.set_bell_escalation(
+                            self.config.sound_effects.bell_escalation(),
+                        );
+                        sound_manager.set_category_volumes(
+                            self.config.sound_effects.category_volumes(),
+                        );
+                    }
+                    previous => {
+                        // Keep a runtime mute across the rebuild
+                        let muted = previous.is_some_and(|mgr| mgr.is_muted());
+                        self.sound_manager = Self::build_sound_manager(&self.config);
+                        if let Some(sound_manager) = self.sound_manager.as_mut() {
+                            sound_manager.set_muted(muted);
+                        }
+                    }
                 }
 
                 let mut has_checked_adaptive_colors = false;
@@ -595,6 +856,19 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     );
                     route.window.configure_window(&self.config);
 
+                    // Re-apply the scale in case `window.scale-override` changed
+                    let scale = self
+                        .config
+                        .window
+                        .effective_scale(route.window.winit_window.scale_factor())
+                        as f32;
+                    if scale != route.window.screen.sugarloaf.get_scale() {
+                        route
+                            .window
+                            .screen
+                            .set_scale(scale, route.window.winit_window.inner_size());
+                    }
+
                     if let Some(error) = &config_error {
                         route.report_error(&error.to_owned().into());
                     } else {
@@ -602,9 +876,12 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     }
                 }
 
-                // Re-align windows after config reload
-                if self.config.window.auto_align {
+                // Re-align windows after config reload, or put them back
+                // once auto alignment is turned off
+                if self.config.feature_enabled(Feature::AutoAlign) {
                     self.align_windows();
+                } else if was_aligning {
+                    self.restore_windows();
                 }
             }
             RioEventType::Rio(RioEvent::Exit) => {
@@ -632,7 +909,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                         if self.router.routes.is_empty() {
                             event_loop.exit();
-                        } else if self.config.window.auto_align {
+                        } else if self.config.feature_enabled(Feature::AutoAlign) {
                             self.align_windows();
                         }
                     } else {
@@ -688,8 +965,14 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     self.handle_visual_bell(window_id);
                 }
 
-                // Handle audio bell
-                if self.config.bell.audio {
+                // A configured bell sound always plays, the platform beep
+                // only stands in for it with `bell.audio`
+                #[cfg(feature = "sound-effects")]
+                let played = self.play_bell_sound();
+                #[cfg(not(feature = "sound-effects"))]
+                let played = false;
+
+                if self.config.bell.audio && !played {
                     self.handle_audio_bell();
                 }
             }
@@ -757,6 +1040,11 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
             }
             RioEventType::Rio(RioEvent::UpdateTitles) => {
                 self.router.update_titles();
+
+                // Also check on the title timer, so a job printing nothing
+                // is still seen running before the prompt comes back
+                #[cfg(all(feature = "sound-effects", not(target_os = "windows")))]
+                self.play_process_exit_sound(None);
             }
             RioEventType::Rio(RioEvent::MouseCursorDirty) => {
                 if let Some(route) = self.router.routes.get_mut(&window_id) {
@@ -882,7 +1170,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     self.app_id.as_deref(),
                 );
                 // Auto-align: treat the new window as focused
-                if self.config.window.auto_align {
+                if self.config.feature_enabled(Feature::AutoAlign) {
                     self.align_windows_with(Some(new_id));
                 }
 
@@ -938,7 +1226,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 self.router.remove_window(&window_id);
                 if self.router.routes.is_empty() && !self.config.confirm_before_quit {
                     event_loop.exit();
-                } else if self.config.window.auto_align {
+                } else if self.config.feature_enabled(Feature::AutoAlign) {
                     self.align_windows();
                 }
             }
@@ -984,13 +1272,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
             }
             RioEventType::Rio(RioEvent::ToggleFullScreen) => {
                 if let Some(route) = self.router.routes.get_mut(&window_id) {
-                    match route.window.winit_window.fullscreen() {
-                        None => route
-                            .window
-                            .winit_window
-                            .set_fullscreen(Some(Fullscreen::Borderless(None))),
-                        _ => route.window.winit_window.set_fullscreen(None),
-                    }
+                    route.window.toggle_fullscreen(&self.config);
                 }
             }
             RioEventType::Rio(RioEvent::ColorChange(route_id, index, color)) => {
@@ -1011,17 +1293,31 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 }
             }
             RioEventType::Rio(RioEvent::AlignWindows) => {
-                if self.config.window.auto_align {
+                if self.config.feature_enabled(Feature::AutoAlign) {
+                    self.align_windows();
+                }
+            }
+            RioEventType::Rio(RioEvent::AdjustAlignWidth(delta)) => {
+                // Live only: the next config reload brings back the
+                // configured width. The window order is kept, only the
+                // slot sizes change.
+                let window = &mut self.config.window;
+                window.align_width = window.stepped_align_width(delta);
+                tracing::info!("Align width {}", window.align_width);
+                if self.config.feature_enabled(Feature::AutoAlign) {
                     self.align_windows();
                 }
             }
+            RioEventType::Rio(RioEvent::CopyWindowLayout) => {
+                self.copy_window_layout();
+            }
             RioEventType::Rio(RioEvent::CycleWindowNext) => {
-                if self.config.window.auto_align {
+                if self.config.feature_enabled(Feature::AutoAlign) {
                     self.cycle_window_focus(false);
                 }
             }
             RioEventType::Rio(RioEvent::CycleWindowPrev) => {
-                if self.config.window.auto_align {
+                if self.config.feature_enabled(Feature::AutoAlign) {
                     self.cycle_window_focus(true);
                 }
             }
@@ -1056,6 +1352,32 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                     mgr.play(sound_event);
                 }
             }
+            #[cfg(feature = "sound-effects")]
+            RioEventType::Rio(RioEvent::PlaySoundPanned(sound_event, pan)) => {
+                if let Some(ref mut mgr) = self.sound_manager {
+                    mgr.play_panned(sound_event, pan);
+                }
+            }
+            #[cfg(feature = "sound-effects")]
+            RioEventType::Rio(RioEvent::ToggleMuteSounds) => {
+                if let Some(ref mut mgr) = self.sound_manager {
+                    mgr.set_muted(!mgr.is_muted());
+                    tracing::info!(
+                        "Sound effects {}",
+                        if mgr.is_muted() { "muted" } else { "unmuted" }
+                    );
+                }
+            }
+            RioEventType::Rio(RioEvent::CycleNavigationMode) => {
+                // Live only: the next config reload brings back the
+                // configured mode
+                self.config.navigation.mode = self.config.navigation.mode.next();
+                tracing::info!("Navigation mode {}", self.config.navigation.mode);
+                for route in self.router.routes.values_mut() {
+                    route.update_config(&self.config, &self.router.font_library, false);
+                    route.request_redraw();
+                }
+            }
             _ => {}
         }
     }
@@ -1143,7 +1465,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                 if self.router.routes.is_empty() {
                     event_loop.exit();
-                } else if self.config.window.auto_align {
+                } else if self.config.feature_enabled(Feature::AutoAlign) {
                     self.align_windows();
                 }
             }
@@ -1183,6 +1505,13 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                 match state {
                     ElementState::Pressed => {
+                        // Overlay drag handles take the click before the panes
+                        if button == MouseButton::Left
+                            && route.window.screen.start_overlay_drag()
+                        {
+                            return;
+                        }
+
                         // In case need to switch grid current
                         route.window.screen.select_current_based_on_mouse();
 
@@ -1252,6 +1581,12 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                         route.window.screen.process_mouse_bindings(button);
                     }
                     ElementState::Released => {
+                        if button == MouseButton::Left
+                            && route.window.screen.end_overlay_drag()
+                        {
+                            return;
+                        }
+
                         if !route.window.screen.modifiers.state().shift_key()
                             && route.window.screen.mouse_mode()
                         {
@@ -1299,6 +1634,10 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 let x = position.x;
                 let y = position.y;
 
+                if route.window.screen.drag_overlay(x as f32, y as f32) {
+                    return;
+                }
+
                 let lmb_pressed =
                     route.window.screen.mouse.left_button_state == ElementState::Pressed;
                 let rmb_pressed =
@@ -1337,6 +1676,16 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 route.window.screen.mouse.x = x;
                 route.window.screen.mouse.y = y;
 
+                // Leave focus alone while a button is held, e.g. while
+                // dragging a selection across splits
+                if self.config.window.focus_follows_mouse
+                    && !lmb_pressed
+                    && !rmb_pressed
+                    && route.window.screen.focus_split_under_mouse()
+                {
+                    route.request_redraw();
+                }
+
                 let point = route.window.screen.mouse_position(display_offset);
 
                 let square_changed = old_point != point;
@@ -1554,7 +1903,7 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
 
                 // Auto-align: must return early so the route borrow is released
                 // before we call align_windows which borrows self.router mutably.
-                if has_regained_focus && self.config.window.auto_align {
+                if has_regained_focus && self.config.feature_enabled(Feature::AutoAlign) {
                     // If keyboard_only_focus is enabled, only align when focus was
                     // triggered by keyboard shortcuts (CycleWindowNext/Prev).
                     // The keyboard_triggered_focus flag is set by cycle_window_focus()
@@ -1609,13 +1958,18 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                 }
 
                 route.window.screen.resize(new_size);
+
+                #[cfg(feature = "sound-effects")]
+                if let Some(ref mut mgr) = self.sound_manager {
+                    mgr.play(rio_backend::event::SoundEvent::Resize);
+                }
             }
 
             WindowEvent::ScaleFactorChanged {
                 inner_size_writer: _,
                 scale_factor,
             } => {
-                let scale = scale_factor as f32;
+                let scale = self.config.window.effective_scale(scale_factor) as f32;
                 route
                     .window
                     .screen
@@ -1715,6 +2069,16 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
                         &mut self.scheduler,
                         route.window.screen.ctx().current_route(),
                     );
+                } else if route.window.screen.sugarloaf.has_pending_filters()
+                    || route.window.screen.sugarloaf.is_distortion_animating()
+                {
+                    // Filters load in the background and distortion eases
+                    // in over several frames, keep drawing until both are
+                    // done.
+                    route.schedule_redraw(
+                        &mut self.scheduler,
+                        route.window.screen.ctx().current_route(),
+                    );
                 }
 
                 event_loop.set_control_flow(ControlFlow::Wait);
@@ -1724,7 +2088,20 @@ impl ApplicationHandler<EventPayload> for Application<'_> {
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-        let control_flow = match self.scheduler.update() {
+        // Focus events of the window losing and the one gaining focus come
+        // one after the other, settle opacities once both are handled
+        self.update_window_opacities();
+        let next_layout_frame = self.tick_layout_animation();
+        let next_leader_timeout = self.tick_leader_timeouts();
+        let next_update = [
+            self.scheduler.update(),
+            next_layout_frame,
+            next_leader_timeout,
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        let control_flow = match next_update {
             Some(instant) => ControlFlow::WaitUntil(instant),
             None => ControlFlow::Wait,
         };
@@ -8,6 +8,8 @@ use crate::crosswords::vi_mode::ViMotion;
 use crate::crosswords::Mode;
 use bitflags::bitflags;
 use rio_backend::config::bindings::KeyBinding as ConfigKeyBinding;
+use rio_backend::config::command_overlay::{CommandOverlayRef, CommandOverlayStyle};
+use rio_backend::config::features::Feature;
 use rio_backend::config::keyboard::Keyboard as ConfigKeyboard;
 use rio_window::event::MouseButton;
 use rio_window::keyboard::Key::*;
@@ -208,6 +210,49 @@ impl Program {
     }
 }
 
+/// Actions the command palette offers, by config name with a label. Only
+/// parameterless actions the leader menu can run are listed.
+pub const ACTION_REGISTRY: &[(&str, &str)] = &[
+    ("createwindow", "Create window"),
+    ("createtab", "Create tab"),
+    ("closetab", "Close tab"),
+    ("closesplitortab", "Close split or tab"),
+    ("selectnexttab", "Select next tab"),
+    ("selectprevtab", "Select previous tab"),
+    ("splitright", "Split right"),
+    ("splitdown", "Split down"),
+    ("runselection", "Run selection in a split"),
+    ("selectnextsplit", "Select next split"),
+    ("selectprevsplit", "Select previous split"),
+    ("selectsplitleft", "Select split left"),
+    ("selectsplitright", "Select split right"),
+    ("selectsplitup", "Select split up"),
+    ("selectsplitdown", "Select split down"),
+    ("togglezoom", "Toggle zoom"),
+    ("togglequickterminal", "Toggle quick terminal"),
+    ("togglevimode", "Toggle vi mode"),
+    ("togglefullscreen", "Toggle fullscreen"),
+    ("toggleleadermenu", "Open leader menu"),
+    ("searchforward", "Search forward"),
+    ("searchbackward", "Search backward"),
+    ("copy", "Copy"),
+    ("paste", "Paste"),
+    ("clearhistory", "Clear history"),
+    ("increasefontsize", "Increase font size"),
+    ("decreasefontsize", "Decrease font size"),
+    ("resetfontsize", "Reset font size"),
+    ("openconfigeditor", "Open config editor"),
+    ("increasealignwidth", "Widen focused window"),
+    ("decreasealignwidth", "Narrow focused window"),
+    ("copywindowlayout", "Copy window layout as JSON"),
+    ("alignwindows", "Align windows"),
+    ("cyclewindownext", "Cycle to next window"),
+    ("cyclewindowprev", "Cycle to previous window"),
+    ("togglemutesounds", "Toggle sound effects mute"),
+    ("cyclenavigationmode", "Cycle navigation mode"),
+    ("quit", "Quit"),
+];
+
 impl From<String> for Action {
     fn from(action: String) -> Action {
         let action = action.to_lowercase();
@@ -271,9 +316,16 @@ impl From<String> for Action {
             "cyclewindownext" => Some(Action::CycleWindowNext),
             "cyclewindowprev" => Some(Action::CycleWindowPrev),
             "alignwindows" => Some(Action::AlignWindows),
+            "increasealignwidth" => Some(Action::IncreaseAlignWidth),
+            "decreasealignwidth" => Some(Action::DecreaseAlignWidth),
+            "copywindowlayout" => Some(Action::CopyWindowLayout),
+            "togglemutesounds" => Some(Action::ToggleMuteSounds),
+            "cyclenavigationmode" => Some(Action::CycleNavigationMode),
             "togglevimode" => Some(Action::ToggleViMode),
             "togglefullscreen" => Some(Action::ToggleFullscreen),
             "toggleleadermenu" => Some(Action::ToggleLeaderMenu),
+            "togglecommandpalette" => Some(Action::ToggleCommandPalette),
+            "runselection" => Some(Action::RunSelection),
             "none" => Some(Action::None),
             _ => None,
         };
@@ -324,6 +376,16 @@ impl From<String> for Action {
             }
         }
 
+        // commandoverlay(index or id) — toggle a [[command-overlay]] entry
+        let re = regex::Regex::new(r"^commandoverlay\(([^()]+)\)$").unwrap();
+        if let Some(matched) = re.captures(&action).and_then(|c| c.get(1)) {
+            let target = matched.as_str().trim();
+            return Action::ToggleConfiguredOverlay(match target.parse() {
+                Ok(index) => CommandOverlayRef::Index(index),
+                Err(_) => CommandOverlayRef::Id(target.to_string()),
+            });
+        }
+
         // overlay(command args) — toggle a command output overlay
         let re = regex::Regex::new(r"overlay\(([^()]+)\)").unwrap();
         for capture in re.captures_iter(&action) {
@@ -540,6 +602,9 @@ pub enum Action {
     /// The String is the command to run (e.g., "top", "htop", "git log --oneline").
     ToggleCommandOverlay(String),
 
+    /// Toggle a `[[command-overlay]]` entry by position or id
+    ToggleConfiguredOverlay(CommandOverlayRef),
+
     /// Cycle focus to next window (auto-align)
     CycleWindowNext,
 
@@ -549,12 +614,35 @@ pub enum Action {
     /// Re-align all windows using focus-centered layout
     AlignWindows,
 
+    /// Widen the focused window until the config is reloaded (auto-align)
+    IncreaseAlignWidth,
+
+    /// Narrow the focused window until the config is reloaded (auto-align)
+    DecreaseAlignWidth,
+
+    /// Copy the screen area and window slots of the auto-align layout to
+    /// the clipboard as JSON
+    CopyWindowLayout,
+
+    /// Mute or unmute sound effects without reloading the config
+    ToggleMuteSounds,
+
+    /// Switch to the next navigation mode until the config is reloaded
+    CycleNavigationMode,
+
     /// Allow receiving char input.
     ReceiveChar,
 
     /// Toggle leader menu
     ToggleLeaderMenu,
 
+    /// Toggle the command palette, a searchable list of leader items
+    /// and actions
+    ToggleCommandPalette,
+
+    /// Run the selected text as a command in a new split
+    RunSelection,
+
     /// No action.
     None,
 }
@@ -787,11 +875,17 @@ pub fn default_key_bindings(config: &rio_backend::config::Config) -> Vec<KeyBind
     ));
 
     // Add hint bindings
-    bindings.extend(create_hint_bindings(&config.hints.rules));
+    if config.feature_enabled(Feature::Hints) {
+        bindings.extend(create_hint_bindings(&config.hints.rules));
+    }
+
+    bindings.extend(create_command_overlay_bindings(&config.command_overlay));
 
     // Add leader key binding from config
-    if let Some(leader_binding) = create_leader_binding(&config.leader) {
-        bindings.push(leader_binding);
+    if config.feature_enabled(Feature::Leader) {
+        if let Some(leader_binding) = create_leader_binding(&config.leader) {
+            bindings.push(leader_binding);
+        }
     }
 
     config_key_bindings(config.bindings.keys.to_owned(), bindings)
@@ -1002,6 +1096,29 @@ pub fn config_key_bindings(
     bindings
 }
 
+/// `super+alt+1`…`super+alt+9` toggle the `[[command-overlay]]` entries by
+/// position. `super+1`…`super+9` already select tabs.
+pub fn create_command_overlay_bindings(
+    overlays: &[CommandOverlayStyle],
+) -> Vec<KeyBinding> {
+    overlays
+        .iter()
+        .enumerate()
+        .take(9)
+        .filter(|(_, overlay)| !overlay.command.trim().is_empty())
+        .map(|(index, _)| KeyBinding {
+            trigger: BindingKey::Keycode {
+                key: Key::Character((index + 1).to_string().into()),
+                location: KeyLocation::Standard,
+            },
+            mods: ModifiersState::SUPER | ModifiersState::ALT,
+            mode: BindingMode::empty(),
+            notmode: BindingMode::SEARCH | BindingMode::VI,
+            action: Action::ToggleConfiguredOverlay(CommandOverlayRef::Index(index)),
+        })
+        .collect()
+}
+
 /// Create hint bindings from configuration
 pub fn create_hint_bindings(
     hints_config: &[rio_backend::config::hints::Hint],
@@ -1280,6 +1397,71 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_action_registry_names_parse() {
+        for (index, (name, label)) in ACTION_REGISTRY.iter().enumerate() {
+            assert_ne!(Action::from(name.to_string()), Action::None, "{name}");
+            assert!(!label.is_empty());
+            assert!(
+                ACTION_REGISTRY[..index]
+                    .iter()
+                    .all(|(other, _)| other != name),
+                "{name} is listed twice"
+            );
+        }
+        assert_eq!(
+            Action::from("ToggleCommandPalette".to_string()),
+            Action::ToggleCommandPalette
+        );
+    }
+
+    #[test]
+    fn test_command_overlay_action_from_string() {
+        assert_eq!(
+            Action::from("CommandOverlay(1)".to_string()),
+            Action::ToggleConfiguredOverlay(CommandOverlayRef::Index(1))
+        );
+        assert_eq!(
+            Action::from("CommandOverlay(top)".to_string()),
+            Action::ToggleConfiguredOverlay(CommandOverlayRef::Id("top".to_string()))
+        );
+        // Ad-hoc overlays keep their own action
+        assert_eq!(
+            Action::from("overlay(git log)".to_string()),
+            Action::ToggleCommandOverlay("git log".to_string())
+        );
+    }
+
+    #[test]
+    fn test_command_overlay_bindings() {
+        let overlays = vec![
+            CommandOverlayStyle::default(),
+            CommandOverlayStyle {
+                command: "htop".to_string(),
+                ..CommandOverlayStyle::default()
+            },
+        ];
+        let bindings = create_command_overlay_bindings(&overlays);
+
+        // The style-only entry gets no binding
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(
+            bindings[0].trigger,
+            BindingKey::Keycode {
+                key: Key::Character("2".into()),
+                location: KeyLocation::Standard,
+            }
+        );
+        assert_eq!(
+            bindings[0].mods,
+            ModifiersState::SUPER | ModifiersState::ALT
+        );
+        assert_eq!(
+            bindings[0].action,
+            Action::ToggleConfiguredOverlay(CommandOverlayRef::Index(1))
+        );
+    }
+
     #[test]
     fn binding_matches_itself() {
         let binding = MockBinding::default();
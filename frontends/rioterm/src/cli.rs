@@ -35,6 +35,14 @@ pub struct TerminalOptions {
     #[clap(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
     pub write_config: Option<Option<PathBuf>>,
 
+    /// Prints an example config generated from the built-in defaults.
+    #[clap(long)]
+    pub generate_config: bool,
+
+    /// Prints the window layout last applied by auto alignment as JSON.
+    #[clap(long)]
+    pub print_layout: bool,
+
     /// Writes the logs to a file inside the config directory.
     #[clap(long)]
     pub enable_log_file: bool,
@@ -20,4 +20,7 @@ pub const ADDITIONAL_PADDING_Y_ON_UNIFIED_TITLEBAR: f32 = 2.;
 
 pub const PADDING_Y_BOTTOM_TABS: f32 = 22.0;
 
+/// Width of the LeftTab/RightTab strip
+pub const SIDE_TABS_WIDTH: f32 = 140.0;
+
 pub const BELL_DURATION: Duration = Duration::from_millis(125);
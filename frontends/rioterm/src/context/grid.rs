@@ -1,5 +1,6 @@
 use crate::context::Context;
 use crate::mouse::Mouse;
+use rio_backend::config::command_overlay::CommandOverlayStyle;
 use rio_backend::crosswords::grid::Dimensions;
 use rio_backend::event::EventListener;
 use rio_backend::sugarloaf::{
@@ -76,6 +77,7 @@ fn create_border(color: [f32; 4], position: [f32; 2], size: [f32; 2]) -> Object
         border_width: 0.0,
         border_radius: [0.0, 0.0, 0.0, 0.0],
         size,
+        gradient_color: [0.0, 0.0, 0.0, 0.0],
     })
 }
 
@@ -108,6 +110,17 @@ pub struct CommandOverlayBounds {
     pub height: f32,
 }
 
+impl From<&CommandOverlayStyle> for CommandOverlayBounds {
+    fn from(style: &CommandOverlayStyle) -> Self {
+        Self {
+            x: style.x,
+            y: style.y,
+            width: style.width,
+            height: style.height,
+        }
+    }
+}
+
 impl Default for CommandOverlayBounds {
     fn default() -> Self {
         Self {
@@ -119,6 +132,16 @@ impl Default for CommandOverlayBounds {
     }
 }
 
+/// A command overlay being moved by its drag handle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OverlayDrag {
+    /// Index in `command_overlays`
+    index: usize,
+    /// Pointer offset from the overlay's top-left corner, as fractions of
+    /// the window width/height
+    grab: (f32, f32),
+}
+
 /// State for a command output overlay — a real PTY running a specific command,
 /// rendered as a floating panel on top of terminal content. Always click-through
 /// (keyboard input stays on the underlying pane). Auto-dismisses on process exit.
@@ -130,6 +153,10 @@ pub struct CommandOverlayState<T: EventListener> {
     pub visible: bool,
     /// The command string that was spawned (used as an identifier for toggle)
     pub command: String,
+    /// Index in `[[command-overlay]]`, `None` for ad-hoc overlays
+    pub slot: Option<usize>,
+    /// Appearance of this overlay panel
+    pub style: CommandOverlayStyle,
     /// Fractional bounds within the window
     pub bounds: CommandOverlayBounds,
 }
@@ -148,10 +175,11 @@ pub struct ContextGrid<T: EventListener> {
     pub zoomed_key: Option<usize>,
     /// Quick terminal state (overlay terminal at bottom)
     pub quick_terminal: Option<QuickTerminalState<T>>,
-    /// Command output overlays (floating, click-through, real PTY)
+    /// Command output overlays (floating, click-through, real PTY), in
+    /// draw order: configured overlays by slot, then ad-hoc ones
     pub command_overlays: Vec<CommandOverlayState<T>>,
-    /// Appearance config for command overlay panels
-    pub command_overlay_style: rio_backend::config::command_overlay::CommandOverlayStyle,
+    /// Overlay currently moved by its drag handle
+    overlay_drag: Option<OverlayDrag>,
 }
 
 pub struct ContextGridItem<T: EventListener> {
@@ -209,13 +237,65 @@ impl<T: rio_backend::event::EventListener> ContextGridItem<T> {
     }
 }
 
+/// Where an overlay with `slot` goes in the draw order of overlays with
+/// `slots`: configured overlays sorted by slot, ad-hoc ones (`None`) last in
+/// the order they were opened.
+fn overlay_insert_index(
+    slots: impl Iterator<Item = Option<usize>>,
+    slot: Option<usize>,
+) -> usize {
+    let key = |slot: Option<usize>| slot.unwrap_or(usize::MAX);
+    let mut index = 0;
+    for (position, existing) in slots.enumerate() {
+        if key(existing) <= key(slot) {
+            index = position + 1;
+        }
+    }
+    index
+}
+
+/// Position of the terminal content of an overlay at `bounds` in a
+/// `width` x `height` grid, below a drag handle `handle` scaled pixels high.
+#[inline]
+fn overlay_content_position(
+    bounds: &CommandOverlayBounds,
+    handle: f32,
+    width: f32,
+    height: f32,
+    scale: f32,
+) -> [f32; 2] {
+    [
+        (width * bounds.x) / scale,
+        (height * bounds.y) / scale + handle,
+    ]
+}
+
+/// Whether the physical point `(x, y)` is on the drag handle, `handle_height`
+/// physical pixels high, of an overlay at `bounds` in a `width` x `height`
+/// grid.
+fn in_drag_handle(
+    bounds: &CommandOverlayBounds,
+    handle_height: f32,
+    width: f32,
+    height: f32,
+    (x, y): (f32, f32),
+) -> bool {
+    let left = width * bounds.x;
+    let top = height * bounds.y;
+    x >= left && x < left + width * bounds.width && y >= top && y < top + handle_height
+}
+
+/// Fractional position for an overlay of the size of `bounds` dragged to
+/// `(x, y)`, kept inside the window.
+fn clamp_overlay_position(bounds: &CommandOverlayBounds, x: f32, y: f32) -> (f32, f32) {
+    (
+        x.clamp(0.0, (1.0 - bounds.width).max(0.0)),
+        y.clamp(0.0, (1.0 - bounds.height).max(0.0)),
+    )
+}
+
 impl<T: rio_backend::event::EventListener> ContextGrid<T> {
-    pub fn new(
-        context: Context<T>,
-        margin: Delta<f32>,
-        border_color: [f32; 4],
-        command_overlay_style: rio_backend::config::command_overlay::CommandOverlayStyle,
-    ) -> Self {
+    pub fn new(context: Context<T>, margin: Delta<f32>, border_color: [f32; 4]) -> Self {
         let width = context.dimension.width;
         let height = context.dimension.height;
         let scale = context.dimension.dimension.scale;
@@ -235,7 +315,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
             zoomed_key: None,
             quick_terminal: None,
             command_overlays: Vec::new(),
-            command_overlay_style,
+            overlay_drag: None,
         };
         grid.calculate_positions_for_affected_nodes(&[root_key]);
         grid
@@ -450,21 +530,29 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         &mut self,
         context: Context<T>,
         command: String,
-        bounds: CommandOverlayBounds,
+        slot: Option<usize>,
+        style: CommandOverlayStyle,
     ) {
+        let bounds = CommandOverlayBounds::from(&style);
         let mut item = ContextGridItem::new(context);
 
-        // Compute pixel position and size from fractional bounds
+        // Compute pixel position and size from fractional bounds, leaving
+        // room for the drag handle on top
         let scale = item.val.dimension.dimension.scale;
+        let handle = style.drag_handle();
         let pixel_width = self.width * bounds.width;
-        let pixel_height = self.height * bounds.height;
+        let pixel_height = (self.height * bounds.height - handle * scale).max(0.0);
 
         item.val.dimension.update_width(pixel_width);
         item.val.dimension.update_height(pixel_height);
 
-        let pos_x = (self.width * bounds.x) / scale;
-        let pos_y = (self.height * bounds.y) / scale;
-        item.set_position([pos_x, pos_y]);
+        item.set_position(overlay_content_position(
+            &bounds,
+            handle,
+            self.width,
+            self.height,
+            scale,
+        ));
 
         // Resize PTY to match overlay dimensions
         let mut terminal = item.val.terminal.lock();
@@ -476,22 +564,34 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         // NOTE: We do NOT change self.current — the overlay is click-through,
         // so keyboard input stays on whatever pane was focused before.
 
-        self.command_overlays.push(CommandOverlayState {
-            item,
-            visible: true,
-            command,
-            bounds,
-        });
+        // Keep draw order: configured overlays by slot, ad-hoc ones last
+        self.overlay_drag = None;
+        let index = overlay_insert_index(
+            self.command_overlays.iter().map(|overlay| overlay.slot),
+            slot,
+        );
+        self.command_overlays.insert(
+            index,
+            CommandOverlayState {
+                item,
+                visible: true,
+                command,
+                slot,
+                style,
+                bounds,
+            },
+        );
     }
 
-    /// Toggle a command overlay by command string.
+    /// Toggle a command overlay, configured ones by `slot` and ad-hoc ones
+    /// by command string.
     /// Returns `true` if a new context needs to be created (no existing overlay
-    /// matches this command). Returns `false` if an existing overlay was toggled.
-    pub fn toggle_command_overlay(&mut self, command: &str) -> bool {
+    /// matches). Returns `false` if an existing overlay was toggled.
+    pub fn toggle_command_overlay(&mut self, command: &str, slot: Option<usize>) -> bool {
         if let Some(overlay) = self
             .command_overlays
             .iter_mut()
-            .find(|o| o.command == command)
+            .find(|o| o.slot == slot && (slot.is_some() || o.command == command))
         {
             overlay.visible = !overlay.visible;
             false
@@ -507,7 +607,70 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         let before = self.command_overlays.len();
         self.command_overlays
             .retain(|o| o.item.val.route_id != route_id);
-        self.command_overlays.len() < before
+        let dismissed = self.command_overlays.len() < before;
+        if dismissed {
+            self.overlay_drag = None;
+        }
+        dismissed
+    }
+
+    /// Start dragging the topmost visible overlay whose drag handle is
+    /// under the physical point `(x, y)`. Returns whether one was grabbed.
+    pub fn start_overlay_drag(&mut self, x: f32, y: f32) -> bool {
+        let (width, height) = (self.width, self.height);
+        self.overlay_drag = self
+            .command_overlays
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, overlay)| {
+                let scale = overlay.item.val.dimension.dimension.scale;
+                overlay.visible
+                    && in_drag_handle(
+                        &overlay.bounds,
+                        overlay.style.drag_handle() * scale,
+                        width,
+                        height,
+                        (x, y),
+                    )
+            })
+            .map(|(index, overlay)| OverlayDrag {
+                index,
+                grab: (x / width - overlay.bounds.x, y / height - overlay.bounds.y),
+            });
+        self.overlay_drag.is_some()
+    }
+
+    /// Move the dragged overlay so the grabbed point follows the physical
+    /// point `(x, y)`. Returns the overlay slot and its new fractional
+    /// position, `None` when no overlay is being dragged.
+    pub fn drag_overlay(&mut self, x: f32, y: f32) -> Option<(Option<usize>, f32, f32)> {
+        let drag = self.overlay_drag?;
+        let overlay = self.command_overlays.get_mut(drag.index)?;
+        let (new_x, new_y) = clamp_overlay_position(
+            &overlay.bounds,
+            x / self.width - drag.grab.0,
+            y / self.height - drag.grab.1,
+        );
+        overlay.bounds.x = new_x;
+        overlay.bounds.y = new_y;
+        overlay.style.x = new_x;
+        overlay.style.y = new_y;
+
+        let scale = overlay.item.val.dimension.dimension.scale;
+        overlay.item.set_position(overlay_content_position(
+            &overlay.bounds,
+            overlay.style.drag_handle(),
+            self.width,
+            self.height,
+            scale,
+        ));
+        Some((overlay.slot, new_x, new_y))
+    }
+
+    /// Stop dragging. Returns whether an overlay was being dragged.
+    pub fn end_overlay_drag(&mut self) -> bool {
+        self.overlay_drag.take().is_some()
     }
 
     /// Get all keys in the order they appear in the grid (depth-first traversal)
@@ -804,6 +967,26 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         }
     }
 
+    /// Where the current pane sits horizontally, from -1.0 when centered
+    /// on the left edge of the grid to 1.0 on the right edge. Used to pan
+    /// split sounds.
+    pub fn current_pan(&self) -> f32 {
+        let Some(item) = self.inner.get(&self.current) else {
+            return 0.0;
+        };
+        let scale = item.val.dimension.dimension.scale;
+        if self.inner.len() <= 1 || self.width <= 0.0 || scale <= 0.0 {
+            return 0.0;
+        }
+        let center =
+            item.position()[0] - self.margin.x + item.val.dimension.width / scale / 2.0;
+        let width = self.width / scale - self.margin.x;
+        if width <= 0.0 {
+            return 0.0;
+        }
+        (center / width * 2.0 - 1.0).clamp(-1.0, 1.0)
+    }
+
     #[inline]
     pub fn extend_with_objects(
         &self,
@@ -873,10 +1056,13 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                 continue;
             }
             let scale = overlay.item.val.dimension.dimension.scale;
+            let style = &overlay.style;
+            let handle = style.drag_handle();
+            // The panel starts above the content, at the drag handle
             let pos = overlay.item.position();
+            let panel_pos = [pos[0], pos[1] - handle];
             let overlay_w = overlay.item.val.dimension.width / scale;
-            let overlay_h = overlay.item.val.dimension.height / scale;
-            let style = &self.command_overlay_style;
+            let overlay_h = overlay.item.val.dimension.height / scale + handle;
 
             // Background color for the overlay quad. The opacity only
             // affects the panel background — program content (ANSI
@@ -900,7 +1086,7 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
 
             // Opaque background quad with rounded corners
             target.push(Object::Quad(Quad {
-                position: pos,
+                position: panel_pos,
                 color: bg,
                 size: [overlay_w, overlay_h],
                 border_radius: [style.border_radius; 4],
@@ -909,8 +1095,21 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                 shadow_color: style.shadow_color,
                 shadow_offset: style.shadow_offset,
                 shadow_blur_radius: style.shadow_blur_radius,
+                gradient_color: [0.0, 0.0, 0.0, 0.0],
             }));
 
+            // Drag handle strip, rounded only at the top corners
+            if handle > 0.0 {
+                let radius = style.border_radius;
+                target.push(Object::Quad(Quad {
+                    position: panel_pos,
+                    color: bc,
+                    size: [overlay_w, handle],
+                    border_radius: [radius, radius, 0.0, 0.0],
+                    ..Quad::default()
+                }));
+            }
+
             // RichText content (terminal output from PTY)
             target.push(overlay.item.rich_text_object.clone());
         }
@@ -1023,13 +1222,23 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
 
     #[inline]
     pub fn select_current_based_on_mouse(&mut self, mouse: &Mouse) -> bool {
+        if let Some(new_current) = self.split_under_mouse(mouse) {
+            self.current = new_current;
+            return true;
+        }
+
+        false
+    }
+
+    /// Key of the split whose area contains the mouse, `None` outside of
+    /// every split or when there is only one
+    pub fn split_under_mouse(&self, mouse: &Mouse) -> Option<usize> {
         let len = self.inner.len();
         if len <= 1 {
-            return false;
+            return None;
         }
 
         let objects = self.objects();
-        let mut select_new_current = None;
         for obj in objects {
             if let Object::RichText(rich_text_obj) = obj {
                 if let Some(key) = self.find_by_rich_text_id(rich_text_obj.id) {
@@ -1046,20 +1255,14 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
                                 <= (scaled_position_y + item.val.dimension.height)
                                     as usize
                         {
-                            select_new_current = Some(key);
-                            break;
+                            return Some(key);
                         }
                     }
                 }
             }
         }
 
-        if let Some(new_current) = select_new_current {
-            self.current = new_current;
-            return true;
-        }
-
-        false
+        None
     }
 
     pub fn find_by_rich_text_id(&self, searched_rich_text_id: usize) -> Option<usize> {
@@ -1241,15 +1444,20 @@ impl<T: rio_backend::event::EventListener> ContextGrid<T> {
         for overlay in &mut self.command_overlays {
             let scale = overlay.item.val.dimension.dimension.scale;
             let bounds = &overlay.bounds;
+            let handle = overlay.style.drag_handle();
 
             let pixel_width = self.width * bounds.width;
-            let pixel_height = self.height * bounds.height;
+            let pixel_height = (self.height * bounds.height - handle * scale).max(0.0);
             overlay.item.val.dimension.update_width(pixel_width);
             overlay.item.val.dimension.update_height(pixel_height);
 
-            let pos_x = (self.width * bounds.x) / scale;
-            let pos_y = (self.height * bounds.y) / scale;
-            overlay.item.set_position([pos_x, pos_y]);
+            overlay.item.set_position(overlay_content_position(
+                bounds,
+                handle,
+                self.width,
+                self.height,
+                scale,
+            ));
 
             let mut terminal = overlay.item.val.terminal.lock();
             terminal.resize::<ContextDimension>(overlay.item.val.dimension);
@@ -2605,6 +2813,60 @@ pub mod test {
     use pretty_assertions::assert_eq;
     use rio_window::window::WindowId;
 
+    #[test]
+    fn test_overlay_insert_index() {
+        let slots = [Some(0), Some(2), None, None];
+        assert_eq!(overlay_insert_index(slots.into_iter(), Some(1)), 1);
+        assert_eq!(overlay_insert_index(slots.into_iter(), Some(3)), 2);
+        assert_eq!(overlay_insert_index(slots.into_iter(), Some(0)), 1);
+        // Ad-hoc overlays go last
+        assert_eq!(overlay_insert_index(slots.into_iter(), None), 4);
+        assert_eq!(overlay_insert_index(std::iter::empty(), Some(4)), 0);
+    }
+
+    #[test]
+    fn test_overlay_drag_handle_hit() {
+        let bounds = CommandOverlayBounds {
+            x: 0.5,
+            y: 0.1,
+            width: 0.25,
+            height: 0.5,
+        };
+        // Handle spans x 500..750 and y 100..140 in a 1000x1000 grid
+        let hit = |point| in_drag_handle(&bounds, 40.0, 1000.0, 1000.0, point);
+        assert!(hit((500.0, 100.0)));
+        assert!(hit((749.0, 139.0)));
+        assert!(!hit((499.0, 120.0)));
+        assert!(!hit((600.0, 140.0)));
+        assert!(!hit((600.0, 99.0)));
+        // Overlays without a handle can't be grabbed
+        assert!(!in_drag_handle(
+            &bounds,
+            0.0,
+            1000.0,
+            1000.0,
+            (600.0, 100.0)
+        ));
+
+        assert_eq!(
+            overlay_content_position(&bounds, 20.0, 1000.0, 1000.0, 2.0),
+            [250.0, 70.0]
+        );
+    }
+
+    #[test]
+    fn test_clamp_overlay_position() {
+        let bounds = CommandOverlayBounds {
+            x: 0.5,
+            y: 0.1,
+            width: 0.25,
+            height: 0.5,
+        };
+        assert_eq!(clamp_overlay_position(&bounds, 0.2, 0.3), (0.2, 0.3));
+        assert_eq!(clamp_overlay_position(&bounds, -0.1, 0.9), (0.0, 0.5));
+        assert_eq!(clamp_overlay_position(&bounds, 0.9, -1.0), (0.75, 0.0));
+    }
+
     #[test]
     fn test_compute() {
         // (1000. / ((74. / 2.)=37.))
@@ -2671,12 +2933,7 @@ pub mod test {
         let context_width = context.dimension.width;
         let context_height = context.dimension.height;
         let context_margin = context.dimension.margin;
-        let grid = ContextGrid::<VoidListener>::new(
-            context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let grid = ContextGrid::<VoidListener>::new(context, margin, [0., 0., 0., 0.]);
         // The first context should fill completely w/h grid
         assert_eq!(grid.width, context_width);
         assert_eq!(grid.height, context_height);
@@ -2744,12 +3001,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -2873,12 +3126,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3098,12 +3347,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3279,12 +3524,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3407,12 +3648,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 1., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 1., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3564,12 +3801,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3774,12 +4007,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 1., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 1., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -3916,12 +4145,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4020,12 +4245,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4112,12 +4333,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4215,12 +4432,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4325,12 +4538,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4417,12 +4626,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4520,12 +4725,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4682,12 +4883,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -4896,12 +5093,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -5206,12 +5399,8 @@ pub mod test {
             )
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [0., 0., 0., 0.]);
 
         assert_eq!(
             grid.objects(),
@@ -5225,6 +5414,7 @@ pub mod test {
         grid.select_current_based_on_mouse(&mouse);
         // On first should always return first item
         assert_eq!(grid.current_index(), 0);
+        assert_eq!(grid.split_under_mouse(&mouse), None);
 
         grid.split_down(second_context);
 
@@ -5266,10 +5456,13 @@ pub mod test {
         assert_eq!(grid.current().rich_text_id, second_context_id);
 
         mouse.x = 304;
+        let third_key = grid.split_under_mouse(&mouse);
+        assert!(third_key.is_some_and(|key| key != grid.current));
         grid.select_current_based_on_mouse(&mouse);
 
         assert_eq!(grid.current_index(), 2);
         assert_eq!(grid.current().rich_text_id, third_context_id);
+        assert_eq!(third_key, Some(grid.current));
     }
 
     #[test]
@@ -5279,12 +5472,8 @@ pub mod test {
         let context =
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context, margin, [0., 0., 0., 0.]);
 
         // Test that we can't remove the last context
         assert_eq!(grid.len(), 1);
@@ -5347,7 +5536,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add multiple splits to create a complex structure
@@ -5398,12 +5586,8 @@ pub mod test {
         let context =
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context, margin, [0., 0., 0., 0.]);
 
         // These operations should not crash
         grid.resize(0.0, 0.0);
@@ -5432,7 +5616,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add a split
@@ -5463,12 +5646,8 @@ pub mod test {
         let context =
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context,
-            margin,
-            [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context, margin, [0., 0., 0., 0.]);
 
         // Test navigation with single context
         grid.select_next_split();
@@ -5500,7 +5679,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Create many splits
@@ -5561,7 +5739,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add a split
@@ -5637,7 +5814,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Single split - should return false
@@ -5682,7 +5858,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add a split down
@@ -5723,7 +5898,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Single split - should return false
@@ -5791,7 +5965,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add a split right
@@ -5856,7 +6029,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add splits
@@ -5898,7 +6070,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Create a complex layout: split right, then split down on the right side
@@ -5943,7 +6114,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Test with zero amount
@@ -5982,7 +6152,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // With only one split, no divider movement should work
@@ -6029,7 +6198,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Create multiple splits
@@ -6088,7 +6256,6 @@ pub mod test {
             create_mock_context(VoidListener {}, WindowId::from(0), 0, context_dimension),
             margin,
             [0., 0., 0., 0.],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Add a horizontal split
@@ -6170,12 +6337,8 @@ pub mod test {
             bottom_y: 30.0,
         };
 
-        let grid = ContextGrid::<VoidListener>::new(
-            context,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let grid =
+            ContextGrid::<VoidListener>::new(context, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Single context should be positioned at margin
         assert_eq!(
@@ -6231,12 +6394,8 @@ pub mod test {
             bottom_y: 0.0,
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1.0, 1.0, 1.0, 1.0]);
         grid.split_right(second_context);
 
         // First context should remain at origin
@@ -6306,12 +6465,8 @@ pub mod test {
             bottom_y: 0.0,
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1.0, 1.0, 1.0, 1.0]);
         grid.split_down(second_context);
 
         // First context should remain at origin
@@ -6380,12 +6535,8 @@ pub mod test {
             bottom_y: 0.0,
         };
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            first_context,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(first_context, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Create layout:
         // [0] [1]
@@ -6453,7 +6604,6 @@ pub mod test {
             context,
             Delta::default(),
             [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
 
         // Verify scaled_padding is correctly calculated and stored
@@ -6489,7 +6639,6 @@ pub mod test {
             first_context,
             Delta::default(),
             [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
         grid.split_right(second_context);
 
@@ -6540,7 +6689,6 @@ pub mod test {
             first_context,
             Delta::default(),
             [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
         grid.split_down(second_context);
 
@@ -6593,7 +6741,6 @@ pub mod test {
             first_context,
             Delta::default(),
             [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
         grid.split_right(second_context);
 
@@ -6644,7 +6791,6 @@ pub mod test {
             first_context,
             Delta::default(),
             [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
         );
         grid.split_down(second_context);
 
@@ -6694,12 +6840,8 @@ pub mod test {
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
         // Build layout: |1|2/3|
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Split right to get |1|2|
         grid.split_right(context2);
@@ -6806,12 +6948,8 @@ pub mod test {
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
         // Build layout: |1|2/3|
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
         grid.split_right(context2);
         grid.split_down(context3);
 
@@ -6873,12 +7011,8 @@ pub mod test {
         let context2 =
             create_mock_context(VoidListener, WindowId::from(1), 2, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
         grid.split_right(context2);
 
         // Try to move divider by a large amount that would violate minimum width
@@ -6922,12 +7056,8 @@ pub mod test {
 
         let context1 =
             create_mock_context(VoidListener, WindowId::from(0), 1, context_dimension);
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Should not be able to move dividers with only one panel
         assert!(!grid.move_divider_left(50.0));
@@ -6957,12 +7087,8 @@ pub mod test {
         let context2 =
             create_mock_context(VoidListener, WindowId::from(1), 2, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
         grid.split_down(context2);
 
         let ordered_keys = grid.get_ordered_keys();
@@ -7033,12 +7159,8 @@ pub mod test {
         let context3 =
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Step 1: Split right to create |1|2|
         grid.split_right(context2);
@@ -7188,12 +7310,7 @@ pub mod test {
             ),
         );
 
-        let mut grid = ContextGrid::new(
-            context,
-            Delta::default(),
-            [0.0, 0.0, 0.0, 0.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid = ContextGrid::new(context, Delta::default(), [0.0, 0.0, 0.0, 0.0]);
         let root_key = grid.root.unwrap();
 
         // Verify root has no parent
@@ -7258,12 +7375,7 @@ pub mod test {
             ),
         );
 
-        let mut grid = ContextGrid::new(
-            context,
-            Delta::default(),
-            [0.0, 0.0, 0.0, 0.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid = ContextGrid::new(context, Delta::default(), [0.0, 0.0, 0.0, 0.0]);
         let panel1_key = grid.root.unwrap();
 
         // Create |1|2| layout
@@ -7342,12 +7454,7 @@ pub mod test {
             ),
         );
 
-        let mut grid = ContextGrid::new(
-            context,
-            Delta::default(),
-            [0.0, 0.0, 0.0, 0.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid = ContextGrid::new(context, Delta::default(), [0.0, 0.0, 0.0, 0.0]);
         let panel1_key = grid.root.unwrap();
 
         // Create |1|2|3| layout
@@ -7428,12 +7535,7 @@ pub mod test {
             ),
         );
 
-        let mut grid = ContextGrid::new(
-            context,
-            Delta::default(),
-            [0.0, 0.0, 0.0, 0.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid = ContextGrid::new(context, Delta::default(), [0.0, 0.0, 0.0, 0.0]);
         let root_key = grid.root.unwrap();
 
         // Verify root has no parent
@@ -7494,12 +7596,8 @@ pub mod test {
         let context3 =
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Build layout: |1|2/3|
         grid.split_right(context2);
@@ -7599,12 +7697,8 @@ pub mod test {
         let context3 =
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Build layout: |1|2/3|
         grid.split_right(context2);
@@ -7694,12 +7788,8 @@ pub mod test {
         let context4 =
             create_mock_context(VoidListener, WindowId::from(3), 4, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Build layout: |1|2/3|4|
         grid.split_right(context2); // |1|2|
@@ -7777,12 +7867,8 @@ pub mod test {
         let context3 =
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Build layout: |1|2/3|
         grid.split_right(context2);
@@ -7838,12 +7924,8 @@ pub mod test {
         let context3 =
             create_mock_context(VoidListener, WindowId::from(2), 3, context_dimension);
 
-        let mut grid = ContextGrid::<VoidListener>::new(
-            context1,
-            margin,
-            [1.0, 1.0, 1.0, 1.0],
-            rio_backend::config::command_overlay::CommandOverlayStyle::default(),
-        );
+        let mut grid =
+            ContextGrid::<VoidListener>::new(context1, margin, [1.0, 1.0, 1.0, 1.0]);
 
         // Build layout: |1|2/3|
         grid.split_right(context2); // |1|2|
@@ -5,7 +5,8 @@ pub mod title;
 use crate::ansi::CursorShape;
 use crate::context::grid::{ContextDimension, ContextGrid, ContextGridItem, Delta};
 use crate::context::title::{
-    create_title_extra_from_context, update_title, ContextManagerTitles,
+    create_title_extra_from_context, tab_title_context, update_title,
+    ContextManagerTitles,
 };
 use crate::event::sync::FairMutex;
 use crate::event::{Msg, RioEvent};
@@ -14,6 +15,8 @@ use crate::messenger::Messenger;
 use crate::performer::{self, Machine};
 use renderable::Cursor;
 use renderable::RenderableContent;
+use rio_backend::config::command_overlay::{ad_hoc_style, CommandOverlayStyle};
+use rio_backend::config::navigation::Navigation;
 use rio_backend::config::Shell;
 use smallvec::{smallvec, SmallVec};
 
@@ -49,6 +52,9 @@ pub struct Context<T: EventListener> {
     pub main_fd: Arc<i32>,
     #[cfg(not(target_os = "windows"))]
     pub shell_pid: u32,
+    /// Foreground process group seen by the last `poll_foreground_exit`
+    #[cfg(not(target_os = "windows"))]
+    foreground_pid: u32,
     pub rich_text_id: usize,
     pub dimension: ContextDimension,
     pub ime: Ime,
@@ -108,6 +114,31 @@ impl<T: EventListener> Context<T> {
             is_ime_enabled: false,
         }
     }
+
+    /// Whether the foreground job finished since the last call, see
+    /// `job_finished`.
+    #[cfg(not(target_os = "windows"))]
+    pub fn poll_foreground_exit(&mut self) -> bool {
+        let pid = teletypewriter::foreground_pid(*self.main_fd, self.shell_pid);
+        record_foreground(&mut self.foreground_pid, pid, self.shell_pid)
+    }
+}
+
+/// A job other than the shell held the terminal and the shell has it
+/// back, so the job exited (or was suspended).
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+#[inline]
+fn job_finished(previous_pid: u32, current_pid: u32, shell_pid: u32) -> bool {
+    previous_pid != shell_pid && current_pid == shell_pid
+}
+
+/// Store `pid` as the foreground process in `last_pid`, returns whether
+/// a job finished since the previous one was stored.
+#[cfg_attr(target_os = "windows", allow(dead_code))]
+#[inline]
+fn record_foreground(last_pid: &mut u32, pid: u32, shell_pid: u32) -> bool {
+    let previous = std::mem::replace(last_pid, pid);
+    job_finished(previous, pid, shell_pid)
 }
 
 #[derive(Clone, Default)]
@@ -122,8 +153,10 @@ pub struct ContextManagerConfig {
     pub should_update_title_extra: bool,
     pub split_color: [f32; 4],
     pub title: rio_backend::config::title::Title,
+    /// See `navigation.tab-title-format`
+    pub tab_title_format: Option<String>,
     pub keyboard: rio_backend::config::keyboard::Keyboard,
-    pub command_overlay_style: rio_backend::config::command_overlay::CommandOverlayStyle,
+    pub command_overlays: Vec<CommandOverlayStyle>,
 }
 
 const DEFAULT_CONTEXT_CAPACITY: usize = 28;
@@ -163,6 +196,8 @@ pub fn create_dead_context<T: rio_backend::event::EventListener>(
         main_fd: Arc::new(-1),
         #[cfg(not(target_os = "windows"))]
         shell_pid: 1,
+        #[cfg(not(target_os = "windows"))]
+        foreground_pid: 1,
         messenger: Messenger::new(sender),
         renderable_content: RenderableContent::new(Cursor::default()),
         terminal,
@@ -309,6 +344,8 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             main_fd,
             #[cfg(not(target_os = "windows"))]
             shell_pid,
+            #[cfg(not(target_os = "windows"))]
+            foreground_pid: shell_pid,
             messenger,
             terminal,
             rich_text_id,
@@ -388,7 +425,6 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                 initial_context,
                 margin,
                 ctx_config.split_color,
-                ctx_config.command_overlay_style,
             )],
             capacity: DEFAULT_CONTEXT_CAPACITY,
             event_proxy,
@@ -436,7 +472,6 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                 initial_context,
                 Delta::<f32>::default(),
                 config.split_color,
-                config.command_overlay_style,
             )],
             capacity,
             event_proxy,
@@ -603,6 +638,30 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             .send_event(RioEvent::AlignWindows, self.window_id);
     }
 
+    #[inline]
+    pub fn adjust_align_width(&self, delta: f32) {
+        self.event_proxy
+            .send_event(RioEvent::AdjustAlignWidth(delta), self.window_id);
+    }
+
+    #[inline]
+    pub fn copy_window_layout(&self) {
+        self.event_proxy
+            .send_event(RioEvent::CopyWindowLayout, self.window_id);
+    }
+
+    #[inline]
+    pub fn toggle_mute_sounds(&self) {
+        self.event_proxy
+            .send_event(RioEvent::ToggleMuteSounds, self.window_id);
+    }
+
+    #[inline]
+    pub fn cycle_navigation_mode(&self) {
+        self.event_proxy
+            .send_event(RioEvent::CycleNavigationMode, self.window_id);
+    }
+
     #[inline]
     pub fn event_proxy(&self) -> &T {
         &self.event_proxy
@@ -792,6 +851,28 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         self.contexts.len()
     }
 
+    /// Number of panes whose foreground job finished since the last
+    /// call, see `Context::poll_foreground_exit`.
+    #[cfg(not(target_os = "windows"))]
+    pub fn poll_foreground_exits(&mut self) -> usize {
+        self.contexts
+            .iter_mut()
+            .flat_map(|grid| grid.contexts_mut().values_mut())
+            .map(|item| item.context_mut().poll_foreground_exit())
+            .filter(|finished| *finished)
+            .count()
+    }
+
+    /// Whether the foreground job of pane `route_id`, in any tab,
+    /// finished since it was last checked.
+    #[cfg(not(target_os = "windows"))]
+    pub fn poll_foreground_exit_of(&mut self, route_id: usize) -> bool {
+        self.contexts
+            .iter_mut()
+            .find_map(|grid| grid.get_mut(route_id))
+            .is_some_and(|item| item.context_mut().poll_foreground_exit())
+    }
+
     pub fn update_titles(&mut self) {
         let interval_time = Duration::from_secs(2);
         if self
@@ -808,6 +889,16 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                 self.event_proxy
                     .send_event(RioEvent::Title(content.to_owned()), self.window_id);
 
+                // The window keeps the `[title]` content, only the tab
+                // label follows `navigation.tab-title-format`
+                let content = match &self.config.tab_title_format {
+                    Some(format) => Navigation::format_tab_title(
+                        format,
+                        &tab_title_context(context.current(), i),
+                    ),
+                    None => content,
+                };
+
                 id.push_str(&format!("{i}{content};"));
 
                 if self.config.should_update_title_extra {
@@ -987,6 +1078,17 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
     }
 
     pub fn split(&mut self, rich_text_id: usize, split_down: bool) {
+        self.split_with_shell(rich_text_id, split_down, None);
+    }
+
+    /// Like `split`, but runs `shell` in the new split instead of the
+    /// configured shell.
+    pub fn split_with_shell(
+        &mut self,
+        rich_text_id: usize,
+        split_down: bool,
+        shell: Option<Shell>,
+    ) {
         // Dismiss quick terminal before splitting
         self.dismiss_quick_terminal();
 
@@ -1017,6 +1119,9 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         if working_dir.is_some() {
             cloned_config.working_dir = working_dir;
         }
+        if let Some(shell) = shell {
+            cloned_config.shell = shell;
+        }
         // Force spawn (not fork) so working_dir is respected
         #[cfg(not(target_os = "windows"))]
         {
@@ -1130,9 +1235,76 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         rich_text_id: usize,
         command: &str,
         overlay_dimensions: Option<SugarDimensions>,
+    ) {
+        let Some(shell) = command_overlay_shell(command) else {
+            tracing::error!("empty command for command overlay");
+            return;
+        };
+        self.toggle_command_overlay_with_shell(
+            rich_text_id,
+            command,
+            shell,
+            overlay_dimensions,
+        );
+    }
+
+    /// Like `toggle_command_overlay`, but runs `shell` as given. `command`
+    /// identifies the overlay for toggling.
+    pub fn toggle_command_overlay_with_shell(
+        &mut self,
+        rich_text_id: usize,
+        command: &str,
+        shell: rio_backend::config::Shell,
+        overlay_dimensions: Option<SugarDimensions>,
+    ) {
+        let style = ad_hoc_style(&self.config.command_overlays);
+        self.toggle_overlay(
+            rich_text_id,
+            command,
+            None,
+            shell,
+            style,
+            overlay_dimensions,
+        );
+    }
+
+    /// Toggle the `[[command-overlay]]` entry at `slot`, spawning its
+    /// command the first time.
+    pub fn toggle_configured_overlay(
+        &mut self,
+        slot: usize,
+        rich_text_id: usize,
+        overlay_dimensions: Option<SugarDimensions>,
+    ) {
+        let Some(style) = self.config.command_overlays.get(slot).cloned() else {
+            return;
+        };
+        let Some(shell) = command_overlay_shell(&style.command) else {
+            tracing::error!("command-overlay {slot} has no command");
+            return;
+        };
+        let command = style.command.clone();
+        self.toggle_overlay(
+            rich_text_id,
+            &command,
+            Some(slot),
+            shell,
+            style,
+            overlay_dimensions,
+        );
+    }
+
+    fn toggle_overlay(
+        &mut self,
+        rich_text_id: usize,
+        command: &str,
+        slot: Option<usize>,
+        shell: rio_backend::config::Shell,
+        style: CommandOverlayStyle,
+        overlay_dimensions: Option<SugarDimensions>,
     ) {
         let grid = &mut self.contexts[self.current_index];
-        let needs_creation = grid.toggle_command_overlay(command);
+        let needs_creation = grid.toggle_command_overlay(command, slot);
 
         if !needs_creation {
             // Existing overlay was toggled (shown/hidden).
@@ -1165,16 +1337,7 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         if working_dir.is_some() {
             cloned_config.working_dir = working_dir;
         }
-        // Parse command string: first token is program, rest are args
-        let parts: Vec<&str> = command.split_whitespace().collect();
-        if parts.is_empty() {
-            tracing::error!("empty command for command overlay");
-            return;
-        }
-        cloned_config.shell = rio_backend::config::Shell {
-            program: parts[0].to_string(),
-            args: parts[1..].iter().map(|s| s.to_string()).collect(),
-        };
+        cloned_config.shell = shell;
         // Must use spawn (not fork) so the shell override actually takes effect
         #[cfg(not(target_os = "windows"))]
         {
@@ -1185,14 +1348,8 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
         let cursor = current.cursor_from_ref();
         let current_dim = current.dimension;
 
-        // Compute dimension for the floating overlay based on configured style
-        let style = &self.config.command_overlay_style;
-        let bounds = grid::CommandOverlayBounds {
-            x: style.x,
-            y: style.y,
-            width: style.width,
-            height: style.height,
-        };
+        // Compute dimension for the floating overlay based on its style
+        let bounds = grid::CommandOverlayBounds::from(&style);
         let overlay_width = grid.width * bounds.width;
         let overlay_height = grid.height * bounds.height;
         let cell_dimensions = overlay_dimensions.unwrap_or(current_dim.dimension);
@@ -1213,7 +1370,7 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             &cloned_config,
         ) {
             Ok(new_context) => {
-                grid.open_command_overlay(new_context, command.to_string(), bounds);
+                grid.open_command_overlay(new_context, command.to_string(), slot, style);
                 // Do NOT update self.current_route — overlay is click-through
             }
             Err(..) => {
@@ -1251,8 +1408,9 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
             should_update_title_extra: !config.navigation.color_automation.is_empty(),
             split_color: config.colors.split,
             title: config.title,
+            tab_title_format: config.navigation.tab_title_format.clone(),
             keyboard: config.keyboard,
-            command_overlay_style: config.command_overlay,
+            command_overlays: config.command_overlay.clone(),
         };
 
         let current = self.current();
@@ -1347,7 +1505,6 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
                         new_context,
                         previous_margin,
                         self.config.split_color,
-                        self.config.command_overlay_style,
                     ));
                     if redirect {
                         self.current_index = last_index;
@@ -1362,6 +1519,17 @@ impl<T: EventListener + Clone + std::marker::Send + 'static> ContextManager<T> {
     }
 }
 
+/// Shell for a command overlay: the first word of `command` is the program,
+/// the rest its arguments. `None` for an empty command.
+fn command_overlay_shell(command: &str) -> Option<Shell> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next()?.to_string();
+    Some(Shell {
+        program,
+        args: parts.map(str::to_string).collect(),
+    })
+}
+
 pub fn process_open_url(
     mut shell: Shell,
     mut working_dir: Option<String>,
@@ -1615,6 +1783,34 @@ pub mod test {
     use super::*;
     use crate::event::VoidListener;
 
+    #[test]
+    fn test_job_finished() {
+        let shell = 100;
+        // `cargo build` started, then the shell got the terminal back
+        assert!(!job_finished(shell, 200, shell));
+        assert!(job_finished(200, shell, shell));
+        // Nothing ran in between
+        assert!(!job_finished(shell, shell, shell));
+        // One job handed the terminal to another
+        assert!(!job_finished(200, 300, shell));
+    }
+
+    #[test]
+    fn test_record_foreground_reports_each_exit_once() {
+        let shell = 100;
+        let mut last = shell;
+        // Foreground process seen on each check: a job printing output,
+        // the prompt twice, then a pipeline handing over to another job
+        let finished: Vec<bool> = [200, 200, shell, shell, 300, 400, shell]
+            .into_iter()
+            .map(|pid| record_foreground(&mut last, pid, shell))
+            .collect();
+        assert_eq!(
+            finished,
+            vec![false, false, true, false, false, false, true]
+        );
+    }
+
     #[test]
     fn test_capacity() {
         let window_id: WindowId = WindowId::from(0);
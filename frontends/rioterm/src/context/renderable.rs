@@ -1,5 +1,6 @@
 use rio_backend::ansi::ProgressState;
 use rio_backend::config::colors::term::TermColors;
+use rio_backend::config::colors::ColorArray;
 use rio_backend::config::CursorConfig;
 use rio_backend::crosswords::grid::row::Row;
 use rio_backend::crosswords::pos::CursorState;
@@ -27,6 +28,14 @@ pub struct Cursor {
     pub is_ime_enabled: bool,
 }
 
+/// Rounded background drawn behind a run of hint label cells
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HintPill {
+    /// `None` uses the theme's hint background
+    pub color: Option<ColorArray>,
+    pub radius: f32,
+}
+
 /// Hint label information for rendering
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
@@ -34,6 +43,9 @@ pub struct HintLabel {
     pub position: rio_backend::crosswords::pos::Pos,
     pub label: Vec<char>,
     pub is_first: bool,
+    pub bold: bool,
+    /// Set when the label sits on a pill instead of a cell background
+    pub pill: Option<HintPill>,
 }
 
 #[derive(Default)]
@@ -1,10 +1,15 @@
 use crate::context::Context;
+use rio_backend::config::navigation::TabTitleContext;
 use rustc_hash::FxHashMap;
 use std::time::Instant;
 
 pub struct ContextTitleExtra {
     pub program: String,
     pub path: String,
+    /// Raw terminal title as set via OSC 0/2
+    pub title: String,
+    /// Host from the last OSC 7 working directory URI
+    pub host: String,
 }
 
 pub struct ContextTitle {
@@ -69,7 +74,60 @@ pub fn create_title_extra_from_context<T: rio_backend::event::EventListener>(
     #[cfg(not(unix))]
     let program = String::default();
 
-    Some(ContextTitleExtra { program, path })
+    let (title, host) = {
+        let terminal = context.terminal.lock();
+        (
+            terminal.title.clone(),
+            terminal.current_host.clone().unwrap_or_default(),
+        )
+    };
+
+    Some(ContextTitleExtra {
+        program,
+        path,
+        title,
+        host,
+    })
+}
+
+/// Values for `navigation.tab-title-format` of the tab at `index` (0-based).
+pub fn tab_title_context<T: rio_backend::event::EventListener>(
+    context: &Context<T>,
+    index: usize,
+) -> TabTitleContext {
+    let (title, directory) = {
+        let terminal = context.terminal.lock();
+        (terminal.title.clone(), terminal.current_directory.clone())
+    };
+
+    #[cfg(unix)]
+    let directory = directory.or_else(|| {
+        teletypewriter::foreground_process_path(*context.main_fd, context.shell_pid).ok()
+    });
+    let cwd = directory
+        .as_deref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    #[cfg(unix)]
+    let (program, pid) = (
+        teletypewriter::foreground_process_name(*context.main_fd, context.shell_pid),
+        Some(teletypewriter::foreground_pid(
+            *context.main_fd,
+            context.shell_pid,
+        )),
+    );
+    #[cfg(not(unix))]
+    let (program, pid) = (String::default(), None);
+
+    TabTitleContext {
+        title,
+        cwd,
+        program,
+        index: index + 1,
+        pid,
+    }
 }
 
 // Possible options:
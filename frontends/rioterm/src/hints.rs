@@ -1,7 +1,15 @@
-use rio_backend::config::hints::Hint;
+use crate::context::renderable::{HintLabel, HintPill};
+use rio_backend::config::hints::{
+    Hint, HintInternalAction, HintLabelStrategy, HintLabelStyle,
+    DEFAULT_HINTS_SCROLLBACK_LINES,
+};
 use rio_backend::crosswords::grid::Dimensions;
+use rio_backend::crosswords::grid::Scroll;
 use rio_backend::crosswords::pos::{Column, Line, Pos};
+use rio_backend::crosswords::square::Flags;
 use rio_backend::event::EventListener;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 /// State for hint selection mode
@@ -20,6 +28,101 @@ pub struct HintState {
 
     /// Alphabet for generating labels
     alphabet: String,
+
+    /// How labels are built from the alphabet
+    label_strategy: HintLabelStrategy,
+
+    /// How labels are drawn
+    label_style: HintLabelStyle,
+
+    /// Most lines `scrollback` hints scan, counted up from the bottom
+    scrollback_lines: usize,
+
+    /// Match count above which typed characters filter matches instead
+    /// of selecting labels
+    filter_threshold: Option<usize>,
+
+    /// Text typed while filtering, matches must contain it
+    filter: String,
+
+    /// Whether too many matches are left for labels, typed characters
+    /// go to `filter`
+    filtering: bool,
+
+    /// Action the next selection runs instead of the configured one,
+    /// cycled with `cycle_target`
+    target: Option<HintInternalAction>,
+
+    /// Last target picked for each hint, offered again the next time the
+    /// same hint starts
+    last_targets: HashMap<HintKey, HintInternalAction>,
+}
+
+/// Built-in actions a selection can be redirected to, in cycle order
+const HINT_TARGETS: [HintInternalAction; 5] = [
+    HintInternalAction::Copy,
+    HintInternalAction::Open,
+    HintInternalAction::CopyAndOpen,
+    HintInternalAction::Paste,
+    HintInternalAction::Select,
+];
+
+/// Identifies a hint rule across config reloads
+type HintKey = (Option<String>, bool);
+
+#[inline]
+fn hint_key(hint: &Hint) -> HintKey {
+    (hint.pattern().map(str::to_string), hint.hyperlinks)
+}
+
+/// Why an alphabet can't be used for hint labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintAlphabetError {
+    Empty,
+    /// A single character can only ever label one match
+    TooShort,
+    /// Characters that appear more than once, which would give several
+    /// matches the same label
+    Duplicates(Vec<char>),
+}
+
+impl std::fmt::Display for HintAlphabetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "hint alphabet is empty"),
+            Self::TooShort => {
+                write!(f, "hint alphabet needs at least two characters")
+            }
+            Self::Duplicates(chars) => {
+                let chars: String = chars.iter().collect();
+                write!(f, "hint alphabet repeats the characters {chars:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HintAlphabetError {}
+
+/// Check that `alphabet` can label any number of matches.
+fn validate_alphabet(alphabet: &str) -> Result<(), HintAlphabetError> {
+    let mut seen = Vec::new();
+    let mut duplicates = Vec::new();
+    for c in alphabet.chars() {
+        if !seen.contains(&c) {
+            seen.push(c);
+        } else if !duplicates.contains(&c) {
+            duplicates.push(c);
+        }
+    }
+
+    if !duplicates.is_empty() {
+        return Err(HintAlphabetError::Duplicates(duplicates));
+    }
+    match seen.len() {
+        0 => Err(HintAlphabetError::Empty),
+        1 => Err(HintAlphabetError::TooShort),
+        _ => Ok(()),
+    }
 }
 
 /// A match found by a hint
@@ -31,22 +134,106 @@ pub struct HintMatch {
     /// Start position of the match
     pub start: Pos,
 
-    /// End position of the match
+    /// End position of the match, on a later line than `start` when the
+    /// match continues across a soft wrap
     pub end: Pos,
 
     /// The hint configuration that created this match
     pub hint: Rc<Hint>,
+
+    /// Working directory of the terminal when the match was selected,
+    /// used to resolve relative paths before opening them
+    pub working_directory: Option<PathBuf>,
+
+    /// Action picked at selection time, replacing `hint.action`
+    pub target: Option<HintInternalAction>,
+}
+
+impl HintMatch {
+    /// Whether running this match launches the system opener.
+    pub fn opens(&self) -> bool {
+        match &self.target {
+            Some(target) => target.opens(),
+            None => self.hint.action.opens(),
+        }
+    }
+
+    /// Text to hand to the system opener. Post-processing always runs
+    /// here, and relative file paths are resolved against
+    /// `working_directory`.
+    pub fn open_target(&self) -> String {
+        let target = post_process_hyperlink_uri(&self.text);
+        if target.contains("://") || target.starts_with("mailto:") {
+            return target;
+        }
+        resolve_path(&target, self.working_directory.as_deref())
+    }
+}
+
+/// `path` with a leading `~/` expanded to the home directory and, when
+/// relative, joined to `working_directory`.
+pub fn resolve_path(path: &str, working_directory: Option<&Path>) -> String {
+    if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest).to_string_lossy().to_string();
+        }
+    }
+
+    match working_directory {
+        Some(cwd) if Path::new(path).is_relative() => {
+            cwd.join(path).to_string_lossy().to_string()
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Text of one or more grid rows, with the cell every char came from.
+#[derive(Debug, Default)]
+struct GridText {
+    text: String,
+    /// Byte offset in `text` and cell of each char, in order
+    cells: Vec<(usize, Pos)>,
+    /// Number of grid rows joined
+    rows: usize,
+}
+
+impl GridText {
+    /// Cell of the char containing the byte at `offset`.
+    fn pos_at(&self, offset: usize) -> Pos {
+        let index = self
+            .cells
+            .partition_point(|(start, _)| *start <= offset)
+            .saturating_sub(1);
+        self.cells
+            .get(index)
+            .map(|(_, pos)| *pos)
+            .unwrap_or_default()
+    }
 }
 
 impl HintState {
-    pub fn new(alphabet: String) -> Self {
-        Self {
+    /// Fails when `alphabet` is empty, a single character or repeats
+    /// characters.
+    pub fn try_new(
+        alphabet: String,
+        label_strategy: HintLabelStrategy,
+    ) -> Result<Self, HintAlphabetError> {
+        validate_alphabet(&alphabet)?;
+        Ok(Self {
             active_hint: None,
             matches: Vec::new(),
             labels: Vec::new(),
             keys: Vec::new(),
             alphabet,
-        }
+            label_strategy,
+            label_style: HintLabelStyle::default(),
+            scrollback_lines: DEFAULT_HINTS_SCROLLBACK_LINES,
+            filter_threshold: None,
+            filter: String::new(),
+            filtering: false,
+            target: None,
+            last_targets: HashMap::new(),
+        })
     }
 
     /// Check if hint mode is active
@@ -56,8 +243,10 @@ impl HintState {
 
     /// Start hint mode with the given hint configuration
     pub fn start(&mut self, hint: Rc<Hint>) {
+        self.target = self.last_targets.get(&hint_key(&hint)).cloned();
         self.active_hint = Some(hint);
         self.keys.clear();
+        self.filter.clear();
         // matches and labels will be updated by update_matches
     }
 
@@ -67,6 +256,33 @@ impl HintState {
         self.matches.clear();
         self.labels.clear();
         self.keys.clear();
+        self.filter.clear();
+        self.filtering = false;
+        self.target = None;
+    }
+
+    /// Action the next selection runs instead of the configured one
+    pub fn target(&self) -> Option<&HintInternalAction> {
+        self.target.as_ref()
+    }
+
+    /// Override the action of the next selection, `None` restores the
+    /// configured action
+    pub fn set_target(&mut self, target: Option<HintInternalAction>) {
+        self.target = target;
+    }
+
+    /// Step to the next target: configured action, then each of
+    /// `HINT_TARGETS`, then back to the configured action
+    pub fn cycle_target(&mut self) {
+        self.target = match &self.target {
+            None => Some(HINT_TARGETS[0].clone()),
+            Some(current) => HINT_TARGETS
+                .iter()
+                .position(|target| target == current)
+                .and_then(|index| HINT_TARGETS.get(index + 1))
+                .cloned(),
+        };
     }
 
     /// Update visible matches for the current hint
@@ -84,10 +300,8 @@ impl HintState {
         };
 
         // Find regex matches if regex is specified
-        if let Some(regex_pattern) = &hint.regex {
-            if let Ok(regex) = regex::Regex::new(regex_pattern) {
-                self.find_regex_matches(term, &regex, hint.clone());
-            }
+        if let Some(Ok(regex)) = hint.build_regex() {
+            self.find_regex_matches(term, &regex, hint.clone());
         }
 
         // Find OSC 8 hyperlinks if enabled
@@ -105,11 +319,29 @@ impl HintState {
         self.matches.sort_by_key(|m| (m.start.row, m.start.col));
         self.matches.dedup_by_key(|m| m.start);
 
-        // Generate labels for matches
-        self.generate_labels();
+        if !self.filter.is_empty() {
+            let filter = &self.filter;
+            self.matches.retain(|m| matches_filter(&m.text, filter));
+        }
+
+        // Too many matches for short labels: keep filtering by text
+        self.filtering = self
+            .filter_threshold
+            .is_some_and(|threshold| self.matches.len() > threshold);
+        if self.filtering {
+            self.labels.clear();
+            self.keys.clear();
+        } else {
+            self.generate_labels();
+        }
     }
 
-    /// Handle keyboard input during hint selection
+    /// Handle keyboard input during hint selection.
+    ///
+    /// Returns the match once its label is complete. The caller is
+    /// responsible for running `hint_match.target` when set, otherwise
+    /// every action of `hint_match.hint.action`, in order (see
+    /// `HintAction::internal_actions`).
     pub fn keyboard_input<T: EventListener>(
         &mut self,
         term: &rio_backend::crosswords::Crosswords<T>,
@@ -118,7 +350,10 @@ impl HintState {
         match c {
             // Use backspace to remove the last character pressed
             '\x08' | '\x1f' => {
-                self.keys.pop();
+                // Undo label keys first, then widen the filter again
+                if self.keys.pop().is_none() {
+                    self.filter.pop();
+                }
                 // Only update matches after backspace to regenerate visible labels
                 self.update_matches(term);
                 return None;
@@ -131,6 +366,21 @@ impl HintState {
             _ => (),
         }
 
+        if self.filtering {
+            let mut filter = self.filter.clone();
+            filter.push(c);
+            // Ignore characters that would filter out every match
+            if self
+                .matches
+                .iter()
+                .any(|m| matches_filter(&m.text, &filter))
+            {
+                self.filter = filter;
+                self.update_matches(term);
+            }
+            return None;
+        }
+
         let hint = self.active_hint.as_ref()?;
 
         // Get visible labels (labels filtered by keys pressed so far)
@@ -143,8 +393,25 @@ impl HintState {
 
         // Check if this completes the label (only one character remaining)
         if remaining_label.len() == 1 {
-            let hint_match = self.matches.get(*index)?.clone();
+            let mut hint_match = self.matches.get(*index)?.clone();
             let hint_config = hint.clone();
+            hint_match.target = self.target.clone();
+
+            // Remember the target for the next time this hint starts
+            match &self.target {
+                Some(target) => {
+                    self.last_targets
+                        .insert(hint_key(&hint_config), target.clone());
+                }
+                None => {
+                    self.last_targets.remove(&hint_key(&hint_config));
+                }
+            }
+
+            // Opening needs the directory relative paths are based on
+            if hint_match.opens() {
+                hint_match.working_directory = term.current_directory.clone();
+            }
 
             // Exit hint mode unless it requires explicit dismissal
             if hint_config.persist {
@@ -166,6 +433,22 @@ impl HintState {
         &self.matches
     }
 
+    /// Current matches paired with their full labels. Stops at the shorter
+    /// of the two while labels are being regenerated.
+    #[allow(dead_code)]
+    pub fn matches_with_labels(&self) -> Vec<(&HintMatch, &[char])> {
+        self.matches
+            .iter()
+            .zip(self.labels.iter().map(Vec::as_slice))
+            .collect()
+    }
+
+    /// Hint rule hint mode was started with, `None` when inactive
+    #[allow(dead_code)]
+    pub fn active_hint(&self) -> Option<&Hint> {
+        self.active_hint.as_deref()
+    }
+
     /// Get keys pressed so far
     #[allow(dead_code)]
     pub fn keys_pressed(&self) -> &[char] {
@@ -189,13 +472,89 @@ impl HintState {
             .collect()
     }
 
-    /// Update the alphabet used for hint labels
-    #[allow(dead_code)]
-    pub fn update_alphabet(&mut self, alphabet: &str) {
+    /// Update the alphabet used for hint labels. An invalid alphabet
+    /// is rejected and the current one kept.
+    pub fn update_alphabet(&mut self, alphabet: &str) -> Result<(), HintAlphabetError> {
         if self.alphabet != alphabet {
+            validate_alphabet(alphabet)?;
             self.alphabet = alphabet.to_string();
             self.keys.clear();
         }
+        Ok(())
+    }
+
+    /// Update how hint labels are generated
+    pub fn update_label_strategy(&mut self, label_strategy: HintLabelStrategy) {
+        if self.label_strategy != label_strategy {
+            self.label_strategy = label_strategy;
+            self.keys.clear();
+        }
+    }
+
+    /// Update the match count above which typed characters filter
+    /// matches, `None` always shows labels
+    pub fn update_filter_threshold(&mut self, filter_threshold: Option<usize>) {
+        self.filter_threshold = filter_threshold;
+    }
+
+    /// Update how labels are drawn
+    pub fn update_label_style(&mut self, label_style: HintLabelStyle) {
+        self.label_style = label_style;
+    }
+
+    /// One render descriptor per visible label character, styled with the
+    /// configured label style. Labels longer than the room left on the row
+    /// continue on the next one, like the match itself.
+    pub fn render_labels(&self, columns: usize) -> Vec<HintLabel> {
+        let style = &self.label_style;
+        let pill = style.pill.then_some(HintPill {
+            color: style.pill_color,
+            radius: style.pill_radius.max(0.0),
+        });
+
+        let mut hint_labels = Vec::new();
+        for (match_index, remaining_label) in self.visible_labels() {
+            let Some(hint_match) = self.matches.get(match_index) else {
+                continue;
+            };
+            for (char_index, &label_char) in remaining_label.iter().enumerate() {
+                hint_labels.push(HintLabel {
+                    position: label_position(hint_match.start, char_index, columns),
+                    label: vec![label_char],
+                    // First character gets different styling
+                    is_first: char_index == 0,
+                    bold: style.bold,
+                    pill,
+                });
+            }
+        }
+        hint_labels
+    }
+
+    /// Update how many lines `scrollback` hints scan
+    pub fn update_scrollback_lines(&mut self, scrollback_lines: usize) {
+        self.scrollback_lines = scrollback_lines;
+    }
+
+    /// First and last line `hint` scans: the viewport, or for
+    /// `scrollback` hints the bottom `scrollback_lines` lines of the
+    /// buffer (always covering the viewport).
+    fn scan_range<T: EventListener>(
+        &self,
+        term: &rio_backend::crosswords::Crosswords<T>,
+        hint: &Hint,
+    ) -> (Line, Line) {
+        let grid = &term.grid;
+        let viewport_top = Line(-(grid.display_offset() as i32));
+        let viewport_bottom = viewport_top + grid.screen_lines() - 1;
+        if !hint.scrollback {
+            return (viewport_top, viewport_bottom);
+        }
+
+        let bottom = grid.bottommost_line();
+        let capped_top = bottom - self.scrollback_lines.saturating_sub(1);
+        let top = std::cmp::max(grid.topmost_line(), capped_top);
+        (std::cmp::min(top, viewport_top), bottom)
     }
 
     // Private helper methods
@@ -206,47 +565,52 @@ impl HintState {
         regex: &regex::Regex,
         hint: Rc<Hint>,
     ) {
-        // Get the visible area of the terminal
-        let grid = &term.grid;
-        let display_offset = grid.display_offset();
-        let visible_lines = grid.screen_lines();
+        let (first_line, last_line) = self.scan_range(term, &hint);
 
-        // Scan each visible line for matches
-        for line_idx in 0..visible_lines {
-            let line = Line(line_idx as i32 - display_offset as i32);
-            if line < Line(0) || line.0 >= grid.total_lines() as i32 {
-                continue;
-            }
-
-            // Extract text from the line
-            let line_text = self.extract_line_text(term, line);
+        // Scan each logical line (rows joined across soft wraps)
+        let mut line = first_line;
+        while line <= last_line {
+            // Extract text from the line and the rows it wraps into
+            let line_text = self.extract_lines_text(term, line, last_line);
+            line += line_text.rows.max(1);
 
             // Find all matches in this line
-            // Use captures_iter to support capture groups: if the regex
-            // contains a capture group, the first group is used as the
-            // copied text while the full match defines the highlight range.
-            for caps in regex.captures_iter(&line_text) {
+            // Use captures_iter to support capture groups: the hint
+            // template builds the copied text from them, otherwise the
+            // first group is used, while the full match defines the
+            // highlight range.
+            for caps in regex.captures_iter(&line_text.text) {
                 let full_match = caps.get(0).unwrap();
-                let start_col = Column(full_match.start());
 
-                // Use first capture group text if available, otherwise full match
-                let mut match_text =
-                    caps.get(1).unwrap_or(full_match).as_str().to_string();
+                let match_text = match hint.expand_template(&caps) {
+                    Some(text) => text,
+                    None => {
+                        // Use first capture group text if available, otherwise full match
+                        let text = caps.get(1).unwrap_or(full_match).as_str();
 
-                // Apply post-processing if enabled
-                if hint.post_processing {
-                    match_text = post_process_hyperlink_uri(&match_text);
-                }
+                        // Apply post-processing if enabled and useful for
+                        // the pattern
+                        if hint.trims_delimiters() {
+                            post_process_hyperlink_uri(text)
+                        } else {
+                            text.to_string()
+                        }
+                    }
+                };
 
-                // Highlight range is based on the full match
-                let end_col =
-                    Column(full_match.start() + full_match.len().saturating_sub(1));
+                // Highlight range is based on the full match and may end
+                // on a different row than it starts
+                let start = line_text.pos_at(full_match.start());
+                let end = line_text
+                    .pos_at(full_match.start() + full_match.len().saturating_sub(1));
 
                 let hint_match = HintMatch {
                     text: match_text,
-                    start: Pos::new(line, start_col),
-                    end: Pos::new(line, end_col),
+                    start,
+                    end,
                     hint: hint.clone(),
+                    working_directory: None,
+                    target: None,
                 };
 
                 self.matches.push(hint_match);
@@ -259,17 +623,11 @@ impl HintState {
         term: &rio_backend::crosswords::Crosswords<T>,
         hint: Rc<Hint>,
     ) {
-        // Scan the visible area for OSC 8 hyperlinks
+        // Scan the visible area (or scrollback) for OSC 8 hyperlinks
         let grid = &term.grid;
-        let display_offset = grid.display_offset();
-        let visible_lines = grid.screen_lines();
-
-        for line_idx in 0..visible_lines {
-            let line = Line(line_idx as i32 - display_offset as i32);
-            if line < Line(0) || line.0 >= grid.total_lines() as i32 {
-                continue;
-            }
+        let (first_line, last_line) = self.scan_range(term, &hint);
 
+        for line in (first_line.0..=last_line.0).map(Line) {
             let mut col = Column(0);
             while col < grid.columns() {
                 let cell = &grid[line][col];
@@ -301,6 +659,8 @@ impl HintState {
                         start: Pos::new(line, start_col),
                         end: Pos::new(line, end_col - 1),
                         hint: hint.clone(),
+                        working_directory: None,
+                        target: None,
                     };
 
                     self.matches.push(hint_match);
@@ -314,70 +674,189 @@ impl HintState {
         }
     }
 
+    /// Append the text of `line` to `text`. Wide char spacers are
+    /// skipped so byte offsets line up with real cells, `text.cells`
+    /// records the column every char starts at.
     fn extract_line_text<T: EventListener>(
         &self,
         term: &rio_backend::crosswords::Crosswords<T>,
         line: Line,
-    ) -> String {
+        text: &mut GridText,
+    ) {
         let grid = &term.grid;
-        let mut text = String::new();
+        let spacers = Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER;
 
         for col in 0..grid.columns() {
             let cell = &grid[line][Column(col)];
-            text.push(cell.c);
+            if cell.flags.intersects(spacers) {
+                continue;
+            }
+
+            text.cells
+                .push((text.text.len(), Pos::new(line, Column(col))));
+            text.text.push(cell.c);
+            if let Some(zerowidth) = cell.zerowidth() {
+                text.text.extend(zerowidth);
+            }
+        }
+    }
+
+    /// Concatenate the rows from `from` up to `to` while they soft-wrap
+    /// (`WRAPLINE` set on their last cell).
+    fn extract_lines_text<T: EventListener>(
+        &self,
+        term: &rio_backend::crosswords::Crosswords<T>,
+        from: Line,
+        to: Line,
+    ) -> GridText {
+        let grid = &term.grid;
+        let last_column = grid.last_column();
+
+        let mut text = GridText::default();
+        let mut current = from;
+        loop {
+            self.extract_line_text(term, current, &mut text);
+            text.rows += 1;
+
+            let wraps = grid[current][last_column].flags.contains(Flags::WRAPLINE);
+            if !wraps || current >= to {
+                break;
+            }
+            current += 1;
         }
 
-        text.trim_end().to_string()
+        text.text.truncate(text.text.trim_end().len());
+        text
     }
 
     fn generate_labels(&mut self) {
         self.labels.clear();
         let count = self.matches.len();
         let alphabet: Vec<char> = self.alphabet.chars().collect();
-        let alphabet_len = alphabet.len();
 
-        if count == 0 {
+        if count == 0 || alphabet.is_empty() {
             return;
         }
 
-        // Determine the minimum label length needed to have unique, non-prefix labels
-        // We need labels where no label is a prefix of another
-        // This means all labels must have the same length
-        let label_len = if count <= alphabet_len {
-            1
-        } else {
-            // Calculate minimum length needed: alphabet_len^length >= count
-            let mut len = 2;
-            let mut capacity = alphabet_len * alphabet_len;
-            while capacity < count {
-                len += 1;
-                capacity *= alphabet_len;
+        self.labels = match self.label_strategy {
+            // A single character can't branch into prefix-free labels
+            HintLabelStrategy::Variable if alphabet.len() > 1 => {
+                variable_labels(&alphabet, count)
             }
-            len
+            _ => fixed_labels(&alphabet, count),
         };
+    }
+}
 
-        // Generate labels of fixed length
-        let mut indices = vec![0usize; label_len];
-
-        for _ in 0..count {
-            // Create label from current indices
-            let label: Vec<char> = indices.iter().map(|&i| alphabet[i]).collect();
-            self.labels.push(label);
-
-            // Increment indices (like counting in base alphabet_len)
-            let mut carry = true;
-            for idx in indices.iter_mut().rev() {
-                if carry {
-                    *idx += 1;
-                    if *idx >= alphabet_len {
-                        *idx = 0;
-                    } else {
-                        carry = false;
-                    }
+/// Whether a match with `text` is kept by the typed `filter`, ignoring
+/// case.
+fn matches_filter(text: &str, filter: &str) -> bool {
+    text.to_lowercase().contains(&filter.to_lowercase())
+}
+
+/// Labels of equal length, the shortest that gives every match its own.
+fn fixed_labels(alphabet: &[char], count: usize) -> Vec<Vec<char>> {
+    let alphabet_len = alphabet.len();
+    let mut labels = Vec::with_capacity(count);
+
+    // Determine the minimum label length needed to have unique, non-prefix labels
+    // We need labels where no label is a prefix of another
+    // This means all labels must have the same length
+    let label_len = if count <= alphabet_len {
+        1
+    } else {
+        // Calculate minimum length needed: alphabet_len^length >= count
+        let mut len = 2;
+        let mut capacity = alphabet_len * alphabet_len;
+        while capacity < count {
+            len += 1;
+            capacity *= alphabet_len;
+        }
+        len
+    };
+
+    // Generate labels of fixed length
+    let mut indices = vec![0usize; label_len];
+
+    for _ in 0..count {
+        // Create label from current indices
+        let label: Vec<char> = indices.iter().map(|&i| alphabet[i]).collect();
+        labels.push(label);
+
+        // Increment indices (like counting in base alphabet_len)
+        let mut carry = true;
+        for idx in indices.iter_mut().rev() {
+            if carry {
+                *idx += 1;
+                if *idx >= alphabet_len {
+                    *idx = 0;
+                } else {
+                    carry = false;
                 }
             }
         }
     }
+
+    labels
+}
+
+/// Prefix-free labels of varying length. Starting from the single
+/// characters, the last of the shortest labels is split into one child
+/// per character until there are enough, so earlier matches keep the
+/// shortest labels. `alphabet` needs at least two characters.
+fn variable_labels(alphabet: &[char], count: usize) -> Vec<Vec<char>> {
+    let mut labels: Vec<Vec<char>> = alphabet.iter().map(|c| vec![*c]).collect();
+
+    // Labels stay sorted by length: splitting one of the shortest
+    // appends labels one character longer, never longer than the rest
+    while labels.len() < count {
+        let shortest = labels[0].len();
+        let Some(index) = labels.iter().rposition(|label| label.len() == shortest) else {
+            break;
+        };
+        let prefix = labels.remove(index);
+        for c in alphabet {
+            let mut label = prefix.clone();
+            label.push(*c);
+            labels.push(label);
+        }
+    }
+
+    labels.truncate(count);
+    labels
+}
+
+/// Scroll the display so `hint_match` is visible, for matches found in
+/// the scrollback. Returns whether the display moved.
+pub fn scroll_to_match<T: EventListener>(
+    term: &mut rio_backend::crosswords::Crosswords<T>,
+    hint_match: &HintMatch,
+) -> bool {
+    let viewport_top = -(term.grid.display_offset() as i32);
+    let viewport_bottom = viewport_top + term.grid.screen_lines() as i32 - 1;
+
+    let delta = if hint_match.start.row.0 < viewport_top {
+        viewport_top - hint_match.start.row.0
+    } else if hint_match.end.row.0 > viewport_bottom {
+        // Never past the start, it matters more than the end
+        let below = hint_match.end.row.0 - viewport_bottom;
+        let above = hint_match.start.row.0 - viewport_top;
+        -below.min(above)
+    } else {
+        return false;
+    };
+
+    term.scroll_display(Scroll::Delta(delta));
+    true
+}
+
+/// Cell for the `offset`-th label character of a match starting at
+/// `start`, continuing on the next row past the last column like the
+/// wrapped text does.
+pub fn label_position(start: Pos, offset: usize, columns: usize) -> Pos {
+    let columns = columns.max(1);
+    let cell = start.col.0 + offset;
+    Pos::new(start.row + cell / columns, Column(cell % columns))
 }
 
 /// Apply post-processing to hyperlink URIs (same as in screen/mod.rs)
@@ -434,11 +913,109 @@ fn post_process_hyperlink_uri(uri: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rio_backend::config::hints::{HintAction, HintInternalAction};
+    use rio_backend::ansi::CursorShape;
+    use rio_backend::config::hints::{
+        BuiltinHintType, HintAction, DEFAULT_HINTS_ALPHABET,
+    };
+    use rio_backend::crosswords::{Crosswords, CrosswordsSize};
+    use rio_backend::event::VoidListener;
+    use rio_window::window::WindowId;
+    use unicode_width::UnicodeWidthChar;
+
+    /// Terminal with one row per entry, rows marked `true` soft-wrap
+    /// into the next one.
+    fn term_with_rows(columns: usize, rows: &[(&str, bool)]) -> Crosswords<VoidListener> {
+        let size = CrosswordsSize::new(columns, rows.len());
+        let mut term = Crosswords::new(
+            size,
+            CursorShape::Block,
+            VoidListener {},
+            WindowId::from(0),
+            0,
+        );
+
+        for (index, (text, wraps)) in rows.iter().enumerate() {
+            let line = Line(index as i32);
+            let mut col = 0;
+            for c in text.chars() {
+                term.grid[line][Column(col)].c = c;
+                if c.width() == Some(2) {
+                    term.grid[line][Column(col)].flags.insert(Flags::WIDE_CHAR);
+                    col += 1;
+                    term.grid[line][Column(col)].c = ' ';
+                    term.grid[line][Column(col)]
+                        .flags
+                        .insert(Flags::WIDE_CHAR_SPACER);
+                }
+                col += 1;
+            }
+            if *wraps {
+                term.grid[line][Column(columns - 1)]
+                    .flags
+                    .insert(Flags::WRAPLINE);
+            }
+        }
+
+        term
+    }
+
+    fn hint_state(alphabet: &str, label_strategy: HintLabelStrategy) -> HintState {
+        HintState::try_new(alphabet.to_string(), label_strategy).unwrap()
+    }
+
+    #[test]
+    fn test_alphabet_validation() {
+        assert!(validate_alphabet(DEFAULT_HINTS_ALPHABET).is_ok());
+        assert!(validate_alphabet("ab").is_ok());
+        assert_eq!(validate_alphabet(""), Err(HintAlphabetError::Empty));
+        assert_eq!(validate_alphabet("a"), Err(HintAlphabetError::TooShort));
+        assert_eq!(
+            validate_alphabet("abacbda"),
+            Err(HintAlphabetError::Duplicates(vec!['a', 'b']))
+        );
+        assert_eq!(
+            validate_alphabet("aa"),
+            Err(HintAlphabetError::Duplicates(vec!['a']))
+        );
+
+        let error = HintState::try_new("a".to_string(), HintLabelStrategy::Fixed).err();
+        assert_eq!(error, Some(HintAlphabetError::TooShort));
+    }
+
+    #[test]
+    fn test_update_alphabet_keeps_previous_on_error() {
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        assert_eq!(
+            state.update_alphabet("xx"),
+            Err(HintAlphabetError::Duplicates(vec!['x']))
+        );
+        assert_eq!(state.alphabet, "abc");
+        assert!(state.update_alphabet("xyz").is_ok());
+        assert_eq!(state.alphabet, "xyz");
+    }
+
+    fn url_hint() -> Rc<Hint> {
+        Rc::new(Hint {
+            regex: Some("https?://[^ ]+".to_string()),
+            hyperlinks: false,
+            post_processing: false,
+            persist: false,
+            action: HintAction::Action {
+                action: HintInternalAction::Copy,
+            },
+            mouse: Default::default(),
+            scrollback: false,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
+            binding: None,
+        })
+    }
 
     #[test]
     fn test_label_generation() {
-        let mut state = HintState::new("abc".to_string());
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
 
         // With 3 matches (fits in single char alphabet of 3)
         state.matches = vec![
@@ -455,8 +1032,15 @@ mod tests {
                         action: HintInternalAction::Copy,
                     },
                     mouse: Default::default(),
+                    scrollback: false,
+                    case_insensitive: false,
+                    smart_case: false,
+                    template: None,
+                    builtin: None,
                     binding: None,
                 }),
+                working_directory: None,
+                target: None,
             },
             HintMatch {
                 text: "m2".to_string(),
@@ -471,8 +1055,15 @@ mod tests {
                         action: HintInternalAction::Copy,
                     },
                     mouse: Default::default(),
+                    scrollback: false,
+                    case_insensitive: false,
+                    smart_case: false,
+                    template: None,
+                    builtin: None,
                     binding: None,
                 }),
+                working_directory: None,
+                target: None,
             },
             HintMatch {
                 text: "m3".to_string(),
@@ -487,8 +1078,15 @@ mod tests {
                         action: HintInternalAction::Copy,
                     },
                     mouse: Default::default(),
+                    scrollback: false,
+                    case_insensitive: false,
+                    smart_case: false,
+                    template: None,
+                    builtin: None,
                     binding: None,
                 }),
+                working_directory: None,
+                target: None,
             },
         ];
         state.generate_labels();
@@ -512,8 +1110,15 @@ mod tests {
                     action: HintInternalAction::Copy,
                 },
                 mouse: Default::default(),
+                scrollback: false,
+                case_insensitive: false,
+                smart_case: false,
+                template: None,
+                builtin: None,
                 binding: None,
             }),
+            working_directory: None,
+            target: None,
         });
         state.generate_labels();
         // 4 matches with alphabet "abc" (len 3) -> need 2 char labels (3^2 = 9 >= 4)
@@ -526,7 +1131,7 @@ mod tests {
 
     #[test]
     fn test_hint_state_lifecycle() {
-        let mut state = HintState::new("abc".to_string());
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
         assert!(!state.is_active());
 
         let hint = Rc::new(Hint {
@@ -538,6 +1143,11 @@ mod tests {
                 action: HintInternalAction::Copy,
             },
             mouse: Default::default(),
+            scrollback: false,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
             binding: None,
         });
 
@@ -550,7 +1160,7 @@ mod tests {
 
     #[test]
     fn test_visible_labels() {
-        let mut state = HintState::new("abc".to_string());
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
         state.labels = vec![vec!['a'], vec!['b'], vec!['a', 'b'], vec!['a', 'c']];
 
         // No input - all labels visible
@@ -568,7 +1178,7 @@ mod tests {
 
     #[test]
     fn test_keyboard_input_logic() {
-        let mut state = HintState::new("jfkdls".to_string());
+        let mut state = hint_state("jfkdls", HintLabelStrategy::Fixed);
 
         // Simulate having some labels
         state.labels = vec![
@@ -601,8 +1211,15 @@ mod tests {
                         action: HintInternalAction::Copy,
                     },
                     mouse: Default::default(),
+                    scrollback: false,
+                    case_insensitive: false,
+                    smart_case: false,
+                    template: None,
+                    builtin: None,
                     binding: None,
                 }),
+                working_directory: None,
+                target: None,
             },
             HintMatch {
                 text: "match1".to_string(),
@@ -623,8 +1240,15 @@ mod tests {
                         action: HintInternalAction::Copy,
                     },
                     mouse: Default::default(),
+                    scrollback: false,
+                    case_insensitive: false,
+                    smart_case: false,
+                    template: None,
+                    builtin: None,
                     binding: None,
                 }),
+                working_directory: None,
+                target: None,
             },
         ];
 
@@ -637,6 +1261,11 @@ mod tests {
                 action: HintInternalAction::Copy,
             },
             mouse: Default::default(),
+            scrollback: false,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
             binding: None,
         });
 
@@ -709,4 +1338,609 @@ mod tests {
         let matches: Vec<&str> = regex.find_iter(line).map(|m| m.as_str()).collect();
         assert_eq!(matches, vec!["`a`", "`b`", "`c`"]);
     }
+
+    #[test]
+    fn test_regex_match_across_wrapped_line() {
+        let term = term_with_rows(
+            12,
+            &[
+                ("see https://", true),
+                ("rio.dev/docs", false),
+                ("done", false),
+            ],
+        );
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(url_hint());
+        state.update_matches(&term);
+
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "https://rio.dev/docs");
+        assert_eq!(matches[0].start, Pos::new(Line(0), Column(4)));
+        assert_eq!(matches[0].end, Pos::new(Line(1), Column(11)));
+    }
+
+    #[test]
+    fn test_regex_match_expands_template() {
+        let term = term_with_rows(32, &[("login deploy@example.com:22", false)]);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        let hint = Hint {
+            regex: Some(r"(\w+)@([\w.]+):\d+".to_string()),
+            template: Some("ssh ${1}@${2}".to_string()),
+            ..(*url_hint()).clone()
+        };
+        state.start(Rc::new(hint));
+        state.update_matches(&term);
+
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "ssh deploy@example.com");
+        // The highlight still covers the full match
+        assert_eq!(matches[0].start, Pos::new(Line(0), Column(6)));
+        assert_eq!(matches[0].end, Pos::new(Line(0), Column(26)));
+    }
+
+    #[test]
+    fn test_builtin_hint_matches() {
+        let term =
+            term_with_rows(40, &[("call +1 555-123-4567, see /etc/hosts.", false)]);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        let phone = Hint {
+            regex: None,
+            builtin: Some(BuiltinHintType::PhoneNumber),
+            ..(*url_hint()).clone()
+        };
+        state.start(Rc::new(phone));
+        state.update_matches(&term);
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "+1 555-123-4567");
+
+        // Paths get the trailing dot trimmed like URLs do
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        let path = Hint {
+            regex: None,
+            builtin: Some(BuiltinHintType::FilePath),
+            post_processing: true,
+            ..(*url_hint()).clone()
+        };
+        state.start(Rc::new(path));
+        state.update_matches(&term);
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "/etc/hosts");
+    }
+
+    #[test]
+    fn test_regex_match_does_not_join_unwrapped_rows() {
+        let term =
+            term_with_rows(12, &[("see https://", false), ("rio.dev/docs", false)]);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(url_hint());
+        state.update_matches(&term);
+
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "https://");
+        assert_eq!(matches[0].end, Pos::new(Line(0), Column(11)));
+    }
+
+    #[test]
+    fn test_extract_lines_text_stops_at_bound() {
+        let term = term_with_rows(4, &[("abcd", true), ("efgh", true), ("ij", false)]);
+        let state = hint_state("abc", HintLabelStrategy::Fixed);
+
+        let text = state.extract_lines_text(&term, Line(0), Line(2));
+        assert_eq!(text.text, "abcdefghij");
+        assert_eq!(text.rows, 3);
+        assert_eq!(text.pos_at(4), Pos::new(Line(1), Column(0)));
+        assert_eq!(text.pos_at(9), Pos::new(Line(2), Column(1)));
+
+        let text = state.extract_lines_text(&term, Line(0), Line(1));
+        assert_eq!(text.text, "abcdefgh");
+        assert_eq!(text.rows, 2);
+    }
+
+    #[test]
+    fn test_extract_line_text_skips_wide_char_spacers() {
+        let term = term_with_rows(10, &[("見る ab", false)]);
+        let state = hint_state("abc", HintLabelStrategy::Fixed);
+
+        let text = state.extract_lines_text(&term, Line(0), Line(0));
+        assert_eq!(text.text, "見る ab");
+        // "見" and "る" take two cells each
+        assert_eq!(text.pos_at(0), Pos::new(Line(0), Column(0)));
+        assert_eq!(text.pos_at(3), Pos::new(Line(0), Column(2)));
+        assert_eq!(text.pos_at(6), Pos::new(Line(0), Column(4)));
+        assert_eq!(text.pos_at(7), Pos::new(Line(0), Column(5)));
+    }
+
+    #[test]
+    fn test_regex_match_after_wide_chars() {
+        let term = term_with_rows(24, &[("見る http://例え.com", false)]);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(url_hint());
+        state.update_matches(&term);
+
+        let matches = state.matches();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "http://例え.com");
+        assert_eq!(matches[0].start, Pos::new(Line(0), Column(5)));
+        assert_eq!(matches[0].end, Pos::new(Line(0), Column(19)));
+    }
+
+    #[test]
+    fn test_label_style_flows_into_render_labels() {
+        let term = term_with_rows(24, &[("see https://rio.dev", false)]);
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(url_hint());
+        state.update_matches(&term);
+
+        let labels = state.render_labels(24);
+        assert_eq!(labels.len(), 1);
+        assert!(labels[0].bold);
+        assert_eq!(labels[0].pill, None);
+
+        state.update_label_style(HintLabelStyle {
+            bold: false,
+            pill: true,
+            pill_color: Some([0.0, 1.0, 0.0, 1.0]),
+            pill_radius: 3.0,
+        });
+
+        let labels = state.render_labels(24);
+        assert_eq!(labels[0].position, Pos::new(Line(0), Column(4)));
+        assert!(!labels[0].bold);
+        assert_eq!(
+            labels[0].pill,
+            Some(HintPill {
+                color: Some([0.0, 1.0, 0.0, 1.0]),
+                radius: 3.0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_label_position_wraps_past_last_column() {
+        let start = Pos::new(Line(2), Column(8));
+        assert_eq!(label_position(start, 0, 10), Pos::new(Line(2), Column(8)));
+        assert_eq!(label_position(start, 1, 10), Pos::new(Line(2), Column(9)));
+        assert_eq!(label_position(start, 2, 10), Pos::new(Line(3), Column(0)));
+    }
+
+    fn hint_with_action(action: HintInternalAction) -> Rc<Hint> {
+        Rc::new(Hint {
+            regex: Some("[a-z]+/[a-z.]+".to_string()),
+            hyperlinks: false,
+            post_processing: false,
+            persist: false,
+            action: HintAction::Action { action },
+            mouse: Default::default(),
+            scrollback: false,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
+            binding: None,
+        })
+    }
+
+    fn match_with_text(text: &str, working_directory: Option<&str>) -> HintMatch {
+        HintMatch {
+            text: text.to_string(),
+            start: Pos::default(),
+            end: Pos::default(),
+            hint: hint_with_action(HintInternalAction::Open),
+            working_directory: working_directory.map(PathBuf::from),
+            target: None,
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_keyboard_input_attaches_working_directory_for_open() {
+        let mut term = term_with_rows(16, &[("see src/main.rs", false)]);
+        term.current_directory = Some(PathBuf::from("/work/rio"));
+
+        for action in [HintInternalAction::Open, HintInternalAction::CopyAndOpen] {
+            let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+            state.start(hint_with_action(action));
+            state.update_matches(&term);
+
+            let hint_match = state.keyboard_input(&term, 'a').unwrap();
+            assert_eq!(
+                hint_match.working_directory,
+                Some(PathBuf::from("/work/rio"))
+            );
+            assert_eq!(hint_match.open_target(), "/work/rio/src/main.rs");
+        }
+    }
+
+    #[test]
+    fn test_keyboard_input_copy_only_skips_working_directory() {
+        let mut term = term_with_rows(16, &[("see src/main.rs", false)]);
+        term.current_directory = Some(PathBuf::from("/work/rio"));
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(hint_with_action(HintInternalAction::Copy));
+        state.update_matches(&term);
+
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.text, "src/main.rs");
+        assert!(hint_match.working_directory.is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_open_target() {
+        // URLs are post-processed but never joined with the directory
+        let url = match_with_text("https://rio.dev/docs).", Some("/work"));
+        assert_eq!(url.open_target(), "https://rio.dev/docs");
+
+        let absolute = match_with_text("/etc/hosts", Some("/work"));
+        assert_eq!(absolute.open_target(), "/etc/hosts");
+
+        let relative = match_with_text("notes.md", None);
+        assert_eq!(relative.open_target(), "notes.md");
+    }
+
+    #[test]
+    fn test_selected_target_is_remembered_per_hint() {
+        let term = term_with_rows(16, &[("see src/main.rs", false)]);
+        let hint = hint_with_action(HintInternalAction::Copy);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(hint.clone());
+        assert_eq!(state.target(), None);
+        state.update_matches(&term);
+        state.set_target(Some(HintInternalAction::Paste));
+
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.target, Some(HintInternalAction::Paste));
+        assert!(!state.is_active());
+
+        // The next invocation defaults to the last target
+        state.start(hint.clone());
+        assert_eq!(state.target(), Some(&HintInternalAction::Paste));
+        state.update_matches(&term);
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.target, Some(HintInternalAction::Paste));
+
+        // Other hints keep their own memory
+        state.start(url_hint());
+        assert_eq!(state.target(), None);
+    }
+
+    #[test]
+    fn test_overridden_target_replaces_remembered_one() {
+        let mut term = term_with_rows(16, &[("see src/main.rs", false)]);
+        term.current_directory = Some(PathBuf::from("/work/rio"));
+        let hint = hint_with_action(HintInternalAction::Copy);
+
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(hint.clone());
+        state.update_matches(&term);
+        state.set_target(Some(HintInternalAction::Paste));
+        state.keyboard_input(&term, 'a').unwrap();
+
+        state.start(hint.clone());
+        state.update_matches(&term);
+        state.set_target(Some(HintInternalAction::Open));
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.target, Some(HintInternalAction::Open));
+        // The override decides whether the match opens
+        assert!(hint_match.opens());
+        assert!(hint_match.working_directory.is_some());
+
+        // Going back to the configured action forgets the target
+        state.start(hint.clone());
+        assert_eq!(state.target(), Some(&HintInternalAction::Open));
+        state.update_matches(&term);
+        state.set_target(None);
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.target, None);
+
+        state.start(hint);
+        assert_eq!(state.target(), None);
+    }
+
+    #[test]
+    fn test_cycle_target_wraps_to_configured_action() {
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(hint_with_action(HintInternalAction::Copy));
+
+        let mut seen = Vec::new();
+        for _ in 0..HINT_TARGETS.len() {
+            state.cycle_target();
+            seen.push(state.target().cloned().unwrap());
+        }
+        assert_eq!(seen, HINT_TARGETS.to_vec());
+
+        state.cycle_target();
+        assert_eq!(state.target(), None);
+    }
+
+    fn state_with_matches(strategy: HintLabelStrategy, count: usize) -> HintState {
+        let mut state = hint_state("abc", strategy);
+        let hint = hint_with_action(HintInternalAction::Copy);
+        for col in 0..count {
+            state.matches.push(HintMatch {
+                text: "match".to_string(),
+                start: Pos::new(Line(0), Column(col)),
+                end: Pos::new(Line(0), Column(col)),
+                hint: hint.clone(),
+                working_directory: None,
+                target: None,
+            });
+        }
+        state.generate_labels();
+        state
+    }
+
+    #[test]
+    fn test_matches_with_labels() {
+        let state = state_with_matches(HintLabelStrategy::Fixed, 3);
+        let pairs = state.matches_with_labels();
+        assert_eq!(pairs.len(), 3);
+        for (index, (hint_match, label)) in pairs.iter().enumerate() {
+            assert_eq!(hint_match.start.col, Column(index));
+            assert_eq!(*label, state.labels[index].as_slice());
+        }
+    }
+
+    #[test]
+    fn test_matches_with_labels_mismatched_counts() {
+        let mut state = state_with_matches(HintLabelStrategy::Fixed, 3);
+        state.labels.truncate(2);
+        assert_eq!(state.matches_with_labels().len(), 2);
+
+        state.matches.clear();
+        assert!(state.matches_with_labels().is_empty());
+    }
+
+    #[test]
+    fn test_active_hint() {
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        assert!(state.active_hint().is_none());
+
+        let hint = hint_with_action(HintInternalAction::Copy);
+        state.start(hint.clone());
+        let active = state.active_hint().unwrap();
+        assert_eq!(active.regex, hint.regex);
+        assert_eq!(active.hyperlinks, hint.hyperlinks);
+
+        state.stop();
+        assert!(state.active_hint().is_none());
+    }
+
+    fn assert_prefix_free(labels: &[Vec<char>]) {
+        for (i, a) in labels.iter().enumerate() {
+            for (j, b) in labels.iter().enumerate() {
+                if i != j {
+                    assert!(!b.starts_with(a), "{a:?} is a prefix of {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_variable_labels_are_prefix_free() {
+        for count in 1..=40 {
+            let state = state_with_matches(HintLabelStrategy::Variable, count);
+            assert_eq!(state.labels.len(), count);
+            assert_prefix_free(&state.labels);
+        }
+    }
+
+    #[test]
+    fn test_variable_labels_favor_earlier_matches() {
+        let state = state_with_matches(HintLabelStrategy::Variable, 5);
+        assert_eq!(
+            state.labels,
+            vec![
+                vec!['a'],
+                vec!['b'],
+                vec!['c', 'a'],
+                vec!['c', 'b'],
+                vec!['c', 'c'],
+            ]
+        );
+
+        // Fixed labels would all be two characters long here
+        let fixed = state_with_matches(HintLabelStrategy::Fixed, 5);
+        assert!(fixed.labels.iter().all(|label| label.len() == 2));
+
+        // Lengths never decrease with the match index
+        let state = state_with_matches(HintLabelStrategy::Variable, 20);
+        assert!(state.labels.windows(2).all(|w| w[0].len() <= w[1].len()));
+    }
+
+    #[test]
+    fn test_variable_labels_select_by_keyboard() {
+        let term = term_with_rows(16, &[("a b c d e", false)]);
+        let hint = Rc::new(Hint {
+            regex: Some("[a-e]".to_string()),
+            ..(*hint_with_action(HintInternalAction::Copy)).clone()
+        });
+
+        let mut state = hint_state("abc", HintLabelStrategy::Variable);
+        state.start(hint);
+        state.update_matches(&term);
+        assert_eq!(state.matches().len(), 5);
+
+        // Single-character labels complete right away
+        let hint_match = state.keyboard_input(&term, 'b').unwrap();
+        assert_eq!(hint_match.text, "b");
+    }
+
+    fn word_hint_state(filter_threshold: Option<usize>) -> HintState {
+        let hint = Rc::new(Hint {
+            regex: Some("\\w+".to_string()),
+            ..(*hint_with_action(HintInternalAction::Copy)).clone()
+        });
+        let mut state = hint_state("abcde", HintLabelStrategy::Fixed);
+        state.update_filter_threshold(filter_threshold);
+        state.start(hint);
+        state
+    }
+
+    #[test]
+    fn test_labels_below_filter_threshold() {
+        let term = term_with_rows(32, &[("foo1 foo2 bar1 bar2 baz1", false)]);
+        let mut state = word_hint_state(Some(5));
+        state.update_matches(&term);
+
+        assert_eq!(state.matches().len(), 5);
+        assert!(!state.filtering);
+        assert_eq!(state.labels.len(), 5);
+        assert!(state.labels.iter().all(|label| label.len() == 1));
+
+        let hint_match = state.keyboard_input(&term, 'c').unwrap();
+        assert_eq!(hint_match.text, "bar1");
+    }
+
+    #[test]
+    fn test_filter_above_threshold() {
+        let term = term_with_rows(32, &[("foo1 foo2 bar1 bar2 baz1", false)]);
+        let mut state = word_hint_state(Some(3));
+        state.update_matches(&term);
+
+        assert!(state.filtering);
+        assert!(state.labels.is_empty());
+        assert!(state.render_labels(32).is_empty());
+
+        // No match contains "x", the key is ignored
+        assert!(state.keyboard_input(&term, 'x').is_none());
+        assert_eq!(state.matches().len(), 5);
+
+        // Three matches contain "B", few enough for labels again
+        assert!(state.keyboard_input(&term, 'B').is_none());
+        assert!(!state.filtering);
+        let texts: Vec<&str> = state.matches().iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["bar1", "bar2", "baz1"]);
+        assert_eq!(state.labels, vec![vec!['a'], vec!['b'], vec!['c']]);
+
+        // Backspace drops the filter and goes back to filtering
+        assert!(state.keyboard_input(&term, '\x08').is_none());
+        assert!(state.filtering);
+        assert_eq!(state.matches().len(), 5);
+
+        state.keyboard_input(&term, 'b');
+        let hint_match = state.keyboard_input(&term, 'b').unwrap();
+        assert_eq!(hint_match.text, "bar2");
+    }
+
+    fn write_row(term: &mut Crosswords<VoidListener>, line: Line, text: &str) {
+        for (col, c) in text.chars().enumerate() {
+            term.grid[line][Column(col)].c = c;
+        }
+    }
+
+    /// Three screen lines scrolled up by two: "one" is above the
+    /// viewport, "two" inside it and "three" below it.
+    fn term_with_scrollback() -> Crosswords<VoidListener> {
+        let mut term = term_with_rows(
+            24,
+            &[
+                ("a http://one.com", false),
+                ("plain", false),
+                ("plain", false),
+            ],
+        );
+        term.scroll_up_relative(Line(0), 3);
+        write_row(&mut term, Line(0), "b http://two.com");
+        write_row(&mut term, Line(1), "plain");
+        write_row(&mut term, Line(2), "c http://three.com");
+        term.scroll_display(Scroll::Delta(2));
+        term
+    }
+
+    fn scrollback_url_hint() -> Rc<Hint> {
+        Rc::new(Hint {
+            scrollback: true,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
+            ..(*url_hint()).clone()
+        })
+    }
+
+    #[test]
+    fn test_viewport_hint_skips_scrollback() {
+        let term = term_with_scrollback();
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(url_hint());
+        state.update_matches(&term);
+
+        let texts: Vec<&str> = state.matches().iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["http://two.com"]);
+        assert_eq!(state.matches()[0].start, Pos::new(Line(0), Column(2)));
+    }
+
+    #[test]
+    fn test_scrollback_hint_matches_above_and_below_viewport() {
+        let term = term_with_scrollback();
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(scrollback_url_hint());
+        state.update_matches(&term);
+
+        let found: Vec<(&str, Line)> = state
+            .matches()
+            .iter()
+            .map(|m| (m.text.as_str(), m.start.row))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("http://one.com", Line(-3)),
+                ("http://two.com", Line(0)),
+                ("http://three.com", Line(2)),
+            ]
+        );
+        assert_eq!(state.labels.len(), 3);
+    }
+
+    #[test]
+    fn test_scrollback_lines_caps_the_scan() {
+        let term = term_with_scrollback();
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.update_scrollback_lines(4);
+        state.start(scrollback_url_hint());
+        state.update_matches(&term);
+
+        // The viewport is always scanned, older lines past the cap are not
+        let texts: Vec<&str> = state.matches().iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["http://two.com", "http://three.com"]);
+    }
+
+    #[test]
+    fn test_scroll_to_match_reveals_selected_match() {
+        let mut term = term_with_scrollback();
+        let mut state = hint_state("abc", HintLabelStrategy::Fixed);
+        state.start(scrollback_url_hint());
+        state.update_matches(&term);
+
+        // Above the viewport
+        let hint_match = state.keyboard_input(&term, 'a').unwrap();
+        assert_eq!(hint_match.text, "http://one.com");
+        assert!(scroll_to_match(&mut term, &hint_match));
+        assert_eq!(term.grid.display_offset(), 3);
+
+        // Below the viewport
+        state.start(scrollback_url_hint());
+        state.update_matches(&term);
+        let hint_match = state.keyboard_input(&term, 'c').unwrap();
+        assert_eq!(hint_match.text, "http://three.com");
+        assert!(scroll_to_match(&mut term, &hint_match));
+        assert_eq!(term.grid.display_offset(), 0);
+
+        // Already visible
+        assert!(!scroll_to_match(&mut term, &hint_match));
+    }
 }
@@ -154,6 +154,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    if args.window_options.terminal_options.generate_config {
+        match rio_backend::config::example::generate_example_config() {
+            Ok(content) => print!("{content}"),
+            Err(err) => return Err(err.into()),
+        }
+        return Ok(());
+    }
+
+    if args.window_options.terminal_options.print_layout {
+        let path = router::alignment::layout_snapshot_path()
+            .ok_or("could not find the cache directory")?;
+        print!("{}", std::fs::read_to_string(path)?);
+        return Ok(());
+    }
+
     let (mut config, config_error) = match rio_backend::config::Config::try_load() {
         Ok(config) => (config, None),
         Err(err) => (rio_backend::config::Config::default(), Some(err)),
@@ -1,26 +1,266 @@
-use rio_backend::config::colors::Colors;
+use crate::screen::leader::PaletteView;
+use rio_backend::config::colors::{ColorArray, Colors};
 use rio_backend::config::leader::LeaderItem;
-use rio_backend::sugarloaf::{Object, Quad, RichText};
+use rio_backend::sugarloaf::{FragmentStyle, Object, Quad, RichText};
 
-/// Draw the leader menu overlay
+/// Highlight color for item keys when the item has no foreground
+pub const LEADER_KEY_COLOR: ColorArray = [0.54, 0.71, 0.99, 1.0];
+
+/// Resolve the key and label styles of a leader item, per-item colors
+/// take precedence and unset ones fall back to the theme.
 #[inline]
-pub fn draw_leader_menu(
+pub fn leader_item_styles(
+    item: &LeaderItem,
+    colors: &Colors,
+) -> (FragmentStyle, FragmentStyle) {
+    let key_style = FragmentStyle {
+        color: item.foreground.unwrap_or(LEADER_KEY_COLOR),
+        background_color: item.background,
+        ..FragmentStyle::default()
+    };
+
+    let label_style = FragmentStyle {
+        color: item.foreground.unwrap_or(colors.foreground),
+        background_color: item.background,
+        ..FragmentStyle::default()
+    };
+
+    (key_style, label_style)
+}
+
+/// Title of the root level of the leader menu
+pub const LEADER_ROOT_TITLE: &str = "Rio Commands";
+
+/// Breadcrumb for the opened submenus, e.g. "Rio Commands > Splits"
+pub fn leader_breadcrumb(path: &[String]) -> String {
+    let mut breadcrumb = String::from(LEADER_ROOT_TITLE);
+    for label in path {
+        breadcrumb.push_str(" > ");
+        breadcrumb.push_str(label);
+    }
+    breadcrumb
+}
+
+/// Title line of the leader menu: the breadcrumb, followed by the search
+/// while one is typed, e.g. "Rio Commands  / spl_"
+pub fn leader_title(path: &[String], search: &str) -> String {
+    let mut title = leader_breadcrumb(path);
+    if !search.is_empty() {
+        title.push_str("  / ");
+        title.push_str(search);
+        title.push('_');
+    }
+    title
+}
+
+/// Rows taken by the description footer: a blank separator and the
+/// description itself, none when there is no description
+#[inline]
+pub fn footer_rows(footer: &str) -> usize {
+    if footer.is_empty() {
+        0
+    } else {
+        2
+    }
+}
+
+/// First line of the command palette: the query and the match count
+pub fn palette_prompt(palette: &PaletteView) -> String {
+    format!("> {}_  ({})", palette.query, palette.total)
+}
+
+/// Draw the command palette box, centered near the top of the window
+#[inline]
+pub fn draw_command_palette(
     objects: &mut Vec<Object>,
     rich_text_id: usize,
     colors: &Colors,
-    items: &[LeaderItem],
+    rows: usize,
+    footer: &str,
     dimensions: (f32, f32, f32),
 ) {
     let (width, height, scale) = dimensions;
     let scaled_width = width / scale;
     let scaled_height = height / scale;
 
-    // Menu dimensions - auto-size based on items
     let item_height = 20.0;
     let padding = 16.0;
-    let menu_width = 220.0_f32.min(scaled_width - 20.0);
+    let menu_width = 480.0_f32.min(scaled_width - 20.0);
+    // Keep the height of a single row when nothing matches
+    let rows = rows.max(1) + footer_rows(footer);
     let menu_height =
-        (items.len() as f32 * item_height + padding * 4.0).min(scaled_height - 20.0);
+        (rows as f32 * item_height + padding * 4.0).min(scaled_height - 20.0);
+    let menu_x = (scaled_width - menu_width) / 2.0;
+    let menu_y = scaled_height * 0.15;
+
+    objects.push(Object::Quad(Quad {
+        position: [menu_x, menu_y],
+        color: colors.bar,
+        size: [menu_width, menu_height],
+        border_radius: [8.0, 8.0, 8.0, 8.0],
+        ..Quad::default()
+    }));
+
+    objects.push(Object::RichText(RichText {
+        id: rich_text_id,
+        position: [menu_x + padding, menu_y + 8.0],
+        lines: None,
+    }));
+}
+
+/// Approximate width of a character of the menu text
+const LEADER_CHAR_WIDTH: f32 = 8.0;
+const LEADER_ITEM_HEIGHT: f32 = 20.0;
+const LEADER_PADDING: f32 = 16.0;
+const LEADER_MIN_WIDTH: f32 = 220.0;
+/// Space kept between the menu and the window edges
+const LEADER_WINDOW_INSET: f32 = 20.0;
+/// Rows a column grows to before another column is added
+const LEADER_ROWS_PER_COLUMN: usize = 8;
+/// Characters before the label: the key (" n ") and two spaces
+const LEADER_KEY_CHARS: usize = 5;
+/// Characters between two columns
+const LEADER_COLUMN_GAP: usize = 2;
+
+/// Layout of the leader menu items: a grid filled column by column, with
+/// as many columns as the window fits and the items need.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeaderGrid {
+    /// Number of items laid out
+    pub items: usize,
+    pub columns: usize,
+    /// Rows needed to show every item
+    pub rows: usize,
+    /// Rows that fit the window, fewer than `rows` when the menu scrolls
+    pub visible_rows: usize,
+    /// Characters each column takes, gap included
+    pub column_chars: usize,
+    /// Panel size in logical pixels
+    pub width: f32,
+    pub height: f32,
+}
+
+impl LeaderGrid {
+    /// Lay out `items` under `title` and above `footer` in a window of
+    /// `scaled_size` logical pixels.
+    pub fn new(
+        items: &[LeaderItem],
+        title: &str,
+        footer: &str,
+        scaled_size: (f32, f32),
+    ) -> Self {
+        let (scaled_width, scaled_height) = scaled_size;
+        let longest_label = items
+            .iter()
+            .map(|item| item.label.chars().count() + usize::from(item.is_submenu()))
+            .max()
+            .unwrap_or(0);
+        let column_chars = LEADER_KEY_CHARS + longest_label + LEADER_COLUMN_GAP;
+
+        // Columns the window fits, the last one doesn't need its gap
+        let max_width = scaled_width - LEADER_WINDOW_INSET;
+        let text_chars = ((max_width - LEADER_PADDING * 2.0) / LEADER_CHAR_WIDTH).max(0.0)
+            as usize
+            + LEADER_COLUMN_GAP;
+        let max_columns = (text_chars / column_chars).max(1);
+        let columns = items
+            .len()
+            .div_ceil(LEADER_ROWS_PER_COLUMN)
+            .clamp(1, max_columns);
+        let rows = items.len().div_ceil(columns);
+
+        // Keep a row for the scroll indicator when the rows don't all fit
+        let footer_rows = footer_rows(footer);
+        let max_height = scaled_height - LEADER_WINDOW_INSET;
+        let fitting_rows =
+            ((max_height - LEADER_PADDING * 4.0) / LEADER_ITEM_HEIGHT).max(0.0) as usize;
+        let fitting_rows = fitting_rows.saturating_sub(footer_rows).max(1);
+        let (visible_rows, indicator_rows) = if rows > fitting_rows {
+            ((fitting_rows - 1).max(1), 1)
+        } else {
+            (rows, 0)
+        };
+
+        let grid_chars = (columns * column_chars).saturating_sub(LEADER_COLUMN_GAP);
+        let text_chars = grid_chars
+            .max(title.chars().count())
+            .max(footer.chars().count());
+        let width = (text_chars as f32 * LEADER_CHAR_WIDTH + LEADER_PADDING * 2.0)
+            .max(LEADER_MIN_WIDTH)
+            .min(max_width);
+        let shown_rows = visible_rows + indicator_rows + footer_rows;
+        let height = (shown_rows as f32 * LEADER_ITEM_HEIGHT + LEADER_PADDING * 4.0)
+            .min(max_height);
+
+        Self {
+            items: items.len(),
+            columns,
+            rows,
+            visible_rows,
+            column_chars,
+            width,
+            height,
+        }
+    }
+
+    /// Whether some rows are hidden and the menu scrolls
+    #[inline]
+    pub fn scrolls(&self) -> bool {
+        self.visible_rows < self.rows
+    }
+
+    /// Index of the item at `row` and `column`, `None` for the empty
+    /// cells at the end of the last column
+    #[inline]
+    pub fn item_at(&self, row: usize, column: usize) -> Option<usize> {
+        let index = column * self.rows + row;
+        (row < self.rows && index < self.items).then_some(index)
+    }
+
+    /// Spaces after a label of `label_chars` characters to reach the next
+    /// column
+    #[inline]
+    pub fn cell_padding(&self, label_chars: usize) -> usize {
+        self.column_chars
+            .saturating_sub(LEADER_KEY_CHARS + label_chars)
+    }
+
+    /// First row shown, scrolled just enough to keep `highlighted` in view
+    pub fn first_visible_row(&self, highlighted: Option<usize>) -> usize {
+        let Some(index) = highlighted.filter(|index| *index < self.items) else {
+            return 0;
+        };
+        let row = index % self.rows;
+        (row + 1).saturating_sub(self.visible_rows)
+    }
+
+    /// Line below the items of a scrolling menu, e.g. "↑ rows 3-10 of 14 ↓"
+    pub fn scroll_indicator(&self, first_row: usize) -> String {
+        let last_row = (first_row + self.visible_rows).min(self.rows);
+        let up = if first_row > 0 { "↑ " } else { "" };
+        let down = if last_row < self.rows { " ↓" } else { "" };
+        format!(
+            "{up}rows {}-{last_row} of {}{down}",
+            first_row + 1,
+            self.rows
+        )
+    }
+}
+
+/// Draw the leader menu overlay, sized by `grid`
+#[inline]
+pub fn draw_leader_menu(
+    objects: &mut Vec<Object>,
+    rich_text_id: usize,
+    colors: &Colors,
+    grid: &LeaderGrid,
+    dimensions: (f32, f32, f32),
+) {
+    let (width, height, scale) = dimensions;
+    let scaled_width = width / scale;
+    let scaled_height = height / scale;
+    let menu_width = grid.width;
+    let menu_height = grid.height;
 
     // Position at bottom-right with margin
     let margin = 10.0;
@@ -55,9 +295,162 @@ pub fn draw_leader_menu(
     // Rich text for menu content
     objects.push(Object::RichText(RichText {
         id: rich_text_id,
-        position: [menu_x + 16.0, menu_y + 8.0],
+        position: [menu_x + LEADER_PADDING, menu_y + 8.0],
         lines: None,
     }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leader_item_styles_fallback_to_theme() {
+        let colors = Colors::default();
+        let item = LeaderItem {
+            key: 'n',
+            label: "New window".to_string(),
+            ..LeaderItem::default()
+        };
+
+        let (key_style, label_style) = leader_item_styles(&item, &colors);
+        assert_eq!(key_style.color, LEADER_KEY_COLOR);
+        assert_eq!(label_style.color, colors.foreground);
+        assert_eq!(label_style.background_color, None);
+    }
+
+    #[test]
+    fn test_leader_item_styles_custom_colors() {
+        let colors = Colors::default();
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let item = LeaderItem {
+            key: 'x',
+            label: "Close".to_string(),
+            foreground: Some(red),
+            background: Some(black),
+            ..LeaderItem::default()
+        };
+
+        let (key_style, label_style) = leader_item_styles(&item, &colors);
+        assert_eq!(key_style.color, red);
+        assert_eq!(label_style.color, red);
+        assert_eq!(key_style.background_color, Some(black));
+        assert_eq!(label_style.background_color, Some(black));
+    }
+
+    #[test]
+    fn test_palette_prompt() {
+        let palette = PaletteView {
+            query: "split".to_string(),
+            total: 3,
+            ..PaletteView::default()
+        };
+        assert_eq!(palette_prompt(&palette), "> split_  (3)");
+    }
+
+    #[test]
+    fn test_leader_title_shows_search() {
+        let path = ["Splits".to_string()];
+        assert_eq!(leader_title(&path, ""), "Rio Commands > Splits");
+        assert_eq!(leader_title(&path, "do"), "Rio Commands > Splits  / do_");
+    }
+
+    fn items(count: usize, label: &str) -> Vec<LeaderItem> {
+        (0..count)
+            .map(|index| LeaderItem {
+                key: char::from(b'a' + index as u8),
+                label: label.to_string(),
+                ..LeaderItem::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_leader_grid_columns_follow_item_count() {
+        let size = (1600.0, 900.0);
+        let grid = LeaderGrid::new(&items(5, "Split right"), "", "", size);
+        assert_eq!((grid.columns, grid.rows), (1, 5));
+        assert_eq!(grid.width, LEADER_MIN_WIDTH);
+
+        let grid = LeaderGrid::new(&items(15, "Split right"), "", "", size);
+        assert_eq!((grid.columns, grid.rows), (2, 8));
+
+        let grid = LeaderGrid::new(&items(20, "Split right"), "", "", size);
+        assert_eq!((grid.columns, grid.rows), (3, 7));
+        // Three columns of 18 characters without the trailing gap
+        assert_eq!(grid.width, 52.0 * LEADER_CHAR_WIDTH + LEADER_PADDING * 2.0);
+        assert_eq!(grid.height, 7.0 * LEADER_ITEM_HEIGHT + LEADER_PADDING * 4.0);
+        assert!(!grid.scrolls());
+    }
+
+    #[test]
+    fn test_leader_grid_columns_limited_by_width() {
+        // 18 characters per column, 300px fits one
+        let grid = LeaderGrid::new(&items(20, "Split right"), "", "", (300.0, 900.0));
+        assert_eq!((grid.columns, grid.rows), (1, 20));
+
+        // Longer labels leave room for fewer columns
+        let grid = LeaderGrid::new(&items(20, "Split right"), "", "", (500.0, 900.0));
+        assert_eq!(grid.columns, 3);
+        let grid = LeaderGrid::new(&items(20, &"x".repeat(60)), "", "", (500.0, 900.0));
+        assert_eq!((grid.columns, grid.rows), (1, 20));
+        assert_eq!(grid.width, 480.0);
+    }
+
+    #[test]
+    fn test_leader_grid_title_widens_panel() {
+        let title = "t".repeat(40);
+        let grid = LeaderGrid::new(&items(3, "New"), &title, "", (1600.0, 900.0));
+        assert_eq!(grid.width, 40.0 * LEADER_CHAR_WIDTH + LEADER_PADDING * 2.0);
+    }
+
+    #[test]
+    fn test_leader_grid_scrolls_when_rows_overflow() {
+        // (300 - 20 - 64) / 20 = 10 rows fit, one is kept for the indicator
+        let grid = LeaderGrid::new(&items(20, "Split right"), "", "", (300.0, 300.0));
+        assert_eq!(grid.rows, 20);
+        assert_eq!(grid.visible_rows, 9);
+        assert!(grid.scrolls());
+        assert_eq!(
+            grid.height,
+            10.0 * LEADER_ITEM_HEIGHT + LEADER_PADDING * 4.0
+        );
+
+        assert_eq!(grid.first_visible_row(None), 0);
+        assert_eq!(grid.first_visible_row(Some(8)), 0);
+        assert_eq!(grid.first_visible_row(Some(12)), 4);
+        assert_eq!(grid.scroll_indicator(0), "rows 1-9 of 20 ↓");
+        assert_eq!(grid.scroll_indicator(4), "↑ rows 5-13 of 20 ↓");
+        assert_eq!(grid.scroll_indicator(11), "↑ rows 12-20 of 20");
+    }
+
+    #[test]
+    fn test_leader_grid_column_major_order() {
+        let grid = LeaderGrid::new(&items(10, "Split right"), "", "", (1600.0, 900.0));
+        assert_eq!((grid.columns, grid.rows), (2, 5));
+        assert_eq!(grid.item_at(0, 0), Some(0));
+        assert_eq!(grid.item_at(1, 0), Some(1));
+        assert_eq!(grid.item_at(0, 1), Some(5));
+        assert_eq!(grid.item_at(4, 1), Some(9));
+        assert_eq!(grid.item_at(5, 0), None);
+
+        let grid = LeaderGrid::new(&items(9, "Split right"), "", "", (1600.0, 900.0));
+        assert_eq!(grid.item_at(4, 1), None);
+    }
+
+    #[test]
+    fn test_footer_rows() {
+        assert_eq!(footer_rows(""), 0);
+        assert_eq!(footer_rows("Open a new window"), 2);
+    }
 
-    let _ = items; // Items will be rendered via the rich text
+    #[test]
+    fn test_leader_breadcrumb() {
+        assert_eq!(leader_breadcrumb(&[]), "Rio Commands");
+        assert_eq!(
+            leader_breadcrumb(&["Splits".to_string(), "Move".to_string()]),
+            "Rio Commands > Splits > Move"
+        );
+    }
 }
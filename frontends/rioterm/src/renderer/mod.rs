@@ -7,6 +7,7 @@ pub mod utils;
 
 use crate::context::renderable::TerminalSnapshot;
 use crate::renderer::font_cache::FontCache;
+use crate::screen::leader::PaletteView;
 use char_cache::CharCache;
 use rio_backend::crosswords::LineDamage;
 use rio_backend::event::TerminalDamage;
@@ -23,11 +24,12 @@ use rio_backend::config::colors::{
     term::{List, DIM_FACTOR},
     AnsiColor, ColorArray, Colors, NamedColor,
 };
+use rio_backend::config::window::BackgroundGradient;
 use rio_backend::config::Config;
 use rio_backend::event::EventProxy;
 use rio_backend::sugarloaf::{
-    drawable_character, Content, FragmentStyle, FragmentStyleDecoration, Graphic, Quad,
-    Stretch, Style, SugarCursor, Sugarloaf, UnderlineInfo, UnderlineShape, Weight,
+    drawable_character, Content, FragmentStyle, FragmentStyleDecoration, Graphic, Object,
+    Quad, Stretch, Style, SugarCursor, Sugarloaf, UnderlineInfo, UnderlineShape, Weight,
 };
 use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::ops::RangeInclusive;
@@ -45,6 +47,14 @@ pub struct LeaderMenu {
     rich_text_id: Option<usize>,
     active: bool,
     items: Vec<rio_backend::config::leader::LeaderItem>,
+    path: Vec<String>,
+    /// Text typed to filter `items`, shown next to the breadcrumb
+    search: String,
+    palette: Option<PaletteView>,
+    /// Item of `items` picked with the arrow keys
+    highlighted: Option<usize>,
+    /// Description of the highlighted item, empty when there is none
+    footer: String,
 }
 
 /// A recorded cursor position for the motion trail effect.
@@ -74,6 +84,12 @@ pub struct Renderer {
     ignore_selection_fg_color: bool,
     pub search: Search,
     pub leader_menu: LeaderMenu,
+    content_background_color: Option<ColorArray>,
+    /// Gradient drawn behind everything, unset when a background image
+    /// takes its place.
+    background_gradient: Option<BackgroundGradient>,
+    has_background_image: bool,
+    window_opacity: f32,
     #[allow(unused)]
     pub option_as_alt: String,
     #[allow(unused)]
@@ -127,6 +143,31 @@ fn resolve_glow_color(
     }
 }
 
+/// Background color of the window surface: the original color, the
+/// color actually cleared with and whether cells with the original
+/// background are left for it to show through.
+fn dynamic_background(
+    named_colors: &Colors,
+    has_gradient: bool,
+    has_background_image: bool,
+    opacity: f32,
+) -> ([f32; 4], wgpu::Color, bool) {
+    let mut dynamic_background =
+        (named_colors.background.0, named_colors.background.1, false);
+    if has_gradient {
+        // The gradient replaces the background color, opacity included
+        dynamic_background.1 = wgpu::Color::TRANSPARENT;
+        dynamic_background.2 = true;
+    } else if opacity < 1. {
+        dynamic_background.1.a = opacity as f64;
+        dynamic_background.2 = true;
+    } else if has_background_image {
+        dynamic_background.1 = wgpu::Color::TRANSPARENT;
+        dynamic_background.2 = true;
+    }
+    dynamic_background
+}
+
 impl Renderer {
     pub fn new(
         config: &Config,
@@ -135,20 +176,44 @@ impl Renderer {
         let colors = List::from(&config.colors);
         let named_colors = config.colors;
 
-        let mut dynamic_background =
-            (named_colors.background.0, named_colors.background.1, false);
-        if config.window.opacity < 1. {
-            dynamic_background.1.a = config.window.opacity as f64;
-            dynamic_background.2 = true;
-        } else if config.window.background_image.is_some() {
-            dynamic_background.1 = wgpu::Color::TRANSPARENT;
-            dynamic_background.2 = true;
-        }
+        let background_gradient = config
+            .window
+            .background_gradient
+            .filter(|_| config.window.background_image.is_none());
+        let has_background_image = config.window.background_image.is_some();
+
+        let dynamic_background = dynamic_background(
+            &named_colors,
+            background_gradient.is_some(),
+            has_background_image,
+            config.window.opacity,
+        );
 
         let mut color_automation: HashMap<String, HashMap<String, [f32; 4]>> =
             HashMap::new();
+        let mut hostname_automation = Vec::new();
+        let mut program_regex_automation = Vec::new();
 
         for rule in &config.navigation.color_automation {
+            // Hostname rules have empty program/path, keep them out of the
+            // map so they don't act as a catch-all
+            if rule.is_hostname_rule() {
+                hostname_automation.push(rule.clone());
+                continue;
+            }
+
+            // Compiled once here rather than on every title update
+            if let Some(regex) = rule.build_program_regex() {
+                match regex {
+                    Ok(regex) => program_regex_automation.push((regex, rule.clone())),
+                    Err(err) => tracing::warn!(
+                        "color-automation program-regex {:?} is invalid: {err}",
+                        rule.program_regex
+                    ),
+                }
+                continue;
+            }
+
             color_automation
                 .entry(rule.program.clone())
                 .or_default()
@@ -170,6 +235,10 @@ impl Renderer {
             navigation: ScreenNavigation::new(
                 config.navigation.clone(),
                 color_automation,
+                hostname_automation,
+                program_regex_automation,
+                config.window.colorspace,
+                config.padding_x,
                 config.padding_y,
             ),
             named_colors,
@@ -180,6 +249,10 @@ impl Renderer {
             progress_bar_last_state: rio_backend::ansi::ProgressState::Hidden,
             search: Search::default(),
             leader_menu: LeaderMenu::default(),
+            content_background_color: config.window.content_background_color,
+            background_gradient,
+            has_background_image,
+            window_opacity: config.window.opacity,
             font_cache: FontCache::new(),
             font_context: font_context.clone(),
             char_cache: CharCache::new(),
@@ -197,19 +270,61 @@ impl Renderer {
         renderer
     }
 
+    /// Pixels of the background gradient for a window of `window_size`,
+    /// `None` when there is no gradient.
+    #[inline]
+    pub fn background_gradient_pixels(
+        &self,
+        window_size: (f32, f32),
+    ) -> Option<(u32, u32, Vec<u8>)> {
+        let gradient = self.background_gradient.as_ref()?;
+        Some(utils::background_gradient_pixels(
+            gradient,
+            window_size,
+            self.window_opacity,
+        ))
+    }
+
+    /// Change the window opacity, e.g. while the window or the whole app
+    /// is unfocused. Returns false when it is already `opacity`.
+    pub fn set_window_opacity(&mut self, opacity: f32) -> bool {
+        if self.window_opacity == opacity {
+            return false;
+        }
+        self.window_opacity = opacity;
+        self.dynamic_background = dynamic_background(
+            &self.named_colors,
+            self.background_gradient.is_some(),
+            self.has_background_image,
+            opacity,
+        );
+        true
+    }
+
     #[inline]
     pub fn set_active_search(&mut self, active_search: Option<String>) {
         self.search.active_search = active_search;
     }
 
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub fn set_leader_menu(
         &mut self,
         active: bool,
         items: Vec<rio_backend::config::leader::LeaderItem>,
+        path: Vec<String>,
+        search: String,
+        palette: Option<PaletteView>,
+        highlighted: Option<usize>,
+        footer: String,
     ) {
         self.leader_menu.active = active;
         self.leader_menu.items = items;
+        self.leader_menu.path = path;
+        self.leader_menu.search = search;
+        self.leader_menu.palette = palette;
+        self.leader_menu.highlighted = highlighted;
+        self.leader_menu.footer = footer;
     }
 
     #[inline]
@@ -427,7 +542,11 @@ impl Renderer {
                 }
 
                 // Apply hint label styling
-                if hint_label.is_first {
+                if hint_label.pill.is_some() {
+                    // The pill drawn under the grid is the background
+                    style.color = self.named_colors.hint_foreground;
+                    style.background_color = None;
+                } else if hint_label.is_first {
                     // Use configurable hint colors
                     style.color = self.named_colors.hint_foreground;
                     style.background_color = Some(self.named_colors.hint_background);
@@ -443,13 +562,15 @@ impl Renderer {
                 }
 
                 // Make hint labels bold for better visibility
-                use rio_backend::sugarloaf::font_introspector::{Attributes, Weight};
-                let current_attrs = style.font_attrs;
-                style.font_attrs = Attributes::new(
-                    current_attrs.stretch(),
-                    Weight::BOLD,
-                    current_attrs.style(),
-                );
+                if hint_label.bold {
+                    use rio_backend::sugarloaf::font_introspector::{Attributes, Weight};
+                    let current_attrs = style.font_attrs;
+                    style.font_attrs = Attributes::new(
+                        current_attrs.stretch(),
+                        Weight::BOLD,
+                        current_attrs.style(),
+                    );
+                }
             }
 
             if !is_active {
@@ -919,46 +1040,130 @@ impl Renderer {
         }
     }
 
-    fn update_leader_rich_text(&self, content: &mut Content, rich_text_id: usize) {
+    fn update_leader_rich_text(
+        &self,
+        content: &mut Content,
+        rich_text_id: usize,
+        grid: &leader::LeaderGrid,
+    ) {
         let title_style = FragmentStyle {
             color: self.named_colors.foreground,
             ..FragmentStyle::default()
         };
 
-        let key_style = FragmentStyle {
-            color: [0.54, 0.71, 0.99, 1.0], // Blue highlight for keys
-            ..FragmentStyle::default()
-        };
-
-        let label_style = FragmentStyle {
-            color: self.named_colors.foreground,
-            ..FragmentStyle::default()
-        };
-
         let line = content.sel(rich_text_id);
         line.clear();
+
+        if let Some(palette) = &self.leader_menu.palette {
+            line.new_line();
+            line.add_text(&leader::palette_prompt(palette), title_style);
+            line.new_line();
+            line.new_line();
+
+            for (index, item) in palette.rows.iter().enumerate() {
+                let (_, label_style) =
+                    leader::leader_item_styles(item, &self.named_colors);
+                let style = if index == palette.selected {
+                    FragmentStyle {
+                        color: self.named_colors.selection_foreground,
+                        background_color: Some(self.named_colors.selection_background),
+                        ..label_style
+                    }
+                } else {
+                    label_style
+                };
+                line.add_text(&format!(" {} ", item.label), style);
+                line.new_line();
+            }
+
+            self.add_leader_footer(line);
+            line.build();
+            return;
+        }
+
         line.new_line();
-        line.add_text("Rio Commands", title_style);
+        line.add_text(
+            &leader::leader_title(&self.leader_menu.path, &self.leader_menu.search),
+            title_style,
+        );
         line.new_line();
         line.new_line();
 
-        for item in &self.leader_menu.items {
-            let key_display = match item.key {
-                ' ' => "SPC".to_string(),
-                '\n' => "RET".to_string(),
-                '\t' => "TAB".to_string(),
-                c => format!(" {} ", c),
-            };
+        // Items fill the grid column by column, only the rows in view are
+        // written
+        let first_row = grid.first_visible_row(self.leader_menu.highlighted);
+        let last_row = (first_row + grid.visible_rows).min(grid.rows);
+        for row in first_row..last_row {
+            for column in 0..grid.columns {
+                let Some(index) = grid.item_at(row, column) else {
+                    continue;
+                };
+                let item = &self.leader_menu.items[index];
+                let key_display = match item.key {
+                    ' ' => "SPC".to_string(),
+                    '\n' => "RET".to_string(),
+                    '\t' => "TAB".to_string(),
+                    c => format!(" {} ", c),
+                };
+
+                let (key_style, label_style) =
+                    leader::leader_item_styles(item, &self.named_colors);
+                let label_style = if self.leader_menu.highlighted == Some(index) {
+                    FragmentStyle {
+                        color: self.named_colors.selection_foreground,
+                        background_color: Some(self.named_colors.selection_background),
+                        ..label_style
+                    }
+                } else {
+                    label_style
+                };
 
-            line.add_text(&key_display, key_style);
-            line.add_text("  ", label_style);
-            line.add_text(&item.label, label_style);
+                let label = if item.is_submenu() {
+                    format!("+{}", item.label)
+                } else {
+                    item.label.clone()
+                };
+                line.add_text(&key_display, key_style);
+                line.add_text("  ", label_style);
+                line.add_text(&label, label_style);
+                if column + 1 < grid.columns {
+                    let padding = grid.cell_padding(label.chars().count());
+                    line.add_text(&" ".repeat(padding), title_style);
+                }
+            }
             line.new_line();
         }
 
+        if grid.scrolls() {
+            line.add_text(&grid.scroll_indicator(first_row), self.leader_dim_style());
+            line.new_line();
+        }
+
+        self.add_leader_footer(line);
         line.build();
     }
 
+    /// Append the highlighted item's description below the items
+    fn add_leader_footer(&self, line: &mut Content) {
+        if self.leader_menu.footer.is_empty() {
+            return;
+        }
+        line.new_line();
+        line.add_text(&self.leader_menu.footer, self.leader_dim_style());
+        line.new_line();
+    }
+
+    /// Style of the leader menu footer and scroll indicator
+    fn leader_dim_style(&self) -> FragmentStyle {
+        FragmentStyle {
+            color: self
+                .named_colors
+                .dim_foreground
+                .unwrap_or(self.named_colors.foreground),
+            ..FragmentStyle::default()
+        }
+    }
+
     #[inline]
     pub fn run(
         &mut self,
@@ -1313,6 +1518,7 @@ impl Renderer {
             if !overlay.visible {
                 continue;
             }
+            let opacity = overlay.style.opacity;
             let context = overlay.item.context_mut();
             context.renderable_content.pending_update.reset();
 
@@ -1356,7 +1562,7 @@ impl Renderer {
                     &None, // no focused match
                     &terminal_snapshot.colors,
                     true, // always render as "active"
-                    Some(grid.command_overlay_style.opacity),
+                    Some(opacity),
                 );
             }
             content.build();
@@ -1367,6 +1573,16 @@ impl Renderer {
         let window_size = sugarloaf.window_size();
         let scale_factor = sugarloaf.scale_factor();
         let mut objects = Vec::with_capacity(15);
+        // Content tint goes first so it sits under navigation and grid
+        if let Some(quad) = utils::content_background_quad(
+            self.content_background_color,
+            (window_size.width, window_size.height),
+            scale_factor,
+            &context_manager.current_grid().margin,
+        ) {
+            objects.push(Object::Quad(quad));
+        }
+
         self.navigation.build_objects(
             sugarloaf,
             (window_size.width, window_size.height, scale_factor),
@@ -1401,19 +1617,47 @@ impl Renderer {
             }
 
             if let Some(rich_text_id) = self.leader_menu.rich_text_id {
-                // Update rich text content with proper styling
-                self.update_leader_rich_text(sugarloaf.content(), rich_text_id);
-
-                leader::draw_leader_menu(
-                    &mut objects,
-                    rich_text_id,
-                    &self.named_colors,
+                let dimensions = (window_size.width, window_size.height, scale_factor);
+                let grid = leader::LeaderGrid::new(
                     &self.leader_menu.items,
-                    (window_size.width, window_size.height, scale_factor),
+                    &leader::leader_title(
+                        &self.leader_menu.path,
+                        &self.leader_menu.search,
+                    ),
+                    &self.leader_menu.footer,
+                    (
+                        window_size.width / scale_factor,
+                        window_size.height / scale_factor,
+                    ),
                 );
+
+                // Update rich text content with proper styling
+                self.update_leader_rich_text(sugarloaf.content(), rich_text_id, &grid);
+
+                if let Some(palette) = &self.leader_menu.palette {
+                    leader::draw_command_palette(
+                        &mut objects,
+                        rich_text_id,
+                        &self.named_colors,
+                        palette.rows.len(),
+                        &self.leader_menu.footer,
+                        dimensions,
+                    );
+                } else {
+                    leader::draw_leader_menu(
+                        &mut objects,
+                        rich_text_id,
+                        &self.named_colors,
+                        &grid,
+                        dimensions,
+                    );
+                }
             }
         }
 
+        // Hint pills go right under the grid so labels are drawn on top
+        self.push_hint_pills(context_manager, &mut objects);
+
         // let _duration = start.elapsed();
         context_manager
             .extend_with_grid_objects(&mut objects, self.named_colors.background.0);
@@ -1773,6 +2017,48 @@ impl Renderer {
         window_update
     }
 
+    /// Rounded quads behind the hint labels of the current pane that
+    /// asked for a pill.
+    fn push_hint_pills(
+        &self,
+        context_manager: &ContextManager<EventProxy>,
+        objects: &mut Vec<Object>,
+    ) {
+        let grid = context_manager.current_grid();
+        let ctx = grid.current();
+        let labels = &ctx.renderable_content.hint_labels;
+        if labels.iter().all(|label| label.pill.is_none()) {
+            return;
+        }
+
+        let (display_offset, screen_lines) = {
+            let terminal = ctx.terminal.lock();
+            (terminal.display_offset(), terminal.screen_lines())
+        };
+        let pane_pos = grid.current_position();
+        let dim = &ctx.dimension;
+        let scale = dim.dimension.scale;
+        let cell_w = dim.dimension.width / scale;
+        let cell_h = (dim.dimension.height / scale) * dim.line_height;
+
+        for (start, len, pill) in hint_pill_runs(labels) {
+            let row = start.row.0 + display_offset as i32;
+            if row < 0 || row as usize >= screen_lines {
+                continue;
+            }
+            objects.push(Object::Quad(Quad {
+                position: [
+                    pane_pos[0] + start.col.0 as f32 * cell_w,
+                    pane_pos[1] + row as f32 * cell_h,
+                ],
+                size: [len as f32 * cell_w, cell_h],
+                color: pill.color.unwrap_or(self.named_colors.hint_background),
+                border_radius: [pill.radius; 4],
+                ..Quad::default()
+            }));
+        }
+    }
+
     /// Find hint label at the specified position
     fn find_hint_label_at_position<'a>(
         &self,
@@ -1786,11 +2072,99 @@ impl Renderer {
     }
 }
 
+/// Group pill labels into runs of adjacent cells on the same row, each
+/// drawn as a single pill: (first cell, cell count, pill).
+fn hint_pill_runs(
+    labels: &[crate::context::renderable::HintLabel],
+) -> Vec<(Pos, usize, crate::context::renderable::HintPill)> {
+    let mut runs: Vec<(Pos, usize, crate::context::renderable::HintPill)> = Vec::new();
+    for label in labels {
+        let Some(pill) = label.pill else {
+            continue;
+        };
+        if !label.is_first {
+            if let Some((start, len, _)) = runs.last_mut() {
+                if start.row == label.position.row
+                    && start.col.0 + *len == label.position.col.0
+                {
+                    *len += 1;
+                    continue;
+                }
+            }
+        }
+        runs.push((label.position, 1, pill));
+    }
+    runs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rio_backend::crosswords::pos::{Column, Line, Pos};
 
+    #[test]
+    fn test_is_position_in_wrapped_hint_match() {
+        // Match starting near the end of row 0 and continuing on row 1
+        let matches = vec![Pos::new(Line(0), Column(8))..=Pos::new(Line(1), Column(3))];
+
+        assert!(Renderer::is_position_in_hint_matches(
+            &matches,
+            Pos::new(Line(0), Column(9))
+        ));
+        assert!(Renderer::is_position_in_hint_matches(
+            &matches,
+            Pos::new(Line(1), Column(0))
+        ));
+        assert!(!Renderer::is_position_in_hint_matches(
+            &matches,
+            Pos::new(Line(0), Column(7))
+        ));
+        assert!(!Renderer::is_position_in_hint_matches(
+            &matches,
+            Pos::new(Line(1), Column(4))
+        ));
+    }
+
+    #[test]
+    fn test_hint_pill_runs() {
+        use crate::context::renderable::{HintLabel, HintPill};
+
+        let pill = HintPill {
+            color: None,
+            radius: 4.0,
+        };
+        let label = |line: i32, col: usize, is_first: bool| HintLabel {
+            position: Pos::new(Line(line), Column(col)),
+            label: vec!['a'],
+            is_first,
+            bold: true,
+            pill: Some(pill),
+        };
+        let labels = vec![
+            label(0, 3, true),
+            label(0, 4, false),
+            // Next match starts right after, still its own pill
+            label(0, 5, true),
+            // Label wrapped onto the next row
+            label(0, 9, true),
+            label(1, 0, false),
+        ];
+
+        let runs = hint_pill_runs(&labels);
+        assert_eq!(
+            runs.iter()
+                .map(|(start, len, _)| (start.row.0, start.col.0, *len))
+                .collect::<Vec<_>>(),
+            vec![(0, 3, 2), (0, 5, 1), (0, 9, 1), (1, 0, 1)]
+        );
+
+        let plain = HintLabel {
+            pill: None,
+            ..label(2, 0, true)
+        };
+        assert!(hint_pill_runs(&[plain]).is_empty());
+    }
+
     #[test]
     fn test_is_position_in_hint_matches() {
         let matches = vec![
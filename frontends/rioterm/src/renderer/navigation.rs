@@ -1,30 +1,72 @@
 use crate::constants::*;
-use crate::context::title::ContextTitle;
+use crate::context::title::{ContextTitle, ContextTitleExtra};
+use regex::Regex;
 use rio_backend::config::colors::Colors;
-use rio_backend::config::navigation::{hsl_to_rgba, Navigation, NavigationMode};
+use rio_backend::config::navigation::{
+    BookmarkSpacingMode, BookmarkStyle, ColorAutomation, Navigation, NavigationMode,
+};
+use rio_backend::config::window::Colorspace;
 use rio_backend::sugarloaf::{FragmentStyle, Object, Quad, RichText, Sugarloaf};
 use rustc_hash::FxHashMap;
 use std::collections::HashMap;
 
 pub struct ScreenNavigation {
     pub navigation: Navigation,
+    pub padding_x: f32,
     pub padding_y: [f32; 2],
     color_automation: HashMap<String, HashMap<String, [f32; 4]>>,
+    hostname_automation: Vec<ColorAutomation>,
+    program_regex_automation: Vec<(Regex, ColorAutomation)>,
+    /// Window colorspace, generated bookmark hues are encoded for it
+    colorspace: Colorspace,
 }
 
 impl ScreenNavigation {
     pub fn new(
         navigation: Navigation,
         color_automation: HashMap<String, HashMap<String, [f32; 4]>>,
+        hostname_automation: Vec<ColorAutomation>,
+        program_regex_automation: Vec<(Regex, ColorAutomation)>,
+        colorspace: Colorspace,
+        padding_x: f32,
         padding_y: [f32; 2],
     ) -> ScreenNavigation {
         ScreenNavigation {
             navigation,
             color_automation,
+            hostname_automation,
+            program_regex_automation,
+            colorspace,
+            padding_x,
             padding_y,
         }
     }
 
+    #[inline]
+    fn has_color_automation(&self) -> bool {
+        !self.color_automation.is_empty()
+            || !self.hostname_automation.is_empty()
+            || !self.program_regex_automation.is_empty()
+    }
+
+    /// Hostname rules win over program regex rules, which win over
+    /// literal program/path rules.
+    #[inline]
+    fn color_overwrite(&self, extra: &ContextTitleExtra) -> Option<[f32; 4]> {
+        get_hostname_color_overwrite(&self.hostname_automation, &extra.title, &extra.host)
+            .or_else(|| {
+                get_program_regex_color_overwrite(
+                    &self.program_regex_automation,
+                    &extra.program,
+                    &extra.path,
+                )
+            })
+            .or_else(|| {
+                get_color_overwrite(&self.color_automation, &extra.program, &extra.path)
+                    .copied()
+            })
+    }
+
     #[inline]
     pub fn build_objects(
         &mut self,
@@ -88,6 +130,34 @@ impl ScreenNavigation {
                     dimensions,
                 );
             }
+            NavigationMode::LeftTab => {
+                self.side_tab(
+                    sugarloaf,
+                    objects,
+                    titles,
+                    colors,
+                    len,
+                    current,
+                    0.0,
+                    self.navigation.hide_if_single,
+                    dimensions,
+                );
+            }
+            NavigationMode::RightTab => {
+                let (width, _, scale) = dimensions;
+                let position_x = (width / scale) - SIDE_TABS_WIDTH;
+                self.side_tab(
+                    sugarloaf,
+                    objects,
+                    titles,
+                    colors,
+                    len,
+                    current,
+                    position_x,
+                    self.navigation.hide_if_single,
+                    dimensions,
+                );
+            }
             // Minimal simply does not do anything
             NavigationMode::Plain => {}
         }
@@ -113,11 +183,10 @@ impl ScreenNavigation {
         let (width, _, scale) = dimensions;
         let style = &self.navigation.bookmark_style;
 
-        let mut initial_position = (width / scale) - style.padding_x;
-        let position_modifier = style.spacing;
         let radius = style.border_radius;
+        let rects = bookmark_rects(style, len, width / scale);
 
-        for i in (0..len).rev() {
+        for (i, [position_x, indicator_width]) in rects.into_iter().enumerate() {
             // When quick terminal is active, no tab appears "active"
             let is_active = !qt_visible && i == current;
 
@@ -128,7 +197,7 @@ impl ScreenNavigation {
                 } else {
                     style.lightness_inactive
                 };
-                hsl_to_rgba(hue, style.saturation, lightness, 1.0)
+                style.hue_color(hue, lightness, self.colorspace)
             } else if is_active {
                 colors.tabs_active_highlight
             } else {
@@ -141,24 +210,27 @@ impl ScreenNavigation {
                 style.height_inactive
             };
 
+            let mut gradient_color = [0.0, 0.0, 0.0, 0.0];
+            if let Some((top, bottom)) = style.indicator_gradient(color, is_active) {
+                color = top;
+                gradient_color = bottom;
+            }
+
             if let Some(title) = titles.get(&i) {
-                if !self.color_automation.is_empty() {
+                if self.has_color_automation() {
                     if let Some(extra) = &title.extra {
-                        if let Some(color_overwrite) = get_color_overwrite(
-                            &self.color_automation,
-                            &extra.program,
-                            &extra.path,
-                        ) {
-                            color = *color_overwrite;
+                        if let Some(color_overwrite) = self.color_overwrite(extra) {
+                            color = color_overwrite;
+                            gradient_color = [0.0, 0.0, 0.0, 0.0];
                         }
                     }
                 }
             }
 
             let renderable = Quad {
-                position: [initial_position, 0.0],
+                position: [position_x, 0.0],
                 color,
-                size: [style.width, height],
+                size: [indicator_width, height],
                 border_radius: [radius, radius, radius, radius],
                 border_width: style.border_width,
                 border_color: style.border_color,
@@ -169,8 +241,8 @@ impl ScreenNavigation {
                 },
                 shadow_offset: style.shadow_offset,
                 shadow_blur_radius: style.shadow_blur_radius,
+                gradient_color,
             };
-            initial_position -= position_modifier;
             objects.push(Object::Quad(renderable));
         }
     }
@@ -216,37 +288,11 @@ impl ScreenNavigation {
         }
 
         for i in tabs {
-            let mut background_color = colors.bar;
-            let mut foreground_color = colors.tabs_foreground;
-
             let is_current = i == current;
-            if is_current {
-                foreground_color = colors.tabs_active_foreground;
-                background_color = colors.tabs_active;
-            }
-
-            let mut name = String::from("tab");
-            if let Some(title) = titles.get(&i) {
-                name = title.content.to_owned();
-
-                if !self.color_automation.is_empty() {
-                    if let Some(extra) = &title.extra {
-                        if let Some(color_overwrite) = get_color_overwrite(
-                            &self.color_automation,
-                            &extra.program,
-                            &extra.path,
-                        ) {
-                            foreground_color = colors.tabs;
-                            background_color = *color_overwrite;
-                        }
-                    }
-                }
-            }
+            let (name, foreground_color, background_color) =
+                self.tab_appearance(titles, colors, i, is_current);
 
             let name_modifier = 90.;
-            if name.len() >= 14 {
-                name = name[0..14].to_string();
-            }
 
             objects.push(Object::Quad(Quad {
                 position: [initial_position_x, position_y],
@@ -276,35 +322,210 @@ impl ScreenNavigation {
             } else {
                 format!("{}.{name}", i + 1)
             };
+            push_tab_label(
+                sugarloaf,
+                objects,
+                &text,
+                foreground_color,
+                [initial_position_x + 4., position_y],
+            );
 
-            let tab = sugarloaf.create_temp_rich_text();
-            sugarloaf.set_rich_text_font_size(&tab, 14.);
-            let content = sugarloaf.content();
-
-            let tab_line = content.sel(tab);
-            tab_line
-                .clear()
-                .new_line()
-                .add_text(
-                    &text,
-                    FragmentStyle {
-                        color: foreground_color,
-                        ..FragmentStyle::default()
-                    },
-                )
-                .build();
+            initial_position_x += name_modifier + 40.;
+        }
+    }
+
+    /// Vertical tab strip for LeftTab/RightTab: one tab per row, starting
+    /// at the top of the window.
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    pub fn side_tab(
+        &mut self,
+        sugarloaf: &mut Sugarloaf,
+        objects: &mut Vec<Object>,
+        titles: &FxHashMap<usize, ContextTitle>,
+        colors: &Colors,
+        len: usize,
+        current: usize,
+        position_x: f32,
+        hide_if_single: bool,
+        dimensions: (f32, f32, f32),
+    ) {
+        if hide_if_single && len <= 1 {
+            return;
+        }
+
+        let (_, height, scale) = dimensions;
+        let initial_position_y = PADDING_Y + self.padding_y[0];
 
-            objects.push(Object::RichText(RichText {
-                id: tab,
-                position: [initial_position_x + 4., position_y],
-                lines: None,
+        objects.push(Object::Quad(Quad {
+            position: [position_x, 0.0],
+            color: colors.bar,
+            size: [SIDE_TABS_WIDTH, height / scale],
+            ..Quad::default()
+        }));
+
+        let screen_limit = (((height / scale) - initial_position_y)
+            / PADDING_Y_BOTTOM_TABS)
+            .floor() as usize;
+        let tabs = visible_tabs(len, current, screen_limit);
+
+        // Highlight sits on the edge facing the terminal
+        let highlight_width = PADDING_Y_BOTTOM_TABS / 10.;
+        let highlight_x = if position_x == 0.0 {
+            SIDE_TABS_WIDTH - highlight_width
+        } else {
+            position_x
+        };
+
+        for (row, i) in tabs.enumerate() {
+            let is_current = i == current;
+            let (name, foreground_color, background_color) =
+                self.tab_appearance(titles, colors, i, is_current);
+            let position_y = initial_position_y + row as f32 * PADDING_Y_BOTTOM_TABS;
+
+            objects.push(Object::Quad(Quad {
+                position: [position_x, position_y],
+                color: background_color,
+                size: [SIDE_TABS_WIDTH, PADDING_Y_BOTTOM_TABS],
+                ..Quad::default()
             }));
 
-            initial_position_x += name_modifier + 40.;
+            if is_current {
+                objects.push(Object::Quad(Quad {
+                    position: [highlight_x, position_y],
+                    color: colors.tabs_active_highlight,
+                    size: [highlight_width, PADDING_Y_BOTTOM_TABS],
+                    ..Quad::default()
+                }));
+            }
+
+            let text = if is_current {
+                format!("▶ {name}")
+            } else {
+                format!("{}.{name}", i + 1)
+            };
+            push_tab_label(
+                sugarloaf,
+                objects,
+                &text,
+                foreground_color,
+                [position_x + 6., position_y],
+            );
+        }
+    }
+
+    /// Title (cut to 14 chars), foreground and background of tab `index`.
+    #[inline]
+    fn tab_appearance(
+        &self,
+        titles: &FxHashMap<usize, ContextTitle>,
+        colors: &Colors,
+        index: usize,
+        is_current: bool,
+    ) -> (String, [f32; 4], [f32; 4]) {
+        let mut background_color = colors.bar;
+        let mut foreground_color = colors.tabs_foreground;
+
+        if is_current {
+            foreground_color = colors.tabs_active_foreground;
+            background_color = colors.tabs_active;
+        }
+
+        let mut name = String::from("tab");
+        if let Some(title) = titles.get(&index) {
+            name = title.content.to_owned();
+
+            if self.has_color_automation() {
+                if let Some(extra) = &title.extra {
+                    if let Some(color_overwrite) = self.color_overwrite(extra) {
+                        foreground_color = colors.tabs;
+                        background_color = color_overwrite;
+                    }
+                }
+            }
+        }
+
+        if name.len() >= 14 {
+            name = name[0..14].to_string();
+        }
+
+        (name, foreground_color, background_color)
+    }
+}
+
+/// Horizontal placement `[x, width]` of each bookmark indicator, in tab
+/// order, on a bar `bar_width` wide.
+fn bookmark_rects(style: &BookmarkStyle, len: usize, bar_width: f32) -> Vec<[f32; 2]> {
+    match style.spacing_mode {
+        BookmarkSpacingMode::Fixed => {
+            let last = bar_width - style.padding_x;
+            (0..len)
+                .map(|i| [last - (len - 1 - i) as f32 * style.spacing, style.width])
+                .collect()
+        }
+        BookmarkSpacingMode::Fill => {
+            if len == 0 {
+                return Vec::new();
+            }
+            // Indicators shrink once they no longer fit side by side
+            let available = (bar_width - style.padding_x * 2.0).max(0.0);
+            let width = style.width.min(available / len as f32);
+            let gap = (available - width * len as f32) / (len + 1) as f32;
+            (0..len)
+                .map(|i| [style.padding_x + gap + i as f32 * (width + gap), width])
+                .collect()
         }
     }
 }
 
+#[inline]
+fn push_tab_label(
+    sugarloaf: &mut Sugarloaf,
+    objects: &mut Vec<Object>,
+    text: &str,
+    color: [f32; 4],
+    position: [f32; 2],
+) {
+    let tab = sugarloaf.create_temp_rich_text();
+    sugarloaf.set_rich_text_font_size(&tab, 14.);
+    let content = sugarloaf.content();
+
+    let tab_line = content.sel(tab);
+    tab_line
+        .clear()
+        .new_line()
+        .add_text(
+            text,
+            FragmentStyle {
+                color,
+                ..FragmentStyle::default()
+            },
+        )
+        .build();
+
+    objects.push(Object::RichText(RichText {
+        id: tab,
+        position,
+        lines: None,
+    }));
+}
+
+/// Tabs that fit in `screen_limit` rows, scrolled so `current` stays
+/// visible.
+#[inline]
+fn visible_tabs(
+    len: usize,
+    current: usize,
+    screen_limit: usize,
+) -> std::ops::Range<usize> {
+    let screen_limit = screen_limit.max(1);
+    if len > screen_limit && current >= screen_limit {
+        current + 1 - screen_limit..current + 1
+    } else {
+        0..len.min(screen_limit)
+    }
+}
+
 #[inline]
 fn get_color_overwrite<'a>(
     color_automation: &'a HashMap<String, HashMap<String, [f32; 4]>>,
@@ -317,11 +538,41 @@ fn get_color_overwrite<'a>(
         .or_else(|| color_automation.get("").and_then(|m| m.get(path)))
 }
 
+#[inline]
+fn get_program_regex_color_overwrite(
+    program_regex_automation: &[(Regex, ColorAutomation)],
+    program: &str,
+    path: &str,
+) -> Option<[f32; 4]> {
+    program_regex_automation
+        .iter()
+        .find(|(regex, rule)| rule.matches_program_regex(regex, program, path))
+        .map(|(_, rule)| rule.color)
+}
+
+#[inline]
+fn get_hostname_color_overwrite(
+    hostname_automation: &[ColorAutomation],
+    title: &str,
+    host: &str,
+) -> Option<[f32; 4]> {
+    hostname_automation
+        .iter()
+        .find(|rule| rule.matches_hostname(title, host))
+        .map(|rule| rule.color)
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::renderer::navigation::get_color_overwrite;
+    use crate::renderer::navigation::{
+        bookmark_rects, get_color_overwrite, get_hostname_color_overwrite,
+        get_program_regex_color_overwrite, visible_tabs,
+    };
+    use regex::Regex;
+    use rio_backend::config::navigation::ColorAutomation;
+    use rio_backend::config::navigation::{BookmarkSpacingMode, BookmarkStyle};
 
     #[test]
     fn test_get_color_overwrite() {
@@ -368,4 +619,126 @@ mod tests {
 
         assert_eq!(&neither, neither_result);
     }
+
+    #[test]
+    fn test_get_program_regex_color_overwrite() {
+        let ssh = [1.0, 0.0, 0.0, 1.0];
+        let rule = ColorAutomation {
+            program_regex: Some("^ssh-".to_string()),
+            color: ssh,
+            ..ColorAutomation::default()
+        };
+        let rules = vec![(Regex::new("^ssh-").unwrap(), rule)];
+
+        assert_eq!(
+            get_program_regex_color_overwrite(&rules, "ssh-prod", "/home/"),
+            Some(ssh)
+        );
+        assert_eq!(
+            get_program_regex_color_overwrite(&rules, "ssh-staging", ""),
+            Some(ssh)
+        );
+        assert_eq!(get_program_regex_color_overwrite(&rules, "mosh", ""), None);
+    }
+
+    fn fill_style(padding_x: f32) -> BookmarkStyle {
+        BookmarkStyle {
+            spacing_mode: BookmarkSpacingMode::Fill,
+            width: 15.0,
+            padding_x,
+            ..BookmarkStyle::default()
+        }
+    }
+
+    #[test]
+    fn test_bookmark_rects_fixed() {
+        let style = BookmarkStyle {
+            width: 15.0,
+            spacing: 20.0,
+            padding_x: 30.0,
+            ..BookmarkStyle::default()
+        };
+        let rects = bookmark_rects(&style, 3, 600.0);
+        assert_eq!(rects, vec![[530.0, 15.0], [550.0, 15.0], [570.0, 15.0]]);
+    }
+
+    #[test]
+    fn test_bookmark_rects_fill_equal_gaps() {
+        let rects = bookmark_rects(&fill_style(0.0), 3, 600.0);
+        assert_eq!(rects.len(), 3);
+
+        // (600 - 3 * 15) / 4 = 138.75 before, between and after
+        let gap = 138.75;
+        assert_eq!(rects[0], [gap, 15.0]);
+        assert_eq!(rects[1][0] - (rects[0][0] + 15.0), gap);
+        assert_eq!(rects[2][0] - (rects[1][0] + 15.0), gap);
+        assert_eq!(600.0 - (rects[2][0] + 15.0), gap);
+    }
+
+    #[test]
+    fn test_bookmark_rects_fill_respects_padding() {
+        let rects = bookmark_rects(&fill_style(30.0), 1, 600.0);
+        // Single indicator centered between the paddings
+        assert_eq!(rects, vec![[292.5, 15.0]]);
+    }
+
+    #[test]
+    fn test_bookmark_rects_fill_collapses_at_high_counts() {
+        let rects = bookmark_rects(&fill_style(0.0), 100, 600.0);
+        assert_eq!(rects.len(), 100);
+        for (i, [x, width]) in rects.iter().enumerate() {
+            assert!((width - 6.0).abs() < 1e-4);
+            assert!((x - i as f32 * 6.0).abs() < 1e-3);
+        }
+        let [last_x, last_width] = rects[99];
+        assert!(last_x + last_width <= 600.0 + 1e-3);
+
+        // No room at all still yields one rect per tab
+        let rects = bookmark_rects(&fill_style(400.0), 4, 600.0);
+        assert!(rects.iter().all(|[_, width]| *width == 0.0));
+        assert!(bookmark_rects(&fill_style(0.0), 0, 600.0).is_empty());
+    }
+
+    #[test]
+    fn test_visible_tabs_fit() {
+        assert_eq!(visible_tabs(3, 1, 10), 0..3);
+        assert_eq!(visible_tabs(12, 4, 10), 0..10);
+    }
+
+    #[test]
+    fn test_visible_tabs_scroll_to_current() {
+        assert_eq!(visible_tabs(12, 11, 10), 2..12);
+        assert_eq!(visible_tabs(12, 10, 10), 1..11);
+        // Window too short for a single row still shows the current tab
+        assert_eq!(visible_tabs(5, 3, 0), 3..4);
+        assert_eq!(visible_tabs(5, 0, 0), 0..1);
+    }
+
+    #[test]
+    fn test_get_hostname_color_overwrite() {
+        let prod = [1.0, 0.0, 0.0, 1.0];
+        let staging = [0.0, 1.0, 0.0, 1.0];
+        let rules = vec![
+            ColorAutomation {
+                hostname: "*.prod.example.com".to_owned(),
+                color: prod,
+                ..ColorAutomation::default()
+            },
+            ColorAutomation {
+                hostname: "staging".to_owned(),
+                color: staging,
+                ..ColorAutomation::default()
+            },
+        ];
+
+        assert_eq!(
+            get_hostname_color_overwrite(&rules, "", "db.prod.example.com"),
+            Some(prod)
+        );
+        assert_eq!(
+            get_hostname_color_overwrite(&rules, "me@staging-1: ~", ""),
+            Some(staging)
+        );
+        assert_eq!(get_hostname_color_overwrite(&rules, "zsh", "laptop"), None);
+    }
 }
@@ -1,7 +1,10 @@
 use crate::constants;
-use crate::context::grid::ContextDimension;
+use crate::context::grid::{ContextDimension, Delta};
+use rio_backend::config::colors::ColorArray;
 use rio_backend::config::navigation::{Navigation, NavigationMode};
+use rio_backend::config::window::BackgroundGradient;
 use rio_backend::config::Config;
+use rio_backend::sugarloaf::Quad;
 use rio_window::window::Theme;
 
 #[inline]
@@ -63,6 +66,44 @@ pub fn padding_bottom_from_config(
     default_padding
 }
 
+/// Left margin of the grid. `LeftTab` pushes the grid past the tab strip.
+#[inline]
+pub fn padding_x_from_config(
+    navigation: &Navigation,
+    padding_x: f32,
+    num_tabs: usize,
+) -> f32 {
+    if navigation.hide_if_single && num_tabs == 1 {
+        return padding_x;
+    }
+
+    if navigation.mode == NavigationMode::LeftTab {
+        return padding_x + constants::SIDE_TABS_WIDTH;
+    }
+
+    padding_x
+}
+
+/// Physical width available to the grid. `RightTab` keeps the tab strip
+/// on the right edge out of it.
+#[inline]
+pub fn grid_width_from_config(
+    navigation: &Navigation,
+    width: f32,
+    scale: f32,
+    num_tabs: usize,
+) -> f32 {
+    if navigation.hide_if_single && num_tabs == 1 {
+        return width;
+    }
+
+    if navigation.mode == NavigationMode::RightTab {
+        return (width - constants::SIDE_TABS_WIDTH * scale).max(0.0);
+    }
+
+    width
+}
+
 #[inline]
 pub fn terminal_dimensions(layout: &ContextDimension) -> teletypewriter::WinsizeBuilder {
     let width = layout.width - (layout.margin.x * 2.);
@@ -75,6 +116,77 @@ pub fn terminal_dimensions(layout: &ContextDimension) -> teletypewriter::Winsize
     }
 }
 
+/// Tint quad covering the grid area, inside the padding/margins.
+/// Coordinates are logical, like the grid item positions. Returns
+/// `None` when no tint is configured or the area is empty.
+#[inline]
+pub fn content_background_quad(
+    color: Option<ColorArray>,
+    window_size: (f32, f32),
+    scale: f32,
+    margin: &Delta<f32>,
+) -> Option<Quad> {
+    let color = color?;
+    let (width, height) = window_size;
+    let scaled_width = width / scale;
+    let scaled_height = height / scale;
+
+    // Grid items sit at [margin.x, margin.top_y] and extend to the right edge
+    let content_width = scaled_width - margin.x;
+    let content_height = scaled_height - margin.top_y - margin.bottom_y;
+    if content_width <= 0.0 || content_height <= 0.0 {
+        return None;
+    }
+
+    Some(Quad {
+        position: [margin.x, margin.top_y],
+        color,
+        size: [content_width, content_height],
+        ..Quad::default()
+    })
+}
+
+/// Width of the texture a background gradient is drawn into. Gradients
+/// are smooth, so a small texture is stretched to the window.
+const BACKGROUND_GRADIENT_WIDTH: u32 = 256;
+
+/// RGBA pixels of `gradient` for a window of `window_size`, with alpha
+/// scaled by the window `opacity`. The texture keeps the window aspect
+/// ratio so angled gradients aren't skewed once stretched.
+pub fn background_gradient_pixels(
+    gradient: &BackgroundGradient,
+    window_size: (f32, f32),
+    opacity: f32,
+) -> (u32, u32, Vec<u8>) {
+    let (window_width, window_height) = window_size;
+    let width = BACKGROUND_GRADIENT_WIDTH;
+    let height = if window_width > 0.0 {
+        ((width as f32 * window_height / window_width).round() as u32).clamp(1, width * 4)
+    } else {
+        1
+    };
+
+    let opacity = opacity.clamp(0.0, 1.0);
+    let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            // Sample at the pixel center
+            let mut color = gradient.color_at(
+                x as f32 + 0.5,
+                y as f32 + 0.5,
+                width as f32,
+                height as f32,
+            );
+            color[3] *= opacity;
+            pixels.extend(
+                color.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8),
+            );
+        }
+    }
+
+    (width, height, pixels)
+}
+
 #[inline]
 pub fn update_colors_based_on_theme(config: &mut Config, theme_opt: Option<Theme>) {
     if let Some(theme) = theme_opt {
@@ -94,3 +206,153 @@ pub fn update_colors_based_on_theme(config: &mut Config, theme_opt: Option<Theme
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn margin(x: f32, top_y: f32, bottom_y: f32) -> Delta<f32> {
+        Delta { x, top_y, bottom_y }
+    }
+
+    fn navigation(mode: NavigationMode, hide_if_single: bool) -> Navigation {
+        Navigation {
+            mode,
+            hide_if_single,
+            ..Navigation::default()
+        }
+    }
+
+    #[test]
+    fn test_padding_x_with_left_tab() {
+        let left = navigation(NavigationMode::LeftTab, false);
+        assert_eq!(
+            padding_x_from_config(&left, 10.0, 1),
+            10.0 + constants::SIDE_TABS_WIDTH
+        );
+
+        let right = navigation(NavigationMode::RightTab, false);
+        assert_eq!(padding_x_from_config(&right, 10.0, 3), 10.0);
+
+        // Hidden strip leaves the padding alone
+        let hidden = navigation(NavigationMode::LeftTab, true);
+        assert_eq!(padding_x_from_config(&hidden, 10.0, 1), 10.0);
+    }
+
+    #[test]
+    fn test_grid_width_with_right_tab() {
+        let right = navigation(NavigationMode::RightTab, false);
+        assert_eq!(
+            grid_width_from_config(&right, 1600.0, 2.0, 2),
+            1600.0 - constants::SIDE_TABS_WIDTH * 2.0
+        );
+        assert_eq!(grid_width_from_config(&right, 100.0, 1.0, 2), 0.0);
+
+        let left = navigation(NavigationMode::LeftTab, false);
+        assert_eq!(grid_width_from_config(&left, 1600.0, 2.0, 2), 1600.0);
+
+        let hidden = navigation(NavigationMode::RightTab, true);
+        assert_eq!(grid_width_from_config(&hidden, 1600.0, 2.0, 1), 1600.0);
+    }
+
+    #[test]
+    fn test_background_gradient_pixels() {
+        let gradient = BackgroundGradient {
+            from: [1.0, 0.0, 0.0, 1.0],
+            to: [0.0, 0.0, 1.0, 1.0],
+            angle: 90.0,
+        };
+        let (width, height, pixels) =
+            background_gradient_pixels(&gradient, (1600.0, 800.0), 1.0);
+        assert_eq!((width, height), (256, 128));
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
+
+        // Red on the left edge, blue on the right, on every row
+        let pixel = |x: u32, y: u32| {
+            let index = ((y * width + x) * 4) as usize;
+            [
+                pixels[index],
+                pixels[index + 1],
+                pixels[index + 2],
+                pixels[index + 3],
+            ]
+        };
+        // Pixel centers sit half a pixel inside the edges
+        let assert_near = |actual: [u8; 4], expected: [u8; 4]| {
+            for (a, e) in actual.iter().zip(expected) {
+                assert!(a.abs_diff(e) <= 1, "{actual:?} != {expected:?}");
+            }
+        };
+        assert_near(pixel(0, 0), [255, 0, 0, 255]);
+        assert_near(pixel(0, height - 1), [255, 0, 0, 255]);
+        assert_near(pixel(width - 1, 64), [0, 0, 255, 255]);
+        assert_near(pixel(width / 2, 64), [127, 0, 128, 255]);
+    }
+
+    #[test]
+    fn test_background_gradient_pixels_respects_opacity() {
+        let gradient = BackgroundGradient {
+            from: [0.0, 0.0, 0.0, 1.0],
+            to: [0.0, 0.0, 0.0, 0.5],
+            angle: 180.0,
+        };
+        let (width, height, pixels) =
+            background_gradient_pixels(&gradient, (100.0, 100.0), 0.5);
+        assert_eq!((width, height), (256, 256));
+        assert!(pixels[3].abs_diff(127) <= 1);
+        assert!(pixels[pixels.len() - 1].abs_diff(64) <= 1);
+
+        // Degenerate window sizes still give a valid texture
+        let (_, height, pixels) = background_gradient_pixels(&gradient, (0.0, 0.0), 1.0);
+        assert_eq!(height, 1);
+        assert_eq!(pixels.len(), 256 * 4);
+    }
+
+    #[test]
+    fn test_content_background_quad_unset() {
+        let quad =
+            content_background_quad(None, (800.0, 600.0), 1.0, &margin(0.0, 0.0, 0.0));
+        assert!(quad.is_none());
+    }
+
+    #[test]
+    fn test_content_background_quad_rect_and_color() {
+        let tint = [0.1, 0.2, 0.3, 0.5];
+        let quad = content_background_quad(
+            Some(tint),
+            (800.0, 600.0),
+            1.0,
+            &margin(0.0, 0.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(quad.position, [0.0, 0.0]);
+        assert_eq!(quad.size, [800.0, 600.0]);
+        assert_eq!(quad.color, tint);
+    }
+
+    #[test]
+    fn test_content_background_quad_respects_padding() {
+        let tint = [0.0, 0.0, 0.0, 0.2];
+        // 2x scale: 1600x1200 physical is 800x600 logical
+        let quad = content_background_quad(
+            Some(tint),
+            (1600.0, 1200.0),
+            2.0,
+            &margin(10.0, 30.0, 20.0),
+        )
+        .unwrap();
+        assert_eq!(quad.position, [10.0, 30.0]);
+        assert_eq!(quad.size, [790.0, 550.0]);
+    }
+
+    #[test]
+    fn test_content_background_quad_empty_area() {
+        let quad = content_background_quad(
+            Some([0.0; 4]),
+            (100.0, 100.0),
+            1.0,
+            &margin(0.0, 60.0, 50.0),
+        );
+        assert!(quad.is_none());
+    }
+}
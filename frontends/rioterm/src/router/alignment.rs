@@ -1,11 +1,16 @@
+use rio_backend::config::window::{
+    AlignMode, AlignSide, StackOrientation, Window as WindowConfig, MAX_ALIGN_WIDTH,
+    MIN_ALIGN_WIDTH,
+};
 use rio_backend::event::WindowId;
-use rio_window::dpi::{LogicalPosition, LogicalSize};
+use rio_window::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use rustc_hash::FxHashMap;
+use serde::Serialize;
 
 use super::Route;
 
 /// Represents the usable screen area.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct ScreenArea {
     pub x: i32,
     pub y: i32,
@@ -13,8 +18,46 @@ pub struct ScreenArea {
     pub height: u32,
 }
 
+impl ScreenArea {
+    #[inline]
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && i64::from(x) < i64::from(self.x) + i64::from(self.width)
+            && i64::from(y) < i64::from(self.y) + i64::from(self.height)
+    }
+
+    /// Squared distance from `(x, y)` to the closest point of the area.
+    fn distance_squared(&self, x: i32, y: i32) -> i64 {
+        let axis = |p: i32, start: i32, len: u32| {
+            let (p, start) = (i64::from(p), i64::from(start));
+            let end = start + i64::from(len);
+            if p < start {
+                start - p
+            } else if p >= end {
+                p - end + 1
+            } else {
+                0
+            }
+        };
+        let dx = axis(x, self.x, self.width);
+        let dy = axis(y, self.y, self.height);
+        dx * dx + dy * dy
+    }
+}
+
+/// The monitor area containing `(x, y)`, or the closest one when the
+/// point is off every monitor. `None` only when `areas` is empty.
+pub fn area_containing(areas: &[ScreenArea], x: i32, y: i32) -> Option<ScreenArea> {
+    areas
+        .iter()
+        .find(|area| area.contains(x, y))
+        .or_else(|| areas.iter().min_by_key(|area| area.distance_squared(x, y)))
+        .copied()
+}
+
 /// A computed position and size for a window slot.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct WindowSlot {
     pub x: i32,
     pub y: i32,
@@ -22,23 +65,264 @@ pub struct WindowSlot {
     pub height: u32,
 }
 
-/// Get the available screen area for the main display.
+impl WindowSlot {
+    /// Slot `t` of the way from `self` to `target`.
+    fn lerp(&self, target: &WindowSlot, t: f32) -> WindowSlot {
+        let mix = |from: f32, to: f32| from + (to - from) * t;
+        WindowSlot {
+            x: mix(self.x as f32, target.x as f32).round() as i32,
+            y: mix(self.y as f32, target.y as f32).round() as i32,
+            width: mix(self.width as f32, target.width as f32).round() as u32,
+            height: mix(self.height as f32, target.height as f32).round() as u32,
+        }
+    }
+
+    /// Outer position and inner size of the slot in physical pixels.
+    /// Slots share the units of `ScreenArea`, which follow the system
+    /// scale factor, so `window.scale-override` doesn't apply here.
+    fn to_physical(
+        &self,
+        scale_factor: f64,
+    ) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+        (
+            LogicalPosition::new(self.x, self.y).to_physical(scale_factor),
+            LogicalSize::new(self.width, self.height).to_physical(scale_factor),
+        )
+    }
+
+    /// Slot of a window at `position` with `size`, the inverse of
+    /// `to_physical`.
+    fn from_physical(
+        position: PhysicalPosition<i32>,
+        size: PhysicalSize<u32>,
+        scale_factor: f64,
+    ) -> Self {
+        let position: LogicalPosition<i32> = position.to_logical(scale_factor);
+        let size: LogicalSize<u32> = size.to_logical(scale_factor);
+        WindowSlot {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        }
+    }
+}
+
+/// Geometry a window had before the layout first moved it, put back by
+/// `restore_layout`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SavedSlot(Option<WindowSlot>);
+
+impl SavedSlot {
+    /// Remember `slot`, unless a geometry is already saved.
+    #[inline]
+    pub fn save(&mut self, slot: WindowSlot) {
+        self.0.get_or_insert(slot);
+    }
+
+    /// The saved geometry, leaving nothing saved.
+    #[inline]
+    pub fn take(&mut self) -> Option<WindowSlot> {
+        self.0.take()
+    }
+}
+
+/// A window gliding from where it was to its layout slot.
+#[derive(Debug, Clone, Copy)]
+struct AnimatedSlot {
+    start_slot: WindowSlot,
+    current_slot: WindowSlot,
+    target_slot: WindowSlot,
+    /// Fraction of the way covered, `0.0` at `start_slot`
+    progress: f32,
+}
+
+/// Remaining progress below which a window snaps to its target
+const SETTLE_EPSILON: f32 = 0.001;
+
+/// Windows moving towards their layout slots, see `window.align-animate`.
 ///
-/// On macOS, uses `CGDisplay::main()` via Core Graphics to avoid
-/// the NSScreen enumeration crash in objc2-foundation, then adjusts
-/// for the menu bar by subtracting a fixed offset from the top.
-/// On other platforms, uses `current_monitor()` with a fallback.
+/// Every `tick` moves each window's `current_slot` toward its
+/// `target_slot` by exponential smoothing,
+/// `current += (target - current) * (1 - exp(-dt / tau))`, tracked as the
+/// progress along the path so integer rounding never stalls the motion.
+#[derive(Debug, Default)]
+pub struct AnimatedLayout {
+    windows: FxHashMap<WindowId, AnimatedSlot>,
+    /// Smoothing time constant in seconds
+    tau: f32,
+}
+
+impl AnimatedLayout {
+    pub fn new(tau: f32) -> Self {
+        Self {
+            windows: FxHashMap::default(),
+            tau,
+        }
+    }
+
+    pub fn set_tau(&mut self, tau: f32) {
+        self.tau = tau;
+    }
+
+    /// Whether a window still has to move.
+    #[inline]
+    pub fn is_animating(&self) -> bool {
+        !self.windows.is_empty()
+    }
+
+    /// Start moving window `id` to `target`. A window already moving
+    /// continues from where it is, otherwise it starts at `current`.
+    pub fn set_target(&mut self, id: WindowId, current: WindowSlot, target: WindowSlot) {
+        let start_slot = self
+            .windows
+            .get(&id)
+            .map_or(current, |animated| animated.current_slot);
+        self.windows.insert(
+            id,
+            AnimatedSlot {
+                start_slot,
+                current_slot: start_slot,
+                target_slot: target,
+                progress: 0.0,
+            },
+        );
+    }
+
+    /// Stop moving window `id`, leaving it where it is.
+    pub fn forget(&mut self, id: WindowId) {
+        self.windows.remove(&id);
+    }
+
+    /// Advance every window by `dt` seconds. Returns the slots to apply
+    /// this frame; windows reaching their target are returned one last
+    /// time and then forgotten.
+    pub fn tick(&mut self, dt: f32) -> Vec<(WindowId, WindowSlot)> {
+        let step = if self.tau > 0.0 {
+            1.0 - (-dt.max(0.0) / self.tau).exp()
+        } else {
+            1.0
+        };
+
+        let mut slots = Vec::with_capacity(self.windows.len());
+        self.windows.retain(|id, animated| {
+            animated.progress += (1.0 - animated.progress) * step;
+            animated.current_slot = animated
+                .start_slot
+                .lerp(&animated.target_slot, animated.progress);
+
+            let settled = 1.0 - animated.progress < SETTLE_EPSILON
+                || animated.current_slot == animated.target_slot;
+            if settled {
+                animated.current_slot = animated.target_slot;
+            }
+            slots.push((*id, animated.current_slot));
+            !settled
+        });
+        slots
+    }
+}
+
+/// Arrangement strategy used by `apply_layout`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    /// Focused window on the left, the others stacked on the right.
+    FocusStack,
+    /// Windows tiled row-major in a grid with `cols` columns, or the
+    /// nearest square grid when `cols` is 0.
+    Grid { cols: u32 },
+    /// Windows side by side in a single row.
+    EvenHorizontal,
+    /// Windows on top of each other in a single column.
+    EvenVertical,
+}
+
+impl LayoutMode {
+    pub fn from_config(window_config: &WindowConfig) -> Self {
+        match window_config.align_mode {
+            AlignMode::Stack => LayoutMode::FocusStack,
+            AlignMode::Grid => LayoutMode::Grid {
+                cols: window_config.align_cols,
+            },
+            AlignMode::EvenHorizontal => LayoutMode::EvenHorizontal,
+            AlignMode::EvenVertical => LayoutMode::EvenVertical,
+        }
+    }
+
+    /// Columns of the grid `count` windows are tiled into, `None` for
+    /// `FocusStack` which isn't a grid.
+    pub fn grid_cols(&self, count: usize) -> Option<u32> {
+        let count = count as u32;
+        match self {
+            LayoutMode::FocusStack => None,
+            LayoutMode::Grid { cols: 0 } => Some(nearest_square_cols(count)),
+            LayoutMode::Grid { cols } => Some(*cols),
+            LayoutMode::EvenHorizontal => Some(count.max(1)),
+            LayoutMode::EvenVertical => Some(1),
+        }
+    }
+}
+
+/// Smallest number of columns `cols` with `cols * cols >= count`, so the
+/// grid is as close to square as possible: 4 windows in 2x2, 5 in 3+2.
+fn nearest_square_cols(count: u32) -> u32 {
+    let mut cols = 1;
+    while cols * cols < count {
+        cols += 1;
+    }
+    cols
+}
+
+/// Get the available screen area of the monitor containing `window`.
+///
+/// On macOS, enumerates the displays via Core Graphics to avoid the
+/// NSScreen enumeration crash in objc2-foundation, picks the one holding
+/// the window's center (the main display when that can't be told), then
+/// adjusts for the menu bar by subtracting a fixed offset from the top.
+/// On other platforms, uses `current_monitor()`, falling back to the
+/// monitor of `available_monitors()` holding the window's center.
+/// Returns `None` only when no monitor is found.
 pub fn get_available_screen_area(
-    _window: &rio_window::window::Window,
+    window: &rio_window::window::Window,
 ) -> Option<ScreenArea> {
+    let center = window.outer_position().ok().map(|position| {
+        let size = window.outer_size();
+        (
+            position.x as f64 + size.width as f64 / 2.0,
+            position.y as f64 + size.height as f64 / 2.0,
+        )
+    });
+
     #[cfg(target_os = "macos")]
     {
         use core_graphics::display::CGDisplay;
 
-        let main = CGDisplay::main();
-        let bounds = main.bounds();
-        let width = bounds.size.width as u32;
-        let height = bounds.size.height as u32;
+        let display_area = |display: CGDisplay| {
+            let bounds = display.bounds();
+            ScreenArea {
+                x: bounds.origin.x as i32,
+                y: bounds.origin.y as i32,
+                width: bounds.size.width as u32,
+                height: bounds.size.height as u32,
+            }
+        };
+        let displays: Vec<ScreenArea> = CGDisplay::active_displays()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|id| display_area(CGDisplay::new(id)))
+            .filter(|area| area.width > 0 && area.height > 0)
+            .collect();
+
+        // Core Graphics bounds are in points, the window in pixels
+        let scale = window.scale_factor();
+        let bounds = center
+            .and_then(|(x, y)| {
+                let (x, y) = ((x / scale).round() as i32, (y / scale).round() as i32);
+                area_containing(&displays, x, y)
+            })
+            .unwrap_or_else(|| display_area(CGDisplay::main()));
+        let width = bounds.width;
+        let height = bounds.height;
         if width == 0 || height == 0 {
             return None;
         }
@@ -48,49 +332,58 @@ pub fn get_available_screen_area(
         // handles the remaining adjustments.
         let menu_bar_height: u32 = 25;
         Some(ScreenArea {
-            x: bounds.origin.x as i32,
-            y: bounds.origin.y as i32 + menu_bar_height as i32,
+            x: bounds.x,
+            y: bounds.y + menu_bar_height as i32,
             width,
             height: height.saturating_sub(menu_bar_height),
         })
     }
     #[cfg(not(target_os = "macos"))]
     {
-        if let Some(monitor) = _window.current_monitor() {
+        let monitor_area = |monitor: rio_window::monitor::MonitorHandle| {
             let size = monitor.size();
             let pos = monitor.position();
-            return Some(ScreenArea {
+            ScreenArea {
                 x: pos.x,
                 y: pos.y,
                 width: size.width,
                 height: size.height,
-            });
+            }
+        };
+        if let Some(monitor) = window.current_monitor() {
+            return Some(monitor_area(monitor));
         }
-        let size = _window.outer_size();
-        if size.width == 0 || size.height == 0 {
-            return None;
+
+        let monitors: Vec<ScreenArea> = window
+            .available_monitors()
+            .map(monitor_area)
+            .filter(|area| area.width > 0 && area.height > 0)
+            .collect();
+        match center {
+            Some((x, y)) => {
+                area_containing(&monitors, x.round() as i32, y.round() as i32)
+            }
+            None => window
+                .primary_monitor()
+                .map(monitor_area)
+                .or_else(|| monitors.first().copied()),
         }
-        Some(ScreenArea {
-            x: 0,
-            y: 0,
-            width: size.width,
-            height: size.height,
-        })
     }
 }
 
 /// Position for the focused window.
 ///
 /// - With 1 window: no alignment (handled by caller returning early).
-/// - With 2+ windows: left-aligned at `align_width` ratio.
+/// - With 2+ windows: aligned to `side` at `align_width` ratio.
 pub fn focused_slot(
     screen: &ScreenArea,
     gap: u32,
     align_width: f32,
     has_peers: bool,
     decoration_height: u32,
+    side: AlignSide,
 ) -> WindowSlot {
-    let ratio = align_width.clamp(0.1, 1.0);
+    let ratio = align_width.clamp(MIN_ALIGN_WIDTH, MAX_ALIGN_WIDTH);
     let usable_width = screen
         .width
         .saturating_sub(if has_peers { gap * 2 } else { 0 });
@@ -98,12 +391,15 @@ pub fn focused_slot(
     // Subtract decoration height so the outer window (content + title bar)
     // fits within the screen area.
     let h = screen.height.saturating_sub(gap * 2 + decoration_height);
-    let x = if has_peers {
+    let x = match (has_peers, side) {
         // Left-aligned with gap
-        screen.x + gap as i32
-    } else {
+        (true, AlignSide::Left) => screen.x + gap as i32,
+        // Right-aligned with gap
+        (true, AlignSide::Right) => {
+            screen.x + screen.width as i32 - gap as i32 - w as i32
+        }
         // Centered
-        screen.x + ((screen.width - w) / 2) as i32
+        (false, _) => screen.x + ((screen.width - w) / 2) as i32,
     };
     let y = screen.y + gap as i32;
     WindowSlot {
@@ -116,49 +412,95 @@ pub fn focused_slot(
 
 /// Apply a computed slot (position + size) to a window using logical coordinates.
 fn apply_slot(route: &mut Route, slot: &WindowSlot) {
-    route
-        .window
-        .winit_window
-        .set_outer_position(LogicalPosition::new(slot.x, slot.y));
-    let _ = route
-        .window
-        .winit_window
-        .request_inner_size(LogicalSize::new(slot.width, slot.height));
+    let winit_window = &route.window.winit_window;
+    let (position, size) = slot.to_physical(winit_window.scale_factor());
+    winit_window.set_outer_position(position);
+    let _ = winit_window.request_inner_size(size);
 }
 
-/// Apply focus-centered layout with right-side stack.
-///
-/// The focused window sits on the left at `align_width` ratio.
-/// All unfocused windows are stacked vertically on the right side,
-/// sharing the remaining screen width equally in height.
-///
-/// Cycling rotates which window is focused — the focused window
-/// always moves to the left, others stack on the right.
-///
-/// Example with [A, B, C], focus B:
-///   left: B (80%)  right stack: [A, C] (20%, split vertically)
-/// Cycle next, focus C:
-///   left: C (80%)  right stack: [A, B] (20%, split vertically)
-pub fn apply_layout(
+/// Current logical position and inner size of a window, the inverse of
+/// `apply_slot`.
+fn current_slot(route: &Route) -> Option<WindowSlot> {
+    let winit_window = &route.window.winit_window;
+    let position = winit_window.outer_position().ok()?;
+    Some(WindowSlot::from_physical(
+        position,
+        winit_window.inner_size(),
+        winit_window.scale_factor(),
+    ))
+}
+
+/// Move window `id` to `slot`, gliding there through `animation` when
+/// given, otherwise right away. The geometry the window had before the
+/// layout first moved it is kept in `Route::saved_slot`.
+fn place_window(
     routes: &mut FxHashMap<WindowId, Route>,
-    focused_id: WindowId,
-    window_order: &[WindowId],
-    screen: &ScreenArea,
-    _peek_width: u32,
-    gap: u32,
-    align_width: f32,
+    id: WindowId,
+    slot: &WindowSlot,
+    animation: Option<&mut AnimatedLayout>,
 ) {
-    let len = window_order.len();
-    // Skip alignment for 0 or 1 window - leave single window at user's position/size
-    if len < 2 {
+    let Some(route) = routes.get_mut(&id) else {
         return;
+    };
+    let current = current_slot(route);
+    if let Some(current) = current {
+        route.saved_slot.save(current);
     }
+    move_window(route, id, current, slot, animation);
+}
 
-    // Determine window decoration (title bar) height by comparing
-    // outer_size vs inner_size on any existing window. This is the
-    // height added by the OS window chrome that we must account for
-    // when positioning windows so they don't overlap.
-    let decoration_height = routes
+fn move_window(
+    route: &mut Route,
+    id: WindowId,
+    current: Option<WindowSlot>,
+    slot: &WindowSlot,
+    animation: Option<&mut AnimatedLayout>,
+) {
+    match (animation, current) {
+        (Some(animation), Some(current)) => animation.set_target(id, current, *slot),
+        (animation, _) => {
+            if let Some(animation) = animation {
+                animation.forget(id);
+            }
+            apply_slot(route, slot);
+        }
+    }
+}
+
+/// Put every window the layout moved back where it was before, and
+/// forget the saved geometry. Used once auto alignment is turned off or
+/// a single window is left.
+pub fn restore_layout(
+    routes: &mut FxHashMap<WindowId, Route>,
+    mut animation: Option<&mut AnimatedLayout>,
+) {
+    for (id, route) in routes.iter_mut() {
+        let Some(saved) = route.saved_slot.take() else {
+            continue;
+        };
+        let current = current_slot(route);
+        move_window(route, *id, current, &saved, animation.as_deref_mut());
+    }
+}
+
+/// Apply the slots of the current animation frame, see `AnimatedLayout::tick`.
+pub fn apply_animation_frame(
+    routes: &mut FxHashMap<WindowId, Route>,
+    slots: &[(WindowId, WindowSlot)],
+) {
+    for (id, slot) in slots {
+        if let Some(route) = routes.get_mut(id) {
+            apply_slot(route, slot);
+        }
+    }
+}
+
+/// Determine window decoration (title bar) height by comparing
+/// outer_size vs inner_size on any existing window. This is the
+/// height added by the OS window chrome that we must account for
+/// when positioning windows so they don't overlap.
+fn decoration_height(routes: &FxHashMap<WindowId, Route>) -> u32 {
+    routes
         .values()
         .next()
         .map(|route| {
@@ -168,31 +510,98 @@ pub fn apply_layout(
             let scale = route.window.winit_window.scale_factor();
             ((outer.height.saturating_sub(inner.height)) as f64 / scale) as u32
         })
-        .unwrap_or(0);
+        .unwrap_or(0)
+}
 
-    // Position focused window (left-aligned since we have multiple windows)
-    let focused = focused_slot(screen, gap, align_width, true, decoration_height);
-    if let Some(route) = routes.get_mut(&focused_id) {
-        apply_slot(route, &focused);
+/// Compute the slots of a `cols` wide grid holding `count` windows,
+/// in row-major order. When `count` isn't a multiple of `cols` the
+/// windows of the last row share the full width between them.
+pub fn grid_slots(
+    screen: &ScreenArea,
+    count: usize,
+    cols: u32,
+    gap: u32,
+    decoration_height: u32,
+) -> Vec<WindowSlot> {
+    if count == 0 {
+        return Vec::new();
     }
 
-    // Collect unfocused windows in ring order (preserves carousel rotation)
-    let focused_idx = window_order
-        .iter()
-        .position(|id| *id == focused_id)
-        .unwrap_or(0);
+    let count = count as u32;
+    let cols = cols.clamp(1, count);
+    let rows = count.div_ceil(cols);
 
-    let mut stack_windows: Vec<WindowId> = Vec::with_capacity(len - 1);
-    for step in 1..len {
-        let idx = (focused_idx + step) % len;
-        stack_windows.push(window_order[idx]);
+    // Each window's outer height = decoration_height + slot height (inner)
+    let available_height = screen
+        .height
+        .saturating_sub(gap * (rows + 1) + rows * decoration_height);
+    let slot_height = available_height / rows;
+
+    let mut slots = Vec::with_capacity(count as usize);
+    for row in 0..rows {
+        let first = row * cols;
+        let in_row = (count - first).min(cols);
+        let slot_width = screen.width.saturating_sub(gap * (in_row + 1)) / in_row;
+        let y = screen.y
+            + gap as i32
+            + (row * (decoration_height + slot_height + gap)) as i32;
+
+        for col in 0..in_row {
+            slots.push(WindowSlot {
+                x: screen.x + gap as i32 + (col * (slot_width + gap)) as i32,
+                y,
+                width: slot_width,
+                height: slot_height,
+            });
+        }
+    }
+
+    slots
+}
+
+/// Slots of `count` windows stacked beside `focused`, on the side
+/// opposite to `side`, filling the width up to the screen edge. Vertical
+/// stacks split the height into rows, horizontal ones the width into
+/// full height columns.
+pub fn stack_slots(
+    screen: &ScreenArea,
+    focused: &WindowSlot,
+    count: usize,
+    gap: u32,
+    decoration_height: u32,
+    side: AlignSide,
+    orientation: StackOrientation,
+) -> Vec<WindowSlot> {
+    if count == 0 {
+        return Vec::new();
     }
 
-    // Stack area: right of focused window + gap, filling to screen edge
-    let stack_x = focused.x + focused.width as i32 + gap as i32;
-    let screen_right = screen.x + screen.width as i32 - gap as i32;
-    let stack_w = (screen_right - stack_x).max(0) as u32;
-    let stack_count = stack_windows.len() as u32;
+    let (stack_x, stack_right) = match side {
+        // Right of focused window + gap, filling to screen edge
+        AlignSide::Left => (
+            focused.x + focused.width as i32 + gap as i32,
+            screen.x + screen.width as i32 - gap as i32,
+        ),
+        // From the screen edge up to the focused window - gap
+        AlignSide::Right => (screen.x + gap as i32, focused.x - gap as i32),
+    };
+    let stack_w = (stack_right - stack_x).max(0) as u32;
+    let stack_count = count as u32;
+
+    if orientation == StackOrientation::Horizontal {
+        // Every column carries a single title bar
+        let total_gaps = (stack_count - 1) * gap;
+        let slot_width = stack_w.saturating_sub(total_gaps) / stack_count;
+        let slot_height = screen.height.saturating_sub(gap * 2 + decoration_height);
+        return (0..stack_count)
+            .map(|i| WindowSlot {
+                x: stack_x + (i * (slot_width + gap)) as i32,
+                y: screen.y + gap as i32,
+                width: slot_width,
+                height: slot_height,
+            })
+            .collect();
+    }
 
     // Divide height evenly among stacked windows, with gap between them.
     // Each window's outer height = decoration_height + slot_height (inner),
@@ -204,22 +613,187 @@ pub fn apply_layout(
         .saturating_sub(gap * 2 + total_gaps + total_decorations);
     let slot_height = available_height / stack_count;
 
-    for (i, id) in stack_windows.iter().enumerate() {
-        // Each window's outer height is (decoration_height + slot_height),
-        // so advance Y by that amount plus the gap between windows.
-        let y = screen.y
-            + gap as i32
-            + (i as u32 * (decoration_height + slot_height + gap)) as i32;
-        let slot = WindowSlot {
-            x: stack_x,
-            y,
-            width: stack_w,
-            height: slot_height,
-        };
-        if let Some(route) = routes.get_mut(id) {
-            apply_slot(route, &slot);
+    (0..stack_count)
+        .map(|i| {
+            // Each window's outer height is (decoration_height + slot_height),
+            // so advance Y by that amount plus the gap between windows.
+            let y = screen.y
+                + gap as i32
+                + (i * (decoration_height + slot_height + gap)) as i32;
+            WindowSlot {
+                x: stack_x,
+                y,
+                width: stack_w,
+                height: slot_height,
+            }
+        })
+        .collect()
+}
+
+/// Compute the slot of every window for the configured layout, see
+/// `LayoutMode`. Empty for fewer than 2 windows, which are left where
+/// they are.
+///
+/// In `FocusStack` mode this is a focus-centered layout with a side stack.
+/// The focused window sits on `align_side` at `align_width` ratio.
+/// All unfocused windows are stacked on the other side, sharing the
+/// remaining screen width equally in height, or in width with
+/// `stack-orientation = "horizontal"`.
+///
+/// Cycling rotates which window is focused — the focused window
+/// always moves to `align_side`, others stack on the other side.
+///
+/// Example with [A, B, C], focus B:
+///   left: B (80%)  right stack: [A, C] (20%, split vertically)
+/// Cycle next, focus C:
+///   left: C (80%)  right stack: [A, B] (20%, split vertically)
+///
+/// Grid layouts tile the windows following `window_order` (reading
+/// order), this also covers `EvenHorizontal` and `EvenVertical`. Focus
+/// doesn't move windows around there, it only changes which cell is active.
+pub fn compute_layout(
+    focused_id: WindowId,
+    window_order: &[WindowId],
+    screen: &ScreenArea,
+    window_config: &WindowConfig,
+    decoration_height: u32,
+) -> Vec<(WindowId, WindowSlot)> {
+    let gap = window_config.align_gap;
+    let len = window_order.len();
+    if len < 2 {
+        return Vec::new();
+    }
+
+    if let Some(cols) = LayoutMode::from_config(window_config).grid_cols(len) {
+        let slots = grid_slots(screen, len, cols, gap, decoration_height);
+        return window_order.iter().copied().zip(slots).collect();
+    }
+
+    // Position focused window (side-aligned since we have multiple windows)
+    let side = window_config.align_side;
+    let focused = focused_slot(
+        screen,
+        gap,
+        window_config.align_width,
+        true,
+        decoration_height,
+        side,
+    );
+
+    // Collect unfocused windows in ring order (preserves carousel rotation)
+    let focused_idx = window_order
+        .iter()
+        .position(|id| *id == focused_id)
+        .unwrap_or(0);
+    let stack_windows = (1..len).map(|step| window_order[(focused_idx + step) % len]);
+
+    let slots = stack_slots(
+        screen,
+        &focused,
+        len - 1,
+        gap,
+        decoration_height,
+        side,
+        window_config.stack_orientation,
+    );
+
+    let mut layout = Vec::with_capacity(len);
+    layout.push((focused_id, focused));
+    layout.extend(stack_windows.zip(slots));
+    layout
+}
+
+/// Apply the configured layout, see `compute_layout`.
+///
+/// With `animation` the windows glide to their slots as it ticks instead
+/// of jumping there.
+pub fn apply_layout(
+    routes: &mut FxHashMap<WindowId, Route>,
+    focused_id: WindowId,
+    window_order: &[WindowId],
+    screen: &ScreenArea,
+    window_config: &WindowConfig,
+    mut animation: Option<&mut AnimatedLayout>,
+) {
+    let decoration_height = decoration_height(routes);
+    let layout = compute_layout(
+        focused_id,
+        window_order,
+        screen,
+        window_config,
+        decoration_height,
+    );
+    for (id, slot) in &layout {
+        place_window(routes, *id, slot, animation.as_deref_mut());
+    }
+}
+
+/// File the layout is written to every time windows are aligned, printed
+/// by `rio --print-layout`.
+pub fn layout_snapshot_path() -> Option<std::path::PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("rio").join("layout.json"))
+}
+
+/// Read-only view of the current layout, for scripts placing windows
+/// themselves. Serializes to JSON like
+/// `{"screen":{"x":0,...},"slots":[{"window":1,"focused":true,"x":...}]}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayoutSnapshot {
+    pub screen: ScreenArea,
+    pub slots: Vec<LayoutSnapshotSlot>,
+}
+
+/// Slot of one window in a `LayoutSnapshot`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayoutSnapshotSlot {
+    /// Raw window id
+    pub window: u64,
+    pub focused: bool,
+    #[serde(flatten)]
+    pub slot: WindowSlot,
+}
+
+impl LayoutSnapshot {
+    pub fn new(
+        screen: ScreenArea,
+        focused_id: WindowId,
+        layout: &[(WindowId, WindowSlot)],
+    ) -> Self {
+        Self {
+            screen,
+            slots: layout
+                .iter()
+                .map(|(id, slot)| LayoutSnapshotSlot {
+                    window: u64::from(*id),
+                    focused: *id == focused_id,
+                    slot: *slot,
+                })
+                .collect(),
         }
     }
+
+    /// Snapshot of the layout `apply_layout` would apply.
+    pub fn compute(
+        routes: &FxHashMap<WindowId, Route>,
+        focused_id: WindowId,
+        window_order: &[WindowId],
+        screen: &ScreenArea,
+        window_config: &WindowConfig,
+    ) -> Self {
+        let decoration_height = decoration_height(routes);
+        let layout = compute_layout(
+            focused_id,
+            window_order,
+            screen,
+            window_config,
+            decoration_height,
+        );
+        Self::new(*screen, focused_id, &layout)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
 }
 
 /// Cycle focus to the next or previous window in order.
@@ -231,10 +805,9 @@ pub fn cycle_focus(
     window_order: &[WindowId],
     current_focused: WindowId,
     screen: &ScreenArea,
-    peek_width: u32,
-    gap: u32,
-    align_width: f32,
+    window_config: &WindowConfig,
     reverse: bool,
+    animation: Option<&mut AnimatedLayout>,
 ) -> Option<WindowId> {
     if window_order.len() < 2 {
         return None;
@@ -267,9 +840,500 @@ pub fn cycle_focus(
         new_focused,
         window_order,
         screen,
-        peek_width,
-        gap,
-        align_width,
+        window_config,
+        animation,
     );
     Some(new_focused)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen() -> ScreenArea {
+        ScreenArea {
+            x: 0,
+            y: 0,
+            width: 1000,
+            height: 800,
+        }
+    }
+
+    #[test]
+    fn test_layout_mode_from_config() {
+        let config = WindowConfig::default();
+        assert_eq!(LayoutMode::from_config(&config), LayoutMode::FocusStack);
+
+        let config = WindowConfig {
+            align_mode: AlignMode::Grid,
+            align_cols: 3,
+            ..WindowConfig::default()
+        };
+        assert_eq!(
+            LayoutMode::from_config(&config),
+            LayoutMode::Grid { cols: 3 }
+        );
+
+        let config = WindowConfig {
+            align_mode: AlignMode::EvenVertical,
+            ..WindowConfig::default()
+        };
+        assert_eq!(LayoutMode::from_config(&config), LayoutMode::EvenVertical);
+    }
+
+    #[test]
+    fn test_layout_mode_grid_cols() {
+        let auto = LayoutMode::Grid { cols: 0 };
+        assert_eq!(auto.grid_cols(1), Some(1));
+        assert_eq!(auto.grid_cols(4), Some(2));
+        assert_eq!(auto.grid_cols(5), Some(3));
+        assert_eq!(auto.grid_cols(10), Some(4));
+        assert_eq!(LayoutMode::Grid { cols: 3 }.grid_cols(8), Some(3));
+        assert_eq!(LayoutMode::EvenHorizontal.grid_cols(3), Some(3));
+        assert_eq!(LayoutMode::EvenVertical.grid_cols(3), Some(1));
+        assert_eq!(LayoutMode::FocusStack.grid_cols(3), None);
+    }
+
+    #[test]
+    fn test_grid_mode_four_windows() {
+        let cols = LayoutMode::Grid { cols: 0 }.grid_cols(4).unwrap();
+        let slots = grid_slots(&screen(), 4, cols, 10, 30);
+        // 2x2, each row loses a title bar: (800 - 3 * 10 - 2 * 30) / 2
+        let expected = [(10, 10), (505, 10), (10, 405), (505, 405)];
+        for (slot, (x, y)) in slots.iter().zip(expected) {
+            assert_eq!((slot.x, slot.y), (x, y));
+            assert_eq!((slot.width, slot.height), (485, 355));
+        }
+    }
+
+    #[test]
+    fn test_grid_mode_five_windows() {
+        let cols = LayoutMode::Grid { cols: 0 }.grid_cols(5).unwrap();
+        let slots = grid_slots(&screen(), 5, cols, 10, 30);
+        assert_eq!(slots.len(), 5);
+
+        // Three on top, (1000 - 4 * 10) / 3 wide
+        for (i, slot) in slots[..3].iter().enumerate() {
+            assert_eq!(slot.x, 10 + i as i32 * 330);
+            assert_eq!(slot.y, 10);
+            assert_eq!((slot.width, slot.height), (320, 355));
+        }
+        // Two below sharing the full width
+        for (i, slot) in slots[3..].iter().enumerate() {
+            assert_eq!(slot.x, 10 + i as i32 * 495);
+            assert_eq!(slot.y, 405);
+            assert_eq!((slot.width, slot.height), (485, 355));
+        }
+    }
+
+    #[test]
+    fn test_even_layouts() {
+        let horizontal = LayoutMode::EvenHorizontal.grid_cols(3).unwrap();
+        let slots = grid_slots(&screen(), 3, horizontal, 10, 0);
+        assert!(slots.iter().all(|slot| slot.y == 10 && slot.height == 780));
+        assert_eq!(slots[2].x, 10 + 2 * 330);
+
+        let vertical = LayoutMode::EvenVertical.grid_cols(3).unwrap();
+        let slots = grid_slots(&screen(), 3, vertical, 10, 0);
+        assert!(slots.iter().all(|slot| slot.x == 10 && slot.width == 980));
+        assert_eq!(slots[2].y, 10 + 2 * 263);
+    }
+
+    /// Focused and stacked slot x-coordinates for `count` windows
+    fn stack_layout_xs(count: usize, side: AlignSide) -> (i32, Vec<i32>) {
+        let focused = focused_slot(&screen(), 10, 0.6, true, 0, side);
+        let stacked = stack_slots(
+            &screen(),
+            &focused,
+            count - 1,
+            10,
+            0,
+            side,
+            StackOrientation::Vertical,
+        );
+        for slot in &stacked {
+            assert_eq!(slot.width, 382);
+        }
+        (focused.x, stacked.iter().map(|slot| slot.x).collect())
+    }
+
+    #[test]
+    fn test_focus_stack_left() {
+        // (1000 - 2 * 10) * 0.6 = 588 wide focused window on the left
+        assert_eq!(stack_layout_xs(2, AlignSide::Left), (10, vec![608]));
+        assert_eq!(stack_layout_xs(3, AlignSide::Left), (10, vec![608, 608]));
+    }
+
+    #[test]
+    fn test_focus_stack_right() {
+        // Mirrored: the focused window ends 10 away from the right edge
+        assert_eq!(stack_layout_xs(2, AlignSide::Right), (402, vec![10]));
+        assert_eq!(stack_layout_xs(3, AlignSide::Right), (402, vec![10, 10]));
+    }
+
+    #[test]
+    fn test_focus_stack_side_keeps_heights() {
+        let left = focused_slot(&screen(), 10, 0.6, true, 0, AlignSide::Left);
+        let right = focused_slot(&screen(), 10, 0.6, true, 0, AlignSide::Right);
+        let vertical = StackOrientation::Vertical;
+        let left_stack =
+            stack_slots(&screen(), &left, 2, 10, 0, AlignSide::Left, vertical);
+        let right_stack =
+            stack_slots(&screen(), &right, 2, 10, 0, AlignSide::Right, vertical);
+        assert_eq!(left.y, right.y);
+        assert_eq!(left.height, right.height);
+        for (left, right) in left_stack.iter().zip(&right_stack) {
+            assert_eq!((left.y, left.height), (right.y, right.height));
+        }
+        assert_eq!(left_stack[1].y, 10 + 385 + 10);
+    }
+
+    /// Horizontal stack of `peers` windows beside a 0.6 focused window on
+    /// the left, with 28 high title bars.
+    fn horizontal_stack(peers: usize) -> Vec<WindowSlot> {
+        let focused = focused_slot(&screen(), 10, 0.6, true, 28, AlignSide::Left);
+        let slots = stack_slots(
+            &screen(),
+            &focused,
+            peers,
+            10,
+            28,
+            AlignSide::Left,
+            StackOrientation::Horizontal,
+        );
+        assert_eq!(slots.len(), peers);
+        for slot in &slots {
+            // Full height, one title bar each
+            assert_eq!(slot.y, 10);
+            assert_eq!(slot.height, 800 - 2 * 10 - 28);
+            assert!(slot.x >= focused.x + focused.width as i32 + 10);
+            assert!(slot.x + slot.width as i32 <= 990);
+        }
+        for pair in slots.windows(2) {
+            assert!(pair[0].x + pair[0].width as i32 + 10 <= pair[1].x);
+        }
+        slots
+    }
+
+    #[test]
+    fn test_horizontal_stack_two_peers() {
+        // The 382 wide stack minus one gap, in two columns
+        let slots = horizontal_stack(2);
+        assert_eq!(
+            slots.iter().map(|s| s.x).collect::<Vec<_>>(),
+            vec![608, 804]
+        );
+        assert!(slots.iter().all(|slot| slot.width == 186));
+    }
+
+    #[test]
+    fn test_horizontal_stack_three_peers() {
+        let slots = horizontal_stack(3);
+        assert_eq!(
+            slots.iter().map(|s| s.x).collect::<Vec<_>>(),
+            vec![608, 738, 868]
+        );
+        assert!(slots.iter().all(|slot| slot.width == 120));
+    }
+
+    #[test]
+    fn test_focused_slot_single_window_centered() {
+        for side in [AlignSide::Left, AlignSide::Right] {
+            let slot = focused_slot(&screen(), 10, 0.6, false, 0, side);
+            assert_eq!(slot.x, 200);
+            assert_eq!(slot.width, 600);
+        }
+    }
+
+    #[test]
+    fn test_grid_slots_full_grid() {
+        let slots = grid_slots(&screen(), 4, 2, 10, 0);
+        assert_eq!(slots.len(), 4);
+
+        // Row-major: first row then second row
+        assert_eq!((slots[0].x, slots[0].y), (10, 10));
+        assert_eq!((slots[1].x, slots[1].y), (505, 10));
+        assert_eq!((slots[2].x, slots[2].y), (10, 405));
+        assert_eq!((slots[3].x, slots[3].y), (505, 405));
+
+        for slot in &slots {
+            assert_eq!(slot.width, 485);
+            assert_eq!(slot.height, 385);
+        }
+    }
+
+    #[test]
+    fn test_grid_slots_last_row_gets_extra_width() {
+        let slots = grid_slots(&screen(), 3, 2, 10, 0);
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0].width, 485);
+        assert_eq!(slots[1].width, 485);
+        // Lone window in the last row spans the whole usable width
+        assert_eq!(slots[2].x, 10);
+        assert_eq!(slots[2].width, 980);
+    }
+
+    #[test]
+    fn test_grid_slots_accounts_for_decorations() {
+        let slots = grid_slots(&screen(), 2, 1, 10, 30);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].height, 355);
+        assert_eq!(slots[1].y, 10 + 30 + 355 + 10);
+    }
+
+    #[test]
+    fn test_grid_slots_more_cols_than_windows() {
+        let slots = grid_slots(&screen(), 2, 4, 10, 0);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].width, 485);
+        assert_eq!(slots[0].height, 780);
+    }
+
+    fn slot(x: i32, width: u32) -> WindowSlot {
+        WindowSlot {
+            x,
+            y: 0,
+            width,
+            height: 100,
+        }
+    }
+
+    #[test]
+    fn test_window_slot_lerp() {
+        let current = WindowSlot {
+            x: 10,
+            y: 20,
+            width: 400,
+            height: 300,
+        };
+        let target = WindowSlot {
+            x: 611,
+            y: -20,
+            width: 801,
+            height: 100,
+        };
+        assert_eq!(current.lerp(&target, 0.0), current);
+        assert_eq!(current.lerp(&target, 1.0), target);
+        assert_eq!(
+            current.lerp(&target, 0.5),
+            WindowSlot {
+                x: 311,
+                y: 0,
+                width: 601,
+                height: 200,
+            }
+        );
+    }
+
+    #[test]
+    fn test_animated_layout_follows_exponential_smoothing() {
+        let id = WindowId::from(1);
+        let mut animation = AnimatedLayout::new(0.12);
+        animation.set_target(id, slot(0, 100), slot(1000, 500));
+        assert!(animation.is_animating());
+
+        // One time constant covers 1 - 1/e of the way
+        let frame = animation.tick(0.12);
+        let covered = 1.0 - (-1.0f32).exp();
+        let expected = slot(
+            (1000.0 * covered).round() as i32,
+            (100.0 + 400.0 * covered).round() as u32,
+        );
+        assert_eq!(frame, vec![(id, expected)]);
+
+        // A second one covers the same share of what is left
+        let frame = animation.tick(0.12);
+        let expected = 1000.0 * (1.0 - (-2.0f32).exp());
+        assert_eq!(frame[0].1.x, expected.round() as i32);
+    }
+
+    #[test]
+    fn test_animated_layout_settles_on_target() {
+        let id = WindowId::from(1);
+        let mut animation = AnimatedLayout::new(0.12);
+        animation.set_target(id, slot(0, 100), slot(200, 100));
+
+        let mut last = None;
+        for _ in 0..120 {
+            if !animation.is_animating() {
+                break;
+            }
+            last = animation.tick(1.0 / 60.0).pop();
+        }
+        assert!(!animation.is_animating());
+        assert_eq!(last, Some((id, slot(200, 100))));
+        assert!(animation.tick(1.0 / 60.0).is_empty());
+    }
+
+    #[test]
+    fn test_animated_layout_retarget_starts_from_current() {
+        let id = WindowId::from(1);
+        let mut animation = AnimatedLayout::new(0.12);
+        animation.set_target(id, slot(0, 100), slot(1000, 100));
+        let (_, midway) = animation.tick(0.12)[0];
+
+        // The `current` passed in is ignored while the window is moving
+        animation.set_target(id, slot(0, 100), slot(0, 100));
+        let (_, next) = animation.tick(0.0)[0];
+        assert_eq!(next, midway);
+    }
+
+    #[test]
+    fn test_animated_layout_zero_tau_jumps() {
+        let id = WindowId::from(1);
+        let mut animation = AnimatedLayout::new(0.0);
+        animation.set_target(id, slot(0, 100), slot(300, 200));
+        assert_eq!(animation.tick(0.0), vec![(id, slot(300, 200))]);
+        assert!(!animation.is_animating());
+    }
+
+    #[test]
+    fn test_saved_slot_restores_original() {
+        let original = slot(120, 300);
+        let mut saved = SavedSlot::default();
+        saved.save(original);
+        // Later layout passes keep the geometry from before the first one
+        saved.save(slot(10, 400));
+        assert_eq!(saved.take(), Some(original));
+        assert_eq!(saved.take(), None);
+    }
+
+    #[test]
+    fn test_animated_layout_forget() {
+        let id = WindowId::from(1);
+        let mut animation = AnimatedLayout::new(0.12);
+        animation.set_target(id, slot(0, 100), slot(300, 200));
+        animation.forget(id);
+        assert!(!animation.is_animating());
+    }
+
+    #[test]
+    fn test_area_containing_picks_monitor() {
+        let left = ScreenArea {
+            x: 0,
+            y: 0,
+            width: 1440,
+            height: 900,
+        };
+        let right = ScreenArea {
+            x: 1440,
+            y: -200,
+            width: 1920,
+            height: 1080,
+        };
+        let areas = [left, right];
+        let pick = |x, y| area_containing(&areas, x, y).map(|area| area.x);
+
+        assert_eq!(pick(700, 450), Some(0));
+        assert_eq!(pick(2000, -100), Some(1440));
+        // The right edge belongs to the next monitor
+        assert_eq!(pick(1440, 10), Some(1440));
+        // Off every monitor: the closest one
+        assert_eq!(pick(-50, 300), Some(0));
+        assert_eq!(pick(4000, 500), Some(1440));
+        assert_eq!(area_containing(&[], 0, 0).map(|area| area.x), None);
+    }
+
+    fn window_ids(count: u64) -> Vec<WindowId> {
+        (1..=count).map(WindowId::from).collect()
+    }
+
+    #[test]
+    fn test_compute_layout_focus_stack() {
+        let config = WindowConfig {
+            align_gap: 10,
+            align_width: 0.6,
+            ..WindowConfig::default()
+        };
+        let order = window_ids(3);
+        let layout = compute_layout(order[1], &order, &screen(), &config, 0);
+
+        // Focused first, then the others in ring order after it
+        let ids: Vec<WindowId> = layout.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![order[1], order[2], order[0]]);
+
+        let focused = focused_slot(&screen(), 10, 0.6, true, 0, config.align_side);
+        let stacked = stack_slots(
+            &screen(),
+            &focused,
+            2,
+            10,
+            0,
+            config.align_side,
+            config.stack_orientation,
+        );
+        assert_eq!(layout[0].1, focused);
+        assert_eq!(layout[1].1, stacked[0]);
+        assert_eq!(layout[2].1, stacked[1]);
+
+        // A single window is left alone
+        assert!(compute_layout(order[0], &order[..1], &screen(), &config, 0).is_empty());
+    }
+
+    #[test]
+    fn test_layout_snapshot_json_matches_layout() {
+        let config = WindowConfig {
+            align_mode: AlignMode::Grid,
+            align_cols: 2,
+            align_gap: 10,
+            ..WindowConfig::default()
+        };
+        let order = window_ids(3);
+        let layout = compute_layout(order[0], &order, &screen(), &config, 20);
+        assert_eq!(layout.len(), 3);
+        let grid = grid_slots(&screen(), 3, 2, 10, 20);
+
+        let snapshot = LayoutSnapshot::new(screen(), order[0], &layout);
+        let json: serde_json::Value =
+            serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+
+        assert_eq!(json["screen"]["width"], 1000);
+        assert_eq!(json["screen"]["height"], 800);
+        let slots = json["slots"].as_array().unwrap();
+        assert_eq!(slots.len(), 3);
+        for (index, (slot, expected)) in slots.iter().zip(&grid).enumerate() {
+            assert_eq!(slot["window"], u64::from(order[index]));
+            assert_eq!(slot["focused"], index == 0);
+            assert_eq!(slot["x"], expected.x);
+            assert_eq!(slot["y"], expected.y);
+            assert_eq!(slot["width"], expected.width);
+            assert_eq!(slot["height"], expected.height);
+        }
+    }
+
+    #[test]
+    fn test_slots_use_system_scale_with_scale_override() {
+        // Rendering at 2x on a 1x monitor must not push windows off it
+        let config = WindowConfig {
+            align_mode: AlignMode::EvenHorizontal,
+            scale_override: Some(2.0),
+            ..WindowConfig::default()
+        };
+        let system_scale = 1.0;
+        assert_ne!(config.effective_scale(system_scale), system_scale);
+
+        let order = window_ids(2);
+        let screen = screen();
+        for (_, slot) in compute_layout(order[0], &order, &screen, &config, 0) {
+            let (position, size) = slot.to_physical(system_scale);
+            assert_eq!((position.x, position.y), (slot.x, slot.y));
+            assert_eq!((size.width, size.height), (slot.width, slot.height));
+            assert!(position.x + size.width as i32 <= screen.x + screen.width as i32);
+            assert!(position.y + size.height as i32 <= screen.y + screen.height as i32);
+        }
+    }
+
+    #[test]
+    fn test_slot_physical_round_trip() {
+        let slot = WindowSlot {
+            x: 10,
+            y: 20,
+            width: 400,
+            height: 300,
+        };
+        let (position, size) = slot.to_physical(1.5);
+        assert_eq!((position.x, position.y), (15, 30));
+        assert_eq!((size.width, size.height), (600, 450));
+        assert_eq!(WindowSlot::from_physical(position, size, 1.5), slot);
+    }
+}
@@ -2,7 +2,7 @@ pub mod alignment;
 pub mod routes;
 mod window;
 use crate::event::EventProxy;
-use crate::router::window::{configure_window, create_window_builder};
+use crate::router::window::{configure_window, create_window_builder, fullscreen_for};
 use crate::screen::{Screen, ScreenWindowProperties};
 use assistant::Assistant;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
@@ -34,6 +34,8 @@ pub struct Route<'a> {
     pub assistant: assistant::Assistant,
     pub path: RoutePath,
     pub window: RouteWindow<'a>,
+    /// Geometry before auto alignment first moved the window
+    pub saved_slot: alignment::SavedSlot,
 }
 
 impl Route<'_> {
@@ -48,6 +50,7 @@ impl Route<'_> {
             assistant,
             path,
             window,
+            saved_slot: alignment::SavedSlot::default(),
         }
     }
 }
@@ -466,6 +469,7 @@ impl Router<'_> {
             window,
             path: RoutePath::Terminal,
             assistant: Assistant::new(),
+            saved_slot: alignment::SavedSlot::default(),
         };
 
         if let Some(err) = &self.propagated_report {
@@ -505,6 +509,7 @@ impl Router<'_> {
                 window,
                 path: RoutePath::Terminal,
                 assistant: Assistant::new(),
+                saved_slot: alignment::SavedSlot::default(),
             },
         );
     }
@@ -529,6 +534,16 @@ impl<'a> RouteWindow<'a> {
         configure_window(&self.winit_window, config);
     }
 
+    /// Leave fullscreen, or enter it on the `fullscreen-monitor` monitor
+    pub fn toggle_fullscreen(&self, config: &rio_backend::config::Config) {
+        if self.winit_window.fullscreen().is_some() {
+            self.winit_window.set_fullscreen(None);
+        } else {
+            self.winit_window
+                .set_fullscreen(Some(fullscreen_for(&self.winit_window, config)));
+        }
+    }
+
     pub fn wait_until(&self) -> Option<Duration> {
         // If we need to render after occlusion, render immediately
         if self.needs_render_after_occlusion {
@@ -637,7 +652,7 @@ impl<'a> RouteWindow<'a> {
 
         let properties = ScreenWindowProperties {
             size: winit_window.inner_size(),
-            scale: winit_window.scale_factor(),
+            scale: config.window.effective_scale(winit_window.scale_factor()),
             raw_window_handle: winit_window.window_handle().unwrap().into(),
             raw_display_handle: winit_window.display_handle().unwrap().into(),
             window_id: winit_window.id(),
@@ -1,5 +1,6 @@
 use rio_backend::config::window::{Decorations, WindowMode};
 use rio_backend::config::Config;
+use rio_window::dpi::LogicalPosition;
 use rio_window::window::{
     CursorIcon, Fullscreen, Icon, ImePurpose, Window, WindowAttributes,
 };
@@ -15,6 +16,30 @@ pub const DEFAULT_MINIMUM_WINDOW_WIDTH: i32 = 300;
 ))]
 pub const APPLICATION_ID: &str = "Rio";
 
+/// Entry `index` of `monitors`, or `None` when it is out of range.
+pub fn select_monitor<M>(
+    monitors: impl IntoIterator<Item = M>,
+    index: usize,
+) -> Option<M> {
+    let monitor = monitors.into_iter().nth(index);
+    if monitor.is_none() {
+        tracing::warn!(
+            "fullscreen-monitor {index} does not exist, using the current monitor"
+        );
+    }
+    monitor
+}
+
+/// Fullscreen on the monitor picked by `fullscreen-monitor`, falling back to
+/// the current one.
+pub fn fullscreen_for(winit_window: &Window, config: &Config) -> Fullscreen {
+    let monitor = config
+        .window
+        .fullscreen_monitor
+        .and_then(|index| select_monitor(winit_window.available_monitors(), index));
+    Fullscreen::Borderless(monitor)
+}
+
 pub fn create_window_builder(
     title: &str,
     config: &Config,
@@ -37,7 +62,7 @@ pub fn create_window_builder(
         })
         .with_resizable(true)
         .with_decorations(true)
-        .with_transparent(config.window.opacity < 1.)
+        .with_transparent(config.window.is_transparent())
         .with_blur(config.window.blur)
         .with_window_icon(Some(icon));
 
@@ -177,20 +202,36 @@ pub fn configure_window(winit_window: &Window, config: &Config) {
         }
     }
 
-    let is_transparent = config.window.opacity < 1.;
+    // Apply `initial-x`/`initial-y` (logical pixels). A missing axis keeps
+    // the position chosen by the system.
+    if let Some((x, y)) = config.window.initial_position() {
+        let scale = winit_window.scale_factor();
+        let current = winit_window
+            .outer_position()
+            .map(|position| position.to_logical::<f64>(scale))
+            .unwrap_or(LogicalPosition::new(0.0, 0.0));
+        winit_window.set_outer_position(LogicalPosition::new(
+            x.map_or(current.x, f64::from),
+            y.map_or(current.y, f64::from),
+        ));
+    }
+
+    // The builder can't enumerate monitors, so move a fullscreen window to
+    // the requested monitor once it exists
+    if config.window.mode == WindowMode::Fullscreen
+        && config.window.fullscreen_monitor.is_some()
+    {
+        winit_window.set_fullscreen(Some(fullscreen_for(winit_window, config)));
+    }
+
+    let is_transparent = config.window.is_transparent();
     winit_window.set_transparent(is_transparent);
 
+    apply_titlebar_color(winit_window, config);
+
     #[cfg(target_os = "macos")]
     {
         use rio_window::platform::macos::WindowExtMacOS;
-        let bg_color = config.colors.background.1;
-        winit_window.set_background_color(
-            bg_color.r,
-            bg_color.g,
-            bg_color.b,
-            config.window.opacity as f64,
-        );
-
         if !config.window.macos_use_shadow {
             winit_window.set_has_shadow(false);
         }
@@ -226,3 +267,76 @@ pub fn configure_window(winit_window: &Window, config: &Config) {
 
     winit_window.set_blur(config.window.blur);
 }
+
+/// RGBA the window background is painted with on MacOS. The titlebar is
+/// transparent (or unified), so this is also the color it shows:
+/// `window.titlebar-color` when set, otherwise the theme background at the
+/// window opacity.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn titlebar_rgba(config: &Config) -> [f64; 4] {
+    match config.window.titlebar_color {
+        Some([r, g, b, a]) => [r as f64, g as f64, b as f64, a as f64],
+        None => {
+            let bg_color = config.colors.background.1;
+            [
+                bg_color.r,
+                bg_color.g,
+                bg_color.b,
+                config.window.opacity as f64,
+            ]
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn apply_titlebar_color(winit_window: &Window, config: &Config) {
+    use rio_window::platform::macos::WindowExtMacOS;
+    let [r, g, b, a] = titlebar_rgba(config);
+    winit_window.set_background_color(r, g, b, a);
+}
+
+/// The titlebar tint is MacOS only.
+#[cfg(not(target_os = "macos"))]
+fn apply_titlebar_color(_winit_window: &Window, _config: &Config) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_monitor_in_range() {
+        let monitors = ["built-in", "left", "right"];
+        assert_eq!(select_monitor(monitors, 0), Some("built-in"));
+        assert_eq!(select_monitor(monitors, 2), Some("right"));
+    }
+
+    #[test]
+    fn test_select_monitor_out_of_range() {
+        assert_eq!(select_monitor(["built-in", "left"], 2), None);
+        assert_eq!(select_monitor(Vec::<&str>::new(), 0), None);
+    }
+
+    #[test]
+    fn test_titlebar_rgba() {
+        let mut config = Config::default();
+        let bg_color = config.colors.background.1;
+        assert_eq!(
+            titlebar_rgba(&config),
+            [
+                bg_color.r,
+                bg_color.g,
+                bg_color.b,
+                config.window.opacity as f64
+            ]
+        );
+
+        config.window.titlebar_color = Some([0.5, 0.25, 0.0, 1.0]);
+        assert_eq!(titlebar_rgba(&config), [0.5, 0.25, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_apply_titlebar_color_signature() {
+        // Both the MacOS and the no-op version fit `configure_window`
+        let _apply: fn(&Window, &Config) = apply_titlebar_color;
+    }
+}
@@ -0,0 +1,115 @@
+// Fuzzy matching for the command palette and the leader menu search
+
+/// Points for every matched character
+const SCORE_MATCH: i32 = 16;
+/// Extra points when a match directly follows the previous one
+const BONUS_CONSECUTIVE: i32 = 16;
+/// Extra points for matching the first character of a word
+const BONUS_WORD_START: i32 = 24;
+/// Points lost per skipped character between two matches
+const PENALTY_GAP: i32 = 2;
+/// Skipped characters before the first match cost at most this much
+const MAX_LEADING_PENALTY: i32 = 12;
+
+#[inline]
+fn is_word_start(chars: &[char], index: usize) -> bool {
+    match index.checked_sub(1).map(|prev| chars[prev]) {
+        None => true,
+        Some(prev) => {
+            !prev.is_alphanumeric()
+                || (prev.is_lowercase() && chars[index].is_uppercase())
+        }
+    }
+}
+
+/// Score `candidate` against `query`, case-insensitively. `None` when the
+/// query characters don't all appear in `candidate` in order, otherwise
+/// higher is better. Whitespace in the query is ignored and an empty query
+/// matches everything with a score of 0.
+///
+/// Every way of placing the query is considered, so "tab" scores the
+/// word "tab" in "Create tab" rather than the first `t` it sees.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<Option<char>> =
+        chars.iter().map(|c| c.to_lowercase().next()).collect();
+    let char_score = |index: usize| {
+        SCORE_MATCH
+            + if is_word_start(&chars, index) {
+                BONUS_WORD_START
+            } else {
+                0
+            }
+    };
+
+    // best[j]: best score with the current query character matched at j
+    let mut best: Vec<Option<i32>> = (0..chars.len())
+        .map(|j| {
+            (lower[j] == Some(query[0])).then(|| {
+                char_score(j) - (j as i32 * PENALTY_GAP).min(MAX_LEADING_PENALTY)
+            })
+        })
+        .collect();
+
+    for &wanted in &query[1..] {
+        best = (0..chars.len())
+            .map(|j| {
+                if lower[j] != Some(wanted) {
+                    return None;
+                }
+                (0..j)
+                    .filter_map(|k| {
+                        let link = if k + 1 == j {
+                            BONUS_CONSECUTIVE
+                        } else {
+                            -((j - k - 1) as i32) * PENALTY_GAP
+                        };
+                        best[k].map(|score| score + link)
+                    })
+                    .max()
+                    .map(|score| score + char_score(j))
+            })
+            .collect();
+    }
+
+    best.into_iter().flatten().max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_requires_ordered_subsequence() {
+        assert!(fuzzy_score("spr", "Split right").is_some());
+        assert!(fuzzy_score("rs", "Split right").is_none());
+        assert!(fuzzy_score("xyz", "Split right").is_none());
+        assert_eq!(fuzzy_score("", "Split right"), Some(0));
+        assert_eq!(fuzzy_score("  ", "Split right"), Some(0));
+    }
+
+    #[test]
+    fn test_fuzzy_score_is_case_insensitive() {
+        assert_eq!(fuzzy_score("SPLIT", "split"), fuzzy_score("split", "Split"));
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts_and_runs() {
+        let initials = fuzzy_score("sr", "Split right").unwrap();
+        let scattered = fuzzy_score("sr", "Close search").unwrap();
+        assert!(initials > scattered);
+
+        let prefix = fuzzy_score("tab", "Tab close").unwrap();
+        let inner = fuzzy_score("tab", "Select last tab").unwrap();
+        assert!(prefix > inner);
+    }
+}
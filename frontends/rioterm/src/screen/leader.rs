@@ -1,7 +1,251 @@
 // Leader key modal menu state and handling
 
 use crate::bindings::Action;
-use rio_backend::config::leader::LeaderItem;
+use crate::screen::fuzzy::fuzzy_score;
+use rio_backend::config::leader::{expand_variables, LeaderItem, WriteContext};
+use rio_backend::config::Shell;
+use std::time::{Duration, Instant};
+
+/// How long an output overlay stays up after its command finished
+pub const OUTPUT_OVERLAY_HOLD_SECS: u32 = 5;
+
+/// How an `exec` leader item runs its (expanded) command
+#[derive(Debug, PartialEq)]
+pub enum LeaderExec {
+    /// Fire and forget, only the progress bar reports the result
+    Background(String),
+    /// Run in a transient command overlay showing stdout/stderr
+    Overlay(String),
+}
+
+impl LeaderExec {
+    pub fn for_item(item: &LeaderItem, command: String) -> Self {
+        if item.show_output {
+            LeaderExec::Overlay(command)
+        } else {
+            LeaderExec::Background(command)
+        }
+    }
+}
+
+/// Shell invocation for an output overlay: runs `command`, then keeps
+/// the PTY alive for `OUTPUT_OVERLAY_HOLD_SECS` so the output stays
+/// readable before the overlay auto-dismisses.
+pub fn output_overlay_shell(command: &str) -> Shell {
+    if cfg!(windows) {
+        Shell {
+            program: "cmd".to_string(),
+            args: vec![
+                "/C".to_string(),
+                format!(
+                    "{command} & timeout /t {OUTPUT_OVERLAY_HOLD_SECS} /nobreak >nul"
+                ),
+            ],
+        }
+    } else {
+        Shell {
+            program: "sh".to_string(),
+            args: vec![
+                "-c".to_string(),
+                format!("{command}; sleep {OUTPUT_OVERLAY_HOLD_SECS}"),
+            ],
+        }
+    }
+}
+
+/// Command for `RunSelection`: the `${SELECTION}` value, trimmed. `None`
+/// when nothing (or only whitespace) is selected.
+pub fn run_selection_command(ctx: &WriteContext) -> Option<String> {
+    let command = expand_variables("${SELECTION}", ctx);
+    let command = command.trim();
+    (!command.is_empty()).then(|| command.to_string())
+}
+
+/// Shell for a `RunSelection` split: `cmd /K` runs `command` and stays
+/// open afterwards so the output stays on screen.
+#[cfg(windows)]
+pub fn run_selection_shell(command: &str, _shell: &Shell) -> Shell {
+    Shell {
+        program: "cmd".to_string(),
+        args: vec!["/K".to_string(), command.to_string()],
+    }
+}
+
+/// Shell for a `RunSelection` split: runs `command`, then hands the split
+/// over to `shell` so the output stays on screen. An empty program is the
+/// user's login shell, same as when spawning a regular split.
+#[cfg(not(windows))]
+pub fn run_selection_shell(command: &str, shell: &Shell) -> Shell {
+    let program = if shell.program.is_empty() {
+        teletypewriter::login_shell().unwrap_or_else(|| "sh".to_string())
+    } else {
+        shell.program.clone()
+    };
+    let exec = std::iter::once(&program)
+        .chain(&shell.args)
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Shell {
+        program: "sh".to_string(),
+        args: vec!["-c".to_string(), format!("{command}; exec {exec}")],
+    }
+}
+
+/// Quote `arg` for `sh` unless it only has characters that need none.
+#[cfg(not(windows))]
+fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Rows the command palette shows at once
+pub const PALETTE_VISIBLE_ROWS: usize = 12;
+
+/// Command palette: every leader item and registered action in one
+/// vertical list, filtered and ranked by a fuzzy query.
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    query: String,
+    entries: Vec<LeaderItem>,
+    /// Indices into `entries` that match `query`, best first
+    matches: Vec<usize>,
+    /// Index into `matches`
+    selected: usize,
+}
+
+/// What the renderer needs to draw the palette
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaletteView {
+    pub query: String,
+    /// Visible rows, at most `PALETTE_VISIBLE_ROWS`
+    pub rows: Vec<LeaderItem>,
+    /// Selected row within `rows`
+    pub selected: usize,
+    /// Number of matches, including the ones scrolled out of view
+    pub total: usize,
+}
+
+impl CommandPalette {
+    /// Build the palette from the leader `items` (submenus flattened,
+    /// labels prefixed with their path) and the `(name, label)` pairs of
+    /// `actions`. Actions already bound to a leader item are left out.
+    pub fn new(items: &[LeaderItem], actions: &[(&str, &str)]) -> Self {
+        let mut entries = Vec::new();
+        flatten_items(items, "", &mut entries);
+
+        for (name, label) in actions {
+            let covered = entries.iter().any(|entry| {
+                entry
+                    .action
+                    .as_deref()
+                    .is_some_and(|action| action.eq_ignore_ascii_case(name))
+            });
+            if !covered {
+                entries.push(LeaderItem {
+                    label: label.to_string(),
+                    action: Some(name.to_string()),
+                    ..LeaderItem::default()
+                });
+            }
+        }
+
+        let mut palette = Self {
+            entries,
+            ..Self::default()
+        };
+        palette.refilter();
+        palette
+    }
+
+    pub fn push(&mut self, character: char) {
+        self.query.push(character);
+        self.refilter();
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.refilter();
+    }
+
+    /// Move the selection by `delta` rows, wrapping around the ends
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.matches.len() as isize;
+        if len > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(len) as usize;
+        }
+    }
+
+    /// Matching entries, best first
+    pub fn matches(&self) -> impl Iterator<Item = &LeaderItem> {
+        self.matches.iter().map(|&index| &self.entries[index])
+    }
+
+    pub fn selected(&self) -> Option<&LeaderItem> {
+        self.matches
+            .get(self.selected)
+            .map(|&index| &self.entries[index])
+    }
+
+    /// Rows around the selection for drawing
+    pub fn view(&self) -> PaletteView {
+        let start = (self.selected + 1).saturating_sub(PALETTE_VISIBLE_ROWS);
+        PaletteView {
+            query: self.query.clone(),
+            rows: self
+                .matches()
+                .skip(start)
+                .take(PALETTE_VISIBLE_ROWS)
+                .cloned()
+                .collect(),
+            selected: self.selected - start,
+            total: self.matches.len(),
+        }
+    }
+
+    /// Rank the entries against the query. Ties keep the entry order, so
+    /// leader items come before plain actions.
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                fuzzy_score(&self.query, &entry.label).map(|score| (score, index))
+            })
+            .collect();
+        scored.sort_by_key(|&(score, index)| (std::cmp::Reverse(score), index));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+}
+
+/// Push the runnable items of `items` into `out`, descending into
+/// submenus with their label as prefix, e.g. "Splits: Split right".
+fn flatten_items(items: &[LeaderItem], prefix: &str, out: &mut Vec<LeaderItem>) {
+    for item in items {
+        let label = if prefix.is_empty() {
+            item.label.clone()
+        } else {
+            format!("{prefix}: {}", item.label)
+        };
+        match &item.submenu {
+            Some(children) => flatten_items(children, &label, out),
+            None => out.push(LeaderItem {
+                label,
+                ..item.clone()
+            }),
+        }
+    }
+}
 
 /// State of the leader menu
 #[derive(Debug, Default)]
@@ -10,6 +254,22 @@ pub struct LeaderMenuState {
     pub active: bool,
     /// Menu items from config
     pub items: Vec<LeaderItem>,
+    /// Opened submenus, innermost last. Empty while on the root level.
+    stack: Vec<Vec<LeaderItem>>,
+    /// Labels of the opened submenus, parallel to `stack`
+    path: Vec<String>,
+    /// Set while the menu shows as a command palette instead of the
+    /// key grid
+    palette: Option<CommandPalette>,
+    /// Item of `visible_items` picked with the arrow keys
+    highlighted: Option<usize>,
+    /// Typed text filtering the current level by label, started by a
+    /// key no item is bound to
+    search_buffer: String,
+    /// Close the menu after this long without a key press
+    timeout: Option<Duration>,
+    /// When the menu opened or last got a key press
+    last_input: Option<Instant>,
 }
 
 impl LeaderMenuState {
@@ -17,22 +277,216 @@ impl LeaderMenuState {
         Self {
             active: false,
             items,
+            stack: Vec::new(),
+            path: Vec::new(),
+            palette: None,
+            highlighted: None,
+            search_buffer: String::new(),
+            timeout: None,
+            last_input: None,
+        }
+    }
+
+    /// Close the menu after `timeout_ms` without a key press, 0 never
+    pub fn set_timeout(&mut self, timeout_ms: u32) {
+        self.timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms.into()));
+    }
+
+    /// Restart the timeout from `now`, on opening and on every key press
+    pub fn touch(&mut self, now: Instant) {
+        self.last_input = self.active.then_some(now);
+    }
+
+    /// When the open menu closes on its own, `None` without a timeout
+    pub fn deadline(&self) -> Option<Instant> {
+        self.last_input
+            .zip(self.timeout)
+            .map(|(last, timeout)| last + timeout)
+    }
+
+    /// Close the menu once its deadline passed by `now`. Returns whether it
+    /// was closed.
+    pub fn close_if_expired(&mut self, now: Instant) -> bool {
+        if !self.active || self.deadline().is_none_or(|deadline| now < deadline) {
+            return false;
         }
+        self.close();
+        true
+    }
+
+    /// Open the command palette over the leader items and `actions`.
+    /// It stays open while typing until an entry runs or it is dismissed.
+    pub fn open_palette(&mut self, actions: &[(&str, &str)]) {
+        self.reset_stack();
+        self.palette = Some(CommandPalette::new(&self.items, actions));
+        self.active = true;
+        self.touch(Instant::now());
+    }
+
+    pub fn palette(&self) -> Option<&CommandPalette> {
+        self.palette.as_ref()
+    }
+
+    pub fn palette_mut(&mut self) -> Option<&mut CommandPalette> {
+        self.palette.as_mut()
     }
 
     /// Toggle the leader menu visibility
     pub fn toggle(&mut self) {
         self.active = !self.active;
+        self.reset_stack();
+        self.touch(Instant::now());
     }
 
     /// Close the leader menu
     pub fn close(&mut self) {
         self.active = false;
+        self.reset_stack();
+    }
+
+    #[inline]
+    fn reset_stack(&mut self) {
+        self.last_input = None;
+        self.stack.clear();
+        self.path.clear();
+        self.palette = None;
+        self.highlighted = None;
+        self.search_buffer.clear();
+    }
+
+    /// Items of the level currently shown
+    pub fn current_items(&self) -> &[LeaderItem] {
+        self.stack.last().unwrap_or(&self.items)
+    }
+
+    /// Labels of the opened submenus, outermost first
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Open the submenu of `item`. Returns false if it has none.
+    pub fn enter_submenu(&mut self, item: &LeaderItem) -> bool {
+        let Some(children) = &item.submenu else {
+            return false;
+        };
+        self.stack.push(children.clone());
+        self.path.push(item.label.clone());
+        self.highlighted = None;
+        self.search_buffer.clear();
+        true
+    }
+
+    /// Go back to the parent level. Returns false when already on the root.
+    pub fn back(&mut self) -> bool {
+        self.path.pop();
+        self.highlighted = None;
+        self.search_buffer.clear();
+        self.stack.pop().is_some()
+    }
+
+    /// Text typed to filter the current level, empty when not searching
+    pub fn search_buffer(&self) -> &str {
+        &self.search_buffer
+    }
+
+    #[inline]
+    pub fn is_searching(&self) -> bool {
+        !self.search_buffer.is_empty()
+    }
+
+    /// Add `character` to the search, the highlight starts over
+    pub fn push_search(&mut self, character: char) {
+        self.search_buffer.push(character);
+        self.highlighted = None;
+    }
+
+    /// Remove the last searched character
+    pub fn pop_search(&mut self) {
+        self.search_buffer.pop();
+        self.highlighted = None;
+    }
+
+    /// Stop searching. Returns false when there was no search to clear.
+    pub fn clear_search(&mut self) -> bool {
+        if self.search_buffer.is_empty() {
+            return false;
+        }
+        self.search_buffer.clear();
+        self.highlighted = None;
+        true
+    }
+
+    /// Items of the current level the menu shows: all of them, or while
+    /// searching the ones whose label fuzzy matches the search, best
+    /// match first. Ties keep the menu order.
+    pub fn visible_items(&self) -> Vec<&LeaderItem> {
+        let items = self.current_items().iter();
+        if self.search_buffer.is_empty() {
+            return items.collect();
+        }
+        let mut scored: Vec<(i32, &LeaderItem)> = items
+            .filter_map(|item| {
+                fuzzy_score(&self.search_buffer, &item.label).map(|score| (score, item))
+            })
+            .collect();
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// First search result, `None` when not searching or nothing matches
+    pub fn top_result(&self) -> Option<&LeaderItem> {
+        if self.search_buffer.is_empty() {
+            return None;
+        }
+        self.visible_items().into_iter().next()
+    }
+
+    /// Move the highlight by `delta` visible items, wrapping around the
+    /// ends. The first move highlights the first (or last) item.
+    pub fn move_highlight(&mut self, delta: isize) {
+        let len = self.visible_items().len() as isize;
+        if len == 0 {
+            return;
+        }
+        let next = match self.highlighted {
+            Some(index) => index as isize + delta,
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+        self.highlighted = Some(next.rem_euclid(len) as usize);
+    }
+
+    /// Index in `visible_items` of the item picked with the arrow keys
+    pub fn highlighted(&self) -> Option<usize> {
+        self.highlighted
+    }
+
+    /// Item picked with the arrow keys, or the selected palette entry
+    pub fn highlighted_item(&self) -> Option<&LeaderItem> {
+        match &self.palette {
+            Some(palette) => palette.selected(),
+            None => self
+                .highlighted
+                .and_then(|index| self.visible_items().get(index).copied()),
+        }
     }
 
-    /// Find item by key and return the action/write
+    /// Footer line: the description of the highlighted item, empty when
+    /// nothing is highlighted or it has no description
+    pub fn footer_text(&self) -> &str {
+        self.highlighted_item()
+            .and_then(|item| item.description.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Find item by key on the current level and return the action/write.
+    /// While searching keys don't pick items, the top result is returned
+    /// instead.
     pub fn find_item(&self, key: char) -> Option<&LeaderItem> {
-        self.items.iter().find(|item| item.key == key)
+        if self.is_searching() {
+            return self.top_result();
+        }
+        self.current_items().iter().find(|item| item.key == key)
     }
 
     /// Parse action string to Action enum
@@ -40,3 +494,510 @@ impl LeaderMenuState {
         Action::from(action_str.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(key: char, label: &str) -> LeaderItem {
+        LeaderItem {
+            key,
+            label: label.to_string(),
+            ..LeaderItem::default()
+        }
+    }
+
+    fn state_with_submenu() -> LeaderMenuState {
+        let splits = LeaderItem {
+            submenu: Some(vec![item('r', "Split right"), item('d', "Split down")]),
+            ..item('s', "Splits")
+        };
+        let mut state = LeaderMenuState::new(vec![item('n', "New window"), splits]);
+        state.toggle();
+        state
+    }
+
+    #[test]
+    fn test_enter_submenu_pushes_children() {
+        let mut state = state_with_submenu();
+        let splits = state.find_item('s').cloned().unwrap();
+        assert!(state.enter_submenu(&splits));
+
+        assert_eq!(state.current_items().len(), 2);
+        assert_eq!(state.path(), ["Splits".to_string()]);
+        // Only the current level is searched
+        assert!(state.find_item('r').is_some());
+        assert!(state.find_item('n').is_none());
+    }
+
+    #[test]
+    fn test_back_pops_to_parent() {
+        let mut state = state_with_submenu();
+        let splits = state.find_item('s').cloned().unwrap();
+        state.enter_submenu(&splits);
+
+        assert!(state.back());
+        assert!(state.find_item('n').is_some());
+        assert!(state.path().is_empty());
+        assert!(!state.back());
+    }
+
+    #[test]
+    fn test_enter_submenu_ignores_plain_items() {
+        let mut state = state_with_submenu();
+        let new_window = state.find_item('n').cloned().unwrap();
+        assert!(!state.enter_submenu(&new_window));
+        assert_eq!(state.current_items().len(), 2);
+    }
+
+    #[test]
+    fn test_footer_follows_highlighted_item() {
+        let mut state = state_with_submenu();
+        state.items[0].description = Some("Open a new Rio window".to_string());
+        assert!(state.highlighted_item().is_none());
+        assert_eq!(state.footer_text(), "");
+
+        state.move_highlight(1);
+        assert_eq!(state.footer_text(), "Open a new Rio window");
+
+        // Items without a description leave the footer empty
+        state.move_highlight(1);
+        assert_eq!(state.highlighted_item().unwrap().label, "Splits");
+        assert_eq!(state.footer_text(), "");
+
+        state.move_highlight(1);
+        assert_eq!(state.highlighted_item().unwrap().label, "New window");
+        state.close();
+        assert_eq!(state.footer_text(), "");
+    }
+
+    #[test]
+    fn test_footer_uses_palette_selection() {
+        let items = vec![LeaderItem {
+            description: Some("Split the current pane".to_string()),
+            ..item('s', "Split right")
+        }];
+        let mut state = LeaderMenuState::new(items);
+        state.open_palette(ACTIONS);
+        assert_eq!(state.footer_text(), "Split the current pane");
+
+        state.palette_mut().unwrap().move_selection(1);
+        assert_eq!(state.footer_text(), "");
+    }
+
+    #[test]
+    fn test_move_highlight_resets_on_level_change() {
+        let mut state = state_with_submenu();
+        state.move_highlight(-1);
+        let splits = state.highlighted_item().cloned().unwrap();
+        assert_eq!(splits.label, "Splits");
+
+        state.enter_submenu(&splits);
+        assert!(state.highlighted_item().is_none());
+        state.move_highlight(1);
+        assert_eq!(state.highlighted_item().unwrap().label, "Split right");
+
+        state.back();
+        assert!(state.highlighted_item().is_none());
+    }
+
+    #[test]
+    fn test_close_resets_stack() {
+        let mut state = state_with_submenu();
+        let splits = state.find_item('s').cloned().unwrap();
+        state.enter_submenu(&splits);
+        state.close();
+        state.toggle();
+
+        assert!(state.active);
+        assert!(state.find_item('n').is_some());
+        assert!(state.path().is_empty());
+    }
+
+    fn search(state: &mut LeaderMenuState, text: &str) {
+        for character in text.chars() {
+            state.push_search(character);
+        }
+    }
+
+    fn visible_labels(state: &LeaderMenuState) -> Vec<&str> {
+        state
+            .visible_items()
+            .into_iter()
+            .map(|item| item.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_search_filters_labels_ignoring_case() {
+        let mut state = LeaderMenuState::new(vec![
+            item('n', "New window"),
+            item('t', "New tab"),
+            item('s', "Split right"),
+        ]);
+        state.toggle();
+        assert_eq!(visible_labels(&state).len(), 3);
+
+        search(&mut state, "NEW");
+        assert_eq!(visible_labels(&state), ["New window", "New tab"]);
+        search(&mut state, " t");
+        assert_eq!(visible_labels(&state), ["New tab"]);
+
+        state.pop_search();
+        state.pop_search();
+        assert_eq!(state.search_buffer(), "NEW");
+        search(&mut state, "z");
+        assert!(state.visible_items().is_empty());
+        assert!(state.top_result().is_none());
+    }
+
+    #[test]
+    fn test_search_fuzzy_ranks_best_match_first() {
+        let mut state = LeaderMenuState::new(vec![
+            item('c', "Clear history"),
+            item('s', "Split right"),
+            item('r', "Split down"),
+            item('y', "Copy mode"),
+        ]);
+        state.toggle();
+
+        // Letters in order, not necessarily adjacent
+        search(&mut state, "sd");
+        assert_eq!(visible_labels(&state), ["Split down"]);
+
+        state.clear_search();
+        // A word start beats an earlier match inside a word
+        search(&mut state, "r");
+        assert_eq!(visible_labels(&state), ["Split right", "Clear history"]);
+        assert_eq!(state.top_result().unwrap().label, "Split right");
+    }
+
+    #[test]
+    fn test_search_find_item_ignores_keys() {
+        let mut state = state_with_submenu();
+        assert!(state.top_result().is_none());
+        search(&mut state, "split");
+        // 'n' is bound to "New window", but the search wins
+        assert_eq!(state.find_item('n').unwrap().label, "Splits");
+        assert_eq!(state.top_result().unwrap().label, "Splits");
+    }
+
+    #[test]
+    fn test_search_highlight_follows_results() {
+        let mut state = LeaderMenuState::new(vec![
+            item('n', "New window"),
+            item('t', "New tab"),
+            item('s', "Split right"),
+        ]);
+        state.toggle();
+        state.move_highlight(1);
+        state.move_highlight(1);
+
+        search(&mut state, "new");
+        assert!(state.highlighted_item().is_none());
+        state.move_highlight(-1);
+        assert_eq!(state.highlighted_item().unwrap().label, "New tab");
+    }
+
+    #[test]
+    fn test_clear_search_then_close() {
+        let mut state = state_with_submenu();
+        assert!(!state.clear_search());
+        search(&mut state, "sp");
+        assert!(state.clear_search());
+        assert!(!state.is_searching());
+        assert_eq!(state.visible_items().len(), 2);
+
+        search(&mut state, "sp");
+        let splits = state.top_result().cloned().unwrap();
+        state.enter_submenu(&splits);
+        assert_eq!(state.search_buffer(), "");
+
+        search(&mut state, "x");
+        state.close();
+        assert_eq!(state.search_buffer(), "");
+    }
+
+    const ACTIONS: &[(&str, &str)] = &[
+        ("createtab", "Create tab"),
+        ("closetab", "Close tab"),
+        ("splitright", "Split right"),
+        ("splitdown", "Split down"),
+        ("selectnextsplit", "Select next split"),
+        ("togglefullscreen", "Toggle fullscreen"),
+    ];
+
+    fn labels(palette: &CommandPalette) -> Vec<&str> {
+        palette.matches().map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_palette_lists_items_then_actions() {
+        let split_right = LeaderItem {
+            action: Some("SplitRight".to_string()),
+            ..item('r', "Split right")
+        };
+        let items = vec![
+            item('n', "New window"),
+            LeaderItem {
+                submenu: Some(vec![split_right]),
+                ..item('s', "Splits")
+            },
+        ];
+        let palette = CommandPalette::new(&items, ACTIONS);
+
+        // Submenus are flattened, actions bound to an item are not repeated
+        assert_eq!(
+            labels(&palette),
+            [
+                "New window",
+                "Splits: Split right",
+                "Create tab",
+                "Close tab",
+                "Split down",
+                "Select next split",
+                "Toggle fullscreen",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_palette_filters_and_ranks() {
+        let mut palette = CommandPalette::new(&[], ACTIONS);
+        for c in "split".chars() {
+            palette.push(c);
+        }
+        assert_eq!(
+            labels(&palette),
+            ["Split right", "Split down", "Select next split"]
+        );
+
+        palette.pop();
+        palette.pop();
+        palette.pop();
+        palette.pop();
+        palette.push('d');
+        assert_eq!(palette.view().query, "sd");
+        assert_eq!(labels(&palette)[0], "Split down");
+
+        palette.push('z');
+        assert!(palette.selected().is_none());
+        assert_eq!(palette.view().total, 0);
+    }
+
+    #[test]
+    fn test_palette_selection_wraps() {
+        let mut palette = CommandPalette::new(&[], ACTIONS);
+        palette.push('t');
+        palette.push('a');
+        palette.push('b');
+        assert_eq!(labels(&palette), ["Create tab", "Close tab"]);
+
+        assert_eq!(
+            palette.selected().unwrap().action.as_deref(),
+            Some("createtab")
+        );
+        palette.move_selection(1);
+        assert_eq!(palette.selected().unwrap().label, "Close tab");
+        palette.move_selection(1);
+        assert_eq!(palette.selected().unwrap().label, "Create tab");
+        palette.move_selection(-1);
+        assert_eq!(palette.selected().unwrap().label, "Close tab");
+
+        // Typing resets the selection to the best match
+        palette.pop();
+        assert_eq!(palette.selected().unwrap().label, "Create tab");
+    }
+
+    #[test]
+    fn test_palette_view_scrolls_to_selection() {
+        let actions: Vec<(String, String)> = (0..20)
+            .map(|i| (format!("action{i}"), format!("Action {i}")))
+            .collect();
+        let actions: Vec<(&str, &str)> = actions
+            .iter()
+            .map(|(name, label)| (name.as_str(), label.as_str()))
+            .collect();
+        let mut palette = CommandPalette::new(&[], &actions);
+
+        let view = palette.view();
+        assert_eq!(view.rows.len(), PALETTE_VISIBLE_ROWS);
+        assert_eq!(view.selected, 0);
+        assert_eq!(view.total, 20);
+
+        palette.move_selection(15);
+        let view = palette.view();
+        assert_eq!(view.rows.len(), PALETTE_VISIBLE_ROWS);
+        assert_eq!(view.rows[view.selected].label, "Action 15");
+    }
+
+    #[test]
+    fn test_open_palette_and_close() {
+        let mut state = state_with_submenu();
+        state.open_palette(ACTIONS);
+        assert!(state.active);
+        assert!(state.palette().is_some());
+
+        state.close();
+        assert!(!state.active);
+        assert!(state.palette().is_none());
+    }
+
+    #[test]
+    fn test_exec_without_show_output_runs_in_background() {
+        let item = LeaderItem {
+            exec: Some("make".to_string()),
+            ..item('m', "Make")
+        };
+        assert_eq!(
+            LeaderExec::for_item(&item, "make".to_string()),
+            LeaderExec::Background("make".to_string())
+        );
+    }
+
+    #[test]
+    fn test_exec_with_show_output_requests_overlay() {
+        let item = LeaderItem {
+            exec: Some("git status".to_string()),
+            show_output: true,
+            ..item('g', "Git status")
+        };
+        assert_eq!(
+            LeaderExec::for_item(&item, "git status".to_string()),
+            LeaderExec::Overlay("git status".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_selection_command_uses_selection() {
+        let ctx = WriteContext {
+            selection: Some("  cargo test --workspace\n".to_string()),
+            word: Some("cargo".to_string()),
+            ..WriteContext::default()
+        };
+        assert_eq!(
+            run_selection_command(&ctx),
+            Some("cargo test --workspace".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_selection_command_without_selection() {
+        assert_eq!(run_selection_command(&WriteContext::default()), None);
+
+        let blank = WriteContext {
+            selection: Some(" \n\t".to_string()),
+            ..WriteContext::default()
+        };
+        assert_eq!(run_selection_command(&blank), None);
+    }
+
+    #[test]
+    fn test_timeout_elapsed() {
+        let mut state = LeaderMenuState::new(vec![item('n', "New window")]);
+        state.set_timeout(500);
+        state.toggle();
+        let opened = Instant::now();
+        state.touch(opened);
+
+        assert_eq!(state.deadline(), Some(opened + Duration::from_millis(500)));
+        assert!(!state.close_if_expired(opened + Duration::from_millis(499)));
+        assert!(state.active);
+        assert!(state.close_if_expired(opened + Duration::from_millis(500)));
+        assert!(!state.active);
+        assert_eq!(state.deadline(), None);
+    }
+
+    #[test]
+    fn test_timeout_reset_by_key_press() {
+        let mut state = LeaderMenuState::new(vec![item('n', "New window")]);
+        state.set_timeout(500);
+        state.toggle();
+        let opened = Instant::now();
+        state.touch(opened);
+        state.touch(opened + Duration::from_millis(400));
+
+        assert!(!state.close_if_expired(opened + Duration::from_millis(600)));
+        assert!(state.close_if_expired(opened + Duration::from_millis(900)));
+    }
+
+    #[test]
+    fn test_timeout_zero_never_closes() {
+        let mut state = LeaderMenuState::new(vec![item('n', "New window")]);
+        state.set_timeout(0);
+        state.toggle();
+        let opened = Instant::now();
+        state.touch(opened);
+
+        assert_eq!(state.deadline(), None);
+        assert!(!state.close_if_expired(opened + Duration::from_secs(3600)));
+        assert!(state.active);
+    }
+
+    #[test]
+    fn test_timeout_ignored_while_closed() {
+        let mut state = LeaderMenuState::new(vec![item('n', "New window")]);
+        state.set_timeout(500);
+        state.touch(Instant::now());
+
+        assert_eq!(state.deadline(), None);
+        assert!(!state.close_if_expired(Instant::now() + Duration::from_secs(1)));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_run_selection_shell_keeps_split_open() {
+        let shell = Shell {
+            program: "zsh".to_string(),
+            args: vec![],
+        };
+        let shell = run_selection_shell("make check", &shell);
+        assert_eq!(shell.program, "sh");
+        assert_eq!(
+            shell.args,
+            vec!["-c".to_string(), "make check; exec zsh".to_string()]
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_run_selection_shell_with_default_shell() {
+        // The default program is empty and means the login shell
+        let default = rio_backend::config::defaults::default_shell();
+        let shell = run_selection_shell("make check", &default);
+        let login = teletypewriter::login_shell().unwrap_or_else(|| "sh".to_string());
+        assert_eq!(
+            shell.args,
+            vec![
+                "-c".to_string(),
+                format!("make check; exec {} --login", shell_quote(&login)),
+            ]
+        );
+        assert!(!shell.args[1].contains("exec  "));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_run_selection_shell_quotes_program_and_args() {
+        let shell = Shell {
+            program: "/opt/my shell/bin/fish".to_string(),
+            args: vec!["-C".to_string(), "echo 'hi'".to_string()],
+        };
+        let shell = run_selection_shell("ls", &shell);
+        assert_eq!(
+            shell.args[1],
+            "ls; exec '/opt/my shell/bin/fish' -C 'echo '\\''hi'\\'''"
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_output_overlay_shell_holds_output() {
+        let shell = output_overlay_shell("git status --short");
+        assert_eq!(shell.program, "sh");
+        assert_eq!(
+            shell.args,
+            vec!["-c".to_string(), "git status --short; sleep 5".to_string()]
+        );
+    }
+}
@@ -6,6 +6,7 @@
 // were retired from https://github.com/alacritty/alacritty/blob/c39c3c97f1a1213418c3629cc59a1d46e34070e0/alacritty/src/input.rs
 // which is licensed under Apache 2.0 license.
 
+pub mod fuzzy;
 pub mod hint;
 pub mod leader;
 pub mod touch;
@@ -13,7 +14,7 @@ pub mod touch;
 use crate::bindings::kitty_keyboard::build_key_sequence;
 use crate::bindings::{
     Action as Act, BindingKey, BindingMode, FontSizeAction, MouseBinding, SearchAction,
-    ViAction,
+    ViAction, ACTION_REGISTRY,
 };
 #[cfg(target_os = "macos")]
 use crate::constants::{DEADZONE_END_Y, DEADZONE_START_Y};
@@ -30,26 +31,43 @@ use crate::crosswords::{
 use crate::hints::HintState;
 use crate::mouse::{calculate_mouse_position, Mouse};
 use crate::renderer::{
-    utils::{padding_bottom_from_config, padding_top_from_config},
+    utils::{
+        grid_width_from_config, padding_bottom_from_config, padding_top_from_config,
+        padding_x_from_config,
+    },
     Renderer,
 };
 use crate::screen::hint::HintMatches;
-use crate::screen::leader::LeaderMenuState;
+use crate::screen::leader::{
+    output_overlay_shell, run_selection_command, run_selection_shell, CommandPalette,
+    LeaderExec, LeaderMenuState,
+};
 use crate::selection::{Selection, SelectionType};
 use core::fmt::Debug;
 use raw_window_handle::{RawDisplayHandle, RawWindowHandle};
 use rio_backend::clipboard::Clipboard;
 use rio_backend::clipboard::ClipboardType;
+use rio_backend::config::command_overlay::{
+    ad_hoc_style, CommandOverlayRef, CommandOverlayStyle,
+};
+use rio_backend::config::features::Feature;
+use rio_backend::config::leader::{expand_variables, LeaderItem, WriteContext};
 use rio_backend::config::renderer::{
     Backend as RendererBackend, Performance as RendererPerformance,
 };
+use rio_backend::config::window::ALIGN_WIDTH_STEP;
 use rio_backend::crosswords::pos::{Boundary, CursorState, Direction, Line};
 use rio_backend::crosswords::search::RegexSearch;
 use rio_backend::event::{ClickState, EventProxy, SearchState};
 use rio_backend::sugarloaf::{
-    layout::RootStyle, DistortionParams, Sugarloaf, SugarloafErrors, SugarloafRenderer,
-    SugarloafWindow, SugarloafWindowSize, DISTORTION_BARREL, DISTORTION_NONE,
-    DISTORTION_PERSPECTIVE,
+    layout::{RootStyle, SugarDimensions},
+    DistortionParams, Sugarloaf, SugarloafErrors, SugarloafRenderer, SugarloafWindow,
+    SugarloafWindowSize, VignetteParams, DISTORTION_BARREL,
+    DISTORTION_CHROMATIC_ABERRATION, DISTORTION_EDGE_BLACK, DISTORTION_EDGE_CLAMP,
+    DISTORTION_EDGE_MIRROR, DISTORTION_FILTER_LINEAR, DISTORTION_FILTER_NEAREST,
+    DISTORTION_FISHEYE, DISTORTION_NONE, DISTORTION_PERSPECTIVE,
+    DISTORTION_PRECISION_AUTO, DISTORTION_PRECISION_HIGH, DISTORTION_PRECISION_STANDARD,
+    DISTORTION_VIGNETTE,
 };
 use rio_window::event::ElementState;
 use rio_window::event::Modifiers;
@@ -81,19 +99,89 @@ const MAX_SEARCH_HISTORY_SIZE: usize = 255;
 fn distortion_params_from_config(
     config: &rio_backend::config::Config,
 ) -> DistortionParams {
-    use rio_backend::config::distortion::DistortionType;
+    use rio_backend::config::distortion::{
+        DistortionPrecision, DistortionType, EdgeFill, SamplingFilter,
+    };
     let distortion_type = match config.distortion.effect {
         DistortionType::None => DISTORTION_NONE,
         DistortionType::Barrel => DISTORTION_BARREL,
         DistortionType::Perspective => DISTORTION_PERSPECTIVE,
+        DistortionType::Fisheye => DISTORTION_FISHEYE,
+        DistortionType::ChromaticAberration => DISTORTION_CHROMATIC_ABERRATION,
+        DistortionType::Vignette => DISTORTION_VIGNETTE,
+    };
+    let edge_fill = match config.distortion.edge_fill {
+        EdgeFill::Black => DISTORTION_EDGE_BLACK,
+        EdgeFill::Clamp => DISTORTION_EDGE_CLAMP,
+        EdgeFill::Mirror => DISTORTION_EDGE_MIRROR,
+    };
+    let filter = match config.distortion.filter {
+        SamplingFilter::Linear => DISTORTION_FILTER_LINEAR,
+        SamplingFilter::Nearest => DISTORTION_FILTER_NEAREST,
+    };
+    let precision = match config.distortion.precision {
+        DistortionPrecision::Auto => DISTORTION_PRECISION_AUTO,
+        DistortionPrecision::Standard => DISTORTION_PRECISION_STANDARD,
+        DistortionPrecision::High => DISTORTION_PRECISION_HIGH,
     };
-    DistortionParams {
+    let params = DistortionParams {
         distortion_type,
         strength: config.distortion.strength,
         center: config.distortion.center,
+        fov: config.distortion.fov_radians(),
+        edge_fill,
+        filter,
+        precision,
+        ..DistortionParams::default()
+    };
+    match distortion_type {
+        DISTORTION_CHROMATIC_ABERRATION => {
+            params.with_chromatic_aberration(config.distortion.strength)
+        }
+        DISTORTION_VIGNETTE => params.with_vignette(
+            config.distortion.inner_radius,
+            config.distortion.outer_radius,
+        ),
+        _ => params,
+    }
+}
+
+/// Convert the config vignette settings into GPU-side params.
+fn vignette_params_from_config(config: &rio_backend::config::Config) -> VignetteParams {
+    VignetteParams {
+        intensity: config.vignette.intensity.max(0.0),
+        radius: config.vignette.radius,
+        smoothness: config.vignette.smoothness,
+        color: config.vignette.color,
+        ..VignetteParams::default()
     }
 }
 
+/// Apply filters, distortion and vignette from the config, all of them
+/// off while the `effects` feature is disabled.
+fn update_post_processing(
+    sugarloaf: &mut Sugarloaf,
+    config: &rio_backend::config::Config,
+) {
+    sugarloaf.set_premultiplied_alpha(config.renderer.premultiplied_alpha);
+    if !config.feature_enabled(Feature::Effects) {
+        sugarloaf.update_filters(&[], config.renderer.restore_alpha);
+        sugarloaf.update_distortion(DistortionParams::default());
+        sugarloaf.update_vignette(VignetteParams::default());
+        return;
+    }
+
+    sugarloaf.update_filters(
+        config.renderer.filters.as_slice(),
+        config.renderer.restore_alpha,
+    );
+    sugarloaf.animate_distortion(
+        distortion_params_from_config(config),
+        config.distortion.animate_duration_ms,
+    );
+    sugarloaf.update_vignette(vignette_params_from_config(config));
+}
+
 pub struct Screen<'screen> {
     bindings: crate::bindings::KeyBindings,
     mouse_bindings: Vec<MouseBinding>,
@@ -198,8 +286,7 @@ impl Screen<'_> {
             }
         };
 
-        sugarloaf.update_filters(config.renderer.filters.as_slice());
-        sugarloaf.update_distortion(distortion_params_from_config(config));
+        update_post_processing(&mut sugarloaf, config);
 
         let renderer = Renderer::new(config, font_library);
 
@@ -227,19 +314,25 @@ impl Screen<'_> {
             should_update_title_extra: !config.navigation.color_automation.is_empty(),
             split_color: config.colors.split,
             title: config.title.clone(),
+            tab_title_format: config.navigation.tab_title_format.clone(),
             keyboard: config.keyboard,
-            command_overlay_style: config.command_overlay,
+            command_overlays: config.command_overlay.clone(),
         };
 
         let rich_text_id = sugarloaf.create_rich_text();
 
         let margin = Delta {
-            x: config.padding_x,
+            x: padding_x_from_config(&config.navigation, config.padding_x, 1),
             top_y: padding_y_top,
             bottom_y: padding_y_bottom,
         };
         let context_dimension = ContextDimension::build(
-            size.width as f32,
+            grid_width_from_config(
+                &config.navigation,
+                size.width as f32,
+                scale as f32,
+                1,
+            ),
             size.height as f32,
             sugarloaf.get_rich_text_dimensions(&rich_text_id),
             config.line_height,
@@ -274,19 +367,51 @@ impl Screen<'_> {
 
         if let Some(image) = &config.window.background_image {
             sugarloaf.set_background_image(image);
+        } else {
+            let size = sugarloaf.window_size();
+            if let Some((width, height, pixels)) =
+                renderer.background_gradient_pixels((size.width, size.height))
+            {
+                sugarloaf.set_background_pixels(width, height, pixels);
+            }
         }
         sugarloaf.render();
 
         Ok(Screen {
             search_state: SearchState::default(),
-            hint_state: HintState::new(config.hints.alphabet.clone()),
-            leader_state: LeaderMenuState::new(config.leader.items()),
-            hints_config: config
-                .hints
-                .rules
-                .iter()
-                .map(|h| std::rc::Rc::new(h.clone()))
-                .collect(),
+            hint_state: {
+                let label_strategy = config.hints.label_strategy;
+                let mut hint_state =
+                    HintState::try_new(config.hints.alphabet.clone(), label_strategy)
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("{e}, using the default hint alphabet");
+                            HintState::try_new(
+                                rio_backend::config::hints::DEFAULT_HINTS_ALPHABET
+                                    .to_string(),
+                                label_strategy,
+                            )
+                            .expect("default hint alphabet is valid")
+                        });
+                hint_state.update_scrollback_lines(config.hints.scrollback_lines);
+                hint_state.update_label_style(config.hints.label_style);
+                hint_state.update_filter_threshold(config.hints.filter_threshold);
+                hint_state
+            },
+            leader_state: {
+                let mut leader_state = LeaderMenuState::new(config.leader.items());
+                leader_state.set_timeout(config.leader.timeout_ms);
+                leader_state
+            },
+            hints_config: if config.feature_enabled(Feature::Hints) {
+                config
+                    .hints
+                    .rules
+                    .iter()
+                    .map(|h| std::rc::Rc::new(h.clone()))
+                    .collect()
+            } else {
+                Vec::new()
+            },
             mouse_bindings: crate::bindings::default_mouse_bindings(),
             modifiers: Modifiers::default(),
             context_manager,
@@ -336,6 +461,54 @@ impl Screen<'_> {
         }
     }
 
+    /// Focus the split under the mouse if it isn't focused already, for
+    /// `window.focus-follows-mouse`. Returns whether focus moved.
+    pub fn focus_split_under_mouse(&mut self) -> bool {
+        let grid = self.context_manager.current_grid_mut();
+        match grid.split_under_mouse(&self.mouse) {
+            Some(key) if key != grid.current => {
+                grid.current = key;
+                self.context_manager.select_route_from_current_grid();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Grab a draggable command overlay by its handle under the mouse
+    #[inline]
+    pub fn start_overlay_drag(&mut self) -> bool {
+        let (x, y) = (self.mouse.x as f32, self.mouse.y as f32);
+        self.context_manager
+            .current_grid_mut()
+            .start_overlay_drag(x, y)
+    }
+
+    /// Move the grabbed command overlay to follow the pointer at `(x, y)`.
+    /// Configured overlays keep the position in memory for the session, so
+    /// it survives closing and reopening them.
+    pub fn drag_overlay(&mut self, x: f32, y: f32) -> bool {
+        let Some((slot, overlay_x, overlay_y)) =
+            self.context_manager.current_grid_mut().drag_overlay(x, y)
+        else {
+            return false;
+        };
+
+        if let Some(style) = slot
+            .and_then(|slot| self.context_manager.config.command_overlays.get_mut(slot))
+        {
+            style.x = overlay_x;
+            style.y = overlay_y;
+        }
+        self.context_manager.request_render();
+        true
+    }
+
+    #[inline]
+    pub fn end_overlay_drag(&mut self) -> bool {
+        self.context_manager.current_grid_mut().end_overlay_drag()
+    }
+
     #[inline]
     pub fn mouse_position(&self, display_offset: usize) -> Pos {
         let current_grid = self.context_manager.current_grid();
@@ -392,28 +565,45 @@ impl Screen<'_> {
             num_tabs,
             self.search_active(),
         );
+        let padding_x =
+            padding_x_from_config(&config.navigation, config.padding_x, num_tabs);
+        let grid_width = grid_width_from_config(
+            &config.navigation,
+            self.sugarloaf.window_size().width,
+            self.sugarloaf.scale_factor(),
+            num_tabs,
+        );
 
         if should_update_font_library {
             self.sugarloaf.update_font(font_library);
         }
+
+        if let Err(e) = self.hint_state.update_alphabet(&config.hints.alphabet) {
+            tracing::warn!("{e}, keeping the current hint alphabet");
+        }
+        self.hint_state
+            .update_label_strategy(config.hints.label_strategy);
+        self.hint_state
+            .update_scrollback_lines(config.hints.scrollback_lines);
+        self.hint_state.update_label_style(config.hints.label_style);
+        self.hint_state
+            .update_filter_threshold(config.hints.filter_threshold);
+        self.leader_state.set_timeout(config.leader.timeout_ms);
         let s = self.sugarloaf.style_mut();
         s.font_size = config.fonts.size;
         s.line_height = config.line_height;
 
-        self.sugarloaf
-            .update_filters(config.renderer.filters.as_slice());
-        self.sugarloaf
-            .update_distortion(distortion_params_from_config(config));
+        update_post_processing(&mut self.sugarloaf, config);
         self.renderer = Renderer::new(config, font_library);
 
         for context_grid in self.context_manager.contexts_mut() {
             context_grid.update_line_height(config.line_height);
 
-            context_grid.update_margin((
-                config.padding_x,
-                padding_y_top,
-                padding_y_bottom,
-            ));
+            context_grid.update_margin((padding_x, padding_y_top, padding_y_bottom));
+            if context_grid.width != grid_width {
+                let height = context_grid.height;
+                context_grid.resize(grid_width, height);
+            }
 
             context_grid.update_dimensions(&self.sugarloaf);
 
@@ -444,20 +634,23 @@ impl Screen<'_> {
 
         // Update keyboard and command overlay config in context manager
         self.context_manager.config.keyboard = config.keyboard;
-        self.context_manager.config.command_overlay_style = config.command_overlay;
+        self.context_manager.config.command_overlays = config.command_overlay.clone();
 
-        // Update command overlay style on all grids for hot-reload
+        // Update command overlay styles on all grids for hot-reload
+        let ad_hoc = ad_hoc_style(&config.command_overlay);
         for context_grid in self.context_manager.contexts_mut() {
-            context_grid.command_overlay_style = config.command_overlay;
-
-            // Update font size on existing command overlays
-            if config.command_overlay.has_custom_font_size() {
-                for overlay in &mut context_grid.command_overlays {
+            for overlay in &mut context_grid.command_overlays {
+                overlay.style = overlay
+                    .slot
+                    .and_then(|slot| config.command_overlay.get(slot))
+                    .unwrap_or(&ad_hoc)
+                    .clone();
+
+                // Update font size on existing command overlays
+                if overlay.style.has_custom_font_size() {
                     let rt_id = overlay.item.context_mut().rich_text_id;
-                    self.sugarloaf.set_rich_text_font_size(
-                        &rt_id,
-                        config.command_overlay.font_size,
-                    );
+                    self.sugarloaf
+                        .set_rich_text_font_size(&rt_id, overlay.style.font_size);
                 }
             }
         }
@@ -471,6 +664,9 @@ impl Screen<'_> {
 
         if let Some(image) = &config.window.background_image {
             self.sugarloaf.set_background_image(image);
+        } else {
+            self.sugarloaf.clear_background_layer();
+            self.update_background_gradient();
         }
 
         self.resize_all_contexts();
@@ -509,7 +705,13 @@ impl Screen<'_> {
             self.clear_selection();
         }
         self.sugarloaf.resize(new_size.width, new_size.height);
-        let width = new_size.width as f32;
+        self.update_background_gradient();
+        let width = grid_width_from_config(
+            &self.renderer.navigation.navigation,
+            new_size.width as f32,
+            self.sugarloaf.scale_factor(),
+            self.ctx().len(),
+        );
         let height = new_size.height as f32;
 
         for context_grid in self.context_manager.contexts_mut() {
@@ -532,7 +734,12 @@ impl Screen<'_> {
         self.context_manager
             .current_grid_mut()
             .update_dimensions(&self.sugarloaf);
-        let width = new_size.width as f32;
+        let width = grid_width_from_config(
+            &self.renderer.navigation.navigation,
+            new_size.width as f32,
+            new_scale,
+            self.ctx().len(),
+        );
         let height = new_size.height as f32;
 
         for context_grid in self.context_manager.contexts_mut() {
@@ -542,6 +749,32 @@ impl Screen<'_> {
         self
     }
 
+    /// Change the window opacity, see `Renderer::set_window_opacity`.
+    /// Returns false when nothing changed.
+    pub fn set_window_opacity(&mut self, opacity: f32) -> bool {
+        if !self.renderer.set_window_opacity(opacity) {
+            return false;
+        }
+        if !cfg!(target_os = "macos") {
+            self.sugarloaf
+                .set_background_color(Some(self.renderer.dynamic_background.1));
+        }
+        self.update_background_gradient();
+        true
+    }
+
+    /// Redraw the gradient background at the current window aspect ratio.
+    /// Does nothing without a gradient.
+    fn update_background_gradient(&mut self) {
+        let size = self.sugarloaf.window_size();
+        if let Some((width, height, pixels)) = self
+            .renderer
+            .background_gradient_pixels((size.width, size.height))
+        {
+            self.sugarloaf.set_background_pixels(width, height, pixels);
+        }
+    }
+
     #[inline]
     pub fn resize_all_contexts(&mut self) {
         // whenever a resize update happens: it will stored in
@@ -661,16 +894,23 @@ impl Screen<'_> {
                     self.render();
                     return;
                 }
+                // Pick another action for the selection, remembered per hint
+                rio_window::keyboard::Key::Named(rio_window::keyboard::NamedKey::Tab) => {
+                    self.hint_state.cycle_target();
+                    return;
+                }
                 _ => {}
             }
 
             // Handle text input
             let text = key.text_with_all_modifiers().unwrap_or_default();
             for character in text.chars() {
-                let terminal = self.context_manager.current().terminal.lock();
+                let mut terminal = self.context_manager.current().terminal.lock();
                 if let Some(hint_match) =
                     self.hint_state.keyboard_input(&*terminal, character)
                 {
+                    // Matches from the scrollback are brought into view first
+                    crate::hints::scroll_to_match(&mut terminal, &hint_match);
                     drop(terminal);
                     self.execute_hint_action(&hint_match);
                     // Stop hint mode and update state with proper damage tracking
@@ -751,6 +991,18 @@ impl Screen<'_> {
         );
     }
 
+    /// Send a `PlaySoundPanned` event, panned towards the side of the
+    /// window the current split is on.
+    fn emit_split_sound(&self, sound: rio_backend::event::SoundEvent) {
+        use rio_backend::event::{EventListener, RioEvent};
+        let pan = self.context_manager.current_grid().current_pan();
+        EventListener::send_event(
+            self.context_manager.event_proxy(),
+            RioEvent::PlaySoundPanned(sound, pan),
+            self.context_manager.window_id(),
+        );
+    }
+
     /// Send a `PlaySound` event for the given key press.
     fn emit_key_sound(&self, key: &rio_window::event::KeyEvent) {
         use rio_backend::event::SoundEvent;
@@ -769,17 +1021,71 @@ impl Screen<'_> {
         if key.state != ElementState::Pressed {
             return;
         }
+        self.leader_state.touch(std::time::Instant::now());
+
+        if self.leader_state.palette().is_some() {
+            self.handle_palette_input(key);
+            return;
+        }
 
-        // Handle Escape to close menu
+        // Escape clears the search first, then goes back to the parent
+        // submenu, or closes on the root level
         if key.logical_key == Key::Named(NamedKey::Escape) {
-            self.leader_state.close();
+            if !self.leader_state.clear_search() && !self.leader_state.back() {
+                self.leader_state.close();
+            }
             self.render();
             return;
         }
 
+        // Arrow keys highlight an item, showing its description in the
+        // footer, and Enter runs the highlighted item
+        match key.logical_key.as_ref() {
+            Key::Named(NamedKey::ArrowUp) => {
+                self.leader_state.move_highlight(-1);
+                self.render();
+                return;
+            }
+            Key::Named(NamedKey::ArrowDown) => {
+                self.leader_state.move_highlight(1);
+                self.render();
+                return;
+            }
+            Key::Named(NamedKey::Enter) => {
+                let item = self
+                    .leader_state
+                    .highlighted_item()
+                    .or_else(|| self.leader_state.top_result())
+                    .cloned();
+                if let Some(item) = item {
+                    if !self.leader_state.enter_submenu(&item) {
+                        self.run_leader_item(item);
+                        return;
+                    }
+                    self.render();
+                    return;
+                }
+            }
+            _ => {}
+        }
+
         // Get the character pressed - try multiple sources
         let text = key.text_with_all_modifiers().unwrap_or_default();
 
+        // While searching, typing refines the search instead of picking
+        // items by key
+        if self.leader_state.is_searching() {
+            if key.logical_key == Key::Named(NamedKey::Backspace) {
+                self.leader_state.pop_search();
+            } else {
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    self.leader_state.push_search(character);
+                }
+            }
+            self.render();
+            return;
+        }
+
         // Also check logical_key for single character keys
         let mut chars_to_check: Vec<char> = text.chars().collect();
         if let Key::Character(c) = &key.logical_key {
@@ -792,54 +1098,109 @@ impl Screen<'_> {
 
         for character in chars_to_check {
             if let Some(item) = self.leader_state.find_item(character).cloned() {
-                // Close the menu first
-                self.leader_state.close();
-
-                // Execute the action
-                if let Some(action_str) = &item.action {
-                    let action = LeaderMenuState::parse_action(action_str);
-                    self.execute_leader_action(action);
-                } else if let Some(write_str) = &item.write {
-                    // Expand variables and write to PTY
-                    let expanded = self.expand_leader_variables(write_str);
-                    self.ctx_mut()
-                        .current_mut()
-                        .messenger
-                        .send_write(expanded.into_bytes());
-                } else if let Some(exec_str) = &item.exec {
-                    // Execute command in background and show progress
-                    let expanded = self.expand_leader_variables(exec_str);
-                    self.execute_background_command(&expanded);
-                } else if let Some(overlay_str) = &item.overlay {
-                    // Toggle a live command output overlay (real PTY)
-                    let expanded = self.expand_leader_variables(overlay_str);
-                    let rich_text_id = self.sugarloaf.create_rich_text();
-                    let style = self.context_manager.config.command_overlay_style;
-                    let overlay_dims = if style.has_custom_font_size() {
-                        self.sugarloaf
-                            .set_rich_text_font_size(&rich_text_id, style.font_size);
-                        let layout = self.sugarloaf.rich_text_layout(&rich_text_id);
-                        Some(layout.dimensions)
-                    } else {
-                        None
-                    };
-                    self.context_manager.toggle_command_overlay(
-                        rich_text_id,
-                        &expanded,
-                        overlay_dims,
-                    );
+                if self.leader_state.enter_submenu(&item) {
+                    self.render();
+                    return;
                 }
 
-                self.render();
+                self.run_leader_item(item);
                 return;
             }
         }
 
+        // A character no item is bound to starts searching the labels
+        let search: Vec<char> = text.chars().filter(|c| !c.is_control()).collect();
+        if !search.is_empty() {
+            for character in search {
+                self.leader_state.push_search(character);
+            }
+            self.render();
+            return;
+        }
+
         // Any other key closes the menu without action
         self.leader_state.close();
         self.render();
     }
 
+    /// Handle input while the leader menu shows as a command palette
+    fn handle_palette_input(&mut self, key: &rio_window::event::KeyEvent) {
+        let Some(palette) = self.leader_state.palette_mut() else {
+            return;
+        };
+
+        match key.logical_key.as_ref() {
+            Key::Named(NamedKey::Escape) => self.leader_state.close(),
+            Key::Named(NamedKey::Enter) => {
+                if let Some(item) = palette.selected().cloned() {
+                    self.run_leader_item(item);
+                    return;
+                }
+            }
+            Key::Named(NamedKey::ArrowUp) => palette.move_selection(-1),
+            Key::Named(NamedKey::ArrowDown) | Key::Named(NamedKey::Tab) => {
+                palette.move_selection(1)
+            }
+            Key::Named(NamedKey::Backspace) => palette.pop(),
+            _ => {
+                let text = key.text_with_all_modifiers().unwrap_or_default();
+                for character in text.chars().filter(|c| !c.is_control()) {
+                    palette.push(character);
+                }
+            }
+        }
+        self.render();
+    }
+
+    /// Close the leader menu and run `item`'s action, write, exec or
+    /// overlay
+    fn run_leader_item(&mut self, item: LeaderItem) {
+        // Close the menu first
+        self.leader_state.close();
+
+        // Execute the action
+        if let Some(action_str) = &item.action {
+            let action = LeaderMenuState::parse_action(action_str);
+            self.execute_leader_action(action);
+        } else if let Some(expanded) = item.expand_write(&self.leader_write_context()) {
+            // Write the expanded text to PTY
+            self.ctx_mut()
+                .current_mut()
+                .messenger
+                .send_write(expanded.into_bytes());
+        } else if let Some(exec_str) = &item.exec {
+            let expanded = expand_variables(exec_str, &self.leader_write_context());
+            match LeaderExec::for_item(&item, expanded) {
+                // Execute command in background and show progress
+                LeaderExec::Background(command) => {
+                    self.execute_background_command(&command);
+                }
+                // Show stdout/stderr in a transient overlay
+                LeaderExec::Overlay(command) => {
+                    let (rich_text_id, overlay_dims) =
+                        self.create_command_overlay_rich_text();
+                    self.context_manager.toggle_command_overlay_with_shell(
+                        rich_text_id,
+                        &command,
+                        output_overlay_shell(&command),
+                        overlay_dims,
+                    );
+                }
+            }
+        } else if let Some(overlay_str) = &item.overlay {
+            // Toggle a live command output overlay (real PTY)
+            let expanded = expand_variables(overlay_str, &self.leader_write_context());
+            let (rich_text_id, overlay_dims) = self.create_command_overlay_rich_text();
+            self.context_manager.toggle_command_overlay(
+                rich_text_id,
+                &expanded,
+                overlay_dims,
+            );
+        }
+
+        self.render();
+    }
+
     /// Execute an action from the leader menu
     fn execute_leader_action(&mut self, action: Act) {
         match action {
@@ -858,6 +1219,9 @@ impl Screen<'_> {
             }
             Act::SplitRight => self.split_right(),
             Act::SplitDown => self.split_down(),
+            Act::RunSelection => self.run_selection(),
+            Act::ToggleLeaderMenu => self.leader_state.toggle(),
+            Act::ToggleCommandPalette => self.leader_state.open_palette(ACTION_REGISTRY),
             Act::ToggleViMode => {
                 let context = self.context_manager.current_mut();
                 let mut terminal = context.terminal.lock();
@@ -876,10 +1240,7 @@ impl Screen<'_> {
             }
             Act::Quit => self.context_manager.quit(),
             Act::Copy => self.copy_selection(ClipboardType::Clipboard),
-            Act::Paste => {
-                let content = self.clipboard.borrow_mut().get(ClipboardType::Clipboard);
-                self.paste(&content, true);
-            }
+            Act::Paste => self.paste_from_clipboard(ClipboardType::Clipboard),
             Act::ClearHistory => {
                 let mut terminal = self.context_manager.current_mut().terminal.lock();
                 terminal.clear_saved_history();
@@ -919,22 +1280,17 @@ impl Screen<'_> {
                 self.context_manager.toggle_quick_terminal(rich_text_id);
             }
             Act::ToggleCommandOverlay(command) => {
-                let rich_text_id = self.sugarloaf.create_rich_text();
-                let style = self.context_manager.config.command_overlay_style;
-                let overlay_dims = if style.has_custom_font_size() {
-                    self.sugarloaf
-                        .set_rich_text_font_size(&rich_text_id, style.font_size);
-                    let layout = self.sugarloaf.rich_text_layout(&rich_text_id);
-                    Some(layout.dimensions)
-                } else {
-                    None
-                };
+                let (rich_text_id, overlay_dims) =
+                    self.create_command_overlay_rich_text();
                 self.context_manager.toggle_command_overlay(
                     rich_text_id,
                     &command,
                     overlay_dims,
                 );
             }
+            Act::ToggleConfiguredOverlay(target) => {
+                self.toggle_configured_overlay(&target);
+            }
             Act::IncreaseFontSize => {
                 self.change_font_size(FontSizeAction::Increase);
             }
@@ -945,12 +1301,58 @@ impl Screen<'_> {
                 self.change_font_size(FontSizeAction::Reset);
             }
             Act::AlignWindows => self.context_manager.align_windows(),
+            Act::IncreaseAlignWidth => {
+                self.context_manager.adjust_align_width(ALIGN_WIDTH_STEP)
+            }
+            Act::DecreaseAlignWidth => {
+                self.context_manager.adjust_align_width(-ALIGN_WIDTH_STEP)
+            }
+            Act::CopyWindowLayout => self.context_manager.copy_window_layout(),
+            Act::ToggleMuteSounds => self.context_manager.toggle_mute_sounds(),
+            Act::CycleNavigationMode => self.context_manager.cycle_navigation_mode(),
             Act::CycleWindowNext => self.context_manager.cycle_window_next(),
             Act::CycleWindowPrev => self.context_manager.cycle_window_prev(),
             _ => {}
         }
     }
 
+    /// Toggle the `[[command-overlay]]` entry picked by `target`
+    fn toggle_configured_overlay(&mut self, target: &CommandOverlayRef) {
+        let overlays = &self.context_manager.config.command_overlays;
+        let Some(slot) = target.resolve(overlays) else {
+            tracing::warn!("no command overlay matches {target:?}");
+            return;
+        };
+        let style = overlays[slot].clone();
+        let (rich_text_id, overlay_dims) = self.create_overlay_rich_text(&style);
+        self.context_manager
+            .toggle_configured_overlay(slot, rich_text_id, overlay_dims);
+    }
+
+    /// Create the rich text for an ad-hoc command overlay, applying the
+    /// overlay font size when one is configured.
+    fn create_command_overlay_rich_text(&mut self) -> (usize, Option<SugarDimensions>) {
+        let style = ad_hoc_style(&self.context_manager.config.command_overlays);
+        self.create_overlay_rich_text(&style)
+    }
+
+    /// Create the rich text for an overlay drawn with `style`
+    fn create_overlay_rich_text(
+        &mut self,
+        style: &CommandOverlayStyle,
+    ) -> (usize, Option<SugarDimensions>) {
+        let rich_text_id = self.sugarloaf.create_rich_text();
+        let overlay_dims = if style.has_custom_font_size() {
+            self.sugarloaf
+                .set_rich_text_font_size(&rich_text_id, style.font_size);
+            let layout = self.sugarloaf.rich_text_layout(&rich_text_id);
+            Some(layout.dimensions)
+        } else {
+            None
+        };
+        (rich_text_id, overlay_dims)
+    }
+
     /// Execute a command in background and show progress bar with result
     fn execute_background_command(&mut self, command: &str) {
         use rio_backend::ansi::ProgressState;
@@ -1019,38 +1421,66 @@ impl Screen<'_> {
         });
     }
 
-    /// Expand variables in leader menu write strings
-    fn expand_leader_variables(&self, input: &str) -> String {
-        let mut result = input.to_string();
-
-        // Get selection if any
-        let selection = {
-            let terminal = self.context_manager.current().terminal.lock();
-            if let Some(selection) = &terminal.selection {
-                let range = selection.to_range(&terminal);
-                if let Some(range) = range {
-                    Some(terminal.bounds_to_string(range.start, range.end))
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        };
+    /// Values for the variables of leader menu strings, taken from the
+    /// current terminal
+    fn leader_write_context(&self) -> WriteContext {
+        let current = self.context_manager.current();
+        let terminal = current.terminal.lock();
+
+        let selection = terminal
+            .selection
+            .as_ref()
+            .and_then(|selection| selection.to_range(&terminal))
+            .map(|range| terminal.bounds_to_string(range.start, range.end));
+
+        let cursor = terminal.grid.cursor.pos;
+        let last_column = Column(terminal.columns().saturating_sub(1));
+        let line = terminal
+            .bounds_to_string(
+                Pos::new(cursor.row, Column(0)),
+                Pos::new(cursor.row, last_column),
+            )
+            .trim_end()
+            .to_string();
 
-        if let Some(sel) = selection {
-            result = result.replace("${SELECTION}", &sel);
-        } else {
-            result = result.replace("${SELECTION}", "");
+        // The cursor usually sits right after the word that was just typed
+        let mut word_pos = cursor;
+        if terminal.grid[cursor].c.is_whitespace() && cursor.col.0 > 0 {
+            word_pos.col -= 1;
         }
+        let word = terminal
+            .bounds_to_string(
+                terminal.semantic_search_left(word_pos),
+                terminal.semantic_search_right(word_pos),
+            )
+            .trim()
+            .to_string();
 
-        // For now, other variables are empty - can be implemented later
-        result = result.replace("${WORD}", "");
-        result = result.replace("${LINE}", "");
-        result = result.replace("${CWD}", "");
-        result = result.replace("${FILE}", "");
+        let cwd = terminal.current_directory.clone();
+        drop(terminal);
+
+        #[cfg(not(target_os = "windows"))]
+        let cwd = cwd.or_else(|| {
+            teletypewriter::foreground_process_path(*current.main_fd, current.shell_pid)
+                .ok()
+        });
 
-        result
+        let word = Some(word).filter(|word| !word.is_empty());
+        let file = selection
+            .as_deref()
+            .or(word.as_deref())
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| crate::hints::resolve_path(name, cwd.as_deref()))
+            .filter(|path| std::path::Path::new(path).is_file());
+
+        WriteContext {
+            selection,
+            word,
+            line: Some(line).filter(|line| !line.is_empty()),
+            cwd: cwd.map(|cwd| cwd.to_string_lossy().into_owned()),
+            file,
+        }
     }
 
     /// Check whether we should try to build escape sequence for the [`KeyEvent`].
@@ -1102,8 +1532,7 @@ impl Screen<'_> {
             if binding.is_triggered_by(binding_mode.to_owned(), mods, &button)
                 && binding.action == Act::PasteSelection
             {
-                let content = self.clipboard.borrow_mut().get(ClipboardType::Selection);
-                self.paste(&content, true);
+                self.paste_from_clipboard(ClipboardType::Selection);
             }
         }
     }
@@ -1164,17 +1593,13 @@ impl Screen<'_> {
                         self.paste(s, false);
                     }
                     Act::Paste => {
-                        let content =
-                            self.clipboard.borrow_mut().get(ClipboardType::Clipboard);
-                        self.paste(&content, true);
+                        self.paste_from_clipboard(ClipboardType::Clipboard);
                     }
                     Act::ClearSelection => {
                         self.clear_selection();
                     }
                     Act::PasteSelection => {
-                        let content =
-                            self.clipboard.borrow_mut().get(ClipboardType::Selection);
-                        self.paste(&content, true);
+                        self.paste_from_clipboard(ClipboardType::Selection);
                     }
                     Act::Copy => {
                         self.copy_selection(ClipboardType::Clipboard);
@@ -1362,16 +1787,8 @@ impl Screen<'_> {
                         self.render();
                     }
                     Act::ToggleCommandOverlay(ref command) => {
-                        let rich_text_id = self.sugarloaf.create_rich_text();
-                        let style = self.context_manager.config.command_overlay_style;
-                        let overlay_dims = if style.has_custom_font_size() {
-                            self.sugarloaf
-                                .set_rich_text_font_size(&rich_text_id, style.font_size);
-                            let layout = self.sugarloaf.rich_text_layout(&rich_text_id);
-                            Some(layout.dimensions)
-                        } else {
-                            None
-                        };
+                        let (rich_text_id, overlay_dims) =
+                            self.create_command_overlay_rich_text();
                         self.context_manager.toggle_command_overlay(
                             rich_text_id,
                             command,
@@ -1379,6 +1796,10 @@ impl Screen<'_> {
                         );
                         self.render();
                     }
+                    Act::ToggleConfiguredOverlay(ref target) => {
+                        self.toggle_configured_overlay(target);
+                        self.render();
+                    }
                     Act::ConfigEditor => {
                         self.context_manager.switch_to_settings();
                     }
@@ -1394,6 +1815,21 @@ impl Screen<'_> {
                     Act::AlignWindows => {
                         self.context_manager.align_windows();
                     }
+                    Act::IncreaseAlignWidth => {
+                        self.context_manager.adjust_align_width(ALIGN_WIDTH_STEP);
+                    }
+                    Act::DecreaseAlignWidth => {
+                        self.context_manager.adjust_align_width(-ALIGN_WIDTH_STEP);
+                    }
+                    Act::CopyWindowLayout => {
+                        self.context_manager.copy_window_layout();
+                    }
+                    Act::ToggleMuteSounds => {
+                        self.context_manager.toggle_mute_sounds();
+                    }
+                    Act::CycleNavigationMode => {
+                        self.context_manager.cycle_navigation_mode();
+                    }
                     Act::CloseCurrentSplitOrTab => {
                         self.close_split_or_tab();
                     }
@@ -1605,6 +2041,15 @@ impl Screen<'_> {
                         self.leader_state.toggle();
                         self.render();
                     }
+                    Act::ToggleCommandPalette => {
+                        if self.leader_state.active {
+                            self.leader_state.close();
+                        } else {
+                            self.leader_state.open_palette(ACTION_REGISTRY);
+                        }
+                        self.render();
+                    }
+                    Act::RunSelection => self.run_selection(),
                     Act::ReceiveChar | Act::None => (),
                     _ => (),
                 }
@@ -1614,7 +2059,45 @@ impl Screen<'_> {
         ignore_chars.unwrap_or(false)
     }
 
+    /// Whether `navigation.max-splits` leaves room for another split.
+    /// Refusals are logged and, with `bell-on-limit`, ring the bell.
+    fn can_add_split(&self) -> bool {
+        let splits = self.context_manager.current_grid_len();
+        if self.renderer.navigation.navigation.can_add_split(splits) {
+            return true;
+        }
+        tracing::debug!("max-splits reached ({splits}), not splitting");
+        self.ring_limit_bell();
+        false
+    }
+
+    /// Whether `navigation.max-tabs` leaves room for another tab.
+    /// Refusals are logged and, with `bell-on-limit`, ring the bell.
+    fn can_add_tab(&self) -> bool {
+        let tabs = self.ctx().len();
+        if self.renderer.navigation.navigation.can_add_tab(tabs) {
+            return true;
+        }
+        tracing::debug!("max-tabs reached ({tabs}), not opening a tab");
+        self.ring_limit_bell();
+        false
+    }
+
+    fn ring_limit_bell(&self) {
+        use rio_backend::event::{EventListener, RioEvent};
+        if self.renderer.navigation.navigation.bell_on_limit {
+            EventListener::send_event(
+                self.context_manager.event_proxy(),
+                RioEvent::Bell,
+                self.context_manager.window_id(),
+            );
+        }
+    }
+
     pub fn split_right_with_config(&mut self, config: rio_backend::config::Config) {
+        if !self.can_add_split() {
+            return;
+        }
         let rich_text_id = self.sugarloaf.create_rich_text();
         self.context_manager
             .split_from_config(rich_text_id, false, config);
@@ -1623,16 +2106,48 @@ impl Screen<'_> {
     }
 
     pub fn split_right(&mut self) {
+        if !self.can_add_split() {
+            return;
+        }
         let rich_text_id = self.sugarloaf.create_rich_text();
         self.context_manager.split(rich_text_id, false);
-        self.emit_sound(rio_backend::event::SoundEvent::SplitCreate);
+        self.emit_split_sound(rio_backend::event::SoundEvent::SplitCreate);
+        self.render();
+    }
+
+    /// Run the selected text as a command in a new split. Without a
+    /// selection nothing runs and the progress bar flashes red.
+    pub fn run_selection(&mut self) {
+        if !self.can_add_split() {
+            return;
+        }
+        let Some(command) = run_selection_command(&self.leader_write_context()) else {
+            tracing::warn!("RunSelection: nothing is selected");
+            self.context_manager.event_proxy().send_event(
+                rio_backend::event::RioEventType::Rio(
+                    rio_backend::event::RioEvent::UpdateProgressBar(1),
+                ),
+                self.context_manager.window_id(),
+            );
+            return;
+        };
+
+        let shell = run_selection_shell(&command, &self.context_manager.config.shell);
+        let rich_text_id = self.sugarloaf.create_rich_text();
+        self.clear_selection();
+        self.context_manager
+            .split_with_shell(rich_text_id, false, Some(shell));
+        self.emit_split_sound(rio_backend::event::SoundEvent::SplitCreate);
         self.render();
     }
 
     pub fn split_down(&mut self) {
+        if !self.can_add_split() {
+            return;
+        }
         let rich_text_id = self.sugarloaf.create_rich_text();
         self.context_manager.split(rich_text_id, true);
-        self.emit_sound(rio_backend::event::SoundEvent::SplitCreate);
+        self.emit_split_sound(rio_backend::event::SoundEvent::SplitCreate);
         self.render();
     }
 
@@ -1665,6 +2180,9 @@ impl Screen<'_> {
     }
 
     pub fn create_tab(&mut self) {
+        if !self.can_add_tab() {
+            return;
+        }
         let redirect = true;
 
         // We resize the current tab ahead to prepare the
@@ -1684,8 +2202,9 @@ impl Screen<'_> {
     pub fn close_split_or_tab(&mut self) {
         if self.context_manager.current_grid_len() > 1 {
             self.clear_selection();
+            // Pan towards the split going away, not the one taking focus
+            self.emit_split_sound(rio_backend::event::SoundEvent::SplitClose);
             self.context_manager.remove_current_grid();
-            self.emit_sound(rio_backend::event::SoundEvent::SplitClose);
             self.render();
         } else {
             self.close_tab();
@@ -1722,9 +2241,22 @@ impl Screen<'_> {
             num_tabs,
             self.search_active(),
         );
+        let padding_x = padding_x_from_config(
+            &self.renderer.navigation.navigation,
+            self.renderer.navigation.padding_x,
+            num_tabs,
+        );
+        let grid_width = grid_width_from_config(
+            &self.renderer.navigation.navigation,
+            self.sugarloaf.window_size().width,
+            self.sugarloaf.scale_factor(),
+            num_tabs,
+        );
 
-        if previous_margin.top_y != padding_y_top
+        if previous_margin.x != padding_x
+            || previous_margin.top_y != padding_y_top
             || previous_margin.bottom_y != padding_y_bottom
+            || self.context_manager.current_grid().width != grid_width
         {
             let layout = self
                 .sugarloaf
@@ -1734,7 +2266,11 @@ impl Screen<'_> {
             s.line_height = layout.line_height;
 
             let d = self.context_manager.current_grid_mut();
-            d.update_margin((d.margin.x, padding_y_top, padding_y_bottom));
+            d.update_margin((padding_x, padding_y_top, padding_y_bottom));
+            if d.width != grid_width {
+                let height = d.height;
+                d.resize(grid_width, height);
+            }
             self.resize_all_contexts();
         }
     }
@@ -1867,6 +2403,8 @@ impl Screen<'_> {
         }
     }
 
+    /// Copy the selection to the `ty` clipboard. Explicit copies to the
+    /// system clipboard play the copy sound, selecting text doesn't.
     pub fn copy_selection(&mut self, ty: ClipboardType) {
         let terminal = self.context_manager.current_mut().terminal.lock();
         let text = match terminal.selection_to_string().filter(|s| !s.is_empty()) {
@@ -1879,10 +2417,22 @@ impl Screen<'_> {
             self.clipboard
                 .borrow_mut()
                 .set(ClipboardType::Clipboard, text.clone());
+        } else {
+            self.emit_sound(rio_backend::event::SoundEvent::Copy);
         }
         self.clipboard.borrow_mut().set(ty, text);
     }
 
+    /// Paste the content of the `ty` clipboard, playing the paste sound
+    /// when there was anything to paste.
+    fn paste_from_clipboard(&mut self, ty: ClipboardType) {
+        let content = self.clipboard.borrow_mut().get(ty);
+        if !content.is_empty() {
+            self.emit_sound(rio_backend::event::SoundEvent::Paste);
+        }
+        self.paste(&content, true);
+    }
+
     #[inline]
     pub fn clear_selection(&mut self) {
         // Clear the selection on the terminal.
@@ -2101,17 +2651,15 @@ impl Screen<'_> {
                 }
             }
 
-            // Check regex patterns if specified
-            if let Some(regex_pattern) = &hint_config.regex {
-                if let Ok(regex) = regex::Regex::new(regex_pattern) {
-                    if let Some(regex_match) = self.find_regex_match_at_point(
-                        terminal,
-                        point,
-                        &regex,
-                        hint_config.clone(),
-                    ) {
-                        return Some(regex_match);
-                    }
+            // Check regex or built-in patterns if specified
+            if let Some(Ok(regex)) = hint_config.build_regex() {
+                if let Some(regex_match) = self.find_regex_match_at_point(
+                    terminal,
+                    point,
+                    &regex,
+                    hint_config.clone(),
+                ) {
+                    return Some(regex_match);
                 }
             }
         }
@@ -2172,6 +2720,11 @@ impl Screen<'_> {
                     ),
                 },
                 mouse: rio_backend::config::hints::HintMouse::default(),
+                scrollback: false,
+                case_insensitive: false,
+                smart_case: false,
+                template: None,
+                builtin: None,
                 binding: None,
             });
 
@@ -2185,6 +2738,8 @@ impl Screen<'_> {
                 start: rio_backend::crosswords::pos::Pos::new(point.row, start_col),
                 end: rio_backend::crosswords::pos::Pos::new(point.row, end_col),
                 hint: hint_config,
+                working_directory: terminal.current_directory.clone(),
+                target: None,
             });
         }
 
@@ -2226,7 +2781,7 @@ impl Screen<'_> {
                 let mut match_text = original_match_text.clone();
 
                 // Apply grid-based post-processing
-                let (processed_start, processed_end) = if hint_config.post_processing {
+                let (processed_start, processed_end) = if hint_config.trims_delimiters() {
                     self.hint_post_processing(
                         terminal,
                         start_col,
@@ -2239,7 +2794,7 @@ impl Screen<'_> {
                 };
 
                 // Extract the processed text
-                if hint_config.post_processing {
+                if hint_config.trims_delimiters() {
                     let mut processed_text = String::new();
                     for col in processed_start.0..=processed_end.0 {
                         let cell =
@@ -2257,6 +2812,8 @@ impl Screen<'_> {
                     ),
                     end: rio_backend::crosswords::pos::Pos::new(point.row, processed_end),
                     hint: hint_config,
+                    working_directory: terminal.current_directory.clone(),
+                    target: None,
                 });
             }
         }
@@ -2908,6 +3465,7 @@ impl Screen<'_> {
                 let mut terminal = self.context_manager.current_mut().terminal.lock();
                 terminal.scroll_display(Scroll::Delta(lines));
                 drop(terminal);
+                self.emit_sound(rio_backend::event::SoundEvent::Scroll);
             }
         }
 
@@ -3004,8 +3562,19 @@ impl Screen<'_> {
         // let screen_render_start = std::time::Instant::now();
 
         // Update leader menu state in renderer
-        self.renderer
-            .set_leader_menu(self.leader_state.active, self.leader_state.items.clone());
+        self.renderer.set_leader_menu(
+            self.leader_state.active,
+            self.leader_state
+                .visible_items()
+                .into_iter()
+                .cloned()
+                .collect(),
+            self.leader_state.path().to_vec(),
+            self.leader_state.search_buffer().to_string(),
+            self.leader_state.palette().map(CommandPalette::view),
+            self.leader_state.highlighted(),
+            self.leader_state.footer_text().to_string(),
+        );
 
         let is_search_active = self.search_active();
         if is_search_active {
@@ -3134,8 +3703,9 @@ impl Screen<'_> {
     /// Process a new character for keyboard hints
     #[allow(dead_code)]
     pub fn hint_input(&mut self, c: char) {
-        let terminal = self.context_manager.current().terminal.lock();
+        let mut terminal = self.context_manager.current().terminal.lock();
         if let Some(hint_match) = self.hint_state.keyboard_input(&*terminal, c) {
+            crate::hints::scroll_to_match(&mut terminal, &hint_match);
             drop(terminal);
             self.execute_hint_action(&hint_match);
             // Stop hint mode and update state with proper damage tracking
@@ -3164,50 +3734,114 @@ impl Screen<'_> {
         self.render();
     }
 
-    /// Execute the action for a selected hint
+    /// Execute the action for a selected hint. A target picked during
+    /// selection replaces the configured action; chained built-in actions
+    /// run in order against the same match.
     fn execute_hint_action(&mut self, hint_match: &crate::hints::HintMatch) {
-        use rio_backend::config::hints::{HintAction, HintCommand, HintInternalAction};
+        use rio_backend::config::hints::HintAction;
+
+        if let Some(target) = &hint_match.target {
+            self.execute_hint_internal_action(target, hint_match);
+            return;
+        }
 
         match &hint_match.hint.action {
-            HintAction::Action { action } => match action {
-                HintInternalAction::Copy => {
-                    self.clipboard
-                        .borrow_mut()
-                        .set(ClipboardType::Clipboard, hint_match.text.clone());
-                }
-                HintInternalAction::Paste => {
-                    self.paste(&hint_match.text, true);
-                }
-                HintInternalAction::Select => {
-                    // Set selection to the hint match
-                    let selection = rio_backend::selection::SelectionRange::new(
-                        hint_match.start,
-                        hint_match.end,
-                        false, // not a block selection
-                    );
-                    self.context_manager
-                        .current_mut()
-                        .set_selection(Some(selection));
-                    self.render();
+            HintAction::Action { .. } | HintAction::Actions { .. } => {
+                for action in hint_match.hint.action.internal_actions() {
+                    self.execute_hint_internal_action(action, hint_match);
                 }
-                HintInternalAction::MoveViModeCursor => {
-                    // Move vi mode cursor to hint position
-                    let mut terminal = self.context_manager.current().terminal.lock();
-                    terminal.vi_mode_cursor.pos = hint_match.start;
-                    drop(terminal);
-                    self.render();
+            }
+            HintAction::Command { command } => {
+                self.execute_hint_command(command, hint_match);
+            }
+            HintAction::PipeToCommand(command) => {
+                match std::process::Command::new(command)
+                    .arg(&hint_match.text)
+                    .spawn()
+                {
+                    // Reap the child once it exits so it doesn't linger
+                    Ok(mut child) => {
+                        std::thread::spawn(move || child.wait());
+                    }
+                    Err(err) => {
+                        tracing::error!("unable to pipe hint to {command:?}: {err}");
+                    }
                 }
-            },
-            HintAction::Command { command } => match command {
-                HintCommand::Simple(program) => {
-                    self.exec(program, [&hint_match.text]);
+            }
+        }
+    }
+
+    fn execute_hint_internal_action(
+        &mut self,
+        action: &rio_backend::config::hints::HintInternalAction,
+        hint_match: &crate::hints::HintMatch,
+    ) {
+        use rio_backend::config::hints::{
+            default_url_command, HintCommand, HintInternalAction,
+        };
+
+        match action {
+            HintInternalAction::Copy => {
+                self.clipboard
+                    .borrow_mut()
+                    .set(ClipboardType::Clipboard, hint_match.text.clone());
+            }
+            HintInternalAction::Paste => {
+                self.paste(&hint_match.text, true);
+            }
+            HintInternalAction::Select => {
+                // Set selection to the hint match
+                let selection = rio_backend::selection::SelectionRange::new(
+                    hint_match.start,
+                    hint_match.end,
+                    false, // not a block selection
+                );
+                self.context_manager
+                    .current_mut()
+                    .set_selection(Some(selection));
+                self.render();
+            }
+            HintInternalAction::MoveViModeCursor => {
+                // Move vi mode cursor to hint position
+                let mut terminal = self.context_manager.current().terminal.lock();
+                terminal.vi_mode_cursor.pos = hint_match.start;
+                drop(terminal);
+                self.render();
+            }
+            HintInternalAction::Open => {
+                let target = hint_match.open_target();
+                match default_url_command() {
+                    HintCommand::Simple(program) => self.exec(&program, [&target]),
+                    HintCommand::WithArgs { program, mut args } => {
+                        args.push(target);
+                        self.exec(&program, &args);
+                    }
                 }
-                HintCommand::WithArgs { program, args } => {
-                    let mut all_args = args.clone();
-                    all_args.push(hint_match.text.clone());
-                    self.exec(program, &all_args);
+            }
+            HintInternalAction::CopyAndOpen => {
+                for step in action.steps() {
+                    self.execute_hint_internal_action(step, hint_match);
                 }
-            },
+            }
+        }
+    }
+
+    fn execute_hint_command(
+        &mut self,
+        command: &rio_backend::config::hints::HintCommand,
+        hint_match: &crate::hints::HintMatch,
+    ) {
+        use rio_backend::config::hints::HintCommand;
+
+        match command {
+            HintCommand::Simple(program) => {
+                self.exec(program, [&hint_match.text]);
+            }
+            HintCommand::WithArgs { program, args } => {
+                let mut all_args = args.clone();
+                all_args.push(hint_match.text.clone());
+                self.exec(program, &all_args);
+            }
         }
     }
 
@@ -3310,32 +3944,12 @@ impl Screen<'_> {
     }
 
     fn update_hint_labels(&mut self) {
-        use crate::context::renderable::HintLabel;
-
-        let mut hint_labels = Vec::new();
-
-        if self.hint_state.is_active() {
-            let matches = self.hint_state.matches();
-            let visible_labels = self.hint_state.visible_labels();
-
-            for (match_index, remaining_label) in visible_labels {
-                if let Some(hint_match) = matches.get(match_index) {
-                    // Create labels for each character in the hint label
-                    for (char_index, &label_char) in remaining_label.iter().enumerate() {
-                        let position = rio_backend::crosswords::pos::Pos::new(
-                            hint_match.start.row,
-                            hint_match.start.col + char_index,
-                        );
-
-                        hint_labels.push(HintLabel {
-                            position,
-                            label: vec![label_char],
-                            is_first: char_index == 0, // First character gets different styling
-                        });
-                    }
-                }
-            }
-        }
+        let hint_labels = if self.hint_state.is_active() {
+            let columns = self.context_manager.current().terminal.lock().columns();
+            self.hint_state.render_labels(columns)
+        } else {
+            Vec::new()
+        };
 
         self.context_manager
             .current_mut()
@@ -3549,4 +4163,21 @@ mod tests {
             "https://example.com/path[with]brackets"
         );
     }
+
+    #[test]
+    fn test_distortion_params_filter() {
+        use rio_backend::config::distortion::SamplingFilter;
+
+        let mut config = rio_backend::config::Config::default();
+        assert_eq!(
+            distortion_params_from_config(&config).filter,
+            DISTORTION_FILTER_LINEAR
+        );
+
+        config.distortion.filter = SamplingFilter::Nearest;
+        assert_eq!(
+            distortion_params_from_config(&config).filter,
+            DISTORTION_FILTER_NEAREST
+        );
+    }
 }
@@ -1,165 +1,1700 @@
-use rio_backend::event::SoundEvent;
-use rodio::source::Source;
-use rodio::{Decoder, OutputStream, OutputStreamHandle};
-use std::collections::HashMap;
+use rand::Rng;
+use rio_backend::config::ranges::{
+    clamp_field, SOUND_EFFECTS_PITCH_VARIANCE, SOUND_EFFECTS_TYPING_PITCH,
+    SOUND_EFFECTS_VOLUME,
+};
+use rio_backend::event::{SoundCategory, SoundEvent};
+use rodio::buffer::SamplesBuffer;
+use rodio::cpal::traits::HostTrait;
+use rodio::source::{Amplify, Done, Source};
+use rodio::{Decoder, DeviceTrait, OutputStream, OutputStreamHandle};
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Cached decoded audio data with its original sample rate
-/// and channel count.
-struct CachedSound {
+/// A sound file whose metadata has been read but whose samples are
+/// still being decoded on the loader thread.
+#[derive(Clone)]
+struct LazySound {
+    path: PathBuf,
+    sample_rate: u32,
+    channels: u16,
+    /// Length reported by the decoder, not every format knows it
+    /// without decoding
+    duration: Option<Duration>,
+}
+
+/// Decoded audio data with its original sample rate and channel count.
+#[derive(Clone)]
+struct DecodedSound {
     samples: Arc<Vec<f32>>,
     sample_rate: u32,
     channels: u16,
 }
 
-pub struct SoundManager {
-    /// Cached decoded audio, keyed by event; each event can have
-    /// multiple variants (e.g., multiple keyboard sounds).
+/// A sound variant in the cache, playable once the loader thread
+/// decoded it.
+enum CachedSound {
+    Lazy(LazySound),
+    Ready(DecodedSound),
+}
+
+/// What the loader thread sends back to the manager.
+enum Loaded {
+    /// Metadata of the sound files of an event, samples still to come
+    Event(SoundEvent, Vec<CachedSound>),
+    /// Samples of the file at the given path of an event, `None` when
+    /// they can't be decoded or are longer than `max_duration`
+    Decoded(SoundEvent, PathBuf, Option<DecodedSound>),
+}
+
+impl CachedSound {
+    /// Length in seconds, `None` while unknown until decoded.
+    fn duration_secs(&self) -> Option<f32> {
+        match self {
+            CachedSound::Lazy(lazy) => lazy.duration.map(|d| d.as_secs_f32()),
+            CachedSound::Ready(sound) => Some(sound.duration_secs()),
+        }
+    }
+}
+
+/// Open `path` and read its sample rate, channels and duration without
+/// decoding the samples.
+fn read_metadata(path: &Path) -> Option<LazySound> {
+    let decoder = open_decoder(path)?;
+    Some(LazySound {
+        path: path.to_path_buf(),
+        sample_rate: decoder.sample_rate(),
+        channels: decoder.channels(),
+        duration: decoder.total_duration(),
+    })
+}
+
+/// Decode every sample of a sound read by `read_metadata`.
+fn decode_samples(lazy: &LazySound) -> Option<DecodedSound> {
+    let decoder = open_decoder(&lazy.path)?;
+    let samples: Vec<f32> = decoder.convert_samples::<f32>().collect();
+    Some(DecodedSound {
+        samples: Arc::new(samples),
+        sample_rate: lazy.sample_rate,
+        channels: lazy.channels,
+    })
+}
+
+fn open_decoder(path: &Path) -> Option<Decoder<BufReader<File>>> {
+    let file = File::open(path)
+        .map_err(|e| {
+            tracing::warn!("Cannot open sound file {}: {e}", path.display());
+            e
+        })
+        .ok()?;
+
+    Decoder::new(BufReader::new(file))
+        .map_err(|e| {
+            tracing::warn!("Cannot decode sound file {}: {e}", path.display());
+            e
+        })
+        .ok()
+}
+
+/// Whether a sound of `duration_secs` fits in `max_duration`, warning
+/// when it doesn't.
+fn within_max_duration(path: &Path, duration_secs: f32, max_duration: f32) -> bool {
+    if duration_secs > max_duration {
+        tracing::warn!(
+            "Sound file {} exceeds max duration \
+             ({:.1}s > {:.1}s), skipping",
+            path.display(),
+            duration_secs,
+            max_duration,
+        );
+        return false;
+    }
+    true
+}
+
+impl DecodedSound {
+    /// Length of the decoded audio in seconds.
+    fn duration_secs(&self) -> f32 {
+        if self.sample_rate == 0 || self.channels == 0 {
+            return 0.0;
+        }
+        self.samples.len() as f32 / (self.sample_rate as f32 * self.channels as f32)
+    }
+
+    /// Build a playable source at the given volume, resampled by
+    /// `pitch` (1.0 plays as recorded) and panned by `pan` (see
+    /// `pan_samples`). The cached samples are left untouched.
+    fn source(&self, volume: f32, pitch: f32, pan: f32) -> Amplify<SamplesBuffer<f32>> {
+        let sample_rate = ((self.sample_rate as f32 * pitch).round() as u32).max(1);
+        let (channels, samples) = pan_samples(&self.samples, self.channels, pan);
+        SamplesBuffer::new(channels, sample_rate, samples).amplify(volume)
+    }
+}
+
+/// Gains of the left and right channel for `pan`, from -1.0 (left only)
+/// through 0.0 (both at full volume) to 1.0 (right only). The channel on
+/// the side the sound pans to stays at full volume while the other one
+/// fades out.
+#[inline]
+fn channel_gains(pan: f32) -> (f32, f32) {
+    let pan = pan.clamp(-1.0, 1.0);
+    ((1.0 - pan).min(1.0), (1.0 + pan).min(1.0))
+}
+
+/// Interleaved `samples` with `channels` channels, panned by `pan`. Mono
+/// sounds become stereo so they can be panned, with more channels only
+/// the first two (left and right) are scaled. A centered sound is
+/// returned as is.
+fn pan_samples(samples: &[f32], channels: u16, pan: f32) -> (u16, Vec<f32>) {
+    if pan == 0.0 || channels == 0 {
+        return (channels, samples.to_vec());
+    }
+
+    let (left, right) = channel_gains(pan);
+    if channels == 1 {
+        let stereo = samples
+            .iter()
+            .flat_map(|sample| [sample * left, sample * right])
+            .collect();
+        return (2, stereo);
+    }
+
+    let mut panned = samples.to_vec();
+    for frame in panned.chunks_mut(usize::from(channels)) {
+        frame[0] *= left;
+        if let Some(sample) = frame.get_mut(1) {
+            *sample *= right;
+        }
+    }
+    (channels, panned)
+}
+
+/// Playback rate factor shifting the pitch by `offset * variance`
+/// semitones, `offset` being in -1.0..=1.0.
+#[inline]
+fn pitch_factor(variance: f32, offset: f32) -> f32 {
+    2f32.powf(offset.clamp(-1.0, 1.0) * variance / 12.0)
+}
+
+/// Random playback rate factor within `variance` semitones either way.
+fn random_pitch_factor(variance: f32) -> f32 {
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    pitch_factor(variance, rand::rng().random_range(-1.0..=1.0))
+}
+
+/// Typing rates, in keys per second, at which the typing pitch starts
+/// rising and reaches its highest.
+const TYPING_RATE_SLOW: f32 = 3.0;
+const TYPING_RATE_FAST: f32 = 10.0;
+/// Key presses older than this don't count towards the typing rate.
+const TYPING_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Pitch offset in 0.0..=1.0 for typing at `keys_per_second`: 0.0 up to
+/// `TYPING_RATE_SLOW`, rising linearly to 1.0 at `TYPING_RATE_FAST`.
+fn typing_pitch_offset(keys_per_second: f32) -> f32 {
+    ((keys_per_second - TYPING_RATE_SLOW) / (TYPING_RATE_FAST - TYPING_RATE_SLOW))
+        .clamp(0.0, 1.0)
+}
+
+/// Keys per second over `presses`, oldest first, the one at `now`
+/// included. A single press has no rate.
+fn typing_rate(presses: &[Instant], now: Instant) -> f32 {
+    let Some(first) = presses.first() else {
+        return 0.0;
+    };
+    let elapsed = now.saturating_duration_since(*first).as_secs_f32();
+    if presses.len() < 2 || elapsed <= 0.0 {
+        return 0.0;
+    }
+    (presses.len() - 1) as f32 / elapsed
+}
+
+/// Recent key presses, for `sound-effects.typing-pitch`.
+struct TypingPitch {
+    /// Highest pitch raise, in semitones
+    semitones: f32,
+    presses: Vec<Instant>,
+}
+
+impl TypingPitch {
+    fn new(semitones: f32) -> Self {
+        Self {
+            semitones,
+            presses: Vec::new(),
+        }
+    }
+
+    /// Record a key press at `now` and return the playback rate factor
+    /// it plays at.
+    fn record(&mut self, now: Instant) -> f32 {
+        self.presses
+            .retain(|press| now.saturating_duration_since(*press) <= TYPING_RATE_WINDOW);
+        self.presses.push(now);
+        let offset = typing_pitch_offset(typing_rate(&self.presses, now));
+        pitch_factor(self.semitones, offset)
+    }
+}
+
+/// Keep the global volume in the 0.0–1.0 range.
+#[inline]
+fn clamp_volume(volume: f32) -> f32 {
+    clamp_field(SOUND_EFFECTS_VOLUME, volume)
+}
+
+/// Counts the sounds currently playing so bursts (e.g. fast typing)
+/// can't pile up an unbounded number of overlapping voices.
+struct PlaybackLimiter {
+    /// Shared with every playing source, decremented by `Done`
+    /// once the source has been fully consumed by the mixer.
+    active: Arc<AtomicUsize>,
+    /// Maximum number of overlapping sounds, `0` means unlimited.
+    max: usize,
+}
+
+impl PlaybackLimiter {
+    fn new(max: usize) -> Self {
+        Self {
+            active: Arc::new(AtomicUsize::new(0)),
+            max,
+        }
+    }
+
+    /// Reserve a playback slot. Returns the counter that the source
+    /// must decrement when it finishes, or `None` if the limit is reached.
+    fn try_acquire(&self) -> Option<Arc<AtomicUsize>> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                if self.max == 0 || active < self.max {
+                    Some(active + 1)
+                } else {
+                    None
+                }
+            })
+            .ok()?;
+        Some(self.active.clone())
+    }
+
+    /// Give back a slot whose source never reached the mixer.
+    fn release(&self) {
+        let _ = self
+            .active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                active.checked_sub(1)
+            });
+    }
+}
+
+/// Source of the current time, so cooldowns can be tested without
+/// sleeping.
+trait Clock {
+    fn now(&self) -> Instant;
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Keeps an event from retriggering within its cooldown, e.g. a script
+/// printing `\a` in a loop.
+struct CooldownTracker<C: Clock = SystemClock> {
+    clock: C,
+    cooldowns: HashMap<SoundEvent, Duration>,
+    last_played: HashMap<SoundEvent, Instant>,
+}
+
+impl<C: Clock> CooldownTracker<C> {
+    fn new(clock: C, cooldowns: HashMap<SoundEvent, Duration>) -> Self {
+        Self {
+            clock,
+            cooldowns,
+            last_played: HashMap::new(),
+        }
+    }
+
+    /// Whether `event` played less than its cooldown ago.
+    fn is_cooling_down(&self, event: SoundEvent) -> bool {
+        let Some(cooldown) = self.cooldowns.get(&event) else {
+            return false;
+        };
+        self.last_played.get(&event).is_some_and(|last| {
+            self.clock.now().saturating_duration_since(*last) < *cooldown
+        })
+    }
+
+    fn record(&mut self, event: SoundEvent) {
+        self.last_played.insert(event, self.clock.now());
+    }
+
+    /// Note that `event` was skipped while cooling down. Resizing keeps
+    /// restarting its cooldown so a whole drag plays a single sound.
+    fn skipped(&mut self, event: SoundEvent) {
+        if event == SoundEvent::Resize {
+            self.record(event);
+        }
+    }
+}
+
+/// Variant of the bell to play given the times of recent bells, the one
+/// being played included. Variants are intensity levels, subtlest first:
+/// an isolated bell plays level 0 and every further bell within `window`
+/// of `now` moves one level up, capped at the last of `levels`.
+fn bell_intensity(
+    bells: &[Instant],
+    now: Instant,
+    window: Duration,
+    levels: usize,
+) -> usize {
+    let recent = bells
+        .iter()
+        .filter(|bell| now.saturating_duration_since(**bell) <= window)
+        .count();
+    recent.saturating_sub(1).min(levels.saturating_sub(1))
+}
+
+/// Recent bells, for `sound-effects.bell-escalate`.
+struct BellEscalation {
+    window: Duration,
+    bells: Vec<Instant>,
+}
+
+impl BellEscalation {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            bells: Vec::new(),
+        }
+    }
+
+    /// Record a bell at `now` and return the level it plays at.
+    fn record(&mut self, now: Instant, levels: usize) -> usize {
+        let window = self.window;
+        self.bells
+            .retain(|bell| now.saturating_duration_since(*bell) <= window);
+        self.bells.push(now);
+        bell_intensity(&self.bells, now, window, levels)
+    }
+}
+
+/// A cached sound on its way to the mixer. `Done` frees its playback
+/// slot once it has been fully consumed.
+type PlayingSource = Done<Amplify<SamplesBuffer<f32>>>;
+
+/// Where sounds are mixed, so playback can be tested without an audio
+/// device.
+pub(crate) trait Mixer {
+    /// Start playing `source` for `event`. Returns `false` if it never
+    /// reached a mixer.
+    fn mix(&self, event: SoundEvent, source: PlayingSource) -> bool;
+
+    /// Open the output devices again, e.g. after the default one changed.
+    /// Returns `false` if none could be opened.
+    fn reconnect(&mut self) -> bool {
+        false
+    }
+}
+
+/// Minimum time between two attempts to reopen the output devices.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Whether reopening the devices may be tried at `now`, given the last
+/// failed playback at `last_error`.
+#[inline]
+fn reconnect_allowed(last_error: Option<Instant>, now: Instant) -> bool {
+    last_error.is_none_or(|last| now.saturating_duration_since(last) >= RECONNECT_BACKOFF)
+}
+
+/// Output stream an event plays on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum StreamKey {
+    Default,
+    /// Output device with this name
+    Device(String),
+}
+
+/// Stream key of every event with a configured device. Events whose
+/// device isn't in `opened` fall back to the default stream.
+fn route_events(
+    devices: &HashMap<SoundEvent, String>,
+    opened: &HashSet<String>,
+) -> HashMap<SoundEvent, StreamKey> {
+    devices
+        .iter()
+        .map(|(event, name)| {
+            let key = if opened.contains(name) {
+                StreamKey::Device(name.clone())
+            } else {
+                StreamKey::Default
+            };
+            (*event, key)
+        })
+        .collect()
+}
+
+/// One rodio output stream per device in use.
+pub(crate) struct OutputStreams {
+    /// Streams must be kept alive for their handles to play.
+    streams: HashMap<StreamKey, (OutputStream, OutputStreamHandle)>,
+    /// Events playing on a named device, others use the default one.
+    routes: HashMap<SoundEvent, StreamKey>,
+    /// Configured device per event, kept to open them again
+    devices: HashMap<SoundEvent, String>,
+}
+
+impl OutputStreams {
+    /// Open the default device and every device named in `devices`.
+    /// Returns `None` if no device could be opened at all.
+    fn open(devices: &HashMap<SoundEvent, String>) -> Option<Self> {
+        let mut streams = HashMap::new();
+        let mut opened = HashSet::new();
+
+        let wanted: HashSet<&String> = devices.values().collect();
+        if !wanted.is_empty() {
+            match rodio::cpal::default_host().output_devices() {
+                Ok(outputs) => {
+                    for device in outputs {
+                        let Ok(name) = device.name() else {
+                            continue;
+                        };
+                        if !wanted.contains(&name) || opened.contains(&name) {
+                            continue;
+                        }
+                        match OutputStream::try_from_device(&device) {
+                            Ok(stream) => {
+                                streams.insert(StreamKey::Device(name.clone()), stream);
+                                opened.insert(name);
+                            }
+                            Err(e) => {
+                                tracing::warn!("Failed to open audio device {name}: {e}");
+                            }
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to list audio devices: {e}"),
+            }
+        }
+
+        for name in wanted.into_iter().filter(|name| !opened.contains(*name)) {
+            tracing::warn!("Audio device {name} not available, using the default one");
+        }
+
+        match OutputStream::try_default() {
+            Ok(stream) => {
+                streams.insert(StreamKey::Default, stream);
+            }
+            Err(e) if streams.is_empty() => {
+                tracing::warn!(
+                    "Failed to open audio device, \
+                         sound effects disabled: {e}"
+                );
+                return None;
+            }
+            Err(e) => tracing::warn!("Failed to open the default audio device: {e}"),
+        }
+
+        Some(Self {
+            routes: route_events(devices, &opened),
+            streams,
+            devices: devices.clone(),
+        })
+    }
+
+    /// Handle of the stream `event` plays on.
+    fn handle(&self, event: SoundEvent) -> Option<&OutputStreamHandle> {
+        let key = self.routes.get(&event).unwrap_or(&StreamKey::Default);
+        self.streams.get(key).map(|(_, handle)| handle)
+    }
+}
+
+impl Mixer for OutputStreams {
+    fn mix(&self, event: SoundEvent, source: PlayingSource) -> bool {
+        let Some(handle) = self.handle(event) else {
+            tracing::trace!("No audio device for {event:?}, skipping");
+            return false;
+        };
+        // play_raw() mixes concurrently — multiple sounds can overlap
+        // without queuing
+        handle.play_raw(source.convert_samples()).is_ok()
+    }
+
+    /// `OutputStream::try_default` picks whatever the default device is
+    /// now, so this also follows headphones being plugged in or out.
+    fn reconnect(&mut self) -> bool {
+        match Self::open(&self.devices) {
+            Some(streams) => {
+                *self = streams;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct SoundManager<M: Mixer = OutputStreams> {
+    /// Cached audio, keyed by event, decoded by the loader thread; each
+    /// event can have multiple variants (e.g., multiple keyboard sounds).
     cache: HashMap<SoundEvent, Vec<CachedSound>>,
-    /// Rodio output stream (must be kept alive).
-    _stream: OutputStream,
-    /// Handle used to play sounds concurrently via the mixer.
-    stream_handle: OutputStreamHandle,
+    /// Output streams per device, used to play sounds concurrently
+    /// via their mixers.
+    streams: M,
     /// Event → file path mapping from config.
     mapping: HashMap<SoundEvent, Vec<PathBuf>>,
+    /// Sounds read by the loader thread, `None` once it is done.
+    loading: Option<Receiver<Loaded>>,
     /// Round-robin indices for variant selection.
     indices: HashMap<SoundEvent, usize>,
+    /// Events whose variants are picked at random instead.
+    random_events: HashSet<SoundEvent>,
+    /// Picks the bell variant by how many bells came in recently, when
+    /// enabled; takes over from round-robin and random for the bell.
+    bell_escalation: Option<BellEscalation>,
     /// Global volume (0.0–1.0).
     volume: f32,
+    /// Volume multiplier per category, 1.0 when missing.
+    category_volumes: HashMap<SoundCategory, f32>,
+    /// Random pitch shift of keyboard sounds, in semitones.
+    pitch_variance: f32,
+    /// Raises the pitch of keyboard sounds with the typing speed, when
+    /// enabled.
+    typing_pitch: Option<TypingPitch>,
+    /// Drops every sound while set, the cache is kept.
+    muted: bool,
     /// Maximum duration in seconds per sound file.
     max_duration: f32,
+    /// Limits how many sounds can overlap at once.
+    limiter: PlaybackLimiter,
+    /// Drops events retriggered within their cooldown.
+    cooldowns: CooldownTracker,
+    /// Last time a sound failed to reach the output device, to back off
+    /// reconnecting.
+    last_error: Option<Instant>,
 }
 
 impl SoundManager {
     /// Attempt to create a SoundManager. Returns `None` if the
     /// audio device is unavailable (e.g., headless server).
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         mapping: HashMap<SoundEvent, Vec<PathBuf>>,
+        random_events: HashSet<SoundEvent>,
         volume: f32,
+        pitch_variance: f32,
         max_duration: f32,
+        max_concurrent: usize,
+        cooldowns: HashMap<SoundEvent, Duration>,
+        mut devices: HashMap<SoundEvent, String>,
     ) -> Option<Self> {
-        let (_stream, stream_handle) = OutputStream::try_default()
-            .map_err(|e| {
-                tracing::warn!(
-                    "Failed to open audio device, \
-                         sound effects disabled: {e}"
-                );
-                e
-            })
-            .ok()?;
+        // Don't open devices no configured sound would play on
+        devices.retain(|event, _| mapping.contains_key(event));
+        let streams = OutputStreams::open(&devices)?;
+
+        // Read and decode every mapped file off the main thread so a large
+        // sound pack delays neither the first frame nor the first play. The
+        // metadata of every event comes first, then the samples.
+        let (sender, receiver) = mpsc::channel();
+        let loader_mapping = mapping.clone();
+        let spawned = std::thread::Builder::new()
+            .name("sound loader".to_string())
+            .spawn(move || {
+                let mut pending = Vec::new();
+                for (event, paths) in loader_mapping {
+                    let buffers = load_event(&paths, max_duration);
+                    if buffers.is_empty() {
+                        continue;
+                    }
+                    for sound in &buffers {
+                        if let CachedSound::Lazy(lazy) = sound {
+                            pending.push((event, lazy.clone()));
+                        }
+                    }
+                    if sender.send(Loaded::Event(event, buffers)).is_err() {
+                        // The manager was dropped, nothing left to fill
+                        return;
+                    }
+                }
 
-        let mut mgr = Self {
+                for (event, lazy) in pending {
+                    let decoded = decode_samples(&lazy).filter(|sound| {
+                        within_max_duration(
+                            &lazy.path,
+                            sound.duration_secs(),
+                            max_duration,
+                        )
+                    });
+                    if sender
+                        .send(Loaded::Decoded(event, lazy.path, decoded))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        if let Err(err) = spawned {
+            tracing::error!("Cannot spawn the sound loader thread: {err}");
+            return None;
+        }
+
+        Some(Self {
             cache: HashMap::new(),
-            _stream,
-            stream_handle,
+            streams,
             mapping,
+            loading: Some(receiver),
             indices: HashMap::new(),
-            volume,
+            random_events,
+            bell_escalation: None,
+            volume: clamp_volume(volume),
+            category_volumes: HashMap::new(),
+            pitch_variance: clamp_field(SOUND_EFFECTS_PITCH_VARIANCE, pitch_variance),
+            typing_pitch: None,
+            muted: false,
             max_duration,
-        };
+            limiter: PlaybackLimiter::new(max_concurrent),
+            cooldowns: CooldownTracker::new(SystemClock, cooldowns),
+            last_error: None,
+        })
+    }
+}
 
-        // Pre-load all sound files into cache
-        mgr.load_all();
+/// Read the metadata of the sound files of one event, skipping the ones
+/// that can't be read or are known to be longer than `max_duration`.
+fn load_event(paths: &[PathBuf], max_duration: f32) -> Vec<CachedSound> {
+    let mut buffers = Vec::with_capacity(paths.len());
+    for path in paths {
+        let lazy = read_metadata(path).filter(|lazy| {
+            lazy.duration.is_none_or(|duration| {
+                within_max_duration(path, duration.as_secs_f32(), max_duration)
+            })
+        });
+        match lazy {
+            Some(lazy) => buffers.push(CachedSound::Lazy(lazy)),
+            None => {
+                tracing::warn!("Skipping sound file: {}", path.display());
+            }
+        }
+    }
+    buffers
+}
+
+impl<M: Mixer> SoundManager<M> {
+    /// Mute or unmute every sound. Decoded sounds stay cached so
+    /// unmuting is instant.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
+
+    #[inline]
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Change the global volume (clamped to 0.0–1.0). Applies to the
+    /// next sounds played, the cache is kept as is.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = clamp_volume(volume);
+    }
+
+    /// Change the volume multiplier of each category, on top of the
+    /// global volume.
+    pub fn set_category_volumes(&mut self, volumes: HashMap<SoundCategory, f32>) {
+        self.category_volumes = volumes;
+    }
+
+    /// Volume `event` plays at: global × category, clamped to 0.0–1.0.
+    fn event_volume(&self, event: SoundEvent) -> f32 {
+        let category = self
+            .category_volumes
+            .get(&event.category())
+            .copied()
+            .unwrap_or(1.0);
+        clamp_volume(self.volume * category)
+    }
+
+    /// Change the random pitch shift of keyboard sounds, in semitones.
+    pub fn set_pitch_variance(&mut self, pitch_variance: f32) {
+        self.pitch_variance = clamp_field(SOUND_EFFECTS_PITCH_VARIANCE, pitch_variance);
+    }
+
+    /// Raise the pitch of keyboard sounds with the typing speed, up to
+    /// `semitones`. `0.0` keeps a constant pitch.
+    pub fn set_typing_pitch(&mut self, semitones: f32) {
+        let semitones = clamp_field(SOUND_EFFECTS_TYPING_PITCH, semitones);
+        match (semitones > 0.0, self.typing_pitch.as_mut()) {
+            (true, Some(typing_pitch)) => typing_pitch.semitones = semitones,
+            (true, None) => self.typing_pitch = Some(TypingPitch::new(semitones)),
+            (false, _) => self.typing_pitch = None,
+        }
+    }
+
+    /// Pick bell variants by urgency, counting bells within `window`,
+    /// or go back to the usual variant selection with `None`.
+    pub fn set_bell_escalation(&mut self, window: Option<Duration>) {
+        match (window, self.bell_escalation.as_mut()) {
+            (Some(window), Some(escalation)) => escalation.window = window,
+            (window, _) => self.bell_escalation = window.map(BellEscalation::new),
+        }
+    }
 
-        Some(mgr)
+    /// Change the maximum duration per sound. Cached sounds known to be
+    /// longer than the new limit are dropped, sounds whose length is
+    /// only known once decoded are checked when they arrive. Raising the
+    /// limit requires a rebuild to pick up previously skipped files.
+    pub fn set_max_duration(&mut self, max_duration: f32) {
+        self.max_duration = max_duration;
+        self.cache.retain(|_, buffers| {
+            buffers.retain(|sound| {
+                sound
+                    .duration_secs()
+                    .is_none_or(|duration| duration <= max_duration)
+            });
+            !buffers.is_empty()
+        });
+        self.indices
+            .retain(|event, _| self.cache.contains_key(event));
     }
 
-    /// Load and decode all mapped sound files into the cache.
-    fn load_all(&mut self) {
-        for (event, paths) in &self.mapping {
-            let mut buffers = Vec::with_capacity(paths.len());
-            for path in paths {
-                match self.decode_file(path) {
-                    Some(cached) => buffers.push(cached),
-                    None => {
-                        tracing::warn!("Skipping sound file: {}", path.display());
+    /// Move the sounds read by the loader thread so far into the cache.
+    fn receive_loaded(&mut self) {
+        let Some(receiver) = &self.loading else {
+            return;
+        };
+        loop {
+            match receiver.try_recv() {
+                Ok(Loaded::Event(event, mut buffers)) => {
+                    // The limit may have changed while the loader ran
+                    let max_duration = self.max_duration;
+                    buffers.retain(|sound| {
+                        sound
+                            .duration_secs()
+                            .is_none_or(|duration| duration <= max_duration)
+                    });
+                    if !buffers.is_empty() {
+                        self.cache.insert(event, buffers);
                     }
                 }
+                Ok(Loaded::Decoded(event, path, decoded)) => {
+                    self.receive_decoded(event, &path, decoded);
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.loading = None;
+                    return;
+                }
             }
-            if !buffers.is_empty() {
-                self.cache.insert(*event, buffers);
+        }
+    }
+
+    /// Replace the metadata of the file at `path` of `event` with its
+    /// decoded samples. Files that failed to decode or turn out longer
+    /// than `max_duration` are dropped from the cache.
+    fn receive_decoded(
+        &mut self,
+        event: SoundEvent,
+        path: &Path,
+        decoded: Option<DecodedSound>,
+    ) {
+        let Some(buffers) = self.cache.get_mut(&event) else {
+            return;
+        };
+        let Some(index) = buffers.iter().position(
+            |sound| matches!(sound, CachedSound::Lazy(lazy) if lazy.path == path),
+        ) else {
+            return;
+        };
+
+        let max_duration = self.max_duration;
+        match decoded.filter(|sound| {
+            within_max_duration(path, sound.duration_secs(), max_duration)
+        }) {
+            Some(sound) => buffers[index] = CachedSound::Ready(sound),
+            None => {
+                buffers.remove(index);
+                if buffers.is_empty() {
+                    self.cache.remove(&event);
+                    self.indices.remove(&event);
+                } else if let Some(idx) = self.indices.get_mut(&event) {
+                    *idx %= buffers.len();
+                }
             }
         }
     }
 
-    /// Decode a single audio file into a CachedSound.
-    fn decode_file(&self, path: &PathBuf) -> Option<CachedSound> {
-        let file = File::open(path)
-            .map_err(|e| {
-                tracing::warn!("Cannot open sound file {}: {e}", path.display());
-                e
-            })
-            .ok()?;
+    /// Whether a sound of `event` has been decoded and can play.
+    pub fn is_ready(&self, event: SoundEvent) -> bool {
+        self.cache.get(&event).is_some_and(|buffers| {
+            buffers
+                .iter()
+                .any(|sound| matches!(sound, CachedSound::Ready(_)))
+        })
+    }
 
-        let reader = BufReader::new(file);
-        let decoder = Decoder::new(reader)
-            .map_err(|e| {
-                tracing::warn!("Cannot decode sound file {}: {e}", path.display());
-                e
-            })
-            .ok()?;
+    /// Check if a sound is available for the given event. Sounds still
+    /// being loaded count as available, `play` drops them until ready.
+    pub fn has_sound(&self, event: SoundEvent) -> bool {
+        self.cache.contains_key(&event)
+            || (self.loading.is_some() && self.mapping.contains_key(&event))
+    }
 
-        let channels = decoder.channels();
-        let sample_rate = decoder.sample_rate();
+    /// Samples of variant `index` of `event`, `None` while the loader
+    /// thread is still decoding them.
+    fn ready_sound(&self, event: SoundEvent, index: usize) -> Option<DecodedSound> {
+        match self.cache.get(&event)?.get(index)? {
+            CachedSound::Ready(sound) => Some(sound.clone()),
+            CachedSound::Lazy(_) => None,
+        }
+    }
 
-        // Collect all samples as f32
-        let samples: Vec<f32> = decoder.convert_samples::<f32>().collect();
+    /// Reopen the output devices after a sound failed to play, at most
+    /// once per `RECONNECT_BACKOFF`. The decoded sounds are kept.
+    pub fn try_reconnect(&mut self) -> bool {
+        let now = Instant::now();
+        if !reconnect_allowed(self.last_error, now) {
+            return false;
+        }
+        self.last_error = Some(now);
 
-        // Check duration
-        if sample_rate > 0 && channels > 0 {
-            let duration_secs =
-                samples.len() as f32 / (sample_rate as f32 * channels as f32);
-            if duration_secs > self.max_duration {
-                tracing::warn!(
-                    "Sound file {} exceeds max duration \
-                     ({:.1}s > {:.1}s), skipping",
-                    path.display(),
-                    duration_secs,
-                    self.max_duration,
-                );
-                return None;
+        let reconnected = self.streams.reconnect();
+        if reconnected {
+            tracing::info!("Audio output reconnected");
+        } else {
+            tracing::warn!("Audio output unavailable, retrying on the next sound");
+        }
+        reconnected
+    }
+
+    /// Play a sound for the given event, centered. See `play_panned`.
+    #[inline]
+    pub fn play(&mut self, event: SoundEvent) {
+        self.play_panned(event, 0.0);
+    }
+
+    /// Play a sound for the given event, panned from -1.0 (left) to 1.0
+    /// (right). Uses round-robin for events with multiple variants, or a
+    /// random pick for events configured with `{ random = [...] }`. With
+    /// bell escalation on, the bell variant follows how many bells came in
+    /// recently instead. The sound is dropped (not queued) while muted,
+    /// when `max_concurrent` sounds are already playing or the event is
+    /// still within its cooldown. Sounds the loader thread hasn't decoded
+    /// yet are dropped too.
+    pub fn play_panned(&mut self, event: SoundEvent, pan: f32) {
+        if self.muted {
+            return;
+        }
+
+        self.receive_loaded();
+
+        let len = match self.cache.get(&event) {
+            Some(buffers) if !buffers.is_empty() => buffers.len(),
+            _ => return,
+        };
+
+        // Bells dropped by the cooldown still count towards the urgency
+        let level = match self.bell_escalation.as_mut() {
+            Some(escalation) if event == SoundEvent::Bell => {
+                Some(escalation.record(Instant::now(), len))
             }
+            _ => None,
+        };
+
+        // Likewise every key press counts towards the typing speed
+        let typing_pitch = match self.typing_pitch.as_mut() {
+            Some(typing_pitch) if event.is_keyboard() => {
+                typing_pitch.record(Instant::now())
+            }
+            _ => 1.0,
+        };
+
+        if self.cooldowns.is_cooling_down(event) {
+            tracing::trace!("{event:?} is cooling down, skipping");
+            self.cooldowns.skipped(event);
+            return;
         }
 
-        Some(CachedSound {
+        let Some(active) = self.limiter.try_acquire() else {
+            tracing::trace!("Too many sounds playing, skipping {event:?}");
+            return;
+        };
+
+        let index = if let Some(level) = level {
+            level
+        } else if self.random_events.contains(&event) {
+            rand::rng().random_range(0..len)
+        } else {
+            let idx = self.indices.entry(event).or_insert(0);
+            let index = *idx % len;
+            *idx = (index + 1) % len;
+            index
+        };
+
+        let Some(sound) = self.ready_sound(event, index) else {
+            self.limiter.release();
+            return;
+        };
+
+        let pitch = if event.is_keyboard() {
+            random_pitch_factor(self.pitch_variance) * typing_pitch
+        } else {
+            1.0
+        };
+        let volume = self.event_volume(event);
+        let source = |active| Done::new(sound.source(volume, pitch, pan), active);
+        let mut played = self.streams.mix(event, source(active));
+        if !played {
+            self.limiter.release();
+            // The output device may be gone, e.g. headphones unplugged.
+            // Retry once on the devices opened again.
+            if self.try_reconnect() {
+                if let Some(active) = self.limiter.try_acquire() {
+                    played = self.streams.mix(event, source(active));
+                    if !played {
+                        self.limiter.release();
+                    }
+                }
+            }
+        }
+        if played {
+            self.cooldowns.record(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_limiter_skips_beyond_max() {
+        let limiter = PlaybackLimiter::new(2);
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+        assert_eq!(limiter.active.load(Ordering::Acquire), 2);
+    }
+
+    #[test]
+    fn test_limiter_zero_is_unlimited() {
+        let limiter = PlaybackLimiter::new(0);
+        for _ in 0..64 {
+            assert!(limiter.try_acquire().is_some());
+        }
+    }
+
+    #[test]
+    fn test_limiter_release() {
+        let limiter = PlaybackLimiter::new(1);
+        assert!(limiter.try_acquire().is_some());
+        assert!(limiter.try_acquire().is_none());
+        limiter.release();
+        assert!(limiter.try_acquire().is_some());
+
+        // Releasing more than acquired never underflows
+        limiter.release();
+        limiter.release();
+        assert_eq!(limiter.active.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn test_finished_source_frees_slot() {
+        let limiter = PlaybackLimiter::new(1);
+        let active = limiter.try_acquire().unwrap();
+        let source = SamplesBuffer::new(1, 44_100, vec![0.0f32, 0.5, 1.0]);
+
+        // Simulates the mixer draining the source
+        let drained: Vec<f32> = Done::new(source, active).collect();
+        assert_eq!(drained.len(), 3);
+        assert!(limiter.try_acquire().is_some());
+    }
+
+    fn cached(samples: Vec<f32>) -> DecodedSound {
+        DecodedSound {
             samples: Arc::new(samples),
-            sample_rate,
-            channels,
-        })
+            sample_rate: 4,
+            channels: 1,
+        }
     }
 
-    /// Check if a sound is available for the given event.
-    pub fn has_sound(&self, event: SoundEvent) -> bool {
-        self.cache
-            .get(&event)
-            .map(|v| !v.is_empty())
-            .unwrap_or(false)
+    #[test]
+    fn test_volume_scales_source_without_touching_cache() {
+        let sound = cached(vec![0.5, -1.0, 1.0]);
+        let cached_samples = sound.samples.clone();
+
+        let loud: Vec<f32> = sound.source(clamp_volume(1.0), 1.0, 0.0).collect();
+        let quiet: Vec<f32> = sound.source(clamp_volume(0.5), 1.0, 0.0).collect();
+
+        assert_eq!(loud, vec![0.5, -1.0, 1.0]);
+        assert_eq!(quiet, vec![0.25, -0.5, 0.5]);
+        assert!(Arc::ptr_eq(&cached_samples, &sound.samples));
+        assert_eq!(*sound.samples, vec![0.5, -1.0, 1.0]);
     }
 
-    /// Play a sound for the given event. Uses round-robin for
-    /// events with multiple variants.
-    pub fn play(&mut self, event: SoundEvent) {
-        if let Some(buffers) = self.cache.get(&event) {
-            if buffers.is_empty() {
-                return;
+    #[test]
+    fn test_clamp_volume() {
+        assert_eq!(clamp_volume(0.3), 0.3);
+        assert_eq!(clamp_volume(-1.0), 0.0);
+        assert_eq!(clamp_volume(4.0), 1.0);
+        assert_eq!(clamp_volume(f32::NAN), 0.0);
+    }
+
+    /// Clock that only moves when told to.
+    #[derive(Clone)]
+    struct MockClock(Rc<Cell<Instant>>);
+
+    impl MockClock {
+        fn new() -> Self {
+            Self(Rc::new(Cell::new(Instant::now())))
+        }
+
+        fn advance(&self, by: Duration) {
+            self.0.set(self.0.get() + by);
+        }
+    }
+
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.0.get()
+        }
+    }
+
+    fn tracker(
+        clock: &MockClock,
+        cooldowns: &[(SoundEvent, u64)],
+    ) -> CooldownTracker<MockClock> {
+        let cooldowns = cooldowns
+            .iter()
+            .map(|(event, ms)| (*event, Duration::from_millis(*ms)))
+            .collect();
+        CooldownTracker::new(clock.clone(), cooldowns)
+    }
+
+    #[test]
+    fn test_cooldown_skips_rapid_retrigger() {
+        let clock = MockClock::new();
+        let mut cooldowns = tracker(&clock, &[(SoundEvent::Bell, 100)]);
+
+        assert!(!cooldowns.is_cooling_down(SoundEvent::Bell));
+        cooldowns.record(SoundEvent::Bell);
+        assert!(cooldowns.is_cooling_down(SoundEvent::Bell));
+
+        clock.advance(Duration::from_millis(99));
+        assert!(cooldowns.is_cooling_down(SoundEvent::Bell));
+
+        clock.advance(Duration::from_millis(1));
+        assert!(!cooldowns.is_cooling_down(SoundEvent::Bell));
+    }
+
+    #[test]
+    fn test_resize_cooldown_restarts_while_dragging() {
+        let clock = MockClock::new();
+        let mut cooldowns = tracker(
+            &clock,
+            &[(SoundEvent::Resize, 100), (SoundEvent::Bell, 100)],
+        );
+
+        cooldowns.record(SoundEvent::Resize);
+        cooldowns.record(SoundEvent::Bell);
+        // Events keep coming in faster than the cooldown during a drag
+        for _ in 0..5 {
+            clock.advance(Duration::from_millis(50));
+            assert!(cooldowns.is_cooling_down(SoundEvent::Resize));
+            cooldowns.skipped(SoundEvent::Resize);
+        }
+        // Other events still cool down from when they last played
+        assert!(!cooldowns.is_cooling_down(SoundEvent::Bell));
+
+        clock.advance(Duration::from_millis(100));
+        assert!(!cooldowns.is_cooling_down(SoundEvent::Resize));
+    }
+
+    #[test]
+    fn test_cooldown_is_per_event() {
+        let clock = MockClock::new();
+        let mut cooldowns = tracker(
+            &clock,
+            &[(SoundEvent::Bell, 1000), (SoundEvent::TabCreate, 10)],
+        );
+        cooldowns.record(SoundEvent::Bell);
+        cooldowns.record(SoundEvent::TabCreate);
+
+        clock.advance(Duration::from_millis(50));
+        assert!(cooldowns.is_cooling_down(SoundEvent::Bell));
+        assert!(!cooldowns.is_cooling_down(SoundEvent::TabCreate));
+        // Never played
+        assert!(!cooldowns.is_cooling_down(SoundEvent::SplitCreate));
+    }
+
+    #[test]
+    fn test_zero_cooldown_never_skips() {
+        let clock = MockClock::new();
+        let mut cooldowns = tracker(&clock, &[(SoundEvent::KeyLetter, 0)]);
+        cooldowns.record(SoundEvent::KeyLetter);
+        assert!(!cooldowns.is_cooling_down(SoundEvent::KeyLetter));
+    }
+
+    #[test]
+    fn test_route_events_to_opened_devices() {
+        let devices = HashMap::from([
+            (SoundEvent::Bell, "Speakers".to_string()),
+            (SoundEvent::KeyLetter, "Headphones".to_string()),
+            (SoundEvent::KeyEnter, "Headphones".to_string()),
+        ]);
+        let opened = HashSet::from(["Speakers".to_string(), "Headphones".to_string()]);
+        let routes = route_events(&devices, &opened);
+
+        assert_eq!(
+            routes[&SoundEvent::Bell],
+            StreamKey::Device("Speakers".to_string())
+        );
+        assert_eq!(
+            routes[&SoundEvent::KeyLetter],
+            routes[&SoundEvent::KeyEnter]
+        );
+        // Events without a device aren't routed and use the default stream
+        assert!(!routes.contains_key(&SoundEvent::TabCreate));
+    }
+
+    #[test]
+    fn test_route_events_missing_device_falls_back_to_default() {
+        let devices = HashMap::from([
+            (SoundEvent::Bell, "Speakers".to_string()),
+            (SoundEvent::KeyLetter, "Unplugged".to_string()),
+        ]);
+        let opened = HashSet::from(["Speakers".to_string()]);
+        let routes = route_events(&devices, &opened);
+
+        assert_eq!(
+            routes[&SoundEvent::Bell],
+            StreamKey::Device("Speakers".to_string())
+        );
+        assert_eq!(routes[&SoundEvent::KeyLetter], StreamKey::Default);
+        assert!(route_events(&devices, &HashSet::new())
+            .values()
+            .all(|key| *key == StreamKey::Default));
+    }
+
+    /// Mixer counting the sounds it was handed, and what they sound like.
+    /// With `hold` set the sounds are kept instead, still playing.
+    #[derive(Default)]
+    struct CountingMixer {
+        played: Rc<Cell<usize>>,
+        last_peak: Rc<Cell<f32>>,
+        hold: bool,
+        playing: RefCell<Vec<PlayingSource>>,
+    }
+
+    impl Mixer for CountingMixer {
+        fn mix(&self, _event: SoundEvent, source: PlayingSource) -> bool {
+            self.played.set(self.played.get() + 1);
+            if self.hold {
+                self.playing.borrow_mut().push(source);
+            } else {
+                self.last_peak
+                    .set(source.fold(0.0, |peak, s| f32::max(peak, s.abs())));
             }
-            let idx = self.indices.entry(event).or_insert(0);
-            let sound = &buffers[*idx];
-            *idx = (*idx + 1) % buffers.len();
+            true
+        }
+    }
+
+    fn manager<M: Mixer>(mixer: M) -> SoundManager<M> {
+        SoundManager {
+            cache: HashMap::from([(
+                SoundEvent::Bell,
+                vec![CachedSound::Ready(cached(vec![1.0, -1.0]))],
+            )]),
+            streams: mixer,
+            mapping: HashMap::new(),
+            loading: None,
+            indices: HashMap::new(),
+            random_events: HashSet::new(),
+            bell_escalation: None,
+            volume: 1.0,
+            category_volumes: HashMap::new(),
+            pitch_variance: 0.0,
+            typing_pitch: None,
+            muted: false,
+            max_duration: 10.0,
+            limiter: PlaybackLimiter::new(0),
+            cooldowns: CooldownTracker::new(SystemClock, HashMap::new()),
+            last_error: None,
+        }
+    }
+
+    /// Mixer whose device is gone until it reconnects, if `can_reconnect`.
+    #[derive(Default)]
+    struct UnpluggedMixer {
+        connected: bool,
+        can_reconnect: bool,
+        reconnects: Rc<Cell<usize>>,
+        played: Rc<Cell<usize>>,
+    }
+
+    impl Mixer for UnpluggedMixer {
+        fn mix(&self, _event: SoundEvent, _source: PlayingSource) -> bool {
+            if self.connected {
+                self.played.set(self.played.get() + 1);
+            }
+            self.connected
+        }
+
+        fn reconnect(&mut self) -> bool {
+            self.reconnects.set(self.reconnects.get() + 1);
+            self.connected = self.can_reconnect;
+            self.connected
+        }
+    }
+
+    #[test]
+    fn test_failed_play_reconnects_and_keeps_cache() {
+        let mixer = UnpluggedMixer {
+            can_reconnect: true,
+            ..UnpluggedMixer::default()
+        };
+        let reconnects = mixer.reconnects.clone();
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(reconnects.get(), 1);
+        assert_eq!(played.get(), 1);
+        assert!(sounds.has_sound(SoundEvent::Bell));
+
+        // Connected now, no more reconnecting
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(reconnects.get(), 1);
+        assert_eq!(played.get(), 2);
+    }
+
+    #[test]
+    fn test_reconnect_backs_off() {
+        let mixer = UnpluggedMixer::default();
+        let reconnects = mixer.reconnects.clone();
+        let mut sounds = manager(mixer);
+
+        for _ in 0..5 {
+            sounds.play(SoundEvent::Bell);
+        }
+        assert_eq!(reconnects.get(), 1);
+        assert!(!sounds.try_reconnect());
 
-            let source = rodio::buffer::SamplesBuffer::new(
-                sound.channels,
-                sound.sample_rate,
-                (*sound.samples).clone(),
-            )
-            .amplify(self.volume);
+        let now = Instant::now();
+        assert!(reconnect_allowed(None, now));
+        assert!(!reconnect_allowed(
+            Some(now),
+            now + Duration::from_millis(500)
+        ));
+        assert!(reconnect_allowed(Some(now), now + RECONNECT_BACKOFF));
+    }
+
+    #[test]
+    fn test_rapid_key_presses_beyond_max_concurrent_are_dropped() {
+        let mixer = CountingMixer {
+            hold: true,
+            ..CountingMixer::default()
+        };
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+        sounds.cache.insert(
+            SoundEvent::KeyLetter,
+            vec![CachedSound::Ready(cached(vec![1.0, -1.0]))],
+        );
+        sounds.limiter = PlaybackLimiter::new(2);
+        sounds.cooldowns = CooldownTracker::new(
+            SystemClock,
+            HashMap::from([(SoundEvent::KeyLetter, Duration::ZERO)]),
+        );
 
-            // play_raw() mixes concurrently — multiple sounds
-            // can overlap without queuing.
-            let _ = self.stream_handle.play_raw(source.convert_samples());
+        for _ in 0..6 {
+            sounds.play(SoundEvent::KeyLetter);
         }
+        assert_eq!(played.get(), 2);
+    }
+
+    #[test]
+    fn test_play_is_a_no_op_while_muted() {
+        let mixer = CountingMixer::default();
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(played.get(), 1);
+
+        sounds.set_muted(true);
+        assert!(sounds.is_muted());
+        sounds.play(SoundEvent::Bell);
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(played.get(), 1);
+        // Muting keeps the cache so unmuting plays right away
+        assert!(sounds.has_sound(SoundEvent::Bell));
+
+        sounds.set_muted(false);
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(played.get(), 2);
+    }
+
+    #[test]
+    fn test_play_uses_live_volume() {
+        let mixer = CountingMixer::default();
+        let peak = mixer.last_peak.clone();
+        let mut sounds = manager(mixer);
+
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(peak.get(), 1.0);
+
+        sounds.set_volume(0.25);
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(peak.get(), 0.25);
+    }
+
+    #[test]
+    fn test_category_volume_applies_to_its_events() {
+        let mixer = CountingMixer::default();
+        let peak = mixer.last_peak.clone();
+        let mut sounds = manager(mixer);
+        for event in [
+            SoundEvent::TabCreate,
+            SoundEvent::SplitClose,
+            SoundEvent::Resize,
+        ] {
+            sounds
+                .cache
+                .insert(event, vec![CachedSound::Ready(cached(vec![1.0, -1.0]))]);
+        }
+        sounds.set_volume(0.5);
+        sounds.set_category_volumes(HashMap::from([(SoundCategory::Navigation, 0.5)]));
+
+        for event in [
+            SoundEvent::TabCreate,
+            SoundEvent::SplitClose,
+            SoundEvent::Resize,
+        ] {
+            sounds.play(event);
+            assert_eq!(peak.get(), 0.25, "{event:?}");
+        }
+        // Other categories only get the global volume
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(peak.get(), 0.5);
+    }
+
+    #[test]
+    fn test_category_volume_product_is_clamped() {
+        let mixer = CountingMixer::default();
+        let peak = mixer.last_peak.clone();
+        let mut sounds = manager(mixer);
+        sounds.set_category_volumes(HashMap::from([(SoundCategory::System, 3.0)]));
+        sounds.play(SoundEvent::Bell);
+        assert_eq!(peak.get(), 1.0);
+
+        sounds.set_category_volumes(HashMap::from([(SoundCategory::System, -1.0)]));
+        assert_eq!(sounds.event_volume(SoundEvent::Bell), 0.0);
+    }
+
+    #[test]
+    fn test_typing_pitch_offset_slow_and_fast() {
+        assert_eq!(typing_pitch_offset(0.0), 0.0);
+        assert_eq!(typing_pitch_offset(TYPING_RATE_SLOW), 0.0);
+        let halfway = (TYPING_RATE_SLOW + TYPING_RATE_FAST) / 2.0;
+        assert!((typing_pitch_offset(halfway) - 0.5).abs() < 1e-6);
+        assert_eq!(typing_pitch_offset(TYPING_RATE_FAST), 1.0);
+        // Clamped however fast the keys come in
+        assert_eq!(typing_pitch_offset(1000.0), 1.0);
+    }
+
+    #[test]
+    fn test_typing_rate() {
+        let now = Instant::now();
+        assert_eq!(typing_rate(&[], now), 0.0);
+        assert_eq!(typing_rate(&[now], now), 0.0);
+        let presses: Vec<Instant> = (0..5)
+            .rev()
+            .map(|i| now - Duration::from_millis(i * 100))
+            .collect();
+        assert!((typing_rate(&presses, now) - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_typing_pitch_rises_with_speed() {
+        let start = Instant::now();
+        let play = |interval_ms: u64| {
+            let mut typing_pitch = TypingPitch::new(2.0);
+            (0..6)
+                .map(|i| {
+                    typing_pitch.record(start + Duration::from_millis(i * interval_ms))
+                })
+                .last()
+                .unwrap()
+        };
+
+        // Slow typing plays as recorded, fast typing up to 2 semitones higher
+        assert_eq!(play(500), 1.0);
+        assert_eq!(play(50), pitch_factor(2.0, 1.0));
+        let medium = play(150);
+        assert!(medium > 1.0 && medium < pitch_factor(2.0, 1.0));
+
+        // A pause starts over
+        let mut typing_pitch = TypingPitch::new(2.0);
+        for i in 0..6 {
+            typing_pitch.record(start + Duration::from_millis(i * 50));
+        }
+        assert_eq!(typing_pitch.record(start + Duration::from_secs(5)), 1.0);
+    }
+
+    #[test]
+    fn test_bell_intensity_sparse_bells_stay_subtle() {
+        let now = Instant::now();
+        let window = Duration::from_secs(2);
+        let bells = [
+            now - Duration::from_secs(10),
+            now - Duration::from_secs(5),
+            now,
+        ];
+        assert_eq!(bell_intensity(&bells, now, window, 3), 0);
+        assert_eq!(bell_intensity(&[now], now, window, 3), 0);
+    }
+
+    #[test]
+    fn test_bell_intensity_bursts_escalate() {
+        let now = Instant::now();
+        let window = Duration::from_secs(2);
+        let burst = |count: u64| -> Vec<Instant> {
+            (0..count)
+                .rev()
+                .map(|i| now - Duration::from_millis(i * 100))
+                .collect()
+        };
+        assert_eq!(bell_intensity(&burst(2), now, window, 3), 1);
+        assert_eq!(bell_intensity(&burst(3), now, window, 3), 2);
+        // Capped at the loudest variant
+        assert_eq!(bell_intensity(&burst(10), now, window, 3), 2);
+        assert_eq!(bell_intensity(&burst(10), now, window, 1), 0);
+    }
+
+    #[test]
+    fn test_bell_escalation_forgets_old_bells() {
+        let start = Instant::now();
+        let mut escalation = BellEscalation::new(Duration::from_secs(1));
+        assert_eq!(escalation.record(start, 3), 0);
+        assert_eq!(escalation.record(start + Duration::from_millis(200), 3), 1);
+        assert_eq!(escalation.record(start + Duration::from_millis(400), 3), 2);
+        // A quiet spell calms it down again
+        assert_eq!(escalation.record(start + Duration::from_secs(5), 3), 0);
+        assert_eq!(escalation.bells.len(), 1);
+    }
+
+    #[test]
+    fn test_pitch_factor_range() {
+        let low = 2f32.powf(-2.0 / 12.0);
+        let high = 2f32.powf(2.0 / 12.0);
+        assert_eq!(pitch_factor(2.0, -1.0), low);
+        assert_eq!(pitch_factor(2.0, 1.0), high);
+        assert_eq!(pitch_factor(2.0, 0.0), 1.0);
+        // Offsets past the ends don't widen the range
+        assert_eq!(pitch_factor(2.0, 5.0), high);
+
+        for _ in 0..256 {
+            let factor = random_pitch_factor(2.0);
+            assert!((low..=high).contains(&factor), "{factor} out of range");
+        }
+        assert_eq!(random_pitch_factor(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_pitch_resamples_source() {
+        let sound = cached(vec![0.0; 8]);
+        assert_eq!(sound.source(1.0, 1.0, 0.0).sample_rate(), 4);
+        assert_eq!(sound.source(1.0, 1.5, 0.0).sample_rate(), 6);
+        // Resampling never reaches a zero rate
+        assert_eq!(sound.source(1.0, 0.0, 0.0).sample_rate(), 1);
+    }
+
+    #[test]
+    fn test_channel_gains() {
+        assert_eq!(channel_gains(0.0), (1.0, 1.0));
+        assert_eq!(channel_gains(-1.0), (1.0, 0.0));
+        assert_eq!(channel_gains(1.0), (0.0, 1.0));
+        assert_eq!(channel_gains(0.5), (0.5, 1.0));
+        assert_eq!(channel_gains(-0.25), (1.0, 0.75));
+        // Out of range pans are clamped
+        assert_eq!(channel_gains(3.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_pan_samples() {
+        // Mono becomes stereo once panned
+        assert_eq!(
+            pan_samples(&[1.0, 0.5], 1, 0.5),
+            (2, vec![0.5, 1.0, 0.25, 0.5])
+        );
+        assert_eq!(pan_samples(&[1.0, 0.5], 1, 0.0), (1, vec![1.0, 0.5]));
+        // Stereo frames are scaled per channel
+        assert_eq!(
+            pan_samples(&[1.0, 1.0, 0.5, 0.5], 2, -1.0),
+            (2, vec![1.0, 0.0, 0.5, 0.0])
+        );
+        // Channels past left and right are left alone
+        assert_eq!(
+            pan_samples(&[1.0, 1.0, 1.0], 3, 1.0),
+            (3, vec![0.0, 1.0, 1.0])
+        );
+    }
+
+    #[test]
+    fn test_duration_secs() {
+        // 8 samples at 4 Hz mono
+        assert_eq!(cached(vec![0.0; 8]).duration_secs(), 2.0);
+        let stereo = DecodedSound {
+            channels: 2,
+            ..cached(vec![0.0; 8])
+        };
+        assert_eq!(stereo.duration_secs(), 1.0);
+    }
+
+    /// Write a 16-bit PCM mono WAV holding `samples` at `sample_rate`.
+    fn write_wav(name: &str, sample_rate: u32, samples: &[i16]) -> PathBuf {
+        let data_len = (samples.len() * 2) as u32;
+        let mut bytes = Vec::with_capacity(44 + data_len as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for sample in samples {
+            bytes.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        let path = std::env::temp_dir()
+            .join(format!("rio-sound-{}-{name}.wav", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_sound_plays_once_decoded_by_the_loader() {
+        let path = write_wav("lazy", 8000, &[i16::MAX, i16::MIN, 0, 0]);
+        let lazy = read_metadata(&path).unwrap();
+        assert_eq!(lazy.sample_rate, 8000);
+        assert_eq!(lazy.channels, 1);
+
+        let mixer = CountingMixer::default();
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+        let (sender, receiver) = mpsc::channel();
+        sounds.loading = Some(receiver);
+        sender
+            .send(Loaded::Event(
+                SoundEvent::WindowCreate,
+                vec![CachedSound::Lazy(lazy.clone())],
+            ))
+            .unwrap();
+
+        // Only the metadata arrived, the main thread never decodes
+        sounds.play(SoundEvent::WindowCreate);
+        assert_eq!(played.get(), 0);
+        assert!(sounds.has_sound(SoundEvent::WindowCreate));
+        assert!(!sounds.is_ready(SoundEvent::WindowCreate));
+        assert_eq!(sounds.limiter.active.load(Ordering::Acquire), 0);
+
+        let decoded = decode_samples(&lazy);
+        sender
+            .send(Loaded::Decoded(
+                SoundEvent::WindowCreate,
+                path.clone(),
+                decoded,
+            ))
+            .unwrap();
+        sounds.play(SoundEvent::WindowCreate);
+        assert_eq!(played.get(), 1);
+        match &sounds.cache[&SoundEvent::WindowCreate][0] {
+            CachedSound::Ready(sound) => assert_eq!(sound.samples.len(), 4),
+            CachedSound::Lazy(_) => panic!("decoded samples should replace the metadata"),
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_play_before_load_completes_is_a_no_op() {
+        let mixer = CountingMixer::default();
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+        let (sender, receiver) = mpsc::channel();
+        sounds.loading = Some(receiver);
+        sounds.mapping.insert(
+            SoundEvent::WindowCreate,
+            vec![PathBuf::from("window-create.wav")],
+        );
+
+        // Still loading: reported as configured but not ready
+        assert!(sounds.has_sound(SoundEvent::WindowCreate));
+        assert!(!sounds.is_ready(SoundEvent::WindowCreate));
+        sounds.play(SoundEvent::WindowCreate);
+        assert_eq!(played.get(), 0);
+
+        sender
+            .send(Loaded::Event(
+                SoundEvent::WindowCreate,
+                vec![CachedSound::Ready(cached(vec![0.5]))],
+            ))
+            .unwrap();
+        sounds.play(SoundEvent::WindowCreate);
+        assert_eq!(played.get(), 1);
+        assert!(sounds.is_ready(SoundEvent::WindowCreate));
+
+        // The loader finishing is noticed on the next play
+        drop(sender);
+        sounds.play(SoundEvent::Bell);
+        assert!(sounds.loading.is_none());
+    }
+
+    #[test]
+    fn test_undecodable_sound_is_dropped() {
+        let mixer = CountingMixer::default();
+        let played = mixer.played.clone();
+        let mut sounds = manager(mixer);
+        let missing = LazySound {
+            path: std::env::temp_dir().join("rio-sound-missing.wav"),
+            sample_rate: 8000,
+            channels: 1,
+            duration: None,
+        };
+        let (sender, receiver) = mpsc::channel();
+        sounds.loading = Some(receiver);
+        sender
+            .send(Loaded::Event(
+                SoundEvent::WindowCreate,
+                vec![CachedSound::Lazy(missing.clone())],
+            ))
+            .unwrap();
+        sender
+            .send(Loaded::Decoded(
+                SoundEvent::WindowCreate,
+                missing.path.clone(),
+                decode_samples(&missing),
+            ))
+            .unwrap();
+        drop(sender);
+
+        sounds.play(SoundEvent::WindowCreate);
+        assert_eq!(played.get(), 0);
+        assert!(!sounds.has_sound(SoundEvent::WindowCreate));
+        assert_eq!(sounds.limiter.active.load(Ordering::Acquire), 0);
     }
 }
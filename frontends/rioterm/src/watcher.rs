@@ -1,58 +1,26 @@
 use crate::event::{EventListener, RioEvent};
-use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::Path;
-use std::time::Duration;
+use rio_backend::config::watcher::ConfigWatcher;
+use std::path::PathBuf;
 
-const POLLING_TIMEOUT: Duration = Duration::from_secs(2);
-
-pub fn configuration_file_updates<
-    P: AsRef<Path> + std::marker::Send + 'static,
-    T: EventListener + std::marker::Send + 'static,
->(
-    path: P,
+/// Request a config reload whenever the config file at `path` changes. The
+/// returned watcher has to be kept alive, reloads stop once it is dropped.
+pub fn configuration_file_updates<T: EventListener + std::marker::Send + 'static>(
+    path: PathBuf,
     event_proxy: T,
-) -> notify::Result<()> {
+) -> notify::Result<ConfigWatcher> {
     let (tx, rx) = std::sync::mpsc::channel();
+    let watcher = ConfigWatcher::new(path, tx)?;
 
-    // Automatically select the best implementation for your platform.
-    // You can also access each implementation directly e.g. INotifyWatcher.
-    let mut watcher = RecommendedWatcher::new(
-        tx,
-        Config::default().with_poll_interval(POLLING_TIMEOUT),
-    )?;
-
+    // Exits once the watcher is dropped and `tx` goes with it
     std::thread::spawn(move || {
-        // Add a path to be watched. All files and directories at that path and
-        // below will be monitored for changes.
-        if let Err(err_message) =
-            watcher.watch(path.as_ref(), RecursiveMode::NonRecursive)
-        {
-            tracing::warn!("unable to watch config directory {err_message:?}");
-        };
-
-        for res in rx {
-            match res {
-                Ok(event) => match event.kind {
-                    EventKind::Any
-                    | EventKind::Create(_)
-                    | EventKind::Modify(_)
-                    | EventKind::Other => {
-                        tracing::info!(
-                            "config directory has dispatched an event {event:?}"
-                        );
-                        event_proxy.send_event(
-                            RioEvent::PrepareUpdateConfig,
-                            rio_backend::event::WindowId::from(0),
-                        );
-                    }
-                    _ => (),
-                },
-                Err(err_message) => {
-                    tracing::error!("unable to watch config directory: {err_message:?}")
-                }
-            }
+        for () in rx {
+            tracing::info!("config file has changed, reloading");
+            event_proxy.send_event(
+                RioEvent::PrepareUpdateConfig,
+                rio_backend::event::WindowId::from(0),
+            );
         }
     });
 
-    Ok(())
+    Ok(watcher)
 }
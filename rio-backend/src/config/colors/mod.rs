@@ -681,15 +681,32 @@ where
     }
 }
 
+/// Colors given as a hex string, or as the `[r, g, b, a]` array that
+/// `ColorArray` serializes to (so serialized configs parse back).
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArrayColorValue {
+    Hex(String),
+    Array(ColorArray),
+}
+
+impl ArrayColorValue {
+    fn into_arr<E: de::Error>(self) -> Result<ColorArray, E> {
+        match self {
+            ArrayColorValue::Hex(s) => match ColorBuilder::from_hex(s, Format::SRGB0_1) {
+                Ok(color) => Ok(color.to_arr()),
+                Err(e) => Err(E::custom(e)),
+            },
+            ArrayColorValue::Array(arr) => Ok(arr),
+        }
+    }
+}
+
 pub fn deserialize_to_arr<'de, D>(deserializer: D) -> Result<ColorArray, D::Error>
 where
     D: de::Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
-    match ColorBuilder::from_hex(s, Format::SRGB0_1) {
-        Ok(color) => Ok(color.to_arr()),
-        Err(e) => Err(serde::de::Error::custom(e)),
-    }
+    ArrayColorValue::deserialize(deserializer)?.into_arr()
 }
 
 pub fn deserialize_to_arr_opt<'de, D>(
@@ -698,11 +715,9 @@ pub fn deserialize_to_arr_opt<'de, D>(
 where
     D: de::Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
-    match ColorBuilder::from_hex(s, Format::SRGB0_1) {
-        Ok(color) => Ok(Some(color.to_arr())),
-        Err(e) => Err(serde::de::Error::custom(e)),
-    }
+    ArrayColorValue::deserialize(deserializer)?
+        .into_arr()
+        .map(Some)
 }
 
 #[cfg(test)]
@@ -859,4 +874,21 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_deserialize_to_arr_accepts_hex_and_array() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            #[serde(deserialize_with = "deserialize_to_arr")]
+            color: ColorArray,
+        }
+
+        let hex: Wrapper = toml::from_str("color = '#ff0000'").unwrap();
+        assert_eq!(hex.color, [1.0, 0.0, 0.0, 1.0]);
+
+        let arr: Wrapper = toml::from_str("color = [0.5, 0.25, 0.0, 1.0]").unwrap();
+        assert_eq!(arr.color, [0.5, 0.25, 0.0, 1.0]);
+
+        assert!(toml::from_str::<Wrapper>("color = 'nope'").is_err());
+    }
 }
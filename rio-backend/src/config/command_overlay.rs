@@ -1,15 +1,22 @@
 use crate::config::colors::deserialize_to_arr;
 use crate::config::colors::ColorArray;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Appearance and layout configuration for command overlay panels.
 ///
 /// Command overlays are floating, click-through panels that run a real PTY
 /// command and render its live terminal output on top of the terminal content.
 ///
+/// `[[command-overlay]]` is an array: each entry with a `command` is its own
+/// overlay, toggled by position or `id`. The first entry without a `command`
+/// styles the overlays opened from the leader menu or `overlay(...)` bindings.
+/// A single `[command-overlay]` table is still accepted.
+///
 /// TOML configuration example:
 /// ```toml
-/// [command-overlay]
+/// [[command-overlay]]
+/// id = "top"
+/// command = "htop"
 /// x = 0.6
 /// y = 0.05
 /// width = 0.38
@@ -22,9 +29,19 @@ use serde::{Deserialize, Serialize};
 /// shadow-blur-radius = 8.0
 /// shadow-color = '#00000066'
 /// shadow-offset = [2.0, 4.0]
+/// draggable = true
+/// drag-handle-height = 20.0
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommandOverlayStyle {
+    /// Name to target this overlay from key bindings.
+    #[serde(default = "Option::default", skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Command run by this overlay. Empty for the style of ad-hoc overlays.
+    #[serde(default)]
+    pub command: String,
+
     /// Horizontal position as a fraction of window width (0.0–1.0).
     /// Default: 0.6 (right side of window).
     #[serde(default = "default_overlay_x")]
@@ -102,6 +119,19 @@ pub struct CommandOverlayStyle {
     /// Shadow offset [x, y] in scaled pixels. Default: [0.0, 2.0].
     #[serde(default = "default_overlay_shadow_offset", rename = "shadow-offset")]
     pub shadow_offset: [f32; 2],
+
+    /// Draw a handle strip on top of the panel that moves the overlay when
+    /// dragged with the left mouse button. The new position lasts for the
+    /// session and is not written back to the config file. Default: false.
+    #[serde(default)]
+    pub draggable: bool,
+
+    /// Height of the drag handle strip in scaled pixels. Default: 20.0.
+    #[serde(
+        default = "default_overlay_drag_handle_height",
+        rename = "drag-handle-height"
+    )]
+    pub drag_handle_height: f32,
 }
 
 // --- Default value functions ---
@@ -173,9 +203,16 @@ fn default_overlay_shadow_offset() -> [f32; 2] {
     [0.0, 2.0]
 }
 
+#[inline]
+fn default_overlay_drag_handle_height() -> f32 {
+    20.0
+}
+
 impl Default for CommandOverlayStyle {
     fn default() -> Self {
         CommandOverlayStyle {
+            id: None,
+            command: String::new(),
             x: default_overlay_x(),
             y: default_overlay_y(),
             width: default_overlay_width(),
@@ -189,6 +226,8 @@ impl Default for CommandOverlayStyle {
             shadow_blur_radius: default_overlay_shadow_blur_radius(),
             shadow_color: default_overlay_shadow_color(),
             shadow_offset: default_overlay_shadow_offset(),
+            draggable: false,
+            drag_handle_height: default_overlay_drag_handle_height(),
         }
     }
 }
@@ -213,4 +252,177 @@ impl CommandOverlayStyle {
     pub fn has_custom_font_size(&self) -> bool {
         self.font_size > 0.0
     }
+
+    /// Height of the drag handle strip in scaled pixels, 0.0 unless the
+    /// overlay is draggable.
+    #[inline]
+    pub fn drag_handle(&self) -> f32 {
+        if self.draggable {
+            self.drag_handle_height.max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Picks a configured overlay, by its position in `[[command-overlay]]`
+/// or by its `id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOverlayRef {
+    Index(usize),
+    Id(String),
+}
+
+impl CommandOverlayRef {
+    /// Array index of the overlay this refers to. Entries without a
+    /// `command` can't be toggled and resolve to `None`.
+    pub fn resolve(&self, overlays: &[CommandOverlayStyle]) -> Option<usize> {
+        let index = match self {
+            CommandOverlayRef::Index(index) => *index,
+            // Action names are lowercased, so ids match regardless of case
+            CommandOverlayRef::Id(id) => overlays.iter().position(|overlay| {
+                overlay
+                    .id
+                    .as_deref()
+                    .is_some_and(|overlay_id| overlay_id.eq_ignore_ascii_case(id))
+            })?,
+        };
+        overlays
+            .get(index)
+            .filter(|overlay| !overlay.command.trim().is_empty())
+            .map(|_| index)
+    }
+}
+
+/// Style of overlays opened without a config entry (leader menu,
+/// `overlay(...)` bindings): the first entry without a `command`.
+pub fn ad_hoc_style(overlays: &[CommandOverlayStyle]) -> CommandOverlayStyle {
+    overlays
+        .iter()
+        .find(|overlay| overlay.command.trim().is_empty())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Accepts both `[[command-overlay]]` and a single `[command-overlay]` table.
+pub fn deserialize_command_overlays<'de, D>(
+    deserializer: D,
+) -> Result<Vec<CommandOverlayStyle>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        Many(Vec<CommandOverlayStyle>),
+        One(Box<CommandOverlayStyle>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(overlays) => overlays,
+        OneOrMany::One(overlay) => vec![*overlay],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_command_overlay_array() {
+        let config: Config = toml::from_str(
+            r#"
+            [[command-overlay]]
+            command = "git log --oneline"
+            x = 0.0
+
+            [[command-overlay]]
+            id = "top"
+            command = "htop"
+            x = 0.6
+        "#,
+        )
+        .unwrap();
+
+        let overlays = &config.command_overlay;
+        assert_eq!(overlays.len(), 2);
+        assert_eq!(overlays[0].command, "git log --oneline");
+        assert_eq!(overlays[0].id, None);
+        assert_eq!(overlays[1].id.as_deref(), Some("top"));
+        assert_eq!(overlays[1].x, 0.6);
+        assert_eq!(overlays[1].width, default_overlay_width());
+    }
+
+    #[test]
+    fn test_single_command_overlay_table() {
+        let config: Config = toml::from_str(
+            r#"
+            [command-overlay]
+            opacity = 0.9
+        "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.command_overlay.len(), 1);
+        assert_eq!(ad_hoc_style(&config.command_overlay).opacity, 0.9);
+
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.command_overlay.is_empty());
+        assert_eq!(
+            ad_hoc_style(&config.command_overlay),
+            CommandOverlayStyle::default()
+        );
+    }
+
+    #[test]
+    fn test_command_overlay_ref_resolve() {
+        let overlays = vec![
+            CommandOverlayStyle {
+                opacity: 0.5,
+                ..CommandOverlayStyle::default()
+            },
+            CommandOverlayStyle {
+                id: Some("log".to_string()),
+                command: "git log".to_string(),
+                ..CommandOverlayStyle::default()
+            },
+        ];
+
+        assert_eq!(CommandOverlayRef::Index(1).resolve(&overlays), Some(1));
+        assert_eq!(
+            CommandOverlayRef::Id("log".to_string()).resolve(&overlays),
+            Some(1)
+        );
+        // Style-only entries and missing ones can't be toggled
+        assert_eq!(CommandOverlayRef::Index(0).resolve(&overlays), None);
+        assert_eq!(CommandOverlayRef::Index(2).resolve(&overlays), None);
+        assert_eq!(
+            CommandOverlayRef::Id("top".to_string()).resolve(&overlays),
+            None
+        );
+        assert_eq!(ad_hoc_style(&overlays).opacity, 0.5);
+    }
+
+    #[test]
+    fn test_command_overlay_drag_handle() {
+        let config: Config = toml::from_str(
+            r#"
+            [[command-overlay]]
+            command = "htop"
+
+            [[command-overlay]]
+            command = "top"
+            draggable = true
+            drag-handle-height = 24.0
+        "#,
+        )
+        .unwrap();
+
+        let overlays = &config.command_overlay;
+        assert!(!overlays[0].draggable);
+        assert_eq!(overlays[0].drag_handle_height, 20.0);
+        assert_eq!(overlays[0].drag_handle(), 0.0);
+        assert_eq!(overlays[1].drag_handle(), 24.0);
+    }
 }
@@ -410,6 +410,8 @@ pub fn default_config_file_content() -> String {
 #   • Bookmark
 #   • BottomTab
 #   • TopTab
+#   • LeftTab
+#   • RightTab
 #   • Plain
 #
 # "hide-if-single" - Hide navigation UI if is single.
@@ -1,3 +1,8 @@
+use crate::config::ranges::{
+    clamp_field, DISTORTION_CENTER, DISTORTION_FOV_DEGREES, DISTORTION_INNER_RADIUS,
+    DISTORTION_OUTER_RADIUS, DISTORTION_STRENGTH,
+};
+use serde::de::{value::StrDeserializer, DeserializeOwned};
 use serde::{Deserialize, Serialize};
 
 /// Distortion effect type applied to the rendered frame.
@@ -10,8 +15,57 @@ pub enum DistortionType {
     Barrel,
     /// Perspective tilt (vanishing point effect)
     Perspective,
+    /// Equidistant fisheye lens projection, see `fov-degrees`
+    Fisheye,
+    /// Red and blue sampled `strength` UV units apart, like a cheap lens
+    ChromaticAberration,
+    /// Darkens the frame from `inner-radius` out to `outer-radius`
+    Vignette,
 }
 
+/// How samples outside the rendered frame are filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EdgeFill {
+    /// Opaque black
+    #[default]
+    Black,
+    /// Repeat the nearest edge pixel
+    Clamp,
+    /// Mirror the frame back in
+    Mirror,
+}
+
+/// Texture filter used when sampling the frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SamplingFilter {
+    /// Smooth interpolation between pixels
+    #[default]
+    Linear,
+    /// Nearest pixel, keeps pixelated/retro looks and scanlines sharp
+    Nearest,
+}
+
+/// Precision of the texture the distortion renders into before it is
+/// written back to the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DistortionPrecision {
+    /// High on wide gamut colorspaces (DisplayP3, Rec2020), standard otherwise
+    #[default]
+    Auto,
+    /// Same format as the window surface
+    Standard,
+    /// 16-bit float intermediate, avoids banding in gradients. Falls back
+    /// to standard when the GPU can't render to it.
+    High,
+}
+
+/// Allowed range for `fov-degrees`.
+pub const MIN_FOV_DEGREES: f32 = 60.0;
+pub const MAX_FOV_DEGREES: f32 = 180.0;
+
 /// Configuration for the `[distortion]` TOML section.
 ///
 /// ```toml
@@ -28,7 +82,9 @@ pub struct DistortionConfig {
     pub effect: DistortionType,
 
     /// Effect strength, 0.0 = no distortion, 1.0 = maximum.
-    /// Negative values invert the effect. Default: 0.3
+    /// Negative values invert the effect. For `chromatic-aberration` it is
+    /// the channel separation in UV units, 0.003 is moderate. For `vignette`
+    /// it is how dark the edges get. Default: 0.3
     #[serde(default = "default_strength")]
     pub strength: f32,
 
@@ -36,6 +92,179 @@ pub struct DistortionConfig {
     /// (0.0–1.0). Default: [0.5, 0.5] (screen center).
     #[serde(default = "default_center")]
     pub center: [f32; 2],
+
+    /// Angular field of view of the fisheye lens in degrees
+    /// (60.0–180.0). Only used by `fisheye`. Default: 120.0
+    #[serde(default = "default_fov_degrees")]
+    pub fov_degrees: f32,
+
+    /// Distance from `center` where the vignette starts darkening, 1.0
+    /// being the corners. Only used by `vignette`. Default: 0.4
+    #[serde(default = "default_inner_radius")]
+    pub inner_radius: f32,
+
+    /// Distance from `center` where the vignette reaches full `strength`.
+    /// Only used by `vignette`. Default: 1.0
+    #[serde(default = "default_outer_radius")]
+    pub outer_radius: f32,
+
+    /// Fill for areas that map outside the frame. Default: black
+    #[serde(default)]
+    pub edge_fill: EdgeFill,
+
+    /// Sampling filter, "linear" or "nearest". Default: linear
+    #[serde(default)]
+    pub filter: SamplingFilter,
+
+    /// Intermediate precision, "auto", "standard" or "high". Default: auto
+    #[serde(default)]
+    pub precision: DistortionPrecision,
+
+    /// Milliseconds to ease into the effect when it is enabled or its
+    /// values change, 0 applies them right away. Default: 0
+    #[serde(default)]
+    pub animate_duration_ms: u32,
+}
+
+impl DistortionConfig {
+    /// Field of view clamped to the supported range, in radians.
+    #[inline]
+    pub fn fov_radians(&self) -> f32 {
+        clamp_field(DISTORTION_FOV_DEGREES, self.fov_degrees).to_radians()
+    }
+
+    /// Single line form of the effect to share with others, e.g.
+    /// `barrel;strength=0.3;center=0.5,0.5;edge-fill=mirror`. The effect,
+    /// strength and center are always written, other fields only when
+    /// they differ from the default. Keys are the TOML ones.
+    pub fn to_snippet(&self) -> String {
+        let defaults = DistortionConfig::default();
+        let mut out = format!(
+            "{};strength={};center={},{}",
+            snippet_name(&self.effect),
+            self.strength,
+            self.center[0],
+            self.center[1]
+        );
+        let mut extra = |key: &str, value: String, default: String| {
+            if value != default {
+                out.push_str(&format!(";{key}={value}"));
+            }
+        };
+        extra(
+            "fov-degrees",
+            self.fov_degrees.to_string(),
+            defaults.fov_degrees.to_string(),
+        );
+        extra(
+            "inner-radius",
+            self.inner_radius.to_string(),
+            defaults.inner_radius.to_string(),
+        );
+        extra(
+            "outer-radius",
+            self.outer_radius.to_string(),
+            defaults.outer_radius.to_string(),
+        );
+        extra(
+            "edge-fill",
+            snippet_name(&self.edge_fill),
+            snippet_name(&defaults.edge_fill),
+        );
+        extra(
+            "filter",
+            snippet_name(&self.filter),
+            snippet_name(&defaults.filter),
+        );
+        extra(
+            "precision",
+            snippet_name(&self.precision),
+            snippet_name(&defaults.precision),
+        );
+        extra(
+            "animate-duration-ms",
+            self.animate_duration_ms.to_string(),
+            defaults.animate_duration_ms.to_string(),
+        );
+        out
+    }
+
+    /// Parse a snippet written by `to_snippet`. Fields left out keep their
+    /// default, numbers are clamped to their allowed range.
+    pub fn from_snippet(snippet: &str) -> Result<Self, String> {
+        let mut parts = snippet.trim().split(';').map(str::trim);
+        let effect = parts.next().unwrap_or_default();
+        if effect.is_empty() {
+            return Err("missing distortion effect".to_string());
+        }
+
+        let mut config = DistortionConfig {
+            effect: parse_snippet_name(effect)?,
+            ..DistortionConfig::default()
+        };
+        for part in parts.filter(|part| !part.is_empty()) {
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(format!("expected key=value, found \"{part}\""));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "strength" => config.strength = parse_snippet_number(key, value)?,
+                "center" => {
+                    let Some((x, y)) = value.split_once(',') else {
+                        return Err(format!(
+                            "center needs two values, found \"{value}\""
+                        ));
+                    };
+                    config.center = [
+                        parse_snippet_number(key, x.trim())?,
+                        parse_snippet_number(key, y.trim())?,
+                    ];
+                }
+                "fov-degrees" => config.fov_degrees = parse_snippet_number(key, value)?,
+                "inner-radius" => config.inner_radius = parse_snippet_number(key, value)?,
+                "outer-radius" => config.outer_radius = parse_snippet_number(key, value)?,
+                "edge-fill" => config.edge_fill = parse_snippet_name(value)?,
+                "filter" => config.filter = parse_snippet_name(value)?,
+                "precision" => config.precision = parse_snippet_name(value)?,
+                "animate-duration-ms" => {
+                    config.animate_duration_ms = value
+                        .parse()
+                        .map_err(|_| format!("invalid {key} \"{value}\""))?;
+                }
+                other => return Err(format!("unknown distortion field \"{other}\"")),
+            }
+        }
+
+        config.strength = clamp_field(DISTORTION_STRENGTH, config.strength);
+        for value in config.center.iter_mut() {
+            *value = clamp_field(DISTORTION_CENTER, *value);
+        }
+        config.fov_degrees = clamp_field(DISTORTION_FOV_DEGREES, config.fov_degrees);
+        config.inner_radius = clamp_field(DISTORTION_INNER_RADIUS, config.inner_radius);
+        config.outer_radius = clamp_field(DISTORTION_OUTER_RADIUS, config.outer_radius);
+        Ok(config)
+    }
+}
+
+/// Kebab-case name of a unit variant, as written in TOML.
+fn snippet_name<T: Serialize>(value: &T) -> String {
+    match toml::Value::try_from(value) {
+        Ok(toml::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn parse_snippet_name<T: DeserializeOwned>(name: &str) -> Result<T, String> {
+    T::deserialize(StrDeserializer::<serde::de::value::Error>::new(name))
+        .map_err(|err| format!("invalid distortion value \"{name}\": {err}"))
+}
+
+fn parse_snippet_number(key: &str, value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| format!("invalid {key} \"{value}\""))
 }
 
 fn default_strength() -> f32 {
@@ -46,12 +275,31 @@ fn default_center() -> [f32; 2] {
     [0.5, 0.5]
 }
 
+fn default_fov_degrees() -> f32 {
+    120.0
+}
+
+fn default_inner_radius() -> f32 {
+    0.4
+}
+
+fn default_outer_radius() -> f32 {
+    1.0
+}
+
 impl Default for DistortionConfig {
     fn default() -> Self {
         Self {
             effect: DistortionType::None,
             strength: default_strength(),
             center: default_center(),
+            fov_degrees: default_fov_degrees(),
+            inner_radius: default_inner_radius(),
+            outer_radius: default_outer_radius(),
+            edge_fill: EdgeFill::default(),
+            filter: SamplingFilter::default(),
+            precision: DistortionPrecision::default(),
+            animate_duration_ms: 0,
         }
     }
 }
@@ -66,6 +314,27 @@ mod tests {
         assert_eq!(config.effect, DistortionType::None);
         assert_eq!(config.strength, 0.3);
         assert_eq!(config.center, [0.5, 0.5]);
+        assert_eq!(config.fov_degrees, 120.0);
+        assert_eq!(config.inner_radius, 0.4);
+        assert_eq!(config.outer_radius, 1.0);
+        assert_eq!(config.edge_fill, EdgeFill::Black);
+        assert_eq!(config.filter, SamplingFilter::Linear);
+        assert_eq!(config.precision, DistortionPrecision::Auto);
+        assert_eq!(config.animate_duration_ms, 0);
+    }
+
+    #[test]
+    fn test_distortion_precision_toml() {
+        let toml_str = r#"
+            effect = "barrel"
+            precision = "high"
+        "#;
+        let config: DistortionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.precision, DistortionPrecision::High);
+
+        let config: DistortionConfig =
+            toml::from_str("precision = \"standard\"").unwrap();
+        assert_eq!(config.precision, DistortionPrecision::Standard);
     }
 
     #[test]
@@ -81,6 +350,13 @@ mod tests {
         assert_eq!(config.center, [0.3, 0.7]);
     }
 
+    #[test]
+    fn test_distortion_animate_duration_toml() {
+        let config: DistortionConfig =
+            toml::from_str("animate-duration-ms = 250").unwrap();
+        assert_eq!(config.animate_duration_ms, 250);
+    }
+
     #[test]
     fn test_distortion_none_toml() {
         let toml_str = r#"
@@ -109,4 +385,118 @@ mod tests {
         let config: DistortionConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.effect, DistortionType::None);
     }
+
+    #[test]
+    fn test_distortion_fisheye_toml() {
+        let toml_str = r#"
+            effect = "fisheye"
+            fov-degrees = 150.0
+            edge-fill = "mirror"
+        "#;
+        let config: DistortionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.effect, DistortionType::Fisheye);
+        assert_eq!(config.fov_degrees, 150.0);
+        assert_eq!(config.edge_fill, EdgeFill::Mirror);
+    }
+
+    #[test]
+    fn test_distortion_chromatic_aberration_toml() {
+        let toml_str = r#"
+            effect = "chromatic-aberration"
+            strength = 0.005
+        "#;
+        let config: DistortionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.effect, DistortionType::ChromaticAberration);
+        assert_eq!(config.strength, 0.005);
+    }
+
+    #[test]
+    fn test_distortion_vignette_toml() {
+        let toml_str = r#"
+            effect = "vignette"
+            strength = 0.8
+            inner-radius = 0.2
+        "#;
+        let config: DistortionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.effect, DistortionType::Vignette);
+        assert_eq!(config.strength, 0.8);
+        assert_eq!(config.inner_radius, 0.2);
+        assert_eq!(config.outer_radius, 1.0);
+    }
+
+    #[test]
+    fn test_distortion_fov_is_clamped() {
+        let mut config = DistortionConfig {
+            fov_degrees: 400.0,
+            ..DistortionConfig::default()
+        };
+        assert_eq!(config.fov_radians(), MAX_FOV_DEGREES.to_radians());
+
+        config.fov_degrees = 10.0;
+        assert_eq!(config.fov_radians(), MIN_FOV_DEGREES.to_radians());
+    }
+
+    #[test]
+    fn test_distortion_filter_toml() {
+        let toml_str = r#"
+            effect = "barrel"
+            filter = "nearest"
+        "#;
+        let config: DistortionConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.filter, SamplingFilter::Nearest);
+    }
+
+    #[test]
+    fn test_distortion_snippet_round_trip() {
+        let config = DistortionConfig {
+            effect: DistortionType::Vignette,
+            strength: 0.65,
+            center: [0.25, 0.75],
+            inner_radius: 0.2,
+            outer_radius: 0.9,
+            edge_fill: EdgeFill::Mirror,
+            filter: SamplingFilter::Nearest,
+            animate_duration_ms: 300,
+            ..DistortionConfig::default()
+        };
+        let snippet = config.to_snippet();
+        assert!(!snippet.contains('\n'));
+        assert_eq!(DistortionConfig::from_snippet(&snippet), Ok(config));
+
+        let defaults = DistortionConfig::default();
+        assert_eq!(defaults.to_snippet(), "none;strength=0.3;center=0.5,0.5");
+        assert_eq!(
+            DistortionConfig::from_snippet(&defaults.to_snippet()),
+            Ok(defaults)
+        );
+    }
+
+    #[test]
+    fn test_distortion_snippet_clamps() {
+        let config =
+            DistortionConfig::from_snippet("fisheye; strength=4; fov-degrees=400")
+                .unwrap();
+        assert_eq!(config.effect, DistortionType::Fisheye);
+        assert_eq!(config.strength, 1.0);
+        assert_eq!(config.fov_degrees, MAX_FOV_DEGREES);
+    }
+
+    #[test]
+    fn test_distortion_snippet_malformed() {
+        for snippet in [
+            "",
+            "wobble;strength=0.3",
+            "barrel;strength=lots",
+            "barrel;strength",
+            "barrel;center=0.5",
+            "barrel;speed=2",
+            "barrel;edge-fill=wrap",
+            "barrel;strength=NaN",
+        ] {
+            assert!(
+                DistortionConfig::from_snippet(snippet).is_err(),
+                "{snippet:?} should not parse"
+            );
+        }
+    }
 }
@@ -0,0 +1,201 @@
+// Example config generator
+//
+// Builds commented TOML sections straight from the default structs so the
+// output always matches what Rio would use when a key is omitted.
+
+use crate::config::command_overlay::CommandOverlayStyle;
+use crate::config::distortion::DistortionConfig;
+use crate::config::hints::Hints;
+use crate::config::leader::Leader;
+use crate::config::navigation::Navigation;
+use crate::config::sound_effects::SoundEffects;
+use crate::config::vignette::VignetteConfig;
+use serde::Serialize;
+
+/// A subsystem section of the example config.
+pub struct ExampleSection {
+    /// Top-level TOML key, e.g. `sound-effects`.
+    pub key: &'static str,
+    /// Comment lines written above the section.
+    pub description: &'static [&'static str],
+    pub value: toml::Value,
+}
+
+impl ExampleSection {
+    fn new<T: Serialize>(
+        key: &'static str,
+        description: &'static [&'static str],
+        defaults: &T,
+    ) -> Result<Self, toml::ser::Error> {
+        let mut value = toml::Value::try_from(defaults)?;
+        tidy_floats(&mut value);
+        Ok(Self {
+            key,
+            description,
+            value,
+        })
+    }
+
+    /// Render the section as commented TOML.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        let mut out = String::new();
+        for line in self.description {
+            if line.is_empty() {
+                out.push_str("#\n");
+            } else {
+                out.push_str("# ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+
+        let mut table = toml::Table::new();
+        table.insert(self.key.to_string(), self.value.clone());
+        out.push_str(&toml::to_string(&table)?);
+        Ok(out)
+    }
+}
+
+/// Config values are `f32`, which serialize through `f64` as e.g.
+/// `0.699999988079071`. Rewrite them with the shortest `f32` form so the
+/// example reads like hand-written config and still parses to the same bits.
+fn tidy_floats(value: &mut toml::Value) {
+    match value {
+        toml::Value::Float(f) => {
+            if let Ok(short) = (*f as f32).to_string().parse::<f64>() {
+                *f = short;
+            }
+        }
+        toml::Value::Array(items) => items.iter_mut().for_each(tidy_floats),
+        toml::Value::Table(table) => table.values_mut().for_each(tidy_floats),
+        _ => {}
+    }
+}
+
+/// Example sections for each subsystem, built from their defaults.
+pub fn example_sections() -> Result<Vec<ExampleSection>, toml::ser::Error> {
+    Ok(vec![
+        ExampleSection::new(
+            "sound-effects",
+            &[
+                "Sound effects",
+                "",
+                "Map events (bell, window-create, key-letter, ...) to a sound file",
+                "or a list of files. Paths may start with `~` or be relative to",
+                "the config directory.",
+            ],
+            &SoundEffects::default(),
+        )?,
+        ExampleSection::new(
+            "distortion",
+            &[
+                "Distortion",
+                "",
+                "Post-processing distortion applied after filters.",
+                "effect can be \"none\", \"barrel\", \"perspective\", \"fisheye\",",
+                "\"chromatic-aberration\" or \"vignette\".",
+                "filter can be \"linear\" or \"nearest\".",
+            ],
+            &DistortionConfig::default(),
+        )?,
+        ExampleSection::new(
+            "vignette",
+            &[
+                "Vignette",
+                "",
+                "Darkens the frame edges after distortion. 0.0 intensity disables it.",
+            ],
+            &VignetteConfig::default(),
+        )?,
+        ExampleSection::new(
+            "command-overlay",
+            &[
+                "Command overlays",
+                "",
+                "Each entry with a command is a floating overlay running it. The",
+                "first entry without a command styles overlays opened from the",
+                "leader menu.",
+            ],
+            &[CommandOverlayStyle::default()],
+        )?,
+        ExampleSection::new(
+            "navigation",
+            &[
+                "Navigation",
+                "",
+                "mode can be \"Plain\", \"Bookmark\", \"TopTab\", \"BottomTab\", \"LeftTab\",",
+                "\"RightTab\" or \"NativeTab\" (MacOS only).",
+            ],
+            &Navigation::default(),
+        )?,
+        ExampleSection::new(
+            "leader",
+            &[
+                "Leader menu",
+                "",
+                "Entries in items override the built-in items with the same key.",
+                "timeout-ms closes the menu after that long without a key press,",
+                "0 keeps it open.",
+            ],
+            &Leader::default(),
+        )?,
+        ExampleSection::new(
+            "hints",
+            &["Hints", "", "Setting rules replaces the built-in rules."],
+            &Hints::default(),
+        )?,
+    ])
+}
+
+/// Full example config, one commented section per subsystem.
+pub fn generate_example_config() -> Result<String, toml::ser::Error> {
+    let sections = example_sections()?;
+    let mut out = String::new();
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        out.push_str(&section.to_toml()?);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn parse_generated() -> Config {
+        let content = generate_example_config().unwrap();
+        toml::from_str::<Config>(&content).unwrap()
+    }
+
+    #[test]
+    fn test_example_config_parses() {
+        let content = generate_example_config().unwrap();
+        assert!(toml::from_str::<Config>(&content).is_ok());
+        for section in example_sections().unwrap() {
+            assert!(content.contains(&format!("[{}]", section.key)));
+        }
+    }
+
+    #[test]
+    fn test_example_sound_effects_round_trip() {
+        let config = parse_generated();
+        assert_eq!(config.sound_effects, SoundEffects::default());
+    }
+
+    #[test]
+    fn test_example_distortion_round_trip() {
+        let config = parse_generated();
+        assert_eq!(config.distortion, DistortionConfig::default());
+    }
+
+    #[test]
+    fn test_example_floats_are_short() {
+        let section =
+            ExampleSection::new("sound-effects", &[], &SoundEffects::default()).unwrap();
+        let content = section.to_toml().unwrap();
+        assert!(content.contains("volume = 0.7\n"));
+    }
+}
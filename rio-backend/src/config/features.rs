@@ -0,0 +1,102 @@
+use crate::config::defaults::default_bool_true;
+use serde::{Deserialize, Serialize};
+
+/// A subsystem that can be switched off from `[features]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Sound effects, see `[sound-effects]`
+    Sound,
+    /// Post-processing: filters, distortion and vignette
+    Effects,
+    /// Hint mode, see `[hints]`
+    Hints,
+    /// Leader menu, see `[leader]`
+    Leader,
+    /// Window auto alignment, see `window.auto-align`
+    AutoAlign,
+}
+
+/// Global switches for whole subsystems, the `[features]` TOML section.
+/// Every feature is on by default; turning one off wins over whatever its
+/// own section enables, which makes it quick to strip Rio down while
+/// debugging.
+///
+/// ```toml
+/// [features]
+/// sound = false
+/// effects = false
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Features {
+    #[serde(default = "default_bool_true")]
+    pub sound: bool,
+    #[serde(default = "default_bool_true")]
+    pub effects: bool,
+    #[serde(default = "default_bool_true")]
+    pub hints: bool,
+    #[serde(default = "default_bool_true")]
+    pub leader: bool,
+    #[serde(default = "default_bool_true")]
+    pub auto_align: bool,
+}
+
+impl Features {
+    /// Global switch for `feature`.
+    #[inline]
+    pub fn allows(&self, feature: Feature) -> bool {
+        match feature {
+            Feature::Sound => self.sound,
+            Feature::Effects => self.effects,
+            Feature::Hints => self.hints,
+            Feature::Leader => self.leader,
+            Feature::AutoAlign => self.auto_align,
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            sound: true,
+            effects: true,
+            hints: true,
+            leader: true,
+            auto_align: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_features_default_all_on() {
+        let features = Features::default();
+        for feature in [
+            Feature::Sound,
+            Feature::Effects,
+            Feature::Hints,
+            Feature::Leader,
+            Feature::AutoAlign,
+        ] {
+            assert!(features.allows(feature));
+        }
+        assert_eq!(toml::from_str::<Features>("").unwrap(), features);
+    }
+
+    #[test]
+    fn test_features_toml() {
+        let features: Features = toml::from_str(
+            r#"
+            sound = false
+            auto-align = false
+        "#,
+        )
+        .unwrap();
+        assert!(!features.allows(Feature::Sound));
+        assert!(!features.allows(Feature::AutoAlign));
+        assert!(features.allows(Feature::Effects));
+    }
+}
@@ -1,27 +1,116 @@
+use crate::config::colors::{deserialize_to_arr_opt, ColorArray};
+use crate::config::leader::expand_with;
 use serde::{Deserialize, Serialize};
 
 /// Default alphabet for hint labels
 pub const DEFAULT_HINTS_ALPHABET: &str = "jfkdls;ahgurieowpq";
 
+/// Default cap on scrollback lines scanned by `scrollback` hints
+pub const DEFAULT_HINTS_SCROLLBACK_LINES: usize = 5000;
+
 /// Default URL regex pattern (same as Alacritty)
 pub const DEFAULT_URL_REGEX: &str = "(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file:|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>\"\\s{-}\\^⟨⟩`\\\\]+";
 
 /// Hints configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Hints {
+    /// Whether hint mode can be started, see also `features.hints`
+    #[serde(default = "default_bool_true")]
+    pub enabled: bool,
+
     /// Characters used for hint labels
     #[serde(default = "default_hints_alphabet")]
     pub alphabet: String,
 
+    /// How labels are built from the alphabet
+    #[serde(default, rename = "label-strategy")]
+    pub label_strategy: HintLabelStrategy,
+
+    /// Most lines, counted up from the bottom of the buffer, that
+    /// `scrollback` hints scan
+    #[serde(
+        default = "default_hints_scrollback_lines",
+        rename = "scrollback-lines"
+    )]
+    pub scrollback_lines: usize,
+
+    /// How labels are drawn
+    #[serde(default, rename = "label-style")]
+    pub label_style: HintLabelStyle,
+
+    /// Above this many matches typed characters filter the matches by
+    /// their text instead of picking a label, labels come back once few
+    /// enough are left. `None` always shows labels
+    #[serde(
+        default,
+        rename = "filter-threshold",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub filter_threshold: Option<usize>,
+
     /// List of hint rules
     #[serde(default = "default_hints_enabled")]
     pub rules: Vec<Hint>,
 }
 
+/// How hint labels are generated from the alphabet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HintLabelStrategy {
+    /// Every label has the same length, the shortest that fits all matches
+    #[default]
+    Fixed,
+    /// Prefix-free labels of varying length, earlier matches get the
+    /// shorter ones
+    Variable,
+}
+
+/// Styling of hint labels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HintLabelStyle {
+    /// Draw labels with a bold font
+    #[serde(default = "default_bool_true")]
+    pub bold: bool,
+
+    /// Draw a rounded background behind each label instead of
+    /// coloring its cells
+    #[serde(default = "default_bool_false")]
+    pub pill: bool,
+
+    /// Pill color, falls back to `colors.hint-background`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_to_arr_opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pill_color: Option<ColorArray>,
+
+    /// Pill corner radius
+    #[serde(default = "default_pill_radius")]
+    pub pill_radius: f32,
+}
+
+impl Default for HintLabelStyle {
+    fn default() -> Self {
+        Self {
+            bold: true,
+            pill: false,
+            pill_color: None,
+            pill_radius: default_pill_radius(),
+        }
+    }
+}
+
 impl Default for Hints {
     fn default() -> Self {
         Self {
+            enabled: true,
             alphabet: default_hints_alphabet(),
+            label_strategy: HintLabelStrategy::default(),
+            scrollback_lines: default_hints_scrollback_lines(),
+            label_style: HintLabelStyle::default(),
+            filter_threshold: None,
             rules: default_hints_enabled(),
         }
     }
@@ -54,19 +143,246 @@ pub struct Hint {
     #[serde(default)]
     pub mouse: HintMouse,
 
+    /// Whether to match the scrollback too, not only the visible lines
+    #[serde(default = "default_bool_false")]
+    pub scrollback: bool,
+
+    /// Match the regex regardless of case
+    #[serde(default = "default_bool_false", rename = "case-insensitive")]
+    pub case_insensitive: bool,
+
+    /// Match regardless of case unless the regex contains an uppercase letter
+    #[serde(default = "default_bool_false", rename = "smart-case")]
+    pub smart_case: bool,
+
+    /// Text the hint acts on, built from the regex capture groups, e.g.
+    /// `ssh ${1}@${2}`. `${0}` is the full match. Replaces the default of
+    /// the first group (or the full match) and skips post-processing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Built-in pattern used instead of `regex`, e.g. `phone-number`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builtin: Option<BuiltinHintType>,
+
     /// Keyboard binding to activate hint mode
     #[serde(default)]
     pub binding: Option<HintBinding>,
 }
 
+impl Hint {
+    /// Whether `pattern` should match regardless of case
+    pub fn ignores_case(&self, pattern: &str) -> bool {
+        self.case_insensitive || (self.smart_case && !has_uppercase_literal(pattern))
+    }
+
+    /// Pattern to match: the `builtin` one when set, otherwise `regex`
+    pub fn pattern(&self) -> Option<&str> {
+        match self.builtin {
+            Some(builtin) => Some(builtin.pattern()),
+            None => self.regex.as_deref(),
+        }
+    }
+
+    /// Whether matches get trailing delimiters and unbalanced brackets
+    /// trimmed. Built-in patterns that can't end on a delimiter skip it.
+    pub fn trims_delimiters(&self) -> bool {
+        self.post_processing
+            && self
+                .builtin
+                .is_none_or(|builtin| builtin.trims_delimiters())
+    }
+
+    /// Compile the pattern with the case flags applied, `None` without a
+    /// regex or built-in pattern
+    pub fn build_regex(&self) -> Option<Result<regex::Regex, regex::Error>> {
+        let pattern = self.pattern()?;
+        Some(
+            regex::RegexBuilder::new(pattern)
+                .case_insensitive(self.ignores_case(pattern))
+                .build(),
+        )
+    }
+
+    /// Capture groups `template` refers to, in order of appearance
+    pub fn template_groups(&self) -> Vec<usize> {
+        let mut groups = Vec::new();
+        if let Some(template) = self.template.as_deref() {
+            expand_with(template, |name| {
+                groups.extend(group_index(name));
+                None
+            });
+        }
+        groups
+    }
+
+    /// `template` expanded with `captures`. `None` without a template or
+    /// when it refers to a group the regex doesn't have; groups that took
+    /// no part in the match expand to an empty string.
+    pub fn expand_template(&self, captures: &regex::Captures) -> Option<String> {
+        let template = self.template.as_deref()?;
+        if self
+            .template_groups()
+            .iter()
+            .any(|group| *group >= captures.len())
+        {
+            return None;
+        }
+        Some(expand_with(template, |name| {
+            let group = group_index(name)?;
+            Some(captures.get(group).map_or("", |m| m.as_str()))
+        }))
+    }
+
+    /// Warn when `template` refers to capture groups the regex doesn't
+    /// have, such a hint falls back to the default text, or when
+    /// `pipe-to` has no program to run.
+    pub fn validate(&self) -> Option<String> {
+        if let HintAction::PipeToCommand(command) = &self.action {
+            if command.trim().is_empty() {
+                return Some("hint pipe-to command is empty".to_string());
+            }
+        }
+
+        let template = self.template.as_deref()?;
+        let Some(Ok(regex)) = self.build_regex() else {
+            return Some(format!(
+                "hint template {template:?} needs a valid regex to take groups from"
+            ));
+        };
+        let groups = regex.captures_len() - 1;
+        let missing = self
+            .template_groups()
+            .into_iter()
+            .find(|group| *group > groups)?;
+        Some(format!(
+            "hint template {template:?} refers to group {missing}, but the regex only has {groups} groups"
+        ))
+    }
+}
+
+impl Hints {
+    /// Warnings for every rule that `Hint::validate` rejects
+    pub fn validate(&self) -> Vec<String> {
+        self.rules.iter().filter_map(Hint::validate).collect()
+    }
+}
+
+/// Well-known patterns a hint can match without writing a regex,
+/// `builtin = "phone-number"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinHintType {
+    /// Same pattern as the default URL hint
+    Url,
+    /// Abbreviated or full git commit hash
+    GitHash,
+    /// IPv4 address, with an optional port
+    IpAddress,
+    /// Absolute, home (`~/`) or explicitly relative (`./`, `../`) path
+    FilePath,
+    /// Phone number with an optional country code, e.g. `+1 (555) 123-4567`
+    PhoneNumber,
+    /// Email address
+    Email,
+}
+
+impl BuiltinHintType {
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            BuiltinHintType::Url => DEFAULT_URL_REGEX,
+            BuiltinHintType::GitHash => r"\b[0-9a-f]{7,40}\b",
+            BuiltinHintType::IpAddress => {
+                r"\b(?:(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\b(?::[0-9]{1,5}\b)?"
+            }
+            BuiltinHintType::FilePath => r"(?:~|\.{1,2})?(?:/[\w.@+-]+)+/?",
+            BuiltinHintType::PhoneNumber => {
+                r"(?:\+[0-9]{1,3}[-. ]?)?(?:\([0-9]{3}\)|\b[0-9]{3})[-. ]?[0-9]{3}[-. ]?[0-9]{4}\b"
+            }
+            BuiltinHintType::Email => r"[\w.+-]+@[\w-]+(?:\.[\w-]+)*\.[A-Za-z]{2,}\b",
+        }
+    }
+
+    /// Whether matches can run into surrounding punctuation, like a URL
+    /// or path at the end of a sentence. The other patterns can't end on
+    /// a delimiter, so trimming would only cut valid text.
+    pub fn trims_delimiters(&self) -> bool {
+        matches!(self, BuiltinHintType::Url | BuiltinHintType::FilePath)
+    }
+}
+
+/// Capture group index of a template variable like `${2}`
+fn group_index(name: &str) -> Option<usize> {
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    name.parse().ok()
+}
+
+/// Whether `pattern` contains an uppercase letter outside of escapes like
+/// `\S` or `\W`, which are classes rather than letters to match.
+fn has_uppercase_literal(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            chars.next();
+        } else if c.is_uppercase() {
+            return true;
+        }
+    }
+    false
+}
+
 /// Actions that can be performed with hints
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum HintAction {
     /// Built-in action
     Action { action: HintInternalAction },
+    /// Built-in actions executed in order against the same match
+    Actions {
+        #[serde(deserialize_with = "deserialize_non_empty_actions")]
+        actions: Vec<HintInternalAction>,
+    },
     /// Custom command
     Command { command: HintCommand },
+    /// Program spawned with the match text as its only argument,
+    /// `pipe-to = "xdg-open"`
+    #[serde(rename = "pipe-to")]
+    PipeToCommand(String),
+}
+
+impl HintAction {
+    /// Built-in actions to run, in order. Empty for custom commands.
+    pub fn internal_actions(&self) -> &[HintInternalAction] {
+        match self {
+            HintAction::Action { action } => std::slice::from_ref(action),
+            HintAction::Actions { actions } => actions,
+            HintAction::Command { .. } | HintAction::PipeToCommand(_) => &[],
+        }
+    }
+
+    /// Whether any built-in action launches the system opener.
+    pub fn opens(&self) -> bool {
+        self.internal_actions()
+            .iter()
+            .any(HintInternalAction::opens)
+    }
+}
+
+fn deserialize_non_empty_actions<'de, D>(
+    deserializer: D,
+) -> Result<Vec<HintInternalAction>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let actions = Vec::<HintInternalAction>::deserialize(deserializer)?;
+    if actions.is_empty() {
+        return Err(serde::de::Error::custom(
+            "hint actions must contain at least one action",
+        ));
+    }
+    Ok(actions)
 }
 
 /// Built-in hint actions
@@ -81,6 +397,34 @@ pub enum HintInternalAction {
     Select,
     /// Move vi mode cursor to hint
     MoveViModeCursor,
+    /// Open the hint text with the system opener
+    Open,
+    /// Copy the hint text, then open it with the system opener
+    CopyAndOpen,
+}
+
+impl HintInternalAction {
+    /// Primitive actions this action runs, in order.
+    pub fn steps(&self) -> &'static [HintInternalAction] {
+        match self {
+            HintInternalAction::Copy => &[HintInternalAction::Copy],
+            HintInternalAction::Paste => &[HintInternalAction::Paste],
+            HintInternalAction::Select => &[HintInternalAction::Select],
+            HintInternalAction::MoveViModeCursor => {
+                &[HintInternalAction::MoveViModeCursor]
+            }
+            HintInternalAction::Open => &[HintInternalAction::Open],
+            HintInternalAction::CopyAndOpen => {
+                &[HintInternalAction::Copy, HintInternalAction::Open]
+            }
+        }
+    }
+
+    /// Whether running this action launches the system opener.
+    #[inline]
+    pub fn opens(&self) -> bool {
+        self.steps().contains(&HintInternalAction::Open)
+    }
 }
 
 /// Custom command configuration
@@ -144,6 +488,14 @@ fn default_hints_alphabet() -> String {
     DEFAULT_HINTS_ALPHABET.to_string()
 }
 
+fn default_hints_scrollback_lines() -> usize {
+    DEFAULT_HINTS_SCROLLBACK_LINES
+}
+
+fn default_pill_radius() -> f32 {
+    4.0
+}
+
 fn default_hints_enabled() -> Vec<Hint> {
     vec![Hint {
         regex: Some(DEFAULT_URL_REGEX.to_string()),
@@ -154,6 +506,11 @@ fn default_hints_enabled() -> Vec<Hint> {
             command: default_url_command(),
         },
         mouse: HintMouse::default(),
+        scrollback: false,
+        case_insensitive: false,
+        smart_case: false,
+        template: None,
+        builtin: None,
         binding: Some(HintBinding {
             key: "O".to_string(),
             mods: vec!["Control".to_string(), "Shift".to_string()],
@@ -162,7 +519,8 @@ fn default_hints_enabled() -> Vec<Hint> {
     }]
 }
 
-fn default_url_command() -> HintCommand {
+/// Command used to open URLs with the system default handler
+pub fn default_url_command() -> HintCommand {
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     return HintCommand::Simple("xdg-open".to_string());
 
@@ -212,6 +570,11 @@ mod tests {
                 action: HintInternalAction::Copy,
             },
             mouse: HintMouse::default(),
+            scrollback: false,
+            case_insensitive: false,
+            smart_case: false,
+            template: None,
+            builtin: None,
             binding: None,
         };
 
@@ -244,6 +607,7 @@ mods = ["Control"]
 
         let config: Config = toml::from_str(config_toml).unwrap();
         assert_eq!(config.hints.alphabet, "abcdef");
+        assert_eq!(config.hints.label_strategy, HintLabelStrategy::Fixed);
         assert_eq!(config.hints.rules.len(), 1);
 
         let hint = &config.hints.rules[0];
@@ -252,4 +616,426 @@ mods = ["Control"]
         assert!(hint.post_processing);
         assert!(!hint.persist);
     }
+
+    #[test]
+    fn test_hint_action_chain() {
+        let hint_toml = r#"
+regex = "https?://\\S+"
+
+[actions]
+actions = ["Copy", "Open"]
+"#;
+
+        let hint: Hint = toml::from_str(hint_toml).unwrap();
+        assert_eq!(
+            hint.action.internal_actions(),
+            [HintInternalAction::Copy, HintInternalAction::Open]
+        );
+    }
+
+    #[test]
+    fn test_hint_single_action_unchanged() {
+        let hint_toml = r#"
+regex = "test"
+
+[action]
+action = "Copy"
+"#;
+
+        let hint: Hint = toml::from_str(hint_toml).unwrap();
+        assert_eq!(
+            hint.action,
+            HintAction::Action {
+                action: HintInternalAction::Copy
+            }
+        );
+        assert_eq!(hint.action.internal_actions(), [HintInternalAction::Copy]);
+    }
+
+    #[test]
+    fn test_hint_action_chain_rejects_empty() {
+        let hint_toml = r#"
+regex = "test"
+
+[actions]
+actions = []
+"#;
+
+        assert!(toml::from_str::<Hint>(hint_toml).is_err());
+    }
+
+    #[test]
+    fn test_hint_command_has_no_internal_actions() {
+        let action = HintAction::Command {
+            command: HintCommand::Simple("xdg-open".to_string()),
+        };
+        assert!(action.internal_actions().is_empty());
+    }
+
+    #[test]
+    fn test_hint_pipe_to_deserialization() {
+        let hint_toml = r#"
+regex = "https?://\\S+"
+pipe-to = "xdg-open"
+"#;
+
+        let hint: Hint = toml::from_str(hint_toml).unwrap();
+        assert_eq!(
+            hint.action,
+            HintAction::PipeToCommand("xdg-open".to_string())
+        );
+        assert!(hint.action.internal_actions().is_empty());
+        assert_eq!(hint.validate(), None);
+    }
+
+    #[test]
+    fn test_hint_pipe_to_empty_command_is_rejected() {
+        let hint_toml = r#"
+regex = "test"
+pipe-to = "  "
+"#;
+
+        let hint: Hint = toml::from_str(hint_toml).unwrap();
+        assert!(hint.validate().unwrap().contains("pipe-to"));
+
+        let hints = Hints {
+            rules: vec![hint],
+            ..Hints::default()
+        };
+        assert_eq!(hints.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_copy_and_open_steps() {
+        assert_eq!(
+            HintInternalAction::CopyAndOpen.steps(),
+            &[HintInternalAction::Copy, HintInternalAction::Open]
+        );
+        assert_eq!(
+            HintInternalAction::Copy.steps(),
+            &[HintInternalAction::Copy]
+        );
+        assert!(HintInternalAction::CopyAndOpen.opens());
+        assert!(HintInternalAction::Open.opens());
+        assert!(!HintInternalAction::Copy.opens());
+    }
+
+    #[test]
+    fn test_copy_and_open_deserialization() {
+        let hint_toml = r#"
+regex = "https?://\\S+"
+
+[action]
+action = "CopyAndOpen"
+"#;
+
+        let hint: Hint = toml::from_str(hint_toml).unwrap();
+        assert_eq!(
+            hint.action.internal_actions(),
+            [HintInternalAction::CopyAndOpen]
+        );
+        assert!(hint.action.opens());
+    }
+
+    #[test]
+    fn test_hints_label_strategy() {
+        let hints: Hints = toml::from_str(r#"label-strategy = "variable""#).unwrap();
+        assert_eq!(hints.label_strategy, HintLabelStrategy::Variable);
+
+        let hints: Hints = toml::from_str(r#"label-strategy = "fixed""#).unwrap();
+        assert_eq!(hints.label_strategy, HintLabelStrategy::Fixed);
+
+        assert!(toml::from_str::<Hints>(r#"label-strategy = "huffman""#).is_err());
+    }
+
+    #[test]
+    fn test_hints_scrollback() {
+        let hints: Hints = toml::from_str(
+            r#"
+scrollback-lines = 200
+
+[[rules]]
+regex = "[a-f0-9]{40}"
+scrollback = true
+
+[rules.action]
+action = "Copy"
+"#,
+        )
+        .unwrap();
+        assert_eq!(hints.scrollback_lines, 200);
+        assert!(hints.rules[0].scrollback);
+
+        let hints = Hints::default();
+        assert_eq!(hints.scrollback_lines, DEFAULT_HINTS_SCROLLBACK_LINES);
+        assert!(!hints.rules[0].scrollback);
+    }
+
+    #[test]
+    fn test_hints_label_style() {
+        let hints: Hints = toml::from_str(
+            r##"
+[label-style]
+bold = false
+pill = true
+pill-color = "#ff0000"
+pill-radius = 6.0
+"##,
+        )
+        .unwrap();
+        assert!(!hints.label_style.bold);
+        assert!(hints.label_style.pill);
+        assert_eq!(hints.label_style.pill_color, Some([1.0, 0.0, 0.0, 1.0]));
+        assert_eq!(hints.label_style.pill_radius, 6.0);
+
+        let style = Hints::default().label_style;
+        assert!(style.bold);
+        assert!(!style.pill);
+        assert_eq!(style.pill_color, None);
+        assert_eq!(style.pill_radius, 4.0);
+    }
+
+    fn regex_hint(pattern: &str) -> Hint {
+        let mut hint = default_hints_enabled().remove(0);
+        hint.regex = Some(pattern.to_string());
+        hint
+    }
+
+    #[test]
+    fn test_hint_regex_is_case_sensitive_by_default() {
+        let regex = regex_hint("error").build_regex().unwrap().unwrap();
+        assert!(regex.is_match("error: failed"));
+        assert!(!regex.is_match("ERROR: failed"));
+        assert!(!regex.is_match("Error: failed"));
+    }
+
+    #[test]
+    fn test_hint_regex_case_insensitive() {
+        let hint = Hint {
+            case_insensitive: true,
+            ..regex_hint("error")
+        };
+        let regex = hint.build_regex().unwrap().unwrap();
+        assert!(regex.is_match("ERROR: failed"));
+        assert!(regex.is_match("Error: failed"));
+    }
+
+    #[test]
+    fn test_hint_regex_smart_case() {
+        let lowercase = Hint {
+            smart_case: true,
+            ..regex_hint(r"error\S*")
+        };
+        let regex = lowercase.build_regex().unwrap().unwrap();
+        assert!(regex.is_match("ERROR: failed"));
+        assert!(regex.is_match("Error: failed"));
+
+        let uppercase = Hint {
+            smart_case: true,
+            ..regex_hint("Error")
+        };
+        let regex = uppercase.build_regex().unwrap().unwrap();
+        assert!(regex.is_match("Error: failed"));
+        assert!(!regex.is_match("ERROR: failed"));
+    }
+
+    #[test]
+    fn test_hint_case_flags_deserialize() {
+        let hint: Hint = toml::from_str(
+            r#"
+regex = "error"
+case-insensitive = true
+smart-case = true
+
+[action]
+action = "Copy"
+"#,
+        )
+        .unwrap();
+        assert!(hint.case_insensitive);
+        assert!(hint.smart_case);
+        assert!(regex_hint("error").build_regex().is_some());
+        assert!(Hint {
+            regex: None,
+            ..regex_hint("error")
+        }
+        .build_regex()
+        .is_none());
+    }
+
+    fn template_hint(pattern: &str, template: &str) -> Hint {
+        Hint {
+            template: Some(template.to_string()),
+            ..regex_hint(pattern)
+        }
+    }
+
+    #[test]
+    fn test_hint_template_expands_groups() {
+        let hint = template_hint(r"(\w+)@([\w.]+):\d+", "ssh ${1}@${2}");
+        let regex = hint.build_regex().unwrap().unwrap();
+        let captures = regex.captures("login: deploy@example.com:22").unwrap();
+        assert_eq!(
+            hint.expand_template(&captures).as_deref(),
+            Some("ssh deploy@example.com")
+        );
+
+        let whole = template_hint(r"(\w+)@([\w.]+)", "[${0}] \\${1} ${host}");
+        let regex = whole.build_regex().unwrap().unwrap();
+        let captures = regex.captures("deploy@example.com").unwrap();
+        assert_eq!(
+            whole.expand_template(&captures).as_deref(),
+            Some("[deploy@example.com] ${1} ${host}")
+        );
+        assert_eq!(regex_hint("error").expand_template(&captures), None);
+    }
+
+    #[test]
+    fn test_hint_template_validates_groups() {
+        assert_eq!(
+            template_hint("(a)(b)", "${2}-${1}").template_groups(),
+            vec![2, 1]
+        );
+        assert!(template_hint("(a)(b)", "${2}-${1}").validate().is_none());
+        assert!(regex_hint("(a)").validate().is_none());
+
+        let missing = template_hint("(a)(b)", "${3}");
+        assert!(missing.validate().unwrap().contains("group 3"));
+        let regex = missing.build_regex().unwrap().unwrap();
+        let captures = regex.captures("ab").unwrap();
+        assert_eq!(missing.expand_template(&captures), None);
+
+        let hints = Hints {
+            rules: vec![missing, template_hint("(a)", "${1}")],
+            ..Hints::default()
+        };
+        assert_eq!(hints.validate().len(), 1);
+    }
+
+    #[test]
+    fn test_hint_template_deserialize() {
+        let hint: Hint = toml::from_str(
+            r#"
+regex = "(\\w+)@(\\w+)"
+template = "ssh ${1}@${2}"
+
+[action]
+action = "Copy"
+"#,
+        )
+        .unwrap();
+        assert_eq!(hint.template.as_deref(), Some("ssh ${1}@${2}"));
+        assert!(hint.validate().is_none());
+    }
+
+    /// Matches of `builtin` in `text`, in order
+    fn builtin_matches(builtin: BuiltinHintType, text: &str) -> Vec<String> {
+        let regex = regex::Regex::new(builtin.pattern()).unwrap();
+        regex
+            .find_iter(text)
+            .map(|m| m.as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_builtin_url() {
+        let url = BuiltinHintType::Url;
+        assert_eq!(
+            builtin_matches(url, "see https://rioterm.com/docs."),
+            vec!["https://rioterm.com/docs."]
+        );
+        assert!(builtin_matches(url, "rioterm.com without a scheme").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_git_hash() {
+        let hash = BuiltinHintType::GitHash;
+        assert_eq!(
+            builtin_matches(hash, "commit 3894eaf pushed"),
+            vec!["3894eaf"]
+        );
+        assert_eq!(
+            builtin_matches(hash, "dd70f89a1c2e3d4f5a6b7c8d9e0f1a2b3c4d5e6f"),
+            vec!["dd70f89a1c2e3d4f5a6b7c8d9e0f1a2b3c4d5e6f"]
+        );
+        assert!(builtin_matches(hash, "abc123 deadbeefs 3894eag").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_ip_address() {
+        let ip = BuiltinHintType::IpAddress;
+        assert_eq!(
+            builtin_matches(ip, "ping 192.168.0.1 and 10.0.0.255:8080"),
+            vec!["192.168.0.1", "10.0.0.255:8080"]
+        );
+        assert!(builtin_matches(ip, "256.1.1.1 1.2.3 10.0.0.300").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_file_path() {
+        let path = BuiltinHintType::FilePath;
+        assert_eq!(
+            builtin_matches(path, "edit ~/src/main.rs or ./build.sh in /usr/bin/"),
+            vec!["~/src/main.rs", "./build.sh", "/usr/bin/"]
+        );
+        assert_eq!(
+            builtin_matches(path, "../lib/mod.rs"),
+            vec!["../lib/mod.rs"]
+        );
+        assert!(builtin_matches(path, "no paths in here").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_phone_number() {
+        let phone = BuiltinHintType::PhoneNumber;
+        assert_eq!(
+            builtin_matches(phone, "call +1 (555) 123-4567 or 555.123.4567"),
+            vec!["+1 (555) 123-4567", "555.123.4567"]
+        );
+        assert_eq!(builtin_matches(phone, "5551234567"), vec!["5551234567"]);
+        assert!(builtin_matches(phone, "2024-01-15 123-45 12345678901234").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_email() {
+        let email = BuiltinHintType::Email;
+        assert_eq!(
+            builtin_matches(email, "mail first.last+rio@mail.example.co.uk."),
+            vec!["first.last+rio@mail.example.co.uk"]
+        );
+        assert!(builtin_matches(email, "user@localhost @example.com a@.com").is_empty());
+    }
+
+    #[test]
+    fn test_builtin_hint() {
+        let hint: Hint = toml::from_str(
+            r#"
+builtin = "phone-number"
+
+[action]
+action = "Copy"
+"#,
+        )
+        .unwrap();
+        assert_eq!(hint.builtin, Some(BuiltinHintType::PhoneNumber));
+        assert_eq!(hint.pattern(), Some(BuiltinHintType::PhoneNumber.pattern()));
+        assert!(!hint.trims_delimiters());
+
+        // The built-in pattern wins over `regex`
+        let hint = Hint {
+            regex: Some("unused".to_string()),
+            builtin: Some(BuiltinHintType::FilePath),
+            ..hint
+        };
+        let regex = hint.build_regex().unwrap().unwrap();
+        assert!(regex.is_match("/etc/hosts"));
+        assert!(!regex.is_match("unused"));
+        assert!(hint.trims_delimiters());
+
+        let hint = Hint {
+            post_processing: false,
+            ..hint
+        };
+        assert!(!hint.trims_delimiters());
+    }
 }
@@ -0,0 +1,165 @@
+// Unknown top-level keys
+//
+// serde ignores fields it doesn't know, so a misspelled section like
+// `[navigaton]` silently does nothing. The config is parsed a second time
+// as a plain table and its keys are checked against the known ones.
+
+/// Every top-level key `Config` reads, including aliases.
+pub const TOP_LEVEL_KEYS: &[&str] = &[
+    "adaptive-theme",
+    "adaptive_colors",
+    "bell",
+    "bindings",
+    "colors",
+    "command-overlay",
+    "confirm-before-quit",
+    "cursor",
+    "developer",
+    "distortion",
+    "draw-bold-text-with-light-colors",
+    "editor",
+    "env-vars",
+    "features",
+    "fonts",
+    "hide-cursor-when-typing",
+    "hide-mouse-cursor-when-typing",
+    "hints",
+    "ignore-selection-foreground-color",
+    "keyboard",
+    "leader",
+    "line-height",
+    "navigation",
+    "option-as-alt",
+    "padding-x",
+    "padding-y",
+    "platform",
+    "renderer",
+    "scroll",
+    "shell",
+    "sound-effects",
+    "theme",
+    "title",
+    "use-fork",
+    "vignette",
+    "window",
+    "working-dir",
+];
+
+/// Warnings for the top-level keys of `content` that Rio doesn't know,
+/// with the closest known key as a suggestion. Content that isn't valid
+/// TOML yields none, parsing the config reports that already.
+pub fn unknown_top_level_keys(content: &str) -> Vec<String> {
+    let Ok(table) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    table
+        .iter()
+        .filter(|(key, _)| !TOP_LEVEL_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| {
+            let kind = if value.is_table() || value.is_array() {
+                format!("unknown section [{key}]")
+            } else {
+                format!("unknown key {key:?}")
+            };
+            match nearest(key, TOP_LEVEL_KEYS) {
+                Some(known) => format!("{kind}, did you mean {known}?"),
+                None => format!("{kind}, it is ignored"),
+            }
+        })
+        .collect()
+}
+
+/// Closest candidate to `key`, if any is close enough to be a typo of it.
+fn nearest<'a>(key: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (key.chars().count() / 3).max(2);
+    candidates
+        .iter()
+        .map(|candidate| (levenshtein(key, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Number of single character insertions, deletions or substitutions
+/// turning `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("navigation", "navigation"), 0);
+        assert_eq!(levenshtein("navigaton", "navigation"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "bell"), 4);
+    }
+
+    #[test]
+    fn test_unknown_section_suggests_nearest() {
+        let warnings = unknown_top_level_keys("[navigaton]\nmode = \"TopTab\"\n");
+        assert_eq!(
+            warnings,
+            vec!["unknown section [navigaton], did you mean navigation?"]
+        );
+
+        let warnings = unknown_top_level_keys("line-hieght = 1.2\n");
+        assert_eq!(
+            warnings,
+            vec!["unknown key \"line-hieght\", did you mean line-height?"]
+        );
+
+        let warnings = unknown_top_level_keys("[something-else]\n");
+        assert_eq!(
+            warnings,
+            vec!["unknown section [something-else], it is ignored"]
+        );
+    }
+
+    #[test]
+    fn test_known_keys_yield_no_warnings() {
+        let content = r#"
+            theme = "dracula"
+            padding-x = 10.0
+
+            [navigation]
+            mode = "TopTab"
+
+            [window]
+            opacity = 0.9
+
+            [[command-overlay]]
+            command = "top"
+        "#;
+        assert!(unknown_top_level_keys(content).is_empty());
+        assert!(toml::from_str::<Config>(content).is_ok());
+        assert!(unknown_top_level_keys("not = [valid").is_empty());
+    }
+
+    #[test]
+    fn test_top_level_keys_cover_config() {
+        let content = Config::default().to_string().unwrap();
+        let table = content.parse::<toml::Table>().unwrap();
+        for key in table.keys() {
+            assert!(TOP_LEVEL_KEYS.contains(&key.as_str()), "{key} is missing");
+        }
+    }
+}
@@ -1,5 +1,6 @@
 // Leader key modal menu configuration
 
+use crate::config::colors::{deserialize_to_arr_opt, ColorArray};
 use serde::{Deserialize, Serialize};
 
 /// Leader key configuration (intermediate for deserialization)
@@ -9,6 +10,15 @@ pub struct Leader {
     #[serde(default = "default_leader_key")]
     pub key: String,
 
+    /// Whether the leader key opens the menu, see also `features.leader`
+    #[serde(default = "default_leader_enabled")]
+    pub enabled: bool,
+
+    /// Close the menu after this many milliseconds without a key press,
+    /// 0 keeps it open until dismissed
+    #[serde(default, rename = "timeout-ms")]
+    pub timeout_ms: u32,
+
     /// Menu items from config (will be merged with defaults)
     #[serde(default)]
     items: Vec<LeaderItem>,
@@ -18,6 +28,8 @@ impl Default for Leader {
     fn default() -> Self {
         Self {
             key: default_leader_key(),
+            enabled: default_leader_enabled(),
+            timeout_ms: 0,
             items: Vec::new(),
         }
     }
@@ -25,35 +37,45 @@ impl Default for Leader {
 
 impl Leader {
     /// Get the final list of items, merging config items with defaults.
-    /// Config items override defaults with the same key.
+    /// Config items override defaults with the same key, field by field:
+    /// an item that only sets `label` keeps the default's action. When
+    /// both are submenus their children are merged the same way, so a
+    /// nested default can be overridden by its key path.
     pub fn items(&self) -> Vec<LeaderItem> {
-        let defaults = default_leader_items();
+        let mut result = default_leader_items();
+        merge_items(&mut result, &self.items);
+        result
+    }
+}
 
-        if self.items.is_empty() {
-            return defaults;
-        }
+/// Override or append each of `overrides` in `base`, matching by key and
+/// recursing into submenus present on both sides.
+fn merge_items(base: &mut Vec<LeaderItem>, overrides: &[LeaderItem]) {
+    for config_item in overrides {
+        // Check if this key already exists in defaults
+        let Some(pos) = base.iter().position(|item| item.key == config_item.key) else {
+            // Add new item
+            base.push(config_item.clone());
+            continue;
+        };
 
-        // Start with defaults, then override/add from config
-        let mut result = defaults;
-
-        for config_item in &self.items {
-            // Check if this key already exists in defaults
-            if let Some(pos) = result.iter().position(|item| item.key == config_item.key)
-            {
-                // Override the default
-                result[pos] = config_item.clone();
-            } else {
-                // Add new item
-                result.push(config_item.clone());
+        // Override the default, keeping nested defaults not overridden
+        let mut default = std::mem::take(&mut base[pos]);
+        let mut item = config_item.clone();
+        match (default.submenu.take(), &config_item.submenu) {
+            (Some(mut children), Some(config_children)) => {
+                merge_items(&mut children, config_children);
+                item.submenu = Some(children);
             }
+            (children, _) => default.submenu = children,
         }
-
-        result
+        item.inherit_unset(default);
+        base[pos] = item;
     }
 }
 
 /// Parsed leader key binding
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedLeaderKey {
     /// The key itself (e.g., "space", ";", "a")
     pub key: String,
@@ -71,164 +93,146 @@ impl Leader {
     /// Parse the leader key configuration string (e.g., "ctrl+space", "super+;")
     /// Returns the key and modifier flags
     pub fn parse_key(&self) -> ParsedLeaderKey {
-        let mut result = ParsedLeaderKey {
-            key: String::new(),
-            ctrl: false,
-            alt: false,
-            shift: false,
-            super_key: false,
-        };
+        parse_chord(&self.key)
+    }
 
-        let parts: Vec<&str> = self.key.split('+').collect();
-        for part in parts {
-            match part.trim().to_lowercase().as_str() {
-                "ctrl" | "control" => result.ctrl = true,
-                "alt" | "option" => result.alt = true,
-                "shift" => result.shift = true,
-                "super" | "cmd" | "command" => result.super_key = true,
-                key => result.key = key.to_string(),
-            }
+    /// Warn when the leader chord is one the OS usually grabs before Rio
+    /// sees it (e.g. `super+space` opening Spotlight). Best effort, the
+    /// chord still gets bound.
+    pub fn validate(&self) -> Option<String> {
+        reserved_chord_warning(&self.key, platform_reserved_chords())
+    }
+}
+
+fn parse_chord(chord: &str) -> ParsedLeaderKey {
+    let mut result = ParsedLeaderKey {
+        key: String::new(),
+        ctrl: false,
+        alt: false,
+        shift: false,
+        super_key: false,
+    };
+
+    for part in chord.split('+') {
+        match part.trim().to_lowercase().as_str() {
+            "ctrl" | "control" => result.ctrl = true,
+            "alt" | "option" => result.alt = true,
+            "shift" => result.shift = true,
+            "super" | "cmd" | "command" => result.super_key = true,
+            "esc" => result.key = "escape".to_string(),
+            "return" => result.key = "enter".to_string(),
+            key => result.key = key.to_string(),
         }
+    }
 
-        result
+    result
+}
+
+/// A chord commonly taken by the OS and what takes it
+type ReservedChord = (&'static str, &'static str);
+
+const MACOS_RESERVED_CHORDS: &[ReservedChord] = &[
+    ("super+space", "Spotlight"),
+    ("super+alt+space", "Finder search"),
+    ("ctrl+space", "input source switching"),
+    ("super+tab", "the app switcher"),
+    ("super+shift+3", "screenshots"),
+    ("super+shift+4", "screenshots"),
+    ("super+shift+5", "screenshots"),
+    ("ctrl+up", "Mission Control"),
+    ("ctrl+down", "App Exposé"),
+];
+
+const WINDOWS_RESERVED_CHORDS: &[ReservedChord] = &[
+    ("super+space", "input language switching"),
+    ("super+tab", "Task View"),
+    ("super+d", "Show Desktop"),
+    ("super+e", "File Explorer"),
+    ("super+l", "the lock screen"),
+    ("super+r", "the Run dialog"),
+    ("alt+tab", "the window switcher"),
+    ("alt+space", "the window menu"),
+    ("alt+f4", "closing the window"),
+    ("ctrl+escape", "the Start menu"),
+];
+
+const LINUX_RESERVED_CHORDS: &[ReservedChord] = &[
+    ("super+space", "input source switching"),
+    ("super+tab", "the app switcher"),
+    ("super+l", "the lock screen"),
+    ("alt+tab", "the window switcher"),
+    ("alt+space", "the window menu"),
+    ("alt+f2", "the run dialog"),
+    ("alt+f4", "closing the window"),
+    ("ctrl+alt+t", "opening a terminal"),
+];
+
+fn platform_reserved_chords() -> &'static [ReservedChord] {
+    if cfg!(target_os = "macos") {
+        MACOS_RESERVED_CHORDS
+    } else if cfg!(windows) {
+        WINDOWS_RESERVED_CHORDS
+    } else {
+        LINUX_RESERVED_CHORDS
     }
 }
 
+fn reserved_chord_warning(chord: &str, reserved: &[ReservedChord]) -> Option<String> {
+    let parsed = parse_chord(chord);
+    reserved
+        .iter()
+        .find(|(reserved_chord, _)| parse_chord(reserved_chord) == parsed)
+        .map(|(_, taken_by)| {
+            format!(
+                "leader key \"{chord}\" is usually taken by {taken_by}, \
+                 the leader menu may never open"
+            )
+        })
+}
+
 fn default_leader_key() -> String {
     "super+;".to_string()
 }
 
+fn default_leader_enabled() -> bool {
+    true
+}
+
+fn action_item(key: char, label: &str, action: &str) -> LeaderItem {
+    LeaderItem {
+        key,
+        label: label.to_string(),
+        action: Some(action.to_string()),
+        ..LeaderItem::default()
+    }
+}
+
 fn default_leader_items() -> Vec<LeaderItem> {
     vec![
         // Window/Tab management
-        LeaderItem {
-            key: 'n',
-            label: "New window".to_string(),
-            action: Some("WindowCreateNew".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 't',
-            label: "New tab".to_string(),
-            action: Some("TabCreateNew".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'x',
-            label: "Close".to_string(),
-            action: Some("CloseCurrentSplitOrTab".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: '[',
-            label: "Prev tab".to_string(),
-            action: Some("SelectPrevTab".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: ']',
-            label: "Next tab".to_string(),
-            action: Some("SelectNextTab".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
+        action_item('n', "New window", "WindowCreateNew"),
+        action_item('t', "New tab", "TabCreateNew"),
+        action_item('x', "Close", "CloseCurrentSplitOrTab"),
+        action_item('[', "Prev tab", "SelectPrevTab"),
+        action_item(']', "Next tab", "SelectNextTab"),
         // Split creation
-        LeaderItem {
-            key: 's',
-            label: "Split right".to_string(),
-            action: Some("SplitRight".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'v',
-            label: "Split down".to_string(),
-            action: Some("SplitDown".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
+        action_item('s', "Split right", "SplitRight"),
+        action_item('v', "Split down", "SplitDown"),
         // Pane navigation (vim-style h/j/k/l)
-        LeaderItem {
-            key: 'h',
-            label: "Pane left".to_string(),
-            action: Some("SelectSplitLeft".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'j',
-            label: "Pane down".to_string(),
-            action: Some("SelectSplitDown".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'k',
-            label: "Pane up".to_string(),
-            action: Some("SelectSplitUp".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'l',
-            label: "Pane right".to_string(),
-            action: Some("SelectSplitRight".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'z',
-            label: "Zoom pane".to_string(),
-            action: Some("ToggleZoom".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
+        action_item('h', "Pane left", "SelectSplitLeft"),
+        action_item('j', "Pane down", "SelectSplitDown"),
+        action_item('k', "Pane up", "SelectSplitUp"),
+        action_item('l', "Pane right", "SelectSplitRight"),
+        action_item('z', "Zoom pane", "ToggleZoom"),
         // Other
-        LeaderItem {
-            key: 'y',
-            label: "Copy mode".to_string(),
-            action: Some("ToggleViMode".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: '/',
-            label: "Search".to_string(),
-            action: Some("SearchForward".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
-        LeaderItem {
-            key: 'r',
-            label: "Clear history".to_string(),
-            action: Some("ClearHistory".to_string()),
-            write: None,
-            exec: None,
-            overlay: None,
-        },
+        action_item('y', "Copy mode", "ToggleViMode"),
+        action_item('/', "Search", "SearchForward"),
+        action_item('r', "Clear history", "ClearHistory"),
     ]
 }
 
 /// A single menu item in the leader menu
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct LeaderItem {
     /// Key to press to trigger this item
     pub key: char,
@@ -236,12 +240,19 @@ pub struct LeaderItem {
     /// Display label
     pub label: String,
 
+    /// Longer explanation shown in the menu footer while the item is
+    /// highlighted
+    #[serde(default)]
+    pub description: Option<String>,
+
     /// Built-in Rio action to execute (e.g., "TabCreateNew")
     #[serde(default)]
     pub action: Option<String>,
 
     /// Text to write to PTY (as if user typed it)
-    /// Supports variables: ${SELECTION}, ${WORD}, ${LINE}, ${CWD}, ${FILE}
+    /// Supports variables: ${SELECTION}, ${WORD}, ${LINE}, ${CWD}, ${FILE}.
+    /// Unknown variables are kept as is, `\${...}` is never expanded and
+    /// `$$` writes a single `$`.
     #[serde(default)]
     pub write: Option<String>,
 
@@ -251,10 +262,475 @@ pub struct LeaderItem {
     #[serde(default)]
     pub exec: Option<String>,
 
+    /// Show the output of `exec` in a transient command overlay instead of
+    /// discarding it. The overlay closes a few seconds after the command ends.
+    #[serde(default, rename = "show-output")]
+    pub show_output: bool,
+
     /// Command to run as a live overlay panel (real PTY, full ANSI rendering).
     /// The overlay floats above the terminal content and is click-through.
     /// Toggle: press the key again to hide/show. Auto-dismisses on process exit.
     /// Example: "top", "htop", "git log --oneline -20"
     #[serde(default)]
     pub overlay: Option<String>,
+
+    /// Text color for this item (e.g. red for destructive actions).
+    /// Falls back to the theme colors when not set.
+    #[serde(default, deserialize_with = "deserialize_to_arr_opt")]
+    pub foreground: Option<ColorArray>,
+
+    /// Background color behind this item's row.
+    #[serde(default, deserialize_with = "deserialize_to_arr_opt")]
+    pub background: Option<ColorArray>,
+
+    /// Child items shown as a second level menu when this key is pressed.
+    /// The label is used as the submenu name in the breadcrumb.
+    #[serde(default, alias = "items")]
+    pub submenu: Option<Vec<LeaderItem>>,
+}
+
+impl LeaderItem {
+    #[inline]
+    pub fn is_submenu(&self) -> bool {
+        self.submenu.is_some()
+    }
+
+    /// Whether pressing the key does anything: runs an action, writes,
+    /// executes, opens an overlay or a submenu
+    fn has_behavior(&self) -> bool {
+        self.action.is_some()
+            || self.write.is_some()
+            || self.exec.is_some()
+            || self.overlay.is_some()
+            || self.submenu.is_some()
+    }
+
+    /// Fill the fields this override leaves unset from the `default` it
+    /// replaces. The behavior is taken as a whole, so setting any of
+    /// `action`, `write`, `exec`, `overlay` or `submenu` drops all of the
+    /// default's.
+    fn inherit_unset(&mut self, default: LeaderItem) {
+        if !self.has_behavior() {
+            self.action = default.action;
+            self.write = default.write;
+            self.exec = default.exec;
+            self.show_output = default.show_output;
+            self.overlay = default.overlay;
+            self.submenu = default.submenu;
+        }
+        self.description = self.description.take().or(default.description);
+        self.foreground = self.foreground.or(default.foreground);
+        self.background = self.background.or(default.background);
+    }
+
+    /// `write` with its variables expanded from `ctx`
+    pub fn expand_write(&self, ctx: &WriteContext) -> Option<String> {
+        self.write
+            .as_deref()
+            .map(|write| expand_variables(write, ctx))
+    }
+}
+
+/// Values for the variables of `write`, `exec` and `overlay` strings.
+/// Missing values expand to an empty string.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteContext {
+    /// Selected text
+    pub selection: Option<String>,
+    /// Word under the cursor
+    pub word: Option<String>,
+    /// Line the cursor is on
+    pub line: Option<String>,
+    /// Working directory of the foreground process
+    pub cwd: Option<String>,
+    /// File the selection, or else the word under the cursor, names,
+    /// resolved against `cwd`. Unset when no such file exists
+    pub file: Option<String>,
+}
+
+impl WriteContext {
+    /// Value of the variable `name`, `None` if it is not a known variable
+    fn lookup(&self, name: &str) -> Option<&str> {
+        let value = match name {
+            "SELECTION" => &self.selection,
+            "WORD" => &self.word,
+            "LINE" => &self.line,
+            "CWD" => &self.cwd,
+            "FILE" => &self.file,
+            _ => return None,
+        };
+        Some(value.as_deref().unwrap_or_default())
+    }
+}
+
+/// Expand `${NAME}` variables in `input`.
+///
+/// Unknown or unterminated variables are kept literally, and only the
+/// innermost `${NAME}` of nested braces is expanded, so `${${WORD}}` keeps
+/// the outer braces. A backslash before `$` escapes the variable:
+/// `\${SELECTION}` writes `${SELECTION}`, and `$$` writes a single `$`.
+pub fn expand_variables(input: &str, ctx: &WriteContext) -> String {
+    expand_with(input, |name| ctx.lookup(name))
+}
+
+/// Expand `${NAME}` variables in `input` with the values `lookup` returns,
+/// following the same rules as [`expand_variables`]. Names `lookup`
+/// returns `None` for are kept literally.
+pub fn expand_with<'a>(
+    input: &str,
+    mut lookup: impl FnMut(&str) -> Option<&'a str>,
+) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(index) = rest.find(['$', '\\']) {
+        result.push_str(&rest[..index]);
+        let tail = &rest[index..];
+
+        if let Some(escaped) =
+            tail.strip_prefix("\\$").or_else(|| tail.strip_prefix("$$"))
+        {
+            result.push('$');
+            rest = escaped;
+            continue;
+        }
+
+        if let Some(after) = tail.strip_prefix("${") {
+            if let Some(end) = after.find('}') {
+                if let Some(value) = lookup(&after[..end]) {
+                    result.push_str(value);
+                    rest = &after[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        // Not a variable: keep the character and scan on from the next one
+        result.push_str(&tail[..1]);
+        rest = &tail[1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reserved_chord() {
+        let warning = reserved_chord_warning("cmd+Space", MACOS_RESERVED_CHORDS).unwrap();
+        assert!(warning.contains("Spotlight"));
+        assert!(reserved_chord_warning("alt+esc", &[("alt+escape", "test")]).is_some());
+
+        let leader = Leader {
+            key: "super+tab".to_string(),
+            ..Leader::default()
+        };
+        assert!(leader.validate().is_some());
+    }
+
+    #[test]
+    fn test_validate_safe_chord() {
+        for reserved in [
+            MACOS_RESERVED_CHORDS,
+            WINDOWS_RESERVED_CHORDS,
+            LINUX_RESERVED_CHORDS,
+        ] {
+            assert!(reserved_chord_warning("ctrl+shift+space", reserved).is_none());
+            assert!(reserved_chord_warning("super+;", reserved).is_none());
+        }
+        assert!(Leader::default().validate().is_none());
+    }
+
+    #[test]
+    fn test_leader_timeout_ms() {
+        let leader: Leader = toml::from_str("timeout-ms = 1500").unwrap();
+        assert_eq!(leader.timeout_ms, 1500);
+        assert_eq!(toml::from_str::<Leader>("").unwrap().timeout_ms, 0);
+    }
+
+    #[test]
+    fn test_leader_item_with_custom_colors() {
+        let toml_str = r##"
+            key = "x"
+            label = "Kill"
+            action = "CloseCurrentSplitOrTab"
+            foreground = "#ff0000"
+            background = "#000000"
+        "##;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert_eq!(item.foreground, Some([1.0, 0.0, 0.0, 1.0]));
+        assert_eq!(item.background, Some([0.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_leader_item_without_colors() {
+        let toml_str = r#"
+            key = "n"
+            label = "New"
+            action = "WindowCreateNew"
+        "#;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert_eq!(item.foreground, None);
+        assert_eq!(item.background, None);
+    }
+
+    #[test]
+    fn test_leader_item_with_submenu() {
+        let toml_str = r#"
+            key = "s"
+            label = "Splits"
+
+            [[submenu]]
+            key = "r"
+            label = "Split right"
+            action = "SplitRight"
+
+            [[submenu]]
+            key = "d"
+            label = "Split down"
+            action = "SplitDown"
+        "#;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert!(item.is_submenu());
+        let children = item.submenu.unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].key, 'r');
+        assert_eq!(children[1].action, Some("SplitDown".to_string()));
+    }
+
+    #[test]
+    fn test_leader_item_items_alias() {
+        let toml_str = r#"
+            key = "w"
+            label = "Window"
+
+            [[items]]
+            key = "n"
+            label = "New window"
+            action = "WindowCreateNew"
+        "#;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert_eq!(item.submenu.unwrap()[0].key, 'n');
+    }
+
+    fn submenu_item(key: char, label: &str, children: Vec<LeaderItem>) -> LeaderItem {
+        LeaderItem {
+            key,
+            label: label.to_string(),
+            submenu: Some(children),
+            ..LeaderItem::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_items_overrides_nested_by_key_path() {
+        let mut base = vec![
+            action_item('n', "New window", "WindowCreateNew"),
+            submenu_item(
+                'w',
+                "Window",
+                vec![
+                    action_item('n', "New window", "WindowCreateNew"),
+                    action_item('x', "Close", "CloseCurrentSplitOrTab"),
+                ],
+            ),
+        ];
+        let overrides = vec![submenu_item(
+            'w',
+            "Windows",
+            vec![
+                action_item('x', "Quit", "Quit"),
+                action_item('h', "Pane left", "SelectSplitLeft"),
+            ],
+        )];
+        merge_items(&mut base, &overrides);
+
+        assert_eq!(base.len(), 2);
+        assert_eq!(base[1].label, "Windows");
+        let children = base[1].submenu.as_ref().unwrap();
+        let keys: Vec<char> = children.iter().map(|item| item.key).collect();
+        assert_eq!(keys, vec!['n', 'x', 'h']);
+        assert_eq!(children[0].label, "New window");
+        assert_eq!(children[1].action, Some("Quit".to_string()));
+    }
+
+    #[test]
+    fn test_override_label_keeps_default_action() {
+        let leader: Leader = toml::from_str(
+            r#"
+            [[items]]
+            key = "n"
+            label = "Window"
+        "#,
+        )
+        .unwrap();
+        let items = leader.items();
+        let item = items.iter().find(|item| item.key == 'n').unwrap();
+        assert_eq!(item.label, "Window");
+        assert_eq!(item.action, Some("WindowCreateNew".to_string()));
+    }
+
+    #[test]
+    fn test_override_action_replaces_default_action() {
+        let mut base = vec![LeaderItem {
+            description: Some("Opens a window".to_string()),
+            ..action_item('n', "New window", "WindowCreateNew")
+        }];
+        merge_items(&mut base, &[action_item('n', "New tab", "TabCreateNew")]);
+        assert_eq!(base[0].action, Some("TabCreateNew".to_string()));
+        assert_eq!(base[0].description.as_deref(), Some("Opens a window"));
+
+        // Any other behavior drops the default action as well
+        let overrides = [LeaderItem {
+            key: 'n',
+            label: "Greet".to_string(),
+            write: Some("echo hi\n".to_string()),
+            ..LeaderItem::default()
+        }];
+        merge_items(&mut base, &overrides);
+        assert_eq!(base[0].action, None);
+        assert_eq!(base[0].write.as_deref(), Some("echo hi\n"));
+    }
+
+    #[test]
+    fn test_merge_items_replaces_plain_item_with_submenu() {
+        let mut base = vec![action_item('s', "Split right", "SplitRight")];
+        let overrides = vec![submenu_item(
+            's',
+            "Splits",
+            vec![action_item('d', "Split down", "SplitDown")],
+        )];
+        merge_items(&mut base, &overrides);
+        assert_eq!(base[0].action, None);
+        assert_eq!(base[0].submenu.as_ref().unwrap().len(), 1);
+
+        // A plain override replaces a submenu outright
+        let overrides = vec![action_item('s', "Split right", "SplitRight")];
+        merge_items(&mut base, &overrides);
+        assert!(!base[0].is_submenu());
+    }
+
+    #[test]
+    fn test_leader_item_description() {
+        let toml_str = r#"
+            key = "g"
+            label = "Git status"
+            description = "Show the short git status of the current directory"
+            exec = "git status --short"
+        "#;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            item.description.as_deref(),
+            Some("Show the short git status of the current directory")
+        );
+        assert_eq!(action_item('n', "New", "WindowCreateNew").description, None);
+    }
+
+    #[test]
+    fn test_leader_item_show_output() {
+        let toml_str = r#"
+            key = "g"
+            label = "Git status"
+            exec = "git status --short"
+            show-output = true
+        "#;
+        let item: LeaderItem = toml::from_str(toml_str).unwrap();
+        assert!(item.show_output);
+        assert_eq!(item.exec, Some("git status --short".to_string()));
+
+        let item = action_item('n', "New", "WindowCreateNew");
+        assert!(!item.show_output);
+    }
+
+    fn write_context() -> WriteContext {
+        WriteContext {
+            selection: Some("main.rs".to_string()),
+            word: Some("cargo".to_string()),
+            line: Some("$ cargo build".to_string()),
+            cwd: Some("/home/rio".to_string()),
+            file: Some("/home/rio/main.rs".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_expand_known_variables() {
+        let ctx = write_context();
+        assert_eq!(
+            expand_variables("vim ${SELECTION} # ${WORD} in ${CWD}", &ctx),
+            "vim main.rs # cargo in /home/rio"
+        );
+        assert_eq!(
+            expand_variables("echo '${LINE}'", &ctx),
+            "echo '$ cargo build'"
+        );
+        assert_eq!(
+            expand_variables("git blame ${FILE}", &ctx),
+            "git blame /home/rio/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_expand_missing_values_are_empty() {
+        let ctx = WriteContext::default();
+        assert_eq!(expand_variables("[${SELECTION}][${FILE}]", &ctx), "[][]");
+    }
+
+    #[test]
+    fn test_expand_unknown_variables_stay_literal() {
+        let ctx = write_context();
+        assert_eq!(expand_variables("${UNKNOWN}", &ctx), "${UNKNOWN}");
+        assert_eq!(
+            expand_variables("echo $HOME ${WORD", &ctx),
+            "echo $HOME ${WORD"
+        );
+        assert_eq!(expand_variables("${}", &ctx), "${}");
+    }
+
+    #[test]
+    fn test_expand_nested_braces() {
+        let ctx = write_context();
+        assert_eq!(expand_variables("${${WORD}}", &ctx), "${cargo}");
+        assert_eq!(expand_variables("${A${WORD}}", &ctx), "${Acargo}");
+    }
+
+    #[test]
+    fn test_expand_escaped_variable() {
+        let ctx = write_context();
+        assert_eq!(expand_variables("\\${SELECTION}", &ctx), "${SELECTION}");
+        assert_eq!(
+            expand_variables("\\${SELECTION} ${SELECTION}", &ctx),
+            "${SELECTION} main.rs"
+        );
+        // Other backslashes are untouched
+        assert_eq!(expand_variables("a\\nb \\\\", &ctx), "a\\nb \\\\");
+    }
+
+    #[test]
+    fn test_expand_double_dollar() {
+        let ctx = write_context();
+        assert_eq!(expand_variables("$${WORD} ${WORD}", &ctx), "${WORD} cargo");
+        assert_eq!(expand_variables("cost: $$5", &ctx), "cost: $5");
+        assert_eq!(expand_variables("$$$${CWD}", &ctx), "$${CWD}");
+        // A lone `$` stays as is
+        assert_eq!(expand_variables("$ ${CWD}$", &ctx), "$ /home/rio$");
+    }
+
+    #[test]
+    fn test_expand_write() {
+        let ctx = write_context();
+        let item = LeaderItem {
+            key: 'e',
+            label: "Edit".to_string(),
+            write: Some("$EDITOR ${SELECTION}\n".to_string()),
+            ..LeaderItem::default()
+        };
+        assert_eq!(
+            item.expand_write(&ctx),
+            Some("$EDITOR main.rs\n".to_string())
+        );
+        assert_eq!(LeaderItem::default().expand_write(&ctx), None);
+    }
 }
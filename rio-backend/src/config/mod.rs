@@ -4,23 +4,30 @@ pub mod colors;
 pub mod command_overlay;
 pub mod defaults;
 pub mod distortion;
+pub mod example;
+pub mod features;
 pub mod hints;
 pub mod keyboard;
+pub mod keys;
 pub mod leader;
 pub mod navigation;
 pub mod platform;
+pub mod ranges;
 pub mod renderer;
 pub mod sound_effects;
 pub mod theme;
 pub mod title;
+pub mod vignette;
+pub mod watcher;
 pub mod window;
 
 use crate::ansi::CursorShape;
 use crate::config::bell::Bell;
 use crate::config::bindings::Bindings;
-use crate::config::command_overlay::CommandOverlayStyle;
+use crate::config::command_overlay::{deserialize_command_overlays, CommandOverlayStyle};
 use crate::config::defaults::*;
 use crate::config::distortion::DistortionConfig;
+use crate::config::features::{Feature, Features};
 use crate::config::hints::Hints;
 use crate::config::keyboard::Keyboard;
 use crate::config::leader::Leader;
@@ -29,6 +36,7 @@ use crate::config::platform::{Platform, PlatformConfig};
 use crate::config::renderer::Renderer;
 use crate::config::sound_effects::SoundEffects;
 use crate::config::title::Title;
+use crate::config::vignette::VignetteConfig;
 use crate::config::window::Window;
 use colors::Colors;
 use serde::{Deserialize, Serialize};
@@ -163,12 +171,20 @@ pub struct Config {
     pub bell: Bell,
     #[serde(default = "Leader::default")]
     pub leader: Leader,
-    #[serde(default = "CommandOverlayStyle::default", rename = "command-overlay")]
-    pub command_overlay: CommandOverlayStyle,
+    #[serde(
+        default = "Vec::new",
+        rename = "command-overlay",
+        deserialize_with = "deserialize_command_overlays"
+    )]
+    pub command_overlay: Vec<CommandOverlayStyle>,
     #[serde(default)]
     pub distortion: DistortionConfig,
+    #[serde(default)]
+    pub vignette: VignetteConfig,
     #[serde(default = "SoundEffects::default", rename = "sound-effects")]
     pub sound_effects: SoundEffects,
+    #[serde(default)]
+    pub features: Features,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -462,10 +478,36 @@ impl Config {
         }
     }
 
+    /// Whether `feature` is on. `[features]` has to allow it and so does
+    /// the subsystem's own section; a global switch that is off wins.
+    pub fn feature_enabled(&self, feature: Feature) -> bool {
+        self.features.allows(feature)
+            && match feature {
+                Feature::Sound => self.sound_effects.enabled,
+                // Each effect is off at its neutral value, there is no
+                // section wide switch
+                Feature::Effects => true,
+                Feature::Hints => self.hints.enabled,
+                Feature::Leader => self.leader.enabled,
+                Feature::AutoAlign => self.window.auto_align,
+            }
+    }
+
     pub fn to_string(&self) -> Result<String, toml::ser::Error> {
         toml::to_string(self)
     }
 
+    /// Checks shared by `load` and `try_load` once `content` is decoded:
+    /// clamps out of range values and returns the warnings about unknown
+    /// keys and invalid leader or hints entries.
+    fn finalize(decoded: &mut Config, content: &str) -> Vec<String> {
+        ranges::clamp_config(decoded);
+        let mut warnings = keys::unknown_top_level_keys(content);
+        warnings.extend(decoded.leader.validate());
+        warnings.extend(decoded.hints.validate());
+        warnings
+    }
+
     pub fn load() -> Self {
         let config_path = config_dir_path();
         let path = config_file_path();
@@ -473,6 +515,9 @@ impl Config {
             let content = std::fs::read_to_string(path).unwrap();
             match toml::from_str::<Config>(&content) {
                 Ok(mut decoded) => {
+                    for warning in Config::finalize(&mut decoded, &content) {
+                        warn!("{warning}");
+                    }
                     let theme = &decoded.theme;
                     if theme.is_empty() {
                         return decoded;
@@ -506,6 +551,9 @@ impl Config {
             match std::fs::read_to_string(path) {
                 Ok(content) => match toml::from_str::<Config>(&content) {
                     Ok(mut decoded) => {
+                        for warning in Config::finalize(&mut decoded, &content) {
+                            warn!("{warning}");
+                        }
                         let theme = &decoded.theme;
                         let theme_path = config_dir_path().join("themes");
                         if !theme.is_empty() {
@@ -614,6 +662,15 @@ impl Config {
             if let Some(mode) = window_overwrite.mode {
                 self.window.mode = mode;
             }
+            if let Some(initial_x) = window_overwrite.initial_x {
+                self.window.initial_x = Some(initial_x);
+            }
+            if let Some(initial_y) = window_overwrite.initial_y {
+                self.window.initial_y = Some(initial_y);
+            }
+            if let Some(monitor) = window_overwrite.fullscreen_monitor {
+                self.window.fullscreen_monitor = Some(monitor);
+            }
             if let Some(opacity) = window_overwrite.opacity {
                 self.window.opacity = opacity;
             }
@@ -647,6 +704,9 @@ impl Config {
             if let Some(colorspace) = window_overwrite.colorspace {
                 self.window.colorspace = colorspace;
             }
+            if let Some(scale_override) = window_overwrite.scale_override {
+                self.window.scale_override = Some(scale_override);
+            }
         }
 
         // Merge navigation fields individually
@@ -698,6 +758,9 @@ impl Config {
             if let Some(filters) = &renderer_overwrite.filters {
                 self.renderer.filters = filters.clone();
             }
+            if let Some(restore_alpha) = renderer_overwrite.restore_alpha {
+                self.renderer.restore_alpha = restore_alpha;
+            }
             if let Some(strategy) = &renderer_overwrite.strategy {
                 self.renderer.strategy = strategy.clone();
             }
@@ -749,9 +812,11 @@ impl Default for Config {
             hints: Hints::default(),
             bell: Bell::default(),
             leader: Leader::default(),
-            command_overlay: CommandOverlayStyle::default(),
+            command_overlay: Vec::new(),
             distortion: DistortionConfig::default(),
+            vignette: VignetteConfig::default(),
             sound_effects: SoundEffects::default(),
+            features: Features::default(),
         }
     }
 }
@@ -934,6 +999,23 @@ mod tests {
         assert_eq!(result.colors.cursor, colors::defaults::cursor());
     }
 
+    #[test]
+    fn test_change_config_renderer_restore_alpha() {
+        assert!(Config::default().renderer.restore_alpha);
+
+        let result = create_temporary_config(
+            "change-renderer-restore-alpha",
+            r#"
+            [renderer]
+            filters = ["newpixiecrt"]
+            restore-alpha = false
+        "#,
+        );
+
+        assert!(!result.renderer.restore_alpha);
+        assert_eq!(result.renderer.filters, vec![String::from("newpixiecrt")]);
+    }
+
     #[test]
     fn test_change_config_environment_variables() {
         let result = create_temporary_config(
@@ -1485,6 +1567,45 @@ mod tests {
         assert_eq!(result.shell.args, vec!["-l"]);
     }
 
+    #[test]
+    fn test_feature_enabled_global_switch_wins() {
+        let config = Config::default();
+        assert!(config.feature_enabled(Feature::Sound));
+        assert!(config.feature_enabled(Feature::Leader));
+
+        // Section enabled, global disabled
+        let config: Config = toml::from_str(
+            r#"
+            [features]
+            sound = false
+            hints = false
+
+            [sound-effects]
+            enabled = true
+        "#,
+        )
+        .unwrap();
+        assert!(config.sound_effects.enabled);
+        assert!(!config.feature_enabled(Feature::Sound));
+        assert!(!config.feature_enabled(Feature::Hints));
+        assert!(config.feature_enabled(Feature::Leader));
+
+        // Global enabled, section disabled
+        let config: Config = toml::from_str(
+            r#"
+            [leader]
+            enabled = false
+
+            [window]
+            auto-align = false
+        "#,
+        )
+        .unwrap();
+        assert!(!config.feature_enabled(Feature::Leader));
+        assert!(!config.feature_enabled(Feature::AutoAlign));
+        assert!(config.feature_enabled(Feature::Effects));
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_platform_renderer_merge() {
@@ -1639,4 +1760,20 @@ mod tests {
         assert_eq!(result.env_vars.len(), 1);
         assert!(result.env_vars.contains(&String::from("GLOBAL=1")));
     }
+
+    #[test]
+    fn test_finalize_clamps_and_collects_warnings() {
+        let content = r#"
+            line-hieght = 1.2
+
+            [sound-effects]
+            volume = 2.0
+        "#;
+        let mut decoded: Config = toml::from_str(content).unwrap();
+        let warnings = Config::finalize(&mut decoded, content);
+
+        assert_eq!(decoded.sound_effects.volume, 1.0);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("line-hieght"));
+    }
 }
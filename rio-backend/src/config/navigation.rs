@@ -1,5 +1,6 @@
 use crate::config::colors::{deserialize_to_arr, ColorArray};
 use crate::config::default_bool_true;
+use crate::config::window::Colorspace;
 use serde::{Deserialize, Serialize};
 
 // Default functions for BookmarkStyle fields
@@ -92,6 +93,38 @@ fn default_bookmark_shadow_offset() -> [f32; 2] {
     [0.0, 1.0]
 }
 
+/// How bookmark indicators are spread along the bar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkSpacingMode {
+    /// `spacing` pixels apart, anchored to the right edge
+    #[default]
+    Fixed,
+    /// Evenly distributed across the bar width, ignoring `spacing`
+    Fill,
+}
+
+/// Which way a bookmark gradient runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookmarkGradientDirection {
+    /// Start color on top, end color at the bottom
+    #[default]
+    TopToBottom,
+    BottomToTop,
+}
+
+/// Color model used to derive per-tab hue rotation colors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorModel {
+    /// Classic HSL; equal lightness can still look uneven across hues
+    #[default]
+    Hsl,
+    /// Perceptually uniform OKLCH; `saturation` scales the chroma
+    Oklch,
+}
+
 /// Style configuration for bookmark-mode tab indicators.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct BookmarkStyle {
@@ -114,6 +147,10 @@ pub struct BookmarkStyle {
     #[serde(default = "default_bookmark_spacing")]
     pub spacing: f32,
 
+    /// Fixed gaps or fill the bar width (default: fixed)
+    #[serde(default, rename = "spacing-mode")]
+    pub spacing_mode: BookmarkSpacingMode,
+
     /// Right-edge padding offset (default: 30.0)
     #[serde(default = "default_bookmark_padding_x", rename = "padding-x")]
     pub padding_x: f32,
@@ -185,6 +222,33 @@ pub struct BookmarkStyle {
         rename = "lightness-inactive"
     )]
     pub lightness_inactive: f32,
+
+    /// Gradient start color as hex string (default: transparent = no
+    /// gradient)
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        default = "default_bookmark_gradient_color",
+        rename = "gradient-start-color"
+    )]
+    pub gradient_start_color: ColorArray,
+
+    /// Gradient end color as hex string (default: transparent = no
+    /// gradient). Set alone, only the active indicator fades from its
+    /// regular color to this one.
+    #[serde(
+        deserialize_with = "deserialize_to_arr",
+        default = "default_bookmark_gradient_color",
+        rename = "gradient-end-color"
+    )]
+    pub gradient_end_color: ColorArray,
+
+    /// Gradient direction (default: top-to-bottom)
+    #[serde(default, rename = "gradient-direction")]
+    pub gradient_direction: BookmarkGradientDirection,
+
+    /// Color model for hue rotation colors (default: hsl)
+    #[serde(default, rename = "color-model")]
+    pub color_model: ColorModel,
 }
 
 #[inline]
@@ -197,6 +261,11 @@ fn default_bookmark_shadow_color() -> ColorArray {
     [0.0, 0.0, 0.0, 0.4]
 }
 
+#[inline]
+fn default_bookmark_gradient_color() -> ColorArray {
+    [0.0, 0.0, 0.0, 0.0]
+}
+
 impl Default for BookmarkStyle {
     fn default() -> Self {
         BookmarkStyle {
@@ -204,6 +273,7 @@ impl Default for BookmarkStyle {
             height_active: default_bookmark_height_active(),
             height_inactive: default_bookmark_height_inactive(),
             spacing: default_bookmark_spacing(),
+            spacing_mode: BookmarkSpacingMode::default(),
             padding_x: default_bookmark_padding_x(),
             border_radius: default_bookmark_border_radius(),
             border_width: default_bookmark_border_width(),
@@ -217,13 +287,161 @@ impl Default for BookmarkStyle {
             saturation: default_bookmark_saturation(),
             lightness_active: default_bookmark_lightness_active(),
             lightness_inactive: default_bookmark_lightness_inactive(),
+            gradient_start_color: default_bookmark_gradient_color(),
+            gradient_end_color: default_bookmark_gradient_color(),
+            gradient_direction: BookmarkGradientDirection::default(),
+            color_model: ColorModel::default(),
         }
     }
 }
 
-/// Convert HSL to linear RGB color array [r, g, b, a] with values in 0.0-1.0.
+impl BookmarkStyle {
+    /// Hue rotation color for `hue` at `lightness`, using `color_model`.
+    pub fn hue_color(
+        &self,
+        hue: f32,
+        lightness: f32,
+        colorspace: Colorspace,
+    ) -> [f32; 4] {
+        match self.color_model {
+            ColorModel::Hsl => {
+                hsl_to_rgba_in(hue, self.saturation, lightness, 1.0, colorspace)
+            }
+            ColorModel::Oklch => oklch_to_rgba_in(
+                lightness,
+                self.saturation * OKLCH_MAX_CHROMA,
+                hue,
+                1.0,
+                colorspace,
+            ),
+        }
+    }
+
+    /// Top and bottom colors of the indicator gradient. `None` unless
+    /// both gradient colors are set.
+    pub fn gradient(&self) -> Option<(ColorArray, ColorArray)> {
+        let unset = default_bookmark_gradient_color();
+        if self.gradient_start_color == unset || self.gradient_end_color == unset {
+            return None;
+        }
+
+        Some(match self.gradient_direction {
+            BookmarkGradientDirection::TopToBottom => {
+                (self.gradient_start_color, self.gradient_end_color)
+            }
+            BookmarkGradientDirection::BottomToTop => {
+                (self.gradient_end_color, self.gradient_start_color)
+            }
+        })
+    }
+
+    /// Top and bottom colors of an indicator whose flat color is `color`.
+    /// With both gradient colors set every indicator uses `gradient`;
+    /// with only `gradient-end-color` the active indicator fades from
+    /// `color` to it and inactive ones stay flat.
+    pub fn indicator_gradient(
+        &self,
+        color: ColorArray,
+        is_active: bool,
+    ) -> Option<(ColorArray, ColorArray)> {
+        if let Some(gradient) = self.gradient() {
+            return Some(gradient);
+        }
+
+        let unset = default_bookmark_gradient_color();
+        if !is_active || self.gradient_end_color == unset {
+            return None;
+        }
+
+        Some(match self.gradient_direction {
+            BookmarkGradientDirection::TopToBottom => (color, self.gradient_end_color),
+            BookmarkGradientDirection::BottomToTop => (self.gradient_end_color, color),
+        })
+    }
+}
+
+/// Convert HSL to an sRGB-encoded color array [r, g, b, a] with values
+/// in 0.0-1.0. Same as `hsl_to_rgba_in` with `Colorspace::Srgb`.
 #[inline]
 pub fn hsl_to_rgba(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> [f32; 4] {
+    hsl_to_rgba_in(hue, saturation, lightness, alpha, Colorspace::Srgb)
+}
+
+/// OKLCH chroma reached at `saturation = 1.0`. Higher chroma leaves the
+/// sRGB gamut for most hues and gets clipped.
+const OKLCH_MAX_CHROMA: f32 = 0.2;
+
+/// OKLab to non-linear LMS.
+const OKLAB_TO_LMS: [[f32; 3]; 3] = [
+    [1.0, 0.396_337_8, 0.215_803_8],
+    [1.0, -0.105_561_3, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_486],
+];
+
+/// Linear LMS to linear sRGB.
+const LMS_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [4.076_742, -3.307_712, 0.230_97],
+    [-1.268_438, 2.609_757, -0.341_319_4],
+    [-0.004_196_086, -0.703_418_6, 1.707_615],
+];
+
+/// Linear sRGB to linear Display P3 (both D65).
+const SRGB_TO_DISPLAY_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+/// Linear sRGB to linear Rec. 2020 (both D65).
+const SRGB_TO_REC2020: [[f32; 3]; 3] = [
+    [0.627_404, 0.329_282, 0.043_313_6],
+    [0.069_097, 0.919_54, 0.011_361_2],
+    [0.016_391_6, 0.088_013_2, 0.895_595],
+];
+
+#[inline]
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+#[inline]
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Rec. 2020 (BT.2020) transfer function.
+#[inline]
+fn linear_to_rec2020(c: f32) -> f32 {
+    if c < 0.018_053_97 {
+        c * 4.5
+    } else {
+        1.099_296_8 * c.powf(0.45) - 0.099_296_8
+    }
+}
+
+#[inline]
+fn mul_matrix(matrix: &[[f32; 3]; 3], rgb: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+}
+
+/// Convert HSL (interpreted in sRGB) to a color array [r, g, b, a] encoded
+/// for `colorspace`, so the hue looks the same on a DisplayP3 or Rec2020
+/// window as it does on an sRGB one.
+pub fn hsl_to_rgba_in(
+    hue: f32,
+    saturation: f32,
+    lightness: f32,
+    alpha: f32,
+    colorspace: Colorspace,
+) -> [f32; 4] {
     let h = ((hue % 360.0) + 360.0) % 360.0;
     let s = saturation.clamp(0.0, 1.0);
     let l = lightness.clamp(0.0, 1.0);
@@ -246,8 +464,52 @@ pub fn hsl_to_rgba(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> [f3
     } else {
         (c, 0.0, x)
     };
+    let srgb = [r1 + m, g1 + m, b1 + m];
 
-    [r1 + m, g1 + m, b1 + m, alpha]
+    if colorspace == Colorspace::Srgb {
+        return [srgb[0], srgb[1], srgb[2], alpha];
+    }
+    encode_linear_srgb(srgb.map(srgb_to_linear), alpha, colorspace)
+}
+
+/// Convert OKLCH (lightness 0.0-1.0, chroma, hue in degrees) to an
+/// sRGB-encoded color array [r, g, b, a]. Same as `oklch_to_rgba_in` with
+/// `Colorspace::Srgb`.
+#[inline]
+pub fn oklch_to_rgba(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> [f32; 4] {
+    oklch_to_rgba_in(lightness, chroma, hue, alpha, Colorspace::Srgb)
+}
+
+/// Convert OKLCH to a color array [r, g, b, a] encoded for `colorspace`.
+/// Unlike HSL, equal lightness looks equally bright across hues. Colors
+/// outside the sRGB gamut are clipped per channel.
+pub fn oklch_to_rgba_in(
+    lightness: f32,
+    chroma: f32,
+    hue: f32,
+    alpha: f32,
+    colorspace: Colorspace,
+) -> [f32; 4] {
+    let l = lightness.clamp(0.0, 1.0);
+    let c = chroma.max(0.0);
+    let (sin, cos) = hue.to_radians().sin_cos();
+
+    let lms = mul_matrix(&OKLAB_TO_LMS, [l, c * cos, c * sin]).map(|v| v * v * v);
+    encode_linear_srgb(mul_matrix(&LMS_TO_LINEAR_SRGB, lms), alpha, colorspace)
+}
+
+/// Encode a linear sRGB color for `colorspace`, clipping channels that
+/// fall outside its gamut.
+fn encode_linear_srgb(linear: [f32; 3], alpha: f32, colorspace: Colorspace) -> [f32; 4] {
+    let (rgb, encode): (_, fn(f32) -> f32) = match colorspace {
+        Colorspace::Srgb => (linear, linear_to_srgb),
+        Colorspace::DisplayP3 => {
+            (mul_matrix(&SRGB_TO_DISPLAY_P3, linear), linear_to_srgb)
+        }
+        Colorspace::Rec2020 => (mul_matrix(&SRGB_TO_REC2020, linear), linear_to_rec2020),
+    };
+    let [r, g, b] = rgb.map(|channel| encode(channel.clamp(0.0, 1.0)));
+    [r, g, b, alpha]
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
@@ -261,6 +523,10 @@ pub enum NavigationMode {
     NativeTab,
     #[serde(alias = "bottomtab")]
     BottomTab,
+    #[serde(alias = "lefttab")]
+    LeftTab,
+    #[serde(alias = "righttab")]
+    RightTab,
     #[serde(alias = "bookmark")]
     Bookmark,
 }
@@ -283,6 +549,8 @@ impl NavigationMode {
     const COLLAPSED_TAB_STR: &'static str = "Bookmark";
     const TOP_TAB_STR: &'static str = "TopTab";
     const BOTTOM_TAB_STR: &'static str = "BottomTab";
+    const LEFT_TAB_STR: &'static str = "LeftTab";
+    const RIGHT_TAB_STR: &'static str = "RightTab";
     #[cfg(target_os = "macos")]
     const NATIVE_TAB_STR: &'static str = "NativeTab";
 
@@ -292,25 +560,39 @@ impl NavigationMode {
             Self::Bookmark => Self::COLLAPSED_TAB_STR,
             Self::TopTab => Self::TOP_TAB_STR,
             Self::BottomTab => Self::BOTTOM_TAB_STR,
+            Self::LeftTab => Self::LEFT_TAB_STR,
+            Self::RightTab => Self::RIGHT_TAB_STR,
             #[cfg(target_os = "macos")]
             Self::NativeTab => Self::NATIVE_TAB_STR,
         }
     }
+
+    /// Mode after this one in `MODES`, wrapping around at the end
+    pub fn next(&self) -> NavigationMode {
+        let index = MODES.iter().position(|mode| mode == self).unwrap_or(0);
+        MODES[(index + 1) % MODES.len()]
+    }
 }
 
+/// Every mode available on this platform, in the order
+/// `NavigationMode::next` cycles through them
+const MODES: &[NavigationMode] = &[
+    NavigationMode::Plain,
+    NavigationMode::Bookmark,
+    NavigationMode::TopTab,
+    NavigationMode::BottomTab,
+    NavigationMode::LeftTab,
+    NavigationMode::RightTab,
+    #[cfg(target_os = "macos")]
+    NavigationMode::NativeTab,
+];
+
 #[inline]
 pub fn modes_as_vec_string() -> Vec<String> {
-    [
-        NavigationMode::Plain,
-        NavigationMode::Bookmark,
-        NavigationMode::TopTab,
-        NavigationMode::BottomTab,
-        #[cfg(target_os = "macos")]
-        NavigationMode::NativeTab,
-    ]
-    .iter()
-    .map(|navigation_mode| navigation_mode.to_string())
-    .collect()
+    MODES
+        .iter()
+        .map(|navigation_mode| navigation_mode.to_string())
+        .collect()
 }
 
 impl std::fmt::Display for NavigationMode {
@@ -330,6 +612,8 @@ impl std::str::FromStr for NavigationMode {
             Self::COLLAPSED_TAB_STR => Ok(NavigationMode::Bookmark),
             Self::TOP_TAB_STR => Ok(NavigationMode::TopTab),
             Self::BOTTOM_TAB_STR => Ok(NavigationMode::BottomTab),
+            Self::LEFT_TAB_STR => Ok(NavigationMode::LeftTab),
+            Self::RIGHT_TAB_STR => Ok(NavigationMode::RightTab),
             #[cfg(target_os = "macos")]
             Self::NATIVE_TAB_STR => Ok(NavigationMode::NativeTab),
             Self::PLAIN_STR => Ok(NavigationMode::Plain),
@@ -342,8 +626,23 @@ impl std::str::FromStr for NavigationMode {
 pub struct ColorAutomation {
     #[serde(default = "String::new")]
     pub program: String,
+    /// Regex matched against the program name, e.g. `^ssh-` for both
+    /// `ssh-prod` and `ssh-staging`. When set, `program` is ignored.
+    #[serde(
+        default = "Option::default",
+        rename = "program-regex",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub program_regex: Option<String>,
     #[serde(default = "String::new")]
     pub path: String,
+    /// Matched against the terminal title and the host of the OSC 7
+    /// working directory URI, useful for SSH sessions. Plain values match
+    /// as a substring, values with `*` or `?` match as a glob
+    /// (e.g. `*.prod.example.com`). When set, `program` and `path` are
+    /// ignored.
+    #[serde(default = "String::new")]
+    pub hostname: String,
     #[serde(
         deserialize_with = "deserialize_to_arr",
         default = "crate::config::colors::defaults::tabs"
@@ -351,6 +650,82 @@ pub struct ColorAutomation {
     pub color: ColorArray,
 }
 
+impl ColorAutomation {
+    #[inline]
+    pub fn is_hostname_rule(&self) -> bool {
+        !self.hostname.is_empty()
+    }
+
+    /// Compile `program-regex`, `None` for rules without one
+    pub fn build_program_regex(&self) -> Option<Result<regex::Regex, regex::Error>> {
+        self.program_regex.as_deref().map(regex::Regex::new)
+    }
+
+    /// Check the compiled `program-regex` and `path` of this rule. An
+    /// empty `path` matches any directory.
+    pub fn matches_program_regex(
+        &self,
+        regex: &regex::Regex,
+        program: &str,
+        path: &str,
+    ) -> bool {
+        regex.is_match(program) && (self.path.is_empty() || self.path == path)
+    }
+
+    /// Check the `hostname` pattern against the terminal title and the
+    /// OSC 7 host. Always false for rules without a hostname.
+    pub fn matches_hostname(&self, title: &str, host: &str) -> bool {
+        if !self.is_hostname_rule() {
+            return false;
+        }
+
+        let pattern = self.hostname.to_lowercase();
+        let title = title.to_lowercase();
+        let host = host.to_lowercase();
+
+        if !pattern.contains(['*', '?']) {
+            return title.contains(&pattern)
+                || (!host.is_empty() && host.contains(&pattern));
+        }
+
+        // Titles look like "user@host: ~/dir", so try each host-like word
+        let is_separator =
+            |c: char| c.is_whitespace() || matches!(c, '@' | ':' | '[' | ']');
+        (!host.is_empty() && glob_match(&pattern, &host))
+            || title
+                .split(is_separator)
+                .filter(|word| !word.is_empty())
+                .any(|word| glob_match(&pattern, word))
+    }
+}
+
+/// Minimal glob matching supporting `*` (any run of characters) and
+/// `?` (a single character).
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[inline]
 pub fn default_unfocused_split_opacity() -> f32 {
     0.4
@@ -389,6 +764,47 @@ pub struct Navigation {
     pub unfocused_split_opacity: f32,
     #[serde(default = "BookmarkStyle::default", rename = "bookmark-style")]
     pub bookmark_style: BookmarkStyle,
+    /// Upper bound on open tabs, `None` for no limit
+    #[serde(
+        default = "Option::default",
+        rename = "max-tabs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_tabs: Option<usize>,
+    /// Upper bound on splits within a tab, `None` for no limit
+    #[serde(
+        default = "Option::default",
+        rename = "max-splits",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_splits: Option<usize>,
+    /// Ring the bell when a tab or split is refused by a limit
+    #[serde(default = "bool::default", rename = "bell-on-limit")]
+    pub bell_on_limit: bool,
+    /// Tab title template, see `Navigation::format_tab_title`. `None`
+    /// keeps the `[title]` content
+    #[serde(
+        default = "Option::default",
+        rename = "tab-title-format",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tab_title_format: Option<String>,
+}
+
+/// Values substituted into `navigation.tab-title-format`. Anything that
+/// can't be queried is left empty.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TabTitleContext {
+    /// Terminal title set via OSC 0/2
+    pub title: String,
+    /// Basename of the working directory
+    pub cwd: String,
+    /// Foreground process name
+    pub program: String,
+    /// 1-based tab index
+    pub index: usize,
+    /// Foreground process id
+    pub pid: Option<u32>,
 }
 
 impl Default for Navigation {
@@ -404,11 +820,57 @@ impl Default for Navigation {
             unfocused_split_opacity: default_unfocused_split_opacity(),
             open_config_with_split: true,
             bookmark_style: BookmarkStyle::default(),
+            max_tabs: None,
+            max_splits: None,
+            bell_on_limit: false,
+            tab_title_format: None,
         }
     }
 }
 
 impl Navigation {
+    /// Expand `{title}`, `{cwd}`, `{program}`, `{index}` and `{pid}` in
+    /// `format`. Unknown placeholders and a `{` without a closing `}` are
+    /// kept as written.
+    pub fn format_tab_title(format: &str, ctx: &TabTitleContext) -> String {
+        let mut out = String::with_capacity(format.len());
+        let mut rest = format;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let close = after.find('}');
+            // A nested `{` starts the next placeholder instead
+            let name = match close {
+                Some(close) if !after[..close].contains('{') => &after[..close],
+                _ => {
+                    out.push('{');
+                    rest = after;
+                    continue;
+                }
+            };
+
+            match name {
+                "title" => out.push_str(&ctx.title),
+                "cwd" => out.push_str(&ctx.cwd),
+                "program" => out.push_str(&ctx.program),
+                "index" => out.push_str(&ctx.index.to_string()),
+                "pid" => {
+                    if let Some(pid) = ctx.pid {
+                        out.push_str(&pid.to_string());
+                    }
+                }
+                _ => {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                }
+            }
+            rest = &after[name.len() + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
     #[inline]
     pub fn is_collapsed_mode(&self) -> bool {
         self.mode == NavigationMode::Bookmark
@@ -441,12 +903,39 @@ impl Navigation {
     pub fn is_placed_on_top(&self) -> bool {
         self.mode == NavigationMode::TopTab
     }
+
+    #[inline]
+    pub fn is_placed_on_left(&self) -> bool {
+        self.mode == NavigationMode::LeftTab
+    }
+
+    #[inline]
+    pub fn is_placed_on_right(&self) -> bool {
+        self.mode == NavigationMode::RightTab
+    }
+
+    /// Whether another tab may be opened next to `current_count` tabs.
+    #[inline]
+    pub fn can_add_tab(&self, current_count: usize) -> bool {
+        self.max_tabs.is_none_or(|max| current_count < max)
+    }
+
+    /// Whether another split may be opened next to `current_count` splits.
+    #[inline]
+    pub fn can_add_split(&self, current_count: usize) -> bool {
+        self.max_splits.is_none_or(|max| current_count < max)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::config::colors::hex_to_color_arr;
-    use crate::config::navigation::{Navigation, NavigationMode};
+    use crate::config::navigation::{
+        glob_match, hsl_to_rgba, hsl_to_rgba_in, modes_as_vec_string, oklch_to_rgba,
+        oklch_to_rgba_in, srgb_to_linear, BookmarkGradientDirection, BookmarkSpacingMode,
+        BookmarkStyle, ColorAutomation, ColorModel, Navigation, NavigationMode,
+    };
+    use crate::config::window::Colorspace;
     use serde::Deserialize;
 
     #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -455,6 +944,108 @@ mod tests {
         navigation: Navigation,
     }
 
+    fn assert_close(actual: [f32; 4], expected: [f32; 4]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 0.001, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_hsl_to_rgba_red_per_colorspace() {
+        let srgb = hsl_to_rgba_in(0.0, 1.0, 0.5, 1.0, Colorspace::Srgb);
+        let p3 = hsl_to_rgba_in(0.0, 1.0, 0.5, 1.0, Colorspace::DisplayP3);
+        let rec2020 = hsl_to_rgba_in(0.0, 1.0, 0.5, 1.0, Colorspace::Rec2020);
+
+        assert_eq!(srgb, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(hsl_to_rgba(0.0, 1.0, 0.5, 1.0), srgb);
+        // sRGB red sits inside the wider gamuts
+        assert_close(p3, [0.9175, 0.2003, 0.1386, 1.0]);
+        assert_close(rec2020, [0.7920, 0.2310, 0.0738, 1.0]);
+        assert_ne!(p3, rec2020);
+    }
+
+    #[test]
+    fn test_hsl_to_rgba_grays_match_in_every_colorspace() {
+        for colorspace in [Colorspace::Srgb, Colorspace::DisplayP3, Colorspace::Rec2020] {
+            assert_close(
+                hsl_to_rgba_in(200.0, 0.0, 1.0, 0.5, colorspace),
+                [1.0, 1.0, 1.0, 0.5],
+            );
+            assert_close(
+                hsl_to_rgba_in(200.0, 0.0, 0.0, 1.0, colorspace),
+                [0.0, 0.0, 0.0, 1.0],
+            );
+        }
+    }
+
+    /// Relative luminance of an sRGB-encoded color
+    fn luminance(rgba: [f32; 4]) -> f32 {
+        let [r, g, b] = [rgba[0], rgba[1], rgba[2]].map(srgb_to_linear);
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    #[test]
+    fn test_oklch_to_rgba() {
+        // sRGB red in OKLCH
+        assert_close(
+            oklch_to_rgba(0.627_955, 0.257_683, 29.233_9, 1.0),
+            [1.0, 0.0, 0.0, 1.0],
+        );
+        assert_close(oklch_to_rgba(1.0, 0.0, 120.0, 0.5), [1.0, 1.0, 1.0, 0.5]);
+        assert_close(oklch_to_rgba(0.0, 0.0, 120.0, 1.0), [0.0, 0.0, 0.0, 1.0]);
+        for colorspace in [Colorspace::Srgb, Colorspace::DisplayP3, Colorspace::Rec2020] {
+            assert_close(
+                oklch_to_rgba_in(1.0, 0.0, 240.0, 1.0, colorspace),
+                [1.0, 1.0, 1.0, 1.0],
+            );
+        }
+    }
+
+    #[test]
+    fn test_oklch_lightness_is_even_across_hues() {
+        let spread = |colors: Vec<[f32; 4]>| {
+            let luminances: Vec<f32> = colors.into_iter().map(luminance).collect();
+            let max = luminances.iter().cloned().fold(f32::MIN, f32::max);
+            let min = luminances.iter().cloned().fold(f32::MAX, f32::min);
+            max / min
+        };
+        let hues = (0..9).map(|i| i as f32 * 40.0);
+
+        let hsl = spread(
+            hues.clone()
+                .map(|h| hsl_to_rgba(h, 0.7, 0.65, 1.0))
+                .collect(),
+        );
+        let oklch = spread(hues.map(|h| oklch_to_rgba(0.65, 0.1, h, 1.0)).collect());
+        // HSL yellow is several times brighter than HSL blue
+        assert!(hsl > 3.0, "{hsl}");
+        assert!(oklch < 1.2, "{oklch}");
+    }
+
+    #[test]
+    fn test_bookmark_color_model() {
+        let decoded = toml::from_str::<Root>("[navigation]").unwrap();
+        let style = decoded.navigation.bookmark_style;
+        assert_eq!(style.color_model, ColorModel::Hsl);
+        assert_eq!(
+            style.hue_color(120.0, 0.65, Colorspace::Srgb),
+            hsl_to_rgba(120.0, style.saturation, 0.65, 1.0)
+        );
+
+        let content = r#"
+            [navigation.bookmark-style]
+            color-model = "oklch"
+            saturation = 0.5
+        "#;
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        let style = decoded.navigation.bookmark_style;
+        assert_eq!(style.color_model, ColorModel::Oklch);
+        assert_eq!(
+            style.hue_color(120.0, 0.65, Colorspace::DisplayP3),
+            oklch_to_rgba_in(0.65, 0.1, 120.0, 1.0, Colorspace::DisplayP3)
+        );
+    }
+
     #[test]
     fn test_collapsed_tab() {
         let content = r#"
@@ -494,6 +1085,176 @@ mod tests {
         assert!(decoded.navigation.color_automation.is_empty());
     }
 
+    #[test]
+    fn test_bookmark_spacing_mode() {
+        let decoded = toml::from_str::<Root>("").unwrap();
+        assert_eq!(
+            decoded.navigation.bookmark_style.spacing_mode,
+            BookmarkSpacingMode::Fixed
+        );
+
+        let content = r#"
+            [navigation.bookmark-style]
+            spacing-mode = 'fill'
+        "#;
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert_eq!(
+            decoded.navigation.bookmark_style.spacing_mode,
+            BookmarkSpacingMode::Fill
+        );
+    }
+
+    #[test]
+    fn test_bookmark_gradient_off_by_default() {
+        let style = BookmarkStyle::default();
+        assert_eq!(style.gradient_start_color, [0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(style.gradient_end_color, [0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(
+            style.gradient_direction,
+            BookmarkGradientDirection::TopToBottom
+        );
+        assert_eq!(style.gradient(), None);
+
+        // A single color is not enough
+        let decoded = toml::from_str::<Root>(
+            r##"
+            [navigation.bookmark-style]
+            gradient-start-color = "#ff0000"
+        "##,
+        )
+        .unwrap();
+        assert_eq!(decoded.navigation.bookmark_style.gradient(), None);
+    }
+
+    #[test]
+    fn test_bookmark_gradient_from_hex() {
+        let content = r##"
+            [navigation.bookmark-style]
+            gradient-start-color = "#ff0000"
+            gradient-end-color = "#0000ff"
+            gradient-direction = "bottom-to-top"
+        "##;
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        let style = decoded.navigation.bookmark_style;
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let blue = [0.0, 0.0, 1.0, 1.0];
+        assert_eq!(style.gradient_start_color, red);
+        assert_eq!(style.gradient_end_color, blue);
+        assert_eq!(style.gradient(), Some((blue, red)));
+
+        // Serialized colors parse back to the same style
+        let serialized = toml::to_string(&style).unwrap();
+        let round_trip: BookmarkStyle = toml::from_str(&serialized).unwrap();
+        assert_eq!(round_trip, style);
+    }
+
+    #[test]
+    fn test_bookmark_active_gradient_from_end_color() {
+        let decoded = toml::from_str::<Root>(
+            r##"
+            [navigation.bookmark-style]
+            gradient-end-color = "#000080"
+        "##,
+        )
+        .unwrap();
+        let style = decoded.navigation.bookmark_style;
+        let accent = [1.0, 0.5, 0.0, 1.0];
+        let navy = [0.0, 0.0, 128.0 / 255.0, 1.0];
+
+        assert_eq!(style.gradient(), None);
+        assert_eq!(style.indicator_gradient(accent, true), Some((accent, navy)));
+        // Inactive indicators keep their flat color
+        assert_eq!(style.indicator_gradient(accent, false), None);
+
+        let flipped = BookmarkStyle {
+            gradient_direction: BookmarkGradientDirection::BottomToTop,
+            ..style
+        };
+        assert_eq!(
+            flipped.indicator_gradient(accent, true),
+            Some((navy, accent))
+        );
+    }
+
+    #[test]
+    fn test_bookmark_full_gradient_applies_to_every_indicator() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let blue = [0.0, 0.0, 1.0, 1.0];
+        let style = BookmarkStyle {
+            gradient_start_color: red,
+            gradient_end_color: blue,
+            ..BookmarkStyle::default()
+        };
+        let accent = [1.0, 0.5, 0.0, 1.0];
+        assert_eq!(style.indicator_gradient(accent, true), Some((red, blue)));
+        assert_eq!(style.indicator_gradient(accent, false), Some((red, blue)));
+        assert_eq!(
+            BookmarkStyle::default().indicator_gradient(accent, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_max_tabs_and_splits() {
+        let decoded = toml::from_str::<Root>("[navigation]").unwrap();
+        assert_eq!(decoded.navigation.max_tabs, None);
+        assert!(decoded.navigation.can_add_tab(usize::MAX - 1));
+        assert!(decoded.navigation.can_add_split(100));
+        assert!(!decoded.navigation.bell_on_limit);
+
+        let content = r#"
+            [navigation]
+            max-tabs = 3
+            max-splits = 2
+            bell-on-limit = true
+        "#;
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        let navigation = decoded.navigation;
+        assert_eq!(navigation.max_tabs, Some(3));
+        assert!(navigation.can_add_tab(2));
+        assert!(!navigation.can_add_tab(3));
+        assert!(!navigation.can_add_tab(4));
+        assert_eq!(navigation.max_splits, Some(2));
+        assert!(navigation.can_add_split(1));
+        assert!(!navigation.can_add_split(2));
+        assert!(navigation.bell_on_limit);
+    }
+
+    #[test]
+    fn test_left_tab() {
+        let content = r#"
+            [navigation]
+            mode = 'LeftTab'
+        "#;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert_eq!(decoded.navigation.mode, NavigationMode::LeftTab);
+        assert!(decoded.navigation.is_placed_on_left());
+        assert!(!decoded.navigation.clickable);
+        assert!(decoded.navigation.color_automation.is_empty());
+    }
+
+    #[test]
+    fn test_right_tab() {
+        let content = r#"
+            [navigation]
+            mode = 'righttab'
+        "#;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        assert_eq!(decoded.navigation.mode, NavigationMode::RightTab);
+        assert!(decoded.navigation.is_placed_on_right());
+        assert!(!decoded.navigation.clickable);
+        assert!(decoded.navigation.color_automation.is_empty());
+    }
+
+    #[test]
+    fn test_side_tab_str_round_trip() {
+        for mode in [NavigationMode::LeftTab, NavigationMode::RightTab] {
+            assert_eq!(mode.to_string().parse::<NavigationMode>(), Ok(mode));
+        }
+    }
+
     #[test]
     fn test_color_automation() {
         let content = r#"
@@ -583,4 +1344,197 @@ mod tests {
             hex_to_color_arr("#00b952")
         );
     }
+
+    #[test]
+    fn test_color_automation_program_regex() {
+        let content = r##"
+            [navigation]
+            color-automation = [
+                { program-regex = "^ssh-", color = "#ff0000" },
+                { program = "nvim", color = "#00ff00" }
+            ]
+        "##;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        let rules = &decoded.navigation.color_automation;
+        assert_eq!(rules[0].program_regex, Some("^ssh-".to_string()));
+        assert_eq!(rules[1].program_regex, None);
+        assert!(rules[1].build_program_regex().is_none());
+
+        let regex = rules[0].build_program_regex().unwrap().unwrap();
+        assert!(rules[0].matches_program_regex(&regex, "ssh-prod", "/home"));
+        assert!(rules[0].matches_program_regex(&regex, "ssh-staging", ""));
+        assert!(!rules[0].matches_program_regex(&regex, "mosh", "/home"));
+
+        let invalid = ColorAutomation {
+            program_regex: Some("ssh-(".to_string()),
+            ..ColorAutomation::default()
+        };
+        assert!(invalid.build_program_regex().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_color_automation_hostname() {
+        let content = r##"
+            [navigation]
+            color-automation = [
+                { hostname = "prod*", color = "#ff0000" }
+            ]
+        "##;
+
+        let decoded = toml::from_str::<Root>(content).unwrap();
+        let rule = &decoded.navigation.color_automation[0];
+        assert_eq!(rule.hostname, "prod*".to_string());
+        assert_eq!(rule.program, String::new());
+        assert_eq!(rule.path, String::new());
+        assert_eq!(rule.color, hex_to_color_arr("#ff0000"));
+        assert!(rule.is_hostname_rule());
+    }
+
+    #[test]
+    fn test_color_automation_hostname_round_trip() {
+        let rule = ColorAutomation {
+            hostname: "*.prod.example.com".to_string(),
+            color: hex_to_color_arr("#ff0000"),
+            ..ColorAutomation::default()
+        };
+
+        let serialized = toml::to_string(&rule).unwrap();
+        let deserialized: ColorAutomation = toml::from_str(&serialized).unwrap();
+        assert_eq!(rule, deserialized);
+    }
+
+    #[test]
+    fn test_color_automation_matches_hostname() {
+        let contains = ColorAutomation {
+            hostname: "prod".to_string(),
+            ..ColorAutomation::default()
+        };
+        assert!(contains.matches_hostname("deploy@prod-db-1: ~", ""));
+        assert!(contains.matches_hostname("", "prod-db-1"));
+        assert!(!contains.matches_hostname("staging: ~", "staging"));
+
+        let glob = ColorAutomation {
+            hostname: "*.prod.example.com".to_string(),
+            ..ColorAutomation::default()
+        };
+        assert!(glob.matches_hostname("me@db1.prod.example.com: /var", ""));
+        assert!(glob.matches_hostname("vim", "web.PROD.example.com"));
+        assert!(!glob.matches_hostname("me@db1.staging.example.com", ""));
+
+        let program_only = ColorAutomation {
+            program: "ssh".to_string(),
+            ..ColorAutomation::default()
+        };
+        assert!(!program_only.matches_hostname("ssh prod", "prod"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("prod*", "prod-1"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("db?.prod", "db1.prod"));
+        assert!(glob_match("*.example.*", "a.example.com"));
+        assert!(!glob_match("prod*", "preprod"));
+        assert!(!glob_match("db?", "db12"));
+    }
+
+    #[test]
+    fn test_navigation_mode_next() {
+        assert_eq!(NavigationMode::Plain.next(), NavigationMode::Bookmark);
+        assert_eq!(NavigationMode::Bookmark.next(), NavigationMode::TopTab);
+        assert_eq!(NavigationMode::TopTab.next(), NavigationMode::BottomTab);
+        assert_eq!(NavigationMode::BottomTab.next(), NavigationMode::LeftTab);
+        assert_eq!(NavigationMode::LeftTab.next(), NavigationMode::RightTab);
+
+        #[cfg(target_os = "macos")]
+        {
+            assert_eq!(NavigationMode::RightTab.next(), NavigationMode::NativeTab);
+            assert_eq!(NavigationMode::NativeTab.next(), NavigationMode::Plain);
+        }
+        #[cfg(not(target_os = "macos"))]
+        assert_eq!(NavigationMode::RightTab.next(), NavigationMode::Plain);
+    }
+
+    #[test]
+    fn test_navigation_mode_next_visits_every_mode() {
+        let modes = modes_as_vec_string();
+        let mut mode = NavigationMode::Plain;
+        let mut visited = Vec::new();
+        for _ in 0..modes.len() {
+            visited.push(mode.to_string());
+            mode = mode.next();
+        }
+        assert_eq!(visited, modes);
+        // Back to the start after a full cycle
+        assert_eq!(mode, NavigationMode::Plain);
+    }
+
+    fn tab_title_context() -> TabTitleContext {
+        TabTitleContext {
+            title: String::from("vim notes.md"),
+            cwd: String::from("rio"),
+            program: String::from("vim"),
+            index: 2,
+            pid: Some(4242),
+        }
+    }
+
+    #[test]
+    fn test_format_tab_title_placeholders() {
+        let ctx = tab_title_context();
+        let format = |template| Navigation::format_tab_title(template, &ctx);
+        assert_eq!(format("{title}"), "vim notes.md");
+        assert_eq!(format("{cwd}"), "rio");
+        assert_eq!(format("{program}"), "vim");
+        assert_eq!(format("{index}"), "2");
+        assert_eq!(format("{pid}"), "4242");
+        assert_eq!(format("no placeholders"), "no placeholders");
+    }
+
+    #[test]
+    fn test_format_tab_title_combinations() {
+        let ctx = tab_title_context();
+        assert_eq!(
+            Navigation::format_tab_title("{index}: {program} in {cwd}", &ctx),
+            "2: vim in rio"
+        );
+        assert_eq!(
+            Navigation::format_tab_title("{program}{program} ({pid})", &ctx),
+            "vimvim (4242)"
+        );
+    }
+
+    #[test]
+    fn test_format_tab_title_missing_values() {
+        let ctx = TabTitleContext {
+            index: 1,
+            ..TabTitleContext::default()
+        };
+        assert_eq!(
+            Navigation::format_tab_title("{index}:{title}|{cwd}|{program}|{pid}", &ctx),
+            "1:|||"
+        );
+    }
+
+    #[test]
+    fn test_format_tab_title_literal_braces() {
+        let ctx = tab_title_context();
+        assert_eq!(Navigation::format_tab_title("{cwd", &ctx), "{cwd");
+        assert_eq!(Navigation::format_tab_title("a { b", &ctx), "a { b");
+        assert_eq!(Navigation::format_tab_title("{{cwd}", &ctx), "{rio");
+        assert_eq!(Navigation::format_tab_title("{user}", &ctx), "{user}");
+        assert_eq!(Navigation::format_tab_title("}{index}{", &ctx), "}2{");
+    }
+
+    #[test]
+    fn test_tab_title_format_toml() {
+        let navigation: Navigation =
+            toml::from_str("tab-title-format = \"{index} {program}\"").unwrap();
+        assert_eq!(
+            navigation.tab_title_format.as_deref(),
+            Some("{index} {program}")
+        );
+        assert_eq!(Navigation::default().tab_title_format, None);
+    }
 }
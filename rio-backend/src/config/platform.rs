@@ -41,6 +41,12 @@ pub struct PlatformWindow {
     pub height: Option<i32>,
     #[serde(default = "Option::default")]
     pub mode: Option<window::WindowMode>,
+    #[serde(default = "Option::default", rename = "initial-x")]
+    pub initial_x: Option<i32>,
+    #[serde(default = "Option::default", rename = "initial-y")]
+    pub initial_y: Option<i32>,
+    #[serde(default = "Option::default", rename = "fullscreen-monitor")]
+    pub fullscreen_monitor: Option<usize>,
     #[serde(default = "Option::default")]
     pub opacity: Option<f32>,
     #[serde(default = "Option::default")]
@@ -70,6 +76,8 @@ pub struct PlatformWindow {
     pub windows_corner_preference: Option<window::WindowsCornerPreference>,
     #[serde(default = "Option::default")]
     pub colorspace: Option<window::Colorspace>,
+    #[serde(default = "Option::default", rename = "scale-override")]
+    pub scale_override: Option<f64>,
 }
 
 /// Platform-specific navigation config with optional fields for selective override
@@ -108,6 +116,8 @@ pub struct PlatformRenderer {
     pub disable_occluded_render: Option<bool>,
     #[serde(default = "Option::default", skip_serializing)]
     pub filters: Option<Vec<sugarloaf::Filter>>,
+    #[serde(default = "Option::default", rename = "restore-alpha")]
+    pub restore_alpha: Option<bool>,
     #[serde(default = "Option::default")]
     pub strategy: Option<renderer::RendererStategy>,
 }
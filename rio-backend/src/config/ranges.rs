@@ -0,0 +1,383 @@
+// Ranges of tunable numeric config fields
+//
+// One registry keyed by the TOML path of each field, so the loader clamps
+// every field the same way and a settings UI can build sliders from it.
+
+use crate::config::distortion::{MAX_FOV_DEGREES, MIN_FOV_DEGREES};
+use crate::config::Config;
+use tracing::warn;
+
+/// Unit a numeric field is expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldUnit {
+    /// Fraction of a whole, e.g. opacity or a position relative to the window
+    Ratio,
+    /// Signed effect amount
+    Strength,
+    Degrees,
+    Semitones,
+    /// Scaled pixels
+    Pixels,
+    /// Font points
+    Points,
+    Seconds,
+}
+
+/// Allowed range of a numeric config field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldRange {
+    /// TOML path, e.g. `command-overlay.opacity`
+    pub key: &'static str,
+    pub min: f32,
+    pub max: f32,
+    /// Increment a slider should move by
+    pub step: f32,
+    pub unit: FieldUnit,
+}
+
+impl FieldRange {
+    const fn new(
+        key: &'static str,
+        min: f32,
+        max: f32,
+        step: f32,
+        unit: FieldUnit,
+    ) -> Self {
+        Self {
+            key,
+            min,
+            max,
+            step,
+            unit,
+        }
+    }
+
+    /// Clamp `value` into the range. NaN becomes `min`.
+    #[inline]
+    pub fn clamp(&self, value: f32) -> f32 {
+        if value.is_nan() {
+            return self.min;
+        }
+        value.clamp(self.min, self.max)
+    }
+}
+
+pub const DISTORTION_STRENGTH: &str = "distortion.strength";
+pub const DISTORTION_CENTER: &str = "distortion.center";
+pub const DISTORTION_FOV_DEGREES: &str = "distortion.fov-degrees";
+pub const DISTORTION_INNER_RADIUS: &str = "distortion.inner-radius";
+pub const DISTORTION_OUTER_RADIUS: &str = "distortion.outer-radius";
+pub const SOUND_EFFECTS_VOLUME: &str = "sound-effects.volume";
+pub const SOUND_EFFECTS_PITCH_VARIANCE: &str = "sound-effects.pitch-variance";
+pub const SOUND_EFFECTS_TYPING_PITCH: &str = "sound-effects.typing-pitch";
+
+/// Every field with a known range.
+pub const FIELD_RANGES: &[FieldRange] = &[
+    // Distortion
+    FieldRange::new(DISTORTION_STRENGTH, -1.0, 1.0, 0.01, FieldUnit::Strength),
+    FieldRange::new(DISTORTION_CENTER, 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new(
+        DISTORTION_FOV_DEGREES,
+        MIN_FOV_DEGREES,
+        MAX_FOV_DEGREES,
+        1.0,
+        FieldUnit::Degrees,
+    ),
+    FieldRange::new(DISTORTION_INNER_RADIUS, 0.0, 2.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new(DISTORTION_OUTER_RADIUS, 0.0, 2.0, 0.01, FieldUnit::Ratio),
+    // Vignette
+    FieldRange::new("vignette.intensity", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("vignette.radius", 0.0, 2.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("vignette.smoothness", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    // Command overlay
+    FieldRange::new("command-overlay.x", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("command-overlay.y", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("command-overlay.width", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("command-overlay.height", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new("command-overlay.opacity", 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new(
+        "command-overlay.font-size",
+        0.0,
+        72.0,
+        0.5,
+        FieldUnit::Points,
+    ),
+    FieldRange::new(
+        "command-overlay.border-radius",
+        0.0,
+        64.0,
+        1.0,
+        FieldUnit::Pixels,
+    ),
+    FieldRange::new(
+        "command-overlay.border-width",
+        0.0,
+        16.0,
+        0.5,
+        FieldUnit::Pixels,
+    ),
+    FieldRange::new(
+        "command-overlay.shadow-blur-radius",
+        0.0,
+        64.0,
+        1.0,
+        FieldUnit::Pixels,
+    ),
+    // Navigation and bookmarks
+    FieldRange::new(
+        "navigation.unfocused-split-opacity",
+        0.0,
+        1.0,
+        0.01,
+        FieldUnit::Ratio,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.base-hue",
+        0.0,
+        360.0,
+        1.0,
+        FieldUnit::Degrees,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.hue-step",
+        -360.0,
+        360.0,
+        1.0,
+        FieldUnit::Degrees,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.saturation",
+        0.0,
+        1.0,
+        0.01,
+        FieldUnit::Ratio,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.lightness-active",
+        0.0,
+        1.0,
+        0.01,
+        FieldUnit::Ratio,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.lightness-inactive",
+        0.0,
+        1.0,
+        0.01,
+        FieldUnit::Ratio,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.border-width",
+        0.0,
+        16.0,
+        0.5,
+        FieldUnit::Pixels,
+    ),
+    FieldRange::new(
+        "navigation.bookmark-style.shadow-blur-radius",
+        0.0,
+        64.0,
+        1.0,
+        FieldUnit::Pixels,
+    ),
+    // Sound effects
+    FieldRange::new(SOUND_EFFECTS_VOLUME, 0.0, 1.0, 0.01, FieldUnit::Ratio),
+    FieldRange::new(
+        "sound-effects.max-duration",
+        0.0,
+        60.0,
+        0.5,
+        FieldUnit::Seconds,
+    ),
+    FieldRange::new(
+        SOUND_EFFECTS_PITCH_VARIANCE,
+        0.0,
+        2.0,
+        0.1,
+        FieldUnit::Semitones,
+    ),
+    FieldRange::new(
+        SOUND_EFFECTS_TYPING_PITCH,
+        0.0,
+        2.0,
+        0.1,
+        FieldUnit::Semitones,
+    ),
+];
+
+/// Range of the field at `key`, if it has one.
+pub fn field_range(key: &str) -> Option<&'static FieldRange> {
+    FIELD_RANGES.iter().find(|range| range.key == key)
+}
+
+/// Clamp `value` with the range of `key`. Fields without a range are
+/// returned unchanged.
+#[inline]
+pub fn clamp_field(key: &str, value: f32) -> f32 {
+    field_range(key).map_or(value, |range| range.clamp(value))
+}
+
+/// Clamp every ranged field of `config` in place, warning about each
+/// value that was out of range.
+pub fn clamp_config(config: &mut Config) {
+    let [center_x, center_y] = &mut config.distortion.center;
+    let bookmark = &mut config.navigation.bookmark_style;
+    let fields: [(&str, &mut f32); 20] = [
+        (DISTORTION_STRENGTH, &mut config.distortion.strength),
+        (DISTORTION_CENTER, center_x),
+        (DISTORTION_CENTER, center_y),
+        (DISTORTION_FOV_DEGREES, &mut config.distortion.fov_degrees),
+        (DISTORTION_INNER_RADIUS, &mut config.distortion.inner_radius),
+        (DISTORTION_OUTER_RADIUS, &mut config.distortion.outer_radius),
+        ("vignette.intensity", &mut config.vignette.intensity),
+        ("vignette.radius", &mut config.vignette.radius),
+        ("vignette.smoothness", &mut config.vignette.smoothness),
+        (
+            "navigation.unfocused-split-opacity",
+            &mut config.navigation.unfocused_split_opacity,
+        ),
+        ("navigation.bookmark-style.base-hue", &mut bookmark.base_hue),
+        ("navigation.bookmark-style.hue-step", &mut bookmark.hue_step),
+        (
+            "navigation.bookmark-style.saturation",
+            &mut bookmark.saturation,
+        ),
+        (
+            "navigation.bookmark-style.lightness-active",
+            &mut bookmark.lightness_active,
+        ),
+        (
+            "navigation.bookmark-style.lightness-inactive",
+            &mut bookmark.lightness_inactive,
+        ),
+        (
+            "navigation.bookmark-style.border-width",
+            &mut bookmark.border_width,
+        ),
+        (
+            "navigation.bookmark-style.shadow-blur-radius",
+            &mut bookmark.shadow_blur_radius,
+        ),
+        (SOUND_EFFECTS_VOLUME, &mut config.sound_effects.volume),
+        (
+            "sound-effects.max-duration",
+            &mut config.sound_effects.max_duration,
+        ),
+        (
+            SOUND_EFFECTS_PITCH_VARIANCE,
+            &mut config.sound_effects.pitch_variance,
+        ),
+        (
+            SOUND_EFFECTS_TYPING_PITCH,
+            &mut config.sound_effects.typing_pitch,
+        ),
+    ];
+    for (key, value) in fields {
+        clamp_in_place(key, value);
+    }
+
+    for overlay in &mut config.command_overlay {
+        let fields: [(&str, &mut f32); 9] = [
+            ("command-overlay.x", &mut overlay.x),
+            ("command-overlay.y", &mut overlay.y),
+            ("command-overlay.width", &mut overlay.width),
+            ("command-overlay.height", &mut overlay.height),
+            ("command-overlay.opacity", &mut overlay.opacity),
+            ("command-overlay.font-size", &mut overlay.font_size),
+            ("command-overlay.border-radius", &mut overlay.border_radius),
+            ("command-overlay.border-width", &mut overlay.border_width),
+            (
+                "command-overlay.shadow-blur-radius",
+                &mut overlay.shadow_blur_radius,
+            ),
+        ];
+        for (key, value) in fields {
+            clamp_in_place(key, value);
+        }
+    }
+}
+
+fn clamp_in_place(key: &str, value: &mut f32) {
+    let clamped = clamp_field(key, *value);
+    if clamped != *value {
+        warn!("{key} = {value} is out of range, using {clamped}");
+        *value = clamped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_ranges_are_valid() {
+        for range in FIELD_RANGES {
+            assert!(range.min < range.max, "{} has an empty range", range.key);
+            assert!(range.step > 0.0, "{} has no step", range.key);
+        }
+    }
+
+    #[test]
+    fn test_opacity_clamps_like_before() {
+        for value in [-0.5, 0.0, 0.4, 1.0, 3.0] {
+            assert_eq!(
+                clamp_field("command-overlay.opacity", value),
+                value.clamp(0.0, 1.0)
+            );
+            assert_eq!(
+                clamp_field(SOUND_EFFECTS_VOLUME, value),
+                value.clamp(0.0, 1.0)
+            );
+        }
+        assert_eq!(clamp_field(SOUND_EFFECTS_VOLUME, f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_fov_clamps_like_before() {
+        for value in [0.0, 60.0, 120.0, 180.0, 270.0] {
+            assert_eq!(
+                clamp_field(DISTORTION_FOV_DEGREES, value),
+                value.clamp(MIN_FOV_DEGREES, MAX_FOV_DEGREES)
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_field_is_unchanged() {
+        assert!(field_range("window.width").is_none());
+        assert_eq!(clamp_field("window.width", 1234.0), 1234.0);
+    }
+
+    #[test]
+    fn test_clamp_config() {
+        let mut config: Config = toml::from_str(
+            r#"
+            [distortion]
+            strength = 4.0
+            center = [-1.0, 0.5]
+
+            [command-overlay]
+            opacity = 1.5
+
+            [navigation]
+            unfocused-split-opacity = -0.2
+
+            [sound-effects]
+            volume = 2.0
+        "#,
+        )
+        .unwrap();
+        clamp_config(&mut config);
+
+        assert_eq!(config.distortion.strength, 1.0);
+        assert_eq!(config.distortion.center, [0.0, 0.5]);
+        assert_eq!(config.command_overlay[0].opacity, 1.0);
+        assert_eq!(config.navigation.unfocused_split_opacity, 0.0);
+        assert_eq!(config.sound_effects.volume, 1.0);
+
+        // Defaults are all in range
+        let mut config = Config::default();
+        clamp_config(&mut config);
+        assert_eq!(config, Config::default());
+    }
+}
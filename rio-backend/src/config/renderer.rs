@@ -17,6 +17,14 @@ pub struct Renderer {
     pub disable_occluded_render: bool,
     #[serde(default = "Vec::default")]
     pub filters: Vec<Filter>,
+    /// Composite the original alpha back after the filter chain runs.
+    /// Can be disabled for opaque windows to skip the extra pass.
+    #[serde(default = "default_restore_alpha", rename = "restore-alpha")]
+    pub restore_alpha: bool,
+    /// Write the restored alpha as premultiplied colors, for compositors
+    /// that blend with `rgba = color * alpha`.
+    #[serde(default = "bool::default", rename = "premultiplied-alpha")]
+    pub premultiplied_alpha: bool,
     #[serde(default = "RendererStategy::default")]
     pub strategy: RendererStategy,
 }
@@ -25,6 +33,10 @@ fn default_disable_occluded_render() -> bool {
     false
 }
 
+fn default_restore_alpha() -> bool {
+    true
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub enum RendererStategy {
     #[default]
@@ -55,6 +67,8 @@ impl Default for Renderer {
             disable_unfocused_render: false,
             disable_occluded_render: default_disable_occluded_render(),
             filters: Vec::default(),
+            restore_alpha: default_restore_alpha(),
+            premultiplied_alpha: false,
             strategy: RendererStategy::Events,
         }
     }
@@ -1,24 +1,59 @@
-use crate::event::SoundEvent;
+use crate::event::{SoundCategory, SoundEvent};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// A sound entry can be a single path or a list of paths (variants).
-/// When multiple paths are provided, they are rotated via round-robin.
+/// A bare list is rotated via round-robin, while `{ random = [...] }`
+/// picks a variant at random on every play.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SoundPaths {
     Single(PathBuf),
     Multiple(Vec<PathBuf>),
+    #[serde(with = "random_paths")]
+    Random(Vec<PathBuf>),
 }
 
 impl SoundPaths {
     pub fn into_vec(self) -> Vec<PathBuf> {
         match self {
             SoundPaths::Single(p) => vec![p],
-            SoundPaths::Multiple(v) => v,
+            SoundPaths::Multiple(v) | SoundPaths::Random(v) => v,
         }
     }
+
+    #[inline]
+    pub fn is_random(&self) -> bool {
+        matches!(self, SoundPaths::Random(_))
+    }
+}
+
+/// `SoundPaths::Random` is written as `{ random = [...] }` so it can be
+/// told apart from the bare round-robin list.
+mod random_paths {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::path::PathBuf;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RandomPaths<P> {
+        random: P,
+    }
+
+    pub fn serialize<S: Serializer>(
+        paths: &[PathBuf],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RandomPaths { random: paths }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<PathBuf>, D::Error> {
+        RandomPaths::<Vec<PathBuf>>::deserialize(deserializer).map(|w| w.random)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -46,11 +81,33 @@ pub struct SoundEffects {
     pub key_space: Option<SoundPaths>,
     #[serde(default)]
     pub key_backspace: Option<SoundPaths>,
+    #[serde(default)]
+    pub scroll: Option<SoundPaths>,
+    #[serde(default)]
+    pub resize: Option<SoundPaths>,
+    #[serde(default)]
+    pub copy: Option<SoundPaths>,
+    #[serde(default)]
+    pub paste: Option<SoundPaths>,
+    /// Played when the foreground job of a pane finishes and the shell
+    /// takes the terminal back, e.g. at the end of a long build.
+    #[serde(default)]
+    pub process_exit: Option<SoundPaths>,
 
     /// Global volume multiplier (0.0–1.0).
     #[serde(default = "default_volume")]
     pub volume: f32,
 
+    /// Volume multipliers per category, applied on top of `volume`.
+    /// Keyboard: key presses. Navigation: windows, tabs, splits, scroll
+    /// and resize. System: bell, process exit, copy and paste.
+    #[serde(default = "default_category_volume")]
+    pub keyboard_volume: f32,
+    #[serde(default = "default_category_volume")]
+    pub navigation_volume: f32,
+    #[serde(default = "default_category_volume")]
+    pub system_volume: f32,
+
     /// Whether to play sounds at all.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
@@ -59,16 +116,129 @@ pub struct SoundEffects {
     #[serde(default = "default_keyboard_enabled")]
     pub keyboard_enabled: bool,
 
+    /// Whether to play the scroll sound (default off), scrolling fires
+    /// far more often than any other event.
+    #[serde(default)]
+    pub scroll_enabled: bool,
+
+    /// Treat the bell variants as intensity levels, subtlest first,
+    /// instead of rotating through them: an isolated bell plays the
+    /// first one and every further bell within
+    /// `bell-escalate-window-ms` moves one level up.
+    #[serde(default)]
+    pub bell_escalate: bool,
+
+    /// How long a bell counts towards the escalation, in milliseconds.
+    #[serde(default = "default_bell_escalate_window_ms")]
+    pub bell_escalate_window_ms: u32,
+
+    /// Random pitch shift of keyboard sounds, up to this many semitones
+    /// either way (0.0–2.0), so repeated keystrokes don't sound
+    /// identical. Also shortens or stretches them slightly. `0.0`
+    /// disables it.
+    #[serde(default)]
+    pub pitch_variance: f32,
+
+    /// Raise the pitch of keyboard sounds with the typing speed, up to
+    /// this many semitones (0.0–2.0) when typing fast. Slow typing plays
+    /// as recorded. `0.0` disables it.
+    #[serde(default)]
+    pub typing_pitch: f32,
+
     /// Maximum duration in seconds for any single sound file.
     /// Files exceeding this are skipped during loading.
     #[serde(default = "default_max_duration")]
     pub max_duration: f32,
+
+    /// Maximum number of sounds playing at the same time.
+    /// New sounds beyond this limit are dropped; `0` disables the limit.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+
+    /// Minimum time in milliseconds before the same event can play
+    /// again; `0` disables it. The `<event>-cooldown-ms` fields
+    /// override it per event. Resizing plays once per drag: it only
+    /// plays again after no resize came in for the cooldown.
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u32,
+    #[serde(default)]
+    pub bell_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub window_create_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub window_close_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub tab_create_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub tab_close_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub split_create_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub split_close_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub key_letter_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub key_enter_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub key_space_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub key_backspace_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub scroll_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub resize_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub copy_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub paste_cooldown_ms: Option<u32>,
+    #[serde(default)]
+    pub process_exit_cooldown_ms: Option<u32>,
+
+    /// Name of the output device an event plays on, e.g. bells on the
+    /// speakers and typing on headphones. Events without one, or whose
+    /// device can't be found, play on the default device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bell_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_create_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_close_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_create_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_close_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_create_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_close_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_letter_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_enter_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_space_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_backspace_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scroll_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resize_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub copy_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paste_device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_exit_device: Option<String>,
 }
 
 fn default_volume() -> f32 {
     0.7
 }
 
+fn default_category_volume() -> f32 {
+    1.0
+}
+
 fn default_enabled() -> bool {
     true
 }
@@ -81,6 +251,18 @@ fn default_max_duration() -> f32 {
     5.0
 }
 
+fn default_max_concurrent() -> usize {
+    8
+}
+
+fn default_cooldown_ms() -> u32 {
+    100
+}
+
+fn default_bell_escalate_window_ms() -> u32 {
+    2000
+}
+
 impl Default for SoundEffects {
     fn default() -> Self {
         Self {
@@ -95,10 +277,57 @@ impl Default for SoundEffects {
             key_enter: None,
             key_space: None,
             key_backspace: None,
+            scroll: None,
+            resize: None,
+            copy: None,
+            paste: None,
+            process_exit: None,
             volume: default_volume(),
+            keyboard_volume: default_category_volume(),
+            navigation_volume: default_category_volume(),
+            system_volume: default_category_volume(),
             enabled: default_enabled(),
             keyboard_enabled: default_keyboard_enabled(),
+            scroll_enabled: false,
+            bell_escalate: false,
+            bell_escalate_window_ms: default_bell_escalate_window_ms(),
+            pitch_variance: 0.0,
+            typing_pitch: 0.0,
             max_duration: default_max_duration(),
+            max_concurrent: default_max_concurrent(),
+            cooldown_ms: default_cooldown_ms(),
+            bell_cooldown_ms: None,
+            window_create_cooldown_ms: None,
+            window_close_cooldown_ms: None,
+            tab_create_cooldown_ms: None,
+            tab_close_cooldown_ms: None,
+            split_create_cooldown_ms: None,
+            split_close_cooldown_ms: None,
+            key_letter_cooldown_ms: None,
+            key_enter_cooldown_ms: None,
+            key_space_cooldown_ms: None,
+            key_backspace_cooldown_ms: None,
+            scroll_cooldown_ms: None,
+            resize_cooldown_ms: None,
+            copy_cooldown_ms: None,
+            paste_cooldown_ms: None,
+            process_exit_cooldown_ms: None,
+            bell_device: None,
+            window_create_device: None,
+            window_close_device: None,
+            tab_create_device: None,
+            tab_close_device: None,
+            split_create_device: None,
+            split_close_device: None,
+            key_letter_device: None,
+            key_enter_device: None,
+            key_space_device: None,
+            key_backspace_device: None,
+            scroll_device: None,
+            resize_device: None,
+            copy_device: None,
+            paste_device: None,
+            process_exit_device: None,
         }
     }
 }
@@ -120,15 +349,9 @@ fn resolve_path(path: PathBuf, config_dir: &std::path::Path) -> PathBuf {
 }
 
 impl SoundEffects {
-    /// Build a mapping from `SoundEvent` to resolved file paths.
-    /// Only events with configured paths are included.
-    /// Keyboard events are excluded when `keyboard_enabled` is false.
-    pub fn build_mapping(
-        &self,
-        config_dir: &std::path::Path,
-    ) -> HashMap<SoundEvent, Vec<PathBuf>> {
-        let mut map = HashMap::new();
-
+    /// Configured entries per event, keyboard events only when
+    /// `keyboard_enabled` is set and scroll only when `scroll_enabled` is.
+    fn entries(&self) -> Vec<(SoundEvent, &Option<SoundPaths>)> {
         let mut entries: Vec<(SoundEvent, &Option<SoundPaths>)> = vec![
             (SoundEvent::Bell, &self.bell),
             (SoundEvent::WindowCreate, &self.window_create),
@@ -137,8 +360,16 @@ impl SoundEffects {
             (SoundEvent::TabClose, &self.tab_close),
             (SoundEvent::SplitCreate, &self.split_create),
             (SoundEvent::SplitClose, &self.split_close),
+            (SoundEvent::Resize, &self.resize),
+            (SoundEvent::Copy, &self.copy),
+            (SoundEvent::Paste, &self.paste),
+            (SoundEvent::ProcessExit, &self.process_exit),
         ];
 
+        if self.scroll_enabled {
+            entries.push((SoundEvent::Scroll, &self.scroll));
+        }
+
         if self.keyboard_enabled {
             entries.extend([
                 (SoundEvent::KeyLetter, &self.key_letter),
@@ -148,7 +379,100 @@ impl SoundEffects {
             ]);
         }
 
-        for (event, opt) in entries {
+        entries
+    }
+
+    /// Cooldown of every event, the per-event override when set and
+    /// `cooldown_ms` otherwise.
+    pub fn cooldowns(&self) -> HashMap<SoundEvent, Duration> {
+        [
+            (SoundEvent::Bell, self.bell_cooldown_ms),
+            (SoundEvent::WindowCreate, self.window_create_cooldown_ms),
+            (SoundEvent::WindowClose, self.window_close_cooldown_ms),
+            (SoundEvent::TabCreate, self.tab_create_cooldown_ms),
+            (SoundEvent::TabClose, self.tab_close_cooldown_ms),
+            (SoundEvent::SplitCreate, self.split_create_cooldown_ms),
+            (SoundEvent::SplitClose, self.split_close_cooldown_ms),
+            (SoundEvent::KeyLetter, self.key_letter_cooldown_ms),
+            (SoundEvent::KeyEnter, self.key_enter_cooldown_ms),
+            (SoundEvent::KeySpace, self.key_space_cooldown_ms),
+            (SoundEvent::KeyBackspace, self.key_backspace_cooldown_ms),
+            (SoundEvent::Scroll, self.scroll_cooldown_ms),
+            (SoundEvent::Resize, self.resize_cooldown_ms),
+            (SoundEvent::Copy, self.copy_cooldown_ms),
+            (SoundEvent::Paste, self.paste_cooldown_ms),
+            (SoundEvent::ProcessExit, self.process_exit_cooldown_ms),
+        ]
+        .into_iter()
+        .map(|(event, ms)| {
+            let ms = ms.unwrap_or(self.cooldown_ms);
+            (event, Duration::from_millis(u64::from(ms)))
+        })
+        .collect()
+    }
+
+    /// Window within which bells escalate, `None` unless
+    /// `bell-escalate` is set.
+    pub fn bell_escalation(&self) -> Option<Duration> {
+        self.bell_escalate
+            .then(|| Duration::from_millis(u64::from(self.bell_escalate_window_ms)))
+    }
+
+    /// Volume multiplier of every category.
+    pub fn category_volumes(&self) -> HashMap<SoundCategory, f32> {
+        HashMap::from([
+            (SoundCategory::Keyboard, self.keyboard_volume),
+            (SoundCategory::Navigation, self.navigation_volume),
+            (SoundCategory::System, self.system_volume),
+        ])
+    }
+
+    /// Output device name of every event that has one configured.
+    pub fn devices(&self) -> HashMap<SoundEvent, String> {
+        [
+            (SoundEvent::Bell, &self.bell_device),
+            (SoundEvent::WindowCreate, &self.window_create_device),
+            (SoundEvent::WindowClose, &self.window_close_device),
+            (SoundEvent::TabCreate, &self.tab_create_device),
+            (SoundEvent::TabClose, &self.tab_close_device),
+            (SoundEvent::SplitCreate, &self.split_create_device),
+            (SoundEvent::SplitClose, &self.split_close_device),
+            (SoundEvent::KeyLetter, &self.key_letter_device),
+            (SoundEvent::KeyEnter, &self.key_enter_device),
+            (SoundEvent::KeySpace, &self.key_space_device),
+            (SoundEvent::KeyBackspace, &self.key_backspace_device),
+            (SoundEvent::Scroll, &self.scroll_device),
+            (SoundEvent::Resize, &self.resize_device),
+            (SoundEvent::Copy, &self.copy_device),
+            (SoundEvent::Paste, &self.paste_device),
+            (SoundEvent::ProcessExit, &self.process_exit_device),
+        ]
+        .into_iter()
+        .filter_map(|(event, device)| Some((event, device.clone()?)))
+        .collect()
+    }
+
+    /// Events configured with `{ random = [...] }`, whose variants are
+    /// picked at random instead of round-robin.
+    pub fn random_events(&self) -> HashSet<SoundEvent> {
+        self.entries()
+            .into_iter()
+            .filter(|(_, opt)| opt.as_ref().is_some_and(SoundPaths::is_random))
+            .map(|(event, _)| event)
+            .collect()
+    }
+
+    /// Build a mapping from `SoundEvent` to resolved file paths.
+    /// Only events with configured paths are included.
+    /// Keyboard events are excluded when `keyboard_enabled` is false,
+    /// scroll when `scroll_enabled` is false.
+    pub fn build_mapping(
+        &self,
+        config_dir: &std::path::Path,
+    ) -> HashMap<SoundEvent, Vec<PathBuf>> {
+        let mut map = HashMap::new();
+
+        for (event, opt) in self.entries() {
             if let Some(paths) = opt {
                 let resolved: Vec<PathBuf> = paths
                     .clone()
@@ -195,7 +519,101 @@ mod tests {
         assert_eq!(se.volume, 0.7);
         assert!(se.enabled);
         assert!(!se.keyboard_enabled);
+        assert!(!se.scroll_enabled);
+        assert!(se.scroll.is_none());
+        assert!(se.resize.is_none());
+        assert!(se.copy.is_none());
+        assert!(se.paste.is_none());
+        assert!(se.process_exit.is_none());
+        assert_eq!(se.pitch_variance, 0.0);
+        assert_eq!(se.typing_pitch, 0.0);
         assert_eq!(se.max_duration, 5.0);
+        assert_eq!(se.max_concurrent, 8);
+        assert_eq!(se.cooldown_ms, 100);
+        assert_eq!(se.bell_escalation(), None);
+    }
+
+    #[test]
+    fn test_category_volumes_toml() {
+        let se: SoundEffects = toml::from_str("navigation-volume = 0.5").unwrap();
+        let volumes = se.category_volumes();
+        assert_eq!(volumes[&SoundCategory::Navigation], 0.5);
+        assert_eq!(volumes[&SoundCategory::Keyboard], 1.0);
+        assert_eq!(volumes[&SoundCategory::System], 1.0);
+    }
+
+    #[test]
+    fn test_sound_event_category() {
+        assert_eq!(SoundEvent::KeyLetter.category(), SoundCategory::Keyboard);
+        assert_eq!(SoundEvent::TabCreate.category(), SoundCategory::Navigation);
+        assert_eq!(SoundEvent::Scroll.category(), SoundCategory::Navigation);
+        assert_eq!(SoundEvent::Bell.category(), SoundCategory::System);
+        // Keyboard sounds are exactly the ones gated by `keyboard-enabled`
+        for event in SoundEffects::default().cooldowns().into_keys() {
+            assert_eq!(
+                event.category() == SoundCategory::Keyboard,
+                event.is_keyboard()
+            );
+        }
+    }
+
+    #[test]
+    fn test_bell_escalation_toml() {
+        let se: SoundEffects = toml::from_str(
+            r#"
+            bell = ["soft.wav", "loud.wav"]
+            bell-escalate = true
+            bell-escalate-window-ms = 500
+        "#,
+        )
+        .unwrap();
+        assert_eq!(se.bell_escalation(), Some(Duration::from_millis(500)));
+
+        let se: SoundEffects = toml::from_str("bell-escalate = true").unwrap();
+        assert_eq!(se.bell_escalation(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_cooldowns_use_global_value() {
+        let se = SoundEffects::default();
+        let cooldowns = se.cooldowns();
+        assert_eq!(cooldowns.len(), 15);
+        assert!(cooldowns
+            .values()
+            .all(|cooldown| *cooldown == Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_cooldowns_per_event_override() {
+        let toml_str = r#"
+            cooldown-ms = 50
+            bell-cooldown-ms = 1000
+            key-letter-cooldown-ms = 0
+        "#;
+        let se: SoundEffects = toml::from_str(toml_str).unwrap();
+        let cooldowns = se.cooldowns();
+        assert_eq!(cooldowns[&SoundEvent::Bell], Duration::from_secs(1));
+        assert_eq!(cooldowns[&SoundEvent::KeyLetter], Duration::ZERO);
+        assert_eq!(cooldowns[&SoundEvent::TabCreate], Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_devices() {
+        assert!(SoundEffects::default().devices().is_empty());
+
+        let toml_str = r#"
+            bell-device = "Built-in Speakers"
+            key-letter-device = "USB Headphones"
+        "#;
+        let se: SoundEffects = toml::from_str(toml_str).unwrap();
+        let devices = se.devices();
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[&SoundEvent::Bell], "Built-in Speakers");
+        assert_eq!(devices[&SoundEvent::KeyLetter], "USB Headphones");
+
+        let serialized = toml::to_string(&se).unwrap();
+        assert!(!serialized.contains("tab-create-device"));
+        assert_eq!(toml::from_str::<SoundEffects>(&serialized).unwrap(), se);
     }
 
     #[test]
@@ -286,12 +704,14 @@ mod tests {
             key-backspace = "/tmp/bs.wav"
             keyboard-enabled = true
             max-duration = 3.0
+            max-concurrent = 4
         "#;
         let se: SoundEffects = toml::from_str(toml_str).unwrap();
         assert!(se.window_create.is_some());
         assert!(se.key_backspace.is_some());
         assert!(se.keyboard_enabled);
         assert_eq!(se.max_duration, 3.0);
+        assert_eq!(se.max_concurrent, 4);
     }
 
     #[test]
@@ -336,4 +756,132 @@ mod tests {
         assert!(map.contains_key(&SoundEvent::KeyLetter));
         assert!(map.contains_key(&SoundEvent::KeyEnter));
     }
+
+    #[test]
+    fn test_build_mapping_scroll_resize_clipboard() {
+        let toml_str = r#"
+            scroll = "/s/scroll.wav"
+            resize = "/s/resize.wav"
+            copy = "/s/copy.wav"
+            paste = ["/s/paste1.wav", "/s/paste2.wav"]
+        "#;
+        let se: SoundEffects = toml::from_str(toml_str).unwrap();
+        let config_dir = std::path::Path::new("/tmp");
+        let map = se.build_mapping(config_dir);
+        // Scroll stays silent until scroll-enabled is set
+        assert_eq!(map.len(), 3);
+        assert!(!map.contains_key(&SoundEvent::Scroll));
+        assert_eq!(
+            map[&SoundEvent::Resize],
+            vec![PathBuf::from("/s/resize.wav")]
+        );
+        assert_eq!(map[&SoundEvent::Copy], vec![PathBuf::from("/s/copy.wav")]);
+        assert_eq!(map[&SoundEvent::Paste].len(), 2);
+
+        let se = SoundEffects {
+            scroll_enabled: true,
+            ..se
+        };
+        let map = se.build_mapping(config_dir);
+        assert_eq!(map.len(), 4);
+        assert_eq!(
+            map[&SoundEvent::Scroll],
+            vec![PathBuf::from("/s/scroll.wav")]
+        );
+    }
+
+    #[test]
+    fn test_build_mapping_bell_process_exit() {
+        let toml_str = r#"
+            bell = "bell.wav"
+            process-exit = "/s/done.wav"
+            process-exit-cooldown-ms = 1000
+        "#;
+        let se: SoundEffects = toml::from_str(toml_str).unwrap();
+        let map = se.build_mapping(std::path::Path::new("/cfg"));
+        assert_eq!(map[&SoundEvent::Bell], vec![PathBuf::from("/cfg/bell.wav")]);
+        assert_eq!(
+            map[&SoundEvent::ProcessExit],
+            vec![PathBuf::from("/s/done.wav")]
+        );
+        assert_eq!(
+            se.cooldowns()[&SoundEvent::ProcessExit],
+            Duration::from_millis(1000)
+        );
+    }
+
+    #[test]
+    fn test_sound_paths_into_vec_random() {
+        let sp =
+            SoundPaths::Random(vec![PathBuf::from("k1.wav"), PathBuf::from("k2.wav")]);
+        assert!(sp.is_random());
+        assert_eq!(
+            sp.into_vec(),
+            vec![PathBuf::from("k1.wav"), PathBuf::from("k2.wav")]
+        );
+    }
+
+    #[test]
+    fn test_toml_deserialization_random() {
+        let toml_str = r#"
+            key-letter = { random = ["/a.wav", "/b.wav"] }
+            key-enter = ["/c.wav", "/d.wav"]
+        "#;
+        let se: SoundEffects = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            se.key_letter,
+            Some(SoundPaths::Random(vec![
+                PathBuf::from("/a.wav"),
+                PathBuf::from("/b.wav"),
+            ]))
+        );
+        assert_eq!(
+            se.key_enter,
+            Some(SoundPaths::Multiple(vec![
+                PathBuf::from("/c.wav"),
+                PathBuf::from("/d.wav"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_toml_deserialization_random_rejects_unknown_keys() {
+        let toml_str = r#"
+            key-letter = { shuffle = ["/a.wav"] }
+        "#;
+        assert!(toml::from_str::<SoundEffects>(toml_str).is_err());
+    }
+
+    #[test]
+    fn test_random_round_trip() {
+        let se = SoundEffects {
+            key_letter: Some(SoundPaths::Random(vec![PathBuf::from("/a.wav")])),
+            ..SoundEffects::default()
+        };
+        let serialized = toml::to_string(&se).unwrap();
+        let deserialized: SoundEffects = toml::from_str(&serialized).unwrap();
+        assert_eq!(se, deserialized);
+    }
+
+    #[test]
+    fn test_random_events() {
+        let se = SoundEffects {
+            key_letter: Some(SoundPaths::Random(vec![PathBuf::from("/s/k.wav")])),
+            bell: Some(SoundPaths::Multiple(vec![PathBuf::from("/s/bell.wav")])),
+            tab_create: Some(SoundPaths::Random(vec![PathBuf::from("/s/tab.wav")])),
+            keyboard_enabled: false,
+            ..SoundEffects::default()
+        };
+        // Keyboard events are ignored while keyboard sounds are off
+        assert_eq!(se.random_events(), HashSet::from([SoundEvent::TabCreate]));
+
+        let se = SoundEffects {
+            keyboard_enabled: true,
+            ..se
+        };
+        assert_eq!(
+            se.random_events(),
+            HashSet::from([SoundEvent::TabCreate, SoundEvent::KeyLetter])
+        );
+    }
 }
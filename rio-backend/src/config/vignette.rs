@@ -0,0 +1,100 @@
+use crate::config::colors::{deserialize_to_arr, ColorArray};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for the `[vignette]` TOML section.
+///
+/// ```toml
+/// [vignette]
+/// intensity = 0.6
+/// radius = 0.75
+/// smoothness = 0.45
+/// color = "#000000"
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VignetteConfig {
+    /// How dark the edges get, 0.0 = off, 1.0 = fully tinted.
+    /// Default: 0.0 (disabled)
+    #[serde(default)]
+    pub intensity: f32,
+
+    /// Normalized distance from the center where darkening
+    /// starts (1.0 = corners). Default: 0.75
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+
+    /// Width of the fade from the clear center to the darkened
+    /// edge. Default: 0.45
+    #[serde(default = "default_smoothness")]
+    pub smoothness: f32,
+
+    /// Tint multiplied into the edges. Default: black
+    #[serde(default = "default_color", deserialize_with = "deserialize_to_arr")]
+    pub color: ColorArray,
+}
+
+fn default_radius() -> f32 {
+    0.75
+}
+
+fn default_smoothness() -> f32 {
+    0.45
+}
+
+fn default_color() -> ColorArray {
+    [0.0, 0.0, 0.0, 1.0]
+}
+
+impl Default for VignetteConfig {
+    fn default() -> Self {
+        Self {
+            intensity: 0.0,
+            radius: default_radius(),
+            smoothness: default_smoothness(),
+            color: default_color(),
+        }
+    }
+}
+
+impl VignetteConfig {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.intensity > 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vignette_default() {
+        let config = VignetteConfig::default();
+        assert_eq!(config.intensity, 0.0);
+        assert_eq!(config.radius, 0.75);
+        assert_eq!(config.smoothness, 0.45);
+        assert_eq!(config.color, [0.0, 0.0, 0.0, 1.0]);
+        assert!(!config.is_enabled());
+    }
+
+    #[test]
+    fn test_vignette_toml() {
+        let toml_str = r##"
+            intensity = 0.6
+            radius = 0.5
+            color = "#ff0000"
+        "##;
+        let config: VignetteConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.intensity, 0.6);
+        assert_eq!(config.radius, 0.5);
+        assert_eq!(config.smoothness, 0.45);
+        assert_eq!(config.color, [1.0, 0.0, 0.0, 1.0]);
+        assert!(config.is_enabled());
+    }
+
+    #[test]
+    fn test_vignette_empty_section() {
+        let config: VignetteConfig = toml::from_str("").unwrap();
+        assert_eq!(config, VignetteConfig::default());
+    }
+}
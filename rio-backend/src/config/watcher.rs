@@ -0,0 +1,156 @@
+// Config file watcher
+//
+// Editors rarely write a file in place: many save to a temporary file and
+// rename it over the original, which drops a watch placed on the file
+// itself. The watcher therefore watches the config directory and filters
+// events down to the config file name.
+
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+/// Poll interval for platforms without native file notifications
+const POLLING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Quiet period after the last change before a reload is requested. A
+/// single save usually fires several events (truncate, write, rename,
+/// metadata), this folds them into one reload.
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches the top-level config file and sends `()` on `tx` once it
+/// changes, debounced by [`DEBOUNCE`].
+///
+/// Only the config file itself is watched: themes and other files pulled in
+/// from it don't trigger a reload. Watching stops when the `ConfigWatcher`
+/// is dropped, so keep it around for as long as reloads are wanted.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf, tx: Sender<()>) -> Result<Self, notify::Error> {
+        let Some(file_name) = path.file_name().map(OsString::from) else {
+            return Err(notify::Error::path_not_found().add_path(path));
+        };
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (events_tx, events_rx) = channel();
+        let mut watcher = RecommendedWatcher::new(
+            events_tx,
+            Config::default().with_poll_interval(POLLING_TIMEOUT),
+        )?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        // Exits once `watcher` is dropped and `events_rx` disconnects
+        std::thread::spawn(move || {
+            debounce(
+                events_rx,
+                tx,
+                DEBOUNCE,
+                |res: &notify::Result<Event>| match res {
+                    Ok(event) => touches_config(event, &file_name),
+                    Err(err_message) => {
+                        tracing::error!("unable to watch config file: {err_message:?}");
+                        false
+                    }
+                },
+            );
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Whether `event` changed the file named `file_name`.
+fn touches_config(event: &Event, file_name: &OsString) -> bool {
+    let changed = matches!(
+        event.kind,
+        EventKind::Any | EventKind::Create(_) | EventKind::Modify(_) | EventKind::Other
+    );
+    changed
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == Some(file_name.as_os_str()))
+}
+
+/// Forward relevant messages from `rx` to `tx` as `()`, sending once per
+/// burst: after a relevant message, every message arriving within `delay`
+/// of the previous one is folded into the same notification.
+fn debounce<T>(
+    rx: Receiver<T>,
+    tx: Sender<()>,
+    delay: Duration,
+    is_relevant: impl Fn(&T) -> bool,
+) {
+    while let Ok(message) = rx.recv() {
+        if !is_relevant(&message) {
+            continue;
+        }
+
+        loop {
+            match rx.recv_timeout(delay) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if tx.send(()).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_touches_config_matches_file_name() {
+        let name = OsString::from("config.toml");
+        let modify = EventKind::Modify(ModifyKind::Any);
+        assert!(touches_config(&event(modify, "/rio/config.toml"), &name));
+        assert!(touches_config(
+            &event(EventKind::Create(CreateKind::File), "/rio/config.toml"),
+            &name
+        ));
+        assert!(!touches_config(&event(modify, "/rio/themes.toml"), &name));
+        assert!(!touches_config(
+            &event(EventKind::Remove(RemoveKind::File), "/rio/config.toml"),
+            &name
+        ));
+    }
+
+    #[test]
+    fn test_debounce_folds_a_burst_into_one_notification() {
+        let (events_tx, events_rx) = channel();
+        let (tx, rx) = channel();
+        let delay = Duration::from_millis(50);
+        let handle = std::thread::spawn(move || {
+            debounce(events_rx, tx, delay, |relevant: &bool| *relevant)
+        });
+
+        events_tx.send(false).unwrap();
+        assert!(rx.recv_timeout(delay * 4).is_err());
+
+        for _ in 0..3 {
+            events_tx.send(true).unwrap();
+        }
+        assert_eq!(rx.recv_timeout(Duration::from_secs(2)), Ok(()));
+        assert!(rx.recv_timeout(delay * 4).is_err());
+
+        drop(events_tx);
+        handle.join().unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::colors::{deserialize_to_arr, deserialize_to_arr_opt, ColorArray};
 use crate::config::defaults::*;
 use serde::{Deserialize, Serialize};
 use sugarloaf::ImageProperties;
@@ -14,6 +15,48 @@ pub enum WindowMode {
     Windowed,
 }
 
+/// How windows are arranged when `auto-align` is enabled.
+#[derive(Default, Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
+pub enum AlignMode {
+    /// Focused window on the left, the others stacked on the right
+    #[default]
+    #[serde(alias = "stack", alias = "focus-stack", alias = "FocusStack")]
+    Stack,
+    /// Windows tiled in a grid of `align-cols` columns, or the nearest
+    /// square grid when `align-cols` is 0
+    #[serde(alias = "grid")]
+    Grid,
+    /// Windows side by side in a single row
+    #[serde(alias = "even-horizontal")]
+    EvenHorizontal,
+    /// Windows on top of each other in a single column
+    #[serde(alias = "even-vertical")]
+    EvenVertical,
+}
+
+/// Screen edge the focused window sits on in the `Stack` align mode,
+/// the other windows stack on the opposite side.
+#[derive(Default, Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
+pub enum AlignSide {
+    #[default]
+    #[serde(alias = "left")]
+    Left,
+    #[serde(alias = "right")]
+    Right,
+}
+
+/// How the unfocused windows share the stack in the `Stack` align mode.
+#[derive(Default, Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
+pub enum StackOrientation {
+    /// Rows on top of each other, splitting the height
+    #[default]
+    #[serde(alias = "vertical")]
+    Vertical,
+    /// Columns side by side, each the full height
+    #[serde(alias = "horizontal")]
+    Horizontal,
+}
+
 #[derive(Clone, Serialize, Deserialize, Copy, Debug, PartialEq)]
 pub enum Colorspace {
     #[serde(alias = "srgb")]
@@ -79,8 +122,29 @@ pub struct Window {
     pub height: i32,
     #[serde(default = "WindowMode::default")]
     pub mode: WindowMode,
+    /// Initial horizontal position in logical pixels. Ignored unless
+    /// `mode` is `Windowed`.
+    #[serde(default = "Option::default", rename = "initial-x")]
+    pub initial_x: Option<i32>,
+    /// Initial vertical position in logical pixels. Ignored unless
+    /// `mode` is `Windowed`.
+    #[serde(default = "Option::default", rename = "initial-y")]
+    pub initial_y: Option<i32>,
+    /// Index of the monitor used by `Fullscreen` mode, in the order the
+    /// system lists them. Unset or out of range uses the current monitor.
+    #[serde(default = "Option::default", rename = "fullscreen-monitor")]
+    pub fullscreen_monitor: Option<usize>,
     #[serde(default = "default_opacity")]
     pub opacity: f32,
+    /// Opacity of a window while another Rio window has focus. Unset
+    /// keeps `opacity`.
+    #[serde(default = "Option::default", rename = "unfocused-opacity")]
+    pub unfocused_opacity: Option<f32>,
+    /// Opacity of every Rio window while another application is in the
+    /// foreground. It only ever dims, a window already more transparent
+    /// stays as it is. Unset disables the app-level dim.
+    #[serde(default = "Option::default", rename = "inactive-app-opacity")]
+    pub inactive_app_opacity: Option<f32>,
     #[serde(default = "bool::default")]
     pub blur: bool,
     #[serde(rename = "background-image", skip_serializing)]
@@ -89,6 +153,13 @@ pub struct Window {
     pub decorations: Decorations,
     #[serde(default = "bool::default", rename = "macos-use-unified-titlebar")]
     pub macos_use_unified_titlebar: bool,
+    /// Tint of the titlebar on MacOS, unset follows the theme background.
+    #[serde(
+        default = "Option::default",
+        rename = "titlebar-color",
+        deserialize_with = "deserialize_to_arr_opt"
+    )]
+    pub titlebar_color: Option<ColorArray>,
     #[serde(rename = "macos-use-shadow", default = "default_bool_true")]
     pub macos_use_shadow: bool,
     #[serde(rename = "initial-title", skip_serializing)]
@@ -112,10 +183,91 @@ pub struct Window {
     pub align_gap: u32,
     #[serde(default = "default_align_width", rename = "align-width")]
     pub align_width: f32,
+    #[serde(default = "AlignMode::default", rename = "align-mode")]
+    pub align_mode: AlignMode,
+    #[serde(default = "AlignSide::default", rename = "align-side")]
+    pub align_side: AlignSide,
+    #[serde(default = "StackOrientation::default", rename = "stack-orientation")]
+    pub stack_orientation: StackOrientation,
+    /// Number of columns used by the `Grid` align mode, 0 picks the
+    /// nearest square grid for the number of windows.
+    #[serde(default = "default_align_cols", rename = "align-cols")]
+    pub align_cols: u32,
+    /// Glide windows to their aligned position instead of jumping there.
+    #[serde(default = "bool::default", rename = "align-animate")]
+    pub align_animate: bool,
+    /// Time constant of the `align-animate` smoothing in seconds, lower
+    /// is faster. Windows cover ~95% of the way in three times this.
+    #[serde(
+        default = "default_align_animate_speed",
+        rename = "align-animate-speed"
+    )]
+    pub align_animate_speed: f32,
     /// When true, window focus changes only via keyboard shortcuts (CycleWindowNext/Prev),
     /// ignoring mouse clicks and OS-triggered focus changes for auto-align purposes.
     #[serde(default = "bool::default", rename = "keyboard-only-focus")]
     pub keyboard_only_focus: bool,
+    /// Focus the split under the mouse cursor without clicking it. Only
+    /// moves focus between splits of a window, never between windows.
+    #[serde(default = "bool::default", rename = "focus-follows-mouse")]
+    pub focus_follows_mouse: bool,
+    /// Overrides the scale factor reported by the system (e.g. `1.0` to
+    /// render a HiDPI display at 1x). Used by the renderer and by the
+    /// logical size math of window alignment.
+    #[serde(default = "Option::default", rename = "scale-override")]
+    pub scale_override: Option<f64>,
+    /// Tint drawn behind the terminal grid, over the window background
+    /// and background image. Unset keeps the plain window background.
+    #[serde(
+        default = "Option::default",
+        rename = "content-background-color",
+        deserialize_with = "deserialize_to_arr_opt"
+    )]
+    pub content_background_color: Option<ColorArray>,
+    /// Linear gradient filling the window instead of the solid background
+    /// color. Ignored when `background-image` is set.
+    #[serde(default = "Option::default", rename = "background-gradient")]
+    pub background_gradient: Option<BackgroundGradient>,
+}
+
+/// Two-color linear gradient behind the terminal content.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BackgroundGradient {
+    #[serde(deserialize_with = "deserialize_to_arr")]
+    pub from: ColorArray,
+    #[serde(deserialize_with = "deserialize_to_arr")]
+    pub to: ColorArray,
+    /// Direction in degrees like CSS `linear-gradient`: 0 runs bottom to
+    /// top, 90 left to right (default: 180 = top to bottom)
+    #[serde(default = "default_gradient_angle")]
+    pub angle: f32,
+}
+
+fn default_gradient_angle() -> f32 {
+    180.0
+}
+
+impl BackgroundGradient {
+    /// Color at pixel (`x`, `y`) of a `width` x `height` area. As in CSS,
+    /// the gradient line runs through the center and is long enough for
+    /// the corners to get exactly `from` and `to`.
+    pub fn color_at(&self, x: f32, y: f32, width: f32, height: f32) -> ColorArray {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        // Screen space grows downwards, so 0 degrees points at -y
+        let length = (width * sin).abs() + (height * cos).abs();
+        let t = if length > 0.0 {
+            ((x - width / 2.0) * sin - (y - height / 2.0) * cos) / length + 0.5
+        } else {
+            0.0
+        };
+
+        let t = t.clamp(0.0, 1.0);
+        let mut color = self.from;
+        for (channel, to) in color.iter_mut().zip(self.to) {
+            *channel += (to - *channel) * t;
+        }
+        color
+    }
 }
 
 fn default_peek_width() -> u32 {
@@ -130,17 +282,38 @@ fn default_align_width() -> f32 {
     1.0
 }
 
+/// Range `align-width` is clamped to.
+pub const MIN_ALIGN_WIDTH: f32 = 0.1;
+pub const MAX_ALIGN_WIDTH: f32 = 1.0;
+
+/// How far `IncreaseAlignWidth` and `DecreaseAlignWidth` move `align-width`.
+pub const ALIGN_WIDTH_STEP: f32 = 0.05;
+
+fn default_align_cols() -> u32 {
+    0
+}
+
+fn default_align_animate_speed() -> f32 {
+    0.12
+}
+
 impl Default for Window {
     fn default() -> Window {
         Window {
             width: default_window_width(),
             height: default_window_height(),
             mode: WindowMode::default(),
+            initial_x: None,
+            initial_y: None,
+            fullscreen_monitor: None,
             opacity: default_opacity(),
+            unfocused_opacity: None,
+            inactive_app_opacity: None,
             background_image: None,
             decorations: Decorations::default(),
             blur: false,
             macos_use_unified_titlebar: false,
+            titlebar_color: None,
             macos_use_shadow: true,
             initial_title: None,
             windows_use_undecorated_shadow: None,
@@ -151,7 +324,17 @@ impl Default for Window {
             peek_width: default_peek_width(),
             align_gap: default_align_gap(),
             align_width: default_align_width(),
+            align_mode: AlignMode::default(),
+            align_side: AlignSide::default(),
+            stack_orientation: StackOrientation::default(),
+            align_cols: default_align_cols(),
+            align_animate: false,
+            align_animate_speed: default_align_animate_speed(),
             keyboard_only_focus: false,
+            focus_follows_mouse: false,
+            scale_override: None,
+            content_background_color: None,
+            background_gradient: None,
         }
     }
 }
@@ -184,4 +367,338 @@ impl Window {
     pub fn is_fullscreen(&self) -> bool {
         self.mode == WindowMode::Fullscreen
     }
+
+    /// `align-width` moved by `delta`, rounded to hundredths so repeated
+    /// steps don't drift and saturating at the range bounds.
+    #[inline]
+    pub fn stepped_align_width(&self, delta: f32) -> f32 {
+        (((self.align_width + delta) * 100.0).round() / 100.0)
+            .clamp(MIN_ALIGN_WIDTH, MAX_ALIGN_WIDTH)
+    }
+
+    /// Initial position requested by `initial-x`/`initial-y`, or `None`
+    /// when neither is set or the window is maximized/fullscreen.
+    #[inline]
+    pub fn initial_position(&self) -> Option<(Option<i32>, Option<i32>)> {
+        if self.mode != WindowMode::Windowed
+            || (self.initial_x.is_none() && self.initial_y.is_none())
+        {
+            return None;
+        }
+        Some((self.initial_x, self.initial_y))
+    }
+
+    /// Returns the scale factor Rio should use, `scale-override` takes
+    /// precedence over the system value. Non-positive overrides are ignored.
+    #[inline]
+    pub fn effective_scale(&self, system_scale: f64) -> f64 {
+        match self.scale_override {
+            Some(scale) if scale.is_finite() && scale > 0.0 => scale,
+            _ => system_scale,
+        }
+    }
+
+    /// Opacity of a window given whether it and the application have
+    /// focus. `unfocused-opacity` replaces `opacity` for unfocused
+    /// windows, then `inactive-app-opacity` dims every window further
+    /// while the app is in the background.
+    pub fn effective_opacity(&self, window_focused: bool, app_focused: bool) -> f32 {
+        let mut opacity = self.opacity;
+        if !window_focused {
+            opacity = self.unfocused_opacity.unwrap_or(opacity);
+        }
+        if !app_focused {
+            if let Some(dim) = self.inactive_app_opacity {
+                opacity = opacity.min(dim);
+            }
+        }
+        opacity.clamp(0.0, 1.0)
+    }
+
+    /// Whether windows need a transparent surface, for `opacity` or any
+    /// of the unfocused opacities.
+    pub fn is_transparent(&self) -> bool {
+        [
+            Some(self.opacity),
+            self.unfocused_opacity,
+            self.inactive_app_opacity,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|opacity| opacity < 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_scale_without_override() {
+        let window = Window::default();
+        assert_eq!(window.effective_scale(2.0), 2.0);
+        assert_eq!(window.effective_scale(1.25), 1.25);
+    }
+
+    #[test]
+    fn test_effective_scale_with_override() {
+        let window = Window {
+            scale_override: Some(1.0),
+            ..Window::default()
+        };
+        assert_eq!(window.effective_scale(2.0), 1.0);
+
+        let invalid = Window {
+            scale_override: Some(0.0),
+            ..Window::default()
+        };
+        assert_eq!(invalid.effective_scale(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_content_background_color() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.content_background_color, None);
+
+        let window: Window =
+            toml::from_str("content-background-color = '#00000080'").unwrap();
+        let color = window.content_background_color.unwrap();
+        assert_eq!(&color[..3], &[0.0, 0.0, 0.0]);
+        assert_eq!(color[3], 128.0 / 255.0);
+    }
+
+    #[test]
+    fn test_titlebar_color() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.titlebar_color, None);
+
+        let window: Window = toml::from_str("titlebar-color = '#ff0000'").unwrap();
+        assert_eq!(window.titlebar_color, Some([1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_effective_opacity_defaults_to_opacity() {
+        let window = Window {
+            opacity: 0.9,
+            ..Window::default()
+        };
+        for (window_focused, app_focused) in [(true, true), (false, true), (false, false)]
+        {
+            assert_eq!(window.effective_opacity(window_focused, app_focused), 0.9);
+        }
+        assert!(window.is_transparent());
+        assert!(!Window::default().is_transparent());
+    }
+
+    #[test]
+    fn test_effective_opacity_precedence() {
+        let window = Window {
+            opacity: 1.0,
+            unfocused_opacity: Some(0.8),
+            inactive_app_opacity: Some(0.5),
+            ..Window::default()
+        };
+        assert_eq!(window.effective_opacity(true, true), 1.0);
+        assert_eq!(window.effective_opacity(false, true), 0.8);
+        // The app-level dim wins while Rio is in the background
+        assert_eq!(window.effective_opacity(false, false), 0.5);
+        assert!(window.is_transparent());
+
+        let app_dim_only = Window {
+            unfocused_opacity: None,
+            ..window
+        };
+        assert_eq!(app_dim_only.effective_opacity(false, true), 1.0);
+        assert_eq!(app_dim_only.effective_opacity(false, false), 0.5);
+    }
+
+    #[test]
+    fn test_effective_opacity_app_dim_never_brightens() {
+        let window = Window {
+            opacity: 0.9,
+            unfocused_opacity: Some(0.3),
+            inactive_app_opacity: Some(0.6),
+            ..Window::default()
+        };
+        assert_eq!(window.effective_opacity(false, false), 0.3);
+        assert_eq!(window.effective_opacity(true, false), 0.6);
+
+        let out_of_range = Window {
+            inactive_app_opacity: Some(-1.0),
+            ..window
+        };
+        assert_eq!(out_of_range.effective_opacity(true, false), 0.0);
+    }
+
+    #[test]
+    fn test_align_animate() {
+        let window: Window = toml::from_str("").unwrap();
+        assert!(!window.align_animate);
+        assert_eq!(window.align_animate_speed, 0.12);
+
+        let window: Window =
+            toml::from_str("align-animate = true\nalign-animate-speed = 0.3").unwrap();
+        assert!(window.align_animate);
+        assert_eq!(window.align_animate_speed, 0.3);
+    }
+
+    #[test]
+    fn test_stepped_align_width_saturates() {
+        let mut window = Window::default();
+        assert_eq!(
+            window.stepped_align_width(ALIGN_WIDTH_STEP),
+            MAX_ALIGN_WIDTH
+        );
+
+        for _ in 0..40 {
+            window.align_width = window.stepped_align_width(-ALIGN_WIDTH_STEP);
+        }
+        assert_eq!(window.align_width, MIN_ALIGN_WIDTH);
+        assert_eq!(
+            window.stepped_align_width(-ALIGN_WIDTH_STEP),
+            MIN_ALIGN_WIDTH
+        );
+
+        window.align_width = window.stepped_align_width(ALIGN_WIDTH_STEP);
+        assert_eq!(window.align_width, 0.15);
+    }
+
+    #[test]
+    fn test_align_mode() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.align_mode, AlignMode::Stack);
+        assert_eq!(window.align_cols, 0);
+
+        for (value, mode) in [
+            ("focus-stack", AlignMode::Stack),
+            ("grid", AlignMode::Grid),
+            ("even-horizontal", AlignMode::EvenHorizontal),
+            ("EvenVertical", AlignMode::EvenVertical),
+        ] {
+            let window: Window =
+                toml::from_str(&format!("align-mode = \"{value}\"")).unwrap();
+            assert_eq!(window.align_mode, mode);
+        }
+
+        assert_eq!(window.align_side, AlignSide::Left);
+        let window: Window = toml::from_str("align-side = \"right\"").unwrap();
+        assert_eq!(window.align_side, AlignSide::Right);
+
+        assert_eq!(window.stack_orientation, StackOrientation::Vertical);
+        let window: Window =
+            toml::from_str("stack-orientation = \"horizontal\"").unwrap();
+        assert_eq!(window.stack_orientation, StackOrientation::Horizontal);
+    }
+
+    fn assert_close(actual: ColorArray, expected: ColorArray) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 0.001, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_background_gradient() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.background_gradient, None);
+
+        let window: Window = toml::from_str(
+            "background-gradient = { from = '#000000', to = '#ffffff', angle = 90 }",
+        )
+        .unwrap();
+        let gradient = window.background_gradient.unwrap();
+        assert_eq!(gradient.from, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(gradient.to, [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(gradient.angle, 90.0);
+
+        let window: Window =
+            toml::from_str("background-gradient = { from = '#000000', to = '#ffffff' }")
+                .unwrap();
+        assert_eq!(window.background_gradient.unwrap().angle, 180.0);
+    }
+
+    #[test]
+    fn test_background_gradient_color_at() {
+        let black = [0.0, 0.0, 0.0, 1.0];
+        let white = [1.0, 1.0, 1.0, 0.0];
+        let gradient = |angle| BackgroundGradient {
+            from: black,
+            to: white,
+            angle,
+        };
+
+        // Left to right
+        let horizontal = gradient(90.0);
+        assert_close(horizontal.color_at(0.0, 50.0, 200.0, 100.0), black);
+        assert_close(horizontal.color_at(200.0, 0.0, 200.0, 100.0), white);
+        assert_close(
+            horizontal.color_at(100.0, 100.0, 200.0, 100.0),
+            [0.5, 0.5, 0.5, 0.5],
+        );
+
+        // Top to bottom, the default
+        let vertical = gradient(180.0);
+        assert_close(vertical.color_at(30.0, 0.0, 200.0, 100.0), black);
+        assert_close(
+            vertical.color_at(30.0, 25.0, 200.0, 100.0),
+            [0.25, 0.25, 0.25, 0.75],
+        );
+        assert_close(vertical.color_at(30.0, 100.0, 200.0, 100.0), white);
+
+        // Corner to corner reaches both colors exactly at the corners
+        let diagonal = gradient(135.0);
+        assert_close(diagonal.color_at(0.0, 0.0, 100.0, 100.0), black);
+        assert_close(diagonal.color_at(100.0, 100.0, 100.0, 100.0), white);
+        assert_close(
+            diagonal.color_at(100.0, 0.0, 100.0, 100.0),
+            [0.5, 0.5, 0.5, 0.5],
+        );
+
+        // Outside the area clamps to the end colors
+        assert_close(horizontal.color_at(-50.0, 0.0, 200.0, 100.0), black);
+        assert_close(horizontal.color_at(0.0, 0.0, 0.0, 0.0), black);
+    }
+
+    #[test]
+    fn test_initial_position() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.initial_x, None);
+        assert_eq!(window.initial_y, None);
+        assert_eq!(window.initial_position(), None);
+
+        let window: Window = toml::from_str(
+            r#"
+            initial-x = 1920
+            initial-y = -40
+        "#,
+        )
+        .unwrap();
+        assert_eq!(window.initial_x, Some(1920));
+        assert_eq!(window.initial_y, Some(-40));
+        assert_eq!(window.initial_position(), Some((Some(1920), Some(-40))));
+
+        let window: Window = toml::from_str("initial-y = 100").unwrap();
+        assert_eq!(window.initial_position(), Some((None, Some(100))));
+    }
+
+    #[test]
+    fn test_fullscreen_monitor() {
+        let window: Window = toml::from_str("").unwrap();
+        assert_eq!(window.fullscreen_monitor, None);
+
+        let window: Window =
+            toml::from_str("mode = \"fullscreen\"\nfullscreen-monitor = 1").unwrap();
+        assert_eq!(window.fullscreen_monitor, Some(1));
+    }
+
+    #[test]
+    fn test_initial_position_ignored_when_not_windowed() {
+        for mode in ["maximized", "fullscreen"] {
+            let window: Window = toml::from_str(&format!(
+                "mode = \"{mode}\"\ninitial-x = 10\ninitial-y = 20"
+            ))
+            .unwrap();
+            assert_eq!(window.initial_x, Some(10));
+            assert_eq!(window.initial_position(), None);
+        }
+    }
 }
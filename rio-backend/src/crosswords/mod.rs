@@ -427,6 +427,8 @@ where
     pub route_id: usize,
     title_stack: Vec<String>,
     pub current_directory: Option<std::path::PathBuf>,
+    /// Host of the last OSC 7 working directory URI, if remote.
+    pub current_host: Option<String>,
 
     // The stack for the keyboard modes.
     keyboard_mode_stack: [u8; KEYBOARD_MODE_STACK_MAX_DEPTH],
@@ -483,6 +485,7 @@ impl<U: EventListener> Crosswords<U> {
             route_id,
             title_stack: Default::default(),
             current_directory: None,
+            current_host: None,
             keyboard_mode_stack: Default::default(),
             keyboard_mode_idx: 0,
             inactive_keyboard_mode_stack: Default::default(),
@@ -2040,6 +2043,11 @@ impl<U: EventListener> Handler for Crosswords<U> {
         self.current_directory = Some(path);
     }
 
+    fn set_current_host(&mut self, host: Option<String>) {
+        trace!("Setting working directory host {:?}", host);
+        self.current_host = host;
+    }
+
     #[inline]
     fn set_cursor_style(&mut self, style: Option<CursorShape>, blinking: bool) {
         if let Some(cursor_shape) = style {
@@ -70,6 +70,61 @@ pub enum SoundEvent {
     KeyEnter,
     KeySpace,
     KeyBackspace,
+    Scroll,
+    Resize,
+    Copy,
+    Paste,
+    /// The foreground job of a pane finished
+    ProcessExit,
+}
+
+/// Group of sound events sharing a volume, see
+/// `sound-effects.<category>-volume`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundCategory {
+    /// Key presses
+    Keyboard,
+    /// Windows, tabs and splits coming and going, scrolling and resizing
+    Navigation,
+    /// Bell, finished jobs and the clipboard
+    System,
+}
+
+impl SoundEvent {
+    /// Category whose volume the event plays at.
+    #[inline]
+    pub fn category(self) -> SoundCategory {
+        match self {
+            SoundEvent::KeyLetter
+            | SoundEvent::KeyEnter
+            | SoundEvent::KeySpace
+            | SoundEvent::KeyBackspace => SoundCategory::Keyboard,
+            SoundEvent::WindowCreate
+            | SoundEvent::WindowClose
+            | SoundEvent::TabCreate
+            | SoundEvent::TabClose
+            | SoundEvent::SplitCreate
+            | SoundEvent::SplitClose
+            | SoundEvent::Scroll
+            | SoundEvent::Resize => SoundCategory::Navigation,
+            SoundEvent::Bell
+            | SoundEvent::Copy
+            | SoundEvent::Paste
+            | SoundEvent::ProcessExit => SoundCategory::System,
+        }
+    }
+
+    /// Whether the event is a key press, gated by `keyboard-enabled`.
+    #[inline]
+    pub fn is_keyboard(self) -> bool {
+        matches!(
+            self,
+            SoundEvent::KeyLetter
+                | SoundEvent::KeyEnter
+                | SoundEvent::KeySpace
+                | SoundEvent::KeyBackspace
+        )
+    }
 }
 
 #[derive(Clone)]
@@ -190,6 +245,21 @@ pub enum RioEvent {
     /// Play a sound effect for the given event.
     PlaySound(SoundEvent),
 
+    /// Play a sound effect panned from -1.0 (left) to 1.0 (right).
+    PlaySoundPanned(SoundEvent, f32),
+
+    /// Mute or unmute sound effects.
+    ToggleMuteSounds,
+
+    /// Switch every window to the next navigation mode.
+    CycleNavigationMode,
+
+    /// Move the focused window's `align-width` ratio by the given amount.
+    AdjustAlignWidth(f32),
+
+    /// Copy the computed auto-align layout to the clipboard as JSON.
+    CopyWindowLayout,
+
     // No operation
     Noop,
 }
@@ -257,6 +327,13 @@ impl Debug for RioEvent {
             RioEvent::PlaySound(event) => {
                 write!(f, "PlaySound({event:?})")
             }
+            RioEvent::PlaySoundPanned(event, pan) => {
+                write!(f, "PlaySoundPanned({event:?}, {pan})")
+            }
+            RioEvent::ToggleMuteSounds => write!(f, "ToggleMuteSounds"),
+            RioEvent::CycleNavigationMode => write!(f, "CycleNavigationMode"),
+            RioEvent::AdjustAlignWidth(delta) => write!(f, "AdjustAlignWidth({delta})"),
+            RioEvent::CopyWindowLayout => write!(f, "CopyWindowLayout"),
             RioEvent::Noop => write!(f, "Noop"),
             RioEvent::Copy(_) => write!(f, "Copy"),
             RioEvent::Paste => write!(f, "Paste"),
@@ -144,6 +144,9 @@ pub trait Handler {
     /// OSC to set current directory.
     fn set_current_directory(&mut self, _: std::path::PathBuf) {}
 
+    /// OSC to set the host of the current directory, `None` for local URIs.
+    fn set_current_host(&mut self, _: Option<String>) {}
+
     /// Set the cursor style.
     fn set_cursor_style(&mut self, _style: Option<CursorShape>, _blinking: bool) {}
 
@@ -807,6 +810,12 @@ impl<U: Handler, T: Timeout> copa::Perform for Performer<'_, U, T> {
             b"7" => {
                 if let Ok(s) = simd_utf8::from_utf8_fast(params[1]) {
                     if let Ok(url) = url::Url::parse(s) {
+                        let host = url
+                            .host_str()
+                            .filter(|host| !host.is_empty() && *host != "localhost")
+                            .map(str::to_string);
+                        self.handler.set_current_host(host);
+
                         let path = url.path();
 
                         // NB the path coming from Url has a leading slash; must slice that off
@@ -121,6 +121,7 @@ impl ApplicationHandler for Application {
                 border_width: 2.0,
                 border_radius: [10.0, 10.0, 10.0, 10.0],
                 size: [200.0, 200.0],
+                gradient_color: [0.0, 0.0, 0.0, 0.0],
             }),
             Object::RichText(RichText {
                 id: self.rich_texts[0],
@@ -137,6 +138,7 @@ impl ApplicationHandler for Application {
                 border_width: 2.0,
                 border_radius: [0.0, 0.0, 0.0, 0.0],
                 size: [200.0, 150.0],
+                gradient_color: [0.0, 0.0, 0.0, 0.0],
             }),
             Object::RichText(RichText {
                 id: self.rich_texts[1],
@@ -153,6 +155,7 @@ impl ApplicationHandler for Application {
                 border_width: 2.0,
                 border_radius: [0.0, 0.0, 0.0, 0.0],
                 size: [320.0, 150.0],
+                gradient_color: [0.0, 0.0, 0.0, 0.0],
             }),
             Object::RichText(RichText {
                 id: self.rich_texts[2],
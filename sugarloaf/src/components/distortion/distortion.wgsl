@@ -2,6 +2,13 @@ struct DistortionParams {
     distortion_type: u32,
     strength: f32,
     center: vec2<f32>,
+    fov: f32,
+    edge_fill: u32,
+    _padding: vec2<u32>,
+    red_offset: vec2<f32>,
+    green_offset: vec2<f32>,
+    blue_offset: vec2<f32>,
+    vignette_radius: vec2<f32>,
 }
 
 struct VertexOutput {
@@ -64,11 +71,78 @@ fn perspective_distort(
     );
 }
 
+/// Equidistant fisheye projection.
+/// Each output point is treated as a ray whose angle from the
+/// optical axis grows linearly with its distance from the center
+/// (r = f * theta). The ray is projected back onto the flat source
+/// image, keeping unit scale near the center.
+fn fisheye_distort(
+    uv: vec2<f32>,
+    center: vec2<f32>,
+    fov: f32,
+) -> vec2<f32> {
+    let d = (uv - center) * 2.0;
+    let r = length(d);
+    if r < 0.00001 {
+        return uv;
+    }
+
+    let half_fov = fov * 0.5;
+    let theta = r * half_fov;
+    let ray = vec3<f32>(sin(theta) * d / r, cos(theta));
+
+    // Rays at or beyond 90 degrees never hit the image plane
+    if ray.z <= 0.00001 {
+        return vec2<f32>(-1.0, -1.0);
+    }
+
+    let p = ray.xy / ray.z / half_fov;
+    return center + p * 0.5;
+}
+
+/// Sample each channel at its own offset. Channels shifted out of
+/// the frame go black unless the sampler clamps or mirrors them.
+fn sample_channel(uv: vec2<f32>, offset: vec2<f32>) -> vec4<f32> {
+    let shifted = uv + offset;
+    if params.edge_fill == 0u && (shifted.x < 0.0 || shifted.x > 1.0
+        || shifted.y < 0.0 || shifted.y > 1.0)
+    {
+        return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    }
+    return textureSample(src_texture, tex_sampler, shifted);
+}
+
+fn chromatic_aberration(uv: vec2<f32>) -> vec4<f32> {
+    let red = sample_channel(uv, params.red_offset);
+    let green = sample_channel(uv, params.green_offset);
+    let blue = sample_channel(uv, params.blue_offset);
+    return vec4<f32>(red.r, green.g, blue.b, green.a);
+}
+
+/// Darken the frame from `vignette_radius.x` to `vignette_radius.y`,
+/// measured from the center so that 1.0 reaches the corners.
+fn vignette(uv: vec2<f32>) -> vec4<f32> {
+    let color = textureSample(src_texture, tex_sampler, uv);
+    let dist = length(uv - params.center) * 1.41421356;
+    let fade = smoothstep(
+        params.vignette_radius.x, params.vignette_radius.y, dist,
+    );
+    let factor = clamp(1.0 - params.strength * fade, 0.0, 1.0);
+    return vec4<f32>(color.rgb * factor, color.a);
+}
+
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     var uv = input.tex_coords;
 
-    // 1 = barrel, 2 = perspective
+    if params.distortion_type == 4u {
+        return chromatic_aberration(uv);
+    }
+    if params.distortion_type == 5u {
+        return vignette(uv);
+    }
+
+    // 1 = barrel, 2 = perspective, 3 = fisheye
     if params.distortion_type == 1u {
         uv = barrel_distort(
             uv, params.center, params.strength,
@@ -77,11 +151,16 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
         uv = perspective_distort(
             uv, params.center, params.strength,
         );
+    } else if params.distortion_type == 3u {
+        uv = fisheye_distort(
+            uv, params.center, params.fov,
+        );
     }
 
-    // Out-of-bounds samples return black
-    if uv.x < 0.0 || uv.x > 1.0
-        || uv.y < 0.0 || uv.y > 1.0
+    // Out-of-bounds samples return black unless the sampler
+    // clamps or mirrors them (edge_fill 1 / 2)
+    if params.edge_fill == 0u && (uv.x < 0.0 || uv.x > 1.0
+        || uv.y < 0.0 || uv.y > 1.0)
     {
         return vec4<f32>(0.0, 0.0, 0.0, 1.0);
     }
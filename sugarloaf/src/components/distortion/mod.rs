@@ -1,34 +1,406 @@
 use crate::context::Context;
+use crate::sugarloaf::Colorspace;
 use bytemuck::{Pod, Zeroable};
 use std::mem;
+use std::time::{Duration, Instant};
 
 /// Distortion type constants matching the shader.
 pub const DISTORTION_NONE: u32 = 0;
 pub const DISTORTION_BARREL: u32 = 1;
 pub const DISTORTION_PERSPECTIVE: u32 = 2;
+pub const DISTORTION_FISHEYE: u32 = 3;
+pub const DISTORTION_CHROMATIC_ABERRATION: u32 = 4;
+pub const DISTORTION_VIGNETTE: u32 = 5;
+
+/// Edge fill constants matching the shader.
+pub const DISTORTION_EDGE_BLACK: u32 = 0;
+pub const DISTORTION_EDGE_CLAMP: u32 = 1;
+pub const DISTORTION_EDGE_MIRROR: u32 = 2;
+
+/// Sampling filter constants.
+pub const DISTORTION_FILTER_LINEAR: u32 = 0;
+pub const DISTORTION_FILTER_NEAREST: u32 = 1;
+
+/// Intermediate precision constants.
+pub const DISTORTION_PRECISION_AUTO: u32 = 0;
+pub const DISTORTION_PRECISION_STANDARD: u32 = 1;
+pub const DISTORTION_PRECISION_HIGH: u32 = 2;
+
+/// Format of the high precision intermediate.
+pub const HDR_INTERMEDIATE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
 
 /// GPU-side distortion parameters. Uploaded as a uniform buffer.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Zeroable, Pod)]
 pub struct DistortionParams {
-    /// 0=none, 1=barrel, 2=perspective
+    /// 0=none, 1=barrel, 2=perspective, 3=fisheye, 4=chromatic aberration,
+    /// 5=vignette
     pub distortion_type: u32,
     /// Effect magnitude (can be negative for inverse)
     pub strength: f32,
     /// Normalized center point [x, y]
     pub center: [f32; 2],
+    /// Fisheye field of view in radians
+    pub fov: f32,
+    /// 0=black, 1=clamp, 2=mirror
+    pub edge_fill: u32,
+    /// 0=linear, 1=nearest. Only used to build the sampler.
+    pub filter: u32,
+    /// 0=auto, 1=standard, 2=high. Only used to pick the intermediate
+    /// format.
+    pub precision: u32,
+    /// UV offsets each channel is sampled at. Used by chromatic
+    /// aberration, kept separate (not an array) so the uniform layout
+    /// matches the shader without extra stride padding.
+    pub red_offset: [f32; 2],
+    pub green_offset: [f32; 2],
+    pub blue_offset: [f32; 2],
+    /// Vignette [inner, outer] radius, as a distance from `center` where
+    /// 1.0 is the corners. Also pads the offsets to 16 bytes.
+    pub vignette_radius: [f32; 2],
+}
+
+impl DistortionParams {
+    /// Set the channel offsets for chromatic aberration: red and blue are
+    /// pushed `strength` UV units apart horizontally, green stays put.
+    pub fn with_chromatic_aberration(mut self, strength: f32) -> Self {
+        self.red_offset = [strength, 0.0];
+        self.green_offset = [0.0, 0.0];
+        self.blue_offset = [-strength, 0.0];
+        self
+    }
+
+    /// Set the vignette radii. An outer radius at or inside the inner one
+    /// is nudged out so the shader's smoothstep stays defined.
+    pub fn with_vignette(mut self, inner_radius: f32, outer_radius: f32) -> Self {
+        let inner_radius = inner_radius.max(0.0);
+        self.vignette_radius = [inner_radius, outer_radius.max(inner_radius + 0.001)];
+        self
+    }
+
+    /// Params `t` of the way from `self` to `target`. Numeric fields are
+    /// blended linearly, the effect type, edge fill, filter and precision
+    /// switch to `target` right away.
+    pub fn lerp(&self, target: &DistortionParams, t: f32) -> DistortionParams {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let mix2 = |a: [f32; 2], b: [f32; 2]| [mix(a[0], b[0]), mix(a[1], b[1])];
+        DistortionParams {
+            strength: mix(self.strength, target.strength),
+            center: mix2(self.center, target.center),
+            fov: mix(self.fov, target.fov),
+            red_offset: mix2(self.red_offset, target.red_offset),
+            green_offset: mix2(self.green_offset, target.green_offset),
+            blue_offset: mix2(self.blue_offset, target.blue_offset),
+            vignette_radius: mix2(self.vignette_radius, target.vignette_radius),
+            ..*target
+        }
+    }
+}
+
+/// Clock the distortion animation reads. Only differences between two
+/// readings matter, so a manual clock makes the eased params depend on
+/// the steps taken alone, e.g. to record a session frame by frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TimeSource {
+    /// Monotonic system clock
+    #[default]
+    Real,
+    /// Moves only when `advance` is called
+    Manual(Instant),
+    /// Moves by `step` every rendered frame, and on `advance`
+    FixedStep { now: Instant, step: Duration },
+}
+
+impl TimeSource {
+    /// Manual clock starting now.
+    pub fn manual() -> Self {
+        TimeSource::Manual(Instant::now())
+    }
+
+    /// Clock moving by `step` every rendered frame, starting now.
+    pub fn fixed_step(step: Duration) -> Self {
+        TimeSource::FixedStep {
+            now: Instant::now(),
+            step,
+        }
+    }
+
+    /// Current time of this clock.
+    pub fn now(&self) -> Instant {
+        match self {
+            TimeSource::Real => Instant::now(),
+            TimeSource::Manual(now) | TimeSource::FixedStep { now, .. } => *now,
+        }
+    }
+
+    /// Move a manual or fixed step clock forward by `by`. The real clock
+    /// can't be moved.
+    pub fn advance(&mut self, by: Duration) {
+        match self {
+            TimeSource::Real => {}
+            TimeSource::Manual(now) | TimeSource::FixedStep { now, .. } => *now += by,
+        }
+    }
+
+    /// Time for the next frame, stepping a fixed step clock first.
+    fn next_frame(&mut self) -> Instant {
+        if let TimeSource::FixedStep { now, step } = self {
+            *now += *step;
+        }
+        self.now()
+    }
+}
+
+/// Params easing from `start_params` to `target_params`, see
+/// `DistortionBrush::animate_to`.
+#[derive(Debug, Clone, Copy)]
+struct DistortionAnimation {
+    start_params: DistortionParams,
+    target_params: DistortionParams,
+    start_instant: Instant,
+    duration: Duration,
+}
+
+impl DistortionAnimation {
+    /// Blend factor at `now`, `1.0` once the animation is over.
+    fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.start_instant);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    #[inline]
+    fn params_at(&self, t: f32) -> DistortionParams {
+        self.start_params.lerp(&self.target_params, t)
+    }
+}
+
+impl Default for DistortionParams {
+    fn default() -> Self {
+        Self {
+            distortion_type: DISTORTION_NONE,
+            strength: 0.0,
+            center: [0.5, 0.5],
+            fov: 120.0_f32.to_radians(),
+            edge_fill: DISTORTION_EDGE_BLACK,
+            filter: DISTORTION_FILTER_LINEAR,
+            precision: DISTORTION_PRECISION_AUTO,
+            red_offset: [0.0, 0.0],
+            green_offset: [0.0, 0.0],
+            blue_offset: [0.0, 0.0],
+            vignette_radius: [0.4, 1.0],
+        }
+    }
+}
+
+/// Sampler address mode for an edge fill. Black also clamps, the
+/// shader paints out-of-bounds samples itself.
+#[inline]
+fn edge_fill_address_mode(edge_fill: u32) -> wgpu::AddressMode {
+    match edge_fill {
+        DISTORTION_EDGE_MIRROR => wgpu::AddressMode::MirrorRepeat,
+        _ => wgpu::AddressMode::ClampToEdge,
+    }
+}
+
+#[inline]
+fn filter_mode(filter: u32) -> wgpu::FilterMode {
+    match filter {
+        DISTORTION_FILTER_NEAREST => wgpu::FilterMode::Nearest,
+        _ => wgpu::FilterMode::Linear,
+    }
+}
+
+/// Sampler for the given edge fill and sampling filter.
+fn sampler_descriptor(edge_fill: u32, filter: u32) -> wgpu::SamplerDescriptor<'static> {
+    let address_mode = edge_fill_address_mode(edge_fill);
+    let filter_mode = filter_mode(filter);
+    wgpu::SamplerDescriptor {
+        label: Some("sugarloaf::distortion sampler"),
+        address_mode_u: address_mode,
+        address_mode_v: address_mode,
+        mag_filter: filter_mode,
+        min_filter: filter_mode,
+        ..Default::default()
+    }
+}
+
+/// Format the distortion renders into before it is resolved to the
+/// surface. Wide gamut colorspaces get `HDR_INTERMEDIATE_FORMAT` on auto
+/// so gradients don't band, `supports_hdr` false always falls back to
+/// `surface_format`.
+pub fn intermediate_format(
+    colorspace: Colorspace,
+    surface_format: wgpu::TextureFormat,
+    precision: u32,
+    supports_hdr: bool,
+) -> wgpu::TextureFormat {
+    let wants_hdr = match precision {
+        DISTORTION_PRECISION_STANDARD => false,
+        DISTORTION_PRECISION_HIGH => true,
+        _ => matches!(colorspace, Colorspace::DisplayP3 | Colorspace::Rec2020),
+    };
+    if wants_hdr && supports_hdr && surface_format != HDR_INTERMEDIATE_FORMAT {
+        HDR_INTERMEDIATE_FORMAT
+    } else {
+        surface_format
+    }
+}
+
+fn create_pipeline(
+    ctx: &Context,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::PipelineLayout,
+    format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("sugarloaf::distortion pipeline"),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        })
+}
+
+/// Pipeline, sampler and params of one full-screen draw.
+struct Stage<'a> {
+    pipeline: &'a wgpu::RenderPipeline,
+    sampler: &'a wgpu::Sampler,
+    params_bind_group: &'a wgpu::BindGroup,
+}
+
+/// Resources to distort into a high precision texture and resolve it
+/// back to the surface format.
+struct HdrIntermediate {
+    /// Distortion pipeline targeting `HDR_INTERMEDIATE_FORMAT`
+    pipeline: wgpu::RenderPipeline,
+    /// Pass-through params (no distortion) for the resolve draw
+    resolve_params_bind_group: wgpu::BindGroup,
+    /// Nearest sampler, the resolve is 1:1
+    resolve_sampler: wgpu::Sampler,
+    /// Texture distorted into and resolved from, reused across frames
+    texture: Option<(wgpu::Texture, wgpu::TextureView)>,
+}
+
+impl HdrIntermediate {
+    fn new(
+        ctx: &Context,
+        shader: &wgpu::ShaderModule,
+        pipeline_layout: &wgpu::PipelineLayout,
+        params_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let resolve_params = DistortionParams::default();
+        let resolve_params_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("sugarloaf::distortion resolve params"),
+            size: mem::size_of::<DistortionParams>() as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        ctx.queue.write_buffer(
+            &resolve_params_buffer,
+            0,
+            bytemuck::bytes_of(&resolve_params),
+        );
+
+        Self {
+            pipeline: create_pipeline(
+                ctx,
+                shader,
+                pipeline_layout,
+                HDR_INTERMEDIATE_FORMAT,
+            ),
+            resolve_params_bind_group: ctx.device.create_bind_group(
+                &wgpu::BindGroupDescriptor {
+                    label: Some("sugarloaf::distortion resolve params bind group"),
+                    layout: params_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: resolve_params_buffer.as_entire_binding(),
+                    }],
+                },
+            ),
+            resolve_sampler: ctx.device.create_sampler(&sampler_descriptor(
+                DISTORTION_EDGE_CLAMP,
+                DISTORTION_FILTER_NEAREST,
+            )),
+            texture: None,
+        }
+    }
+
+    /// Allocate `texture` unless one of `size` already exists.
+    fn ensure_texture(&mut self, ctx: &Context, size: wgpu::Extent3d) {
+        if self
+            .texture
+            .as_ref()
+            .is_some_and(|(texture, _)| texture.size() == size)
+        {
+            return;
+        }
+
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("sugarloaf::distortion hdr intermediate"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: HDR_INTERMEDIATE_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.texture = Some((texture, view));
+    }
 }
 
 /// Post-processing brush that applies distortion effects to the
 /// rendered frame via a full-screen triangle draw with distorted
 /// UV sampling.
 pub struct DistortionBrush {
+    shader: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
     pipeline: wgpu::RenderPipeline,
     bind_group_layout: wgpu::BindGroupLayout,
+    params_bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
     params_buffer: wgpu::Buffer,
     params_bind_group: wgpu::BindGroup,
     current_params: DistortionParams,
+    /// Set while easing towards new params
+    animation: Option<DistortionAnimation>,
+    /// Clock the animation reads
+    time_source: TimeSource,
+    /// Set while distorting through `HDR_INTERMEDIATE_FORMAT`
+    hdr: Option<HdrIntermediate>,
+    /// Copy of the frame sampled by `render`, reused across frames
+    src_copy: Option<(wgpu::Texture, wgpu::TextureView)>,
+    /// Size `src_copy` was allocated with
+    current_size: Option<wgpu::Extent3d>,
 }
 
 impl DistortionBrush {
@@ -86,11 +458,7 @@ impl DistortionBrush {
                     }],
                 });
 
-        let params = DistortionParams {
-            distortion_type: DISTORTION_NONE,
-            strength: 0.0,
-            center: [0.5, 0.5],
-        };
+        let params = DistortionParams::default();
 
         let params_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("sugarloaf::distortion params"),
@@ -109,12 +477,9 @@ impl DistortionBrush {
                 }],
             });
 
-        let sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("sugarloaf::distortion sampler"),
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            ..Default::default()
-        });
+        let sampler = ctx
+            .device
+            .create_sampler(&sampler_descriptor(params.edge_fill, params.filter));
 
         let pipeline_layout =
             ctx.device
@@ -124,87 +489,272 @@ impl DistortionBrush {
                     immediate_size: 0,
                 });
 
-        let pipeline =
-            ctx.device
-                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                    label: Some("sugarloaf::distortion pipeline"),
-                    layout: Some(&pipeline_layout),
-                    vertex: wgpu::VertexState {
-                        module: &shader,
-                        entry_point: Some("vs_main"),
-                        buffers: &[],
-                        compilation_options: wgpu::PipelineCompilationOptions::default(),
-                    },
-                    fragment: Some(wgpu::FragmentState {
-                        module: &shader,
-                        entry_point: Some("fs_main"),
-                        targets: &[Some(wgpu::ColorTargetState {
-                            format: ctx.format,
-                            blend: Some(wgpu::BlendState::REPLACE),
-                            write_mask: wgpu::ColorWrites::ALL,
-                        })],
-                        compilation_options: wgpu::PipelineCompilationOptions::default(),
-                    }),
-                    primitive: wgpu::PrimitiveState {
-                        topology: wgpu::PrimitiveTopology::TriangleList,
-                        ..Default::default()
-                    },
-                    depth_stencil: None,
-                    multisample: wgpu::MultisampleState::default(),
-                    multiview_mask: None,
-                    cache: None,
-                });
+        let pipeline = create_pipeline(ctx, &shader, &pipeline_layout, ctx.format);
 
         Self {
+            shader,
+            pipeline_layout,
             pipeline,
             bind_group_layout,
+            params_bind_group_layout,
             sampler,
             params_buffer,
             params_bind_group,
             current_params: params,
+            animation: None,
+            time_source: TimeSource::default(),
+            hdr: None,
+            src_copy: None,
+            current_size: None,
         }
     }
 
     /// Update distortion parameters. Called when config changes.
-    pub fn update_params(&mut self, queue: &wgpu::Queue, params: DistortionParams) {
+    /// The sampler is rebuilt when the edge fill or filter changed, the
+    /// high precision pipeline when the intermediate format changed.
+    pub fn update_params(&mut self, ctx: &Context, params: DistortionParams) {
+        self.prepare(ctx, &params);
+        self.animate_to(&ctx.queue, params, 0);
+    }
+
+    /// Rebuild the sampler and the high precision pipeline for `params`
+    /// when needed.
+    pub fn prepare(&mut self, ctx: &Context, params: &DistortionParams) {
+        let format = intermediate_format(
+            ctx.colorspace,
+            ctx.format,
+            params.precision,
+            ctx.supports_hdr_intermediate,
+        );
+        if format == ctx.format {
+            self.hdr = None;
+        } else if self.hdr.is_none() {
+            tracing::info!("Distortion intermediate format: {format:?}");
+            self.hdr = Some(HdrIntermediate::new(
+                ctx,
+                &self.shader,
+                &self.pipeline_layout,
+                &self.params_bind_group_layout,
+            ));
+        }
+
+        if params.edge_fill != self.current_params.edge_fill
+            || params.filter != self.current_params.filter
+        {
+            self.sampler = ctx
+                .device
+                .create_sampler(&sampler_descriptor(params.edge_fill, params.filter));
+        }
+    }
+
+    /// Ease from the current params to `target` over `duration_ms`,
+    /// `0` applies `target` right away. Call `prepare` first when the
+    /// edge fill, filter or precision may have changed.
+    pub fn animate_to(
+        &mut self,
+        queue: &wgpu::Queue,
+        target: DistortionParams,
+        duration_ms: u32,
+    ) {
+        if duration_ms == 0 {
+            self.animation = None;
+            self.write_params(queue, target);
+            return;
+        }
+
+        let animation = DistortionAnimation {
+            start_params: self.current_params,
+            target_params: target,
+            start_instant: self.time_source.now(),
+            duration: Duration::from_millis(duration_ms as u64),
+        };
+        self.animation = Some(animation);
+        self.write_params(queue, animation.params_at(0.0));
+    }
+
+    /// Whether the params are still easing towards their target.
+    #[inline]
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Read animation time from `time_source` instead of the system clock.
+    /// A running animation carries on from the new clock's time.
+    pub fn set_time_source(&mut self, time_source: TimeSource) {
+        if let Some(animation) = self.animation.as_mut() {
+            let elapsed = self
+                .time_source
+                .now()
+                .saturating_duration_since(animation.start_instant);
+            animation.start_instant = time_source
+                .now()
+                .checked_sub(elapsed)
+                .unwrap_or(time_source.now());
+        }
+        self.time_source = time_source;
+    }
+
+    /// Move a manual or fixed step time source forward by `by`.
+    pub fn advance_time(&mut self, by: Duration) {
+        self.time_source.advance(by);
+    }
+
+    /// Write the params for this frame while animating.
+    pub fn advance_animation(&mut self, queue: &wgpu::Queue) {
+        let Some(animation) = self.animation else {
+            return;
+        };
+        let t = animation.progress(self.time_source.next_frame());
+        if t >= 1.0 {
+            self.animation = None;
+        }
+        self.write_params(queue, animation.params_at(t));
+    }
+
+    fn write_params(&mut self, queue: &wgpu::Queue, params: DistortionParams) {
         self.current_params = params;
         queue.write_buffer(&self.params_buffer, 0, bytemuck::bytes_of(&params));
+
+        if !self.is_active() {
+            self.src_copy = None;
+            self.current_size = None;
+        }
+    }
+
+    /// Allocate `src_copy` unless one of `size` already exists.
+    fn ensure_src_copy(&mut self, ctx: &Context, size: wgpu::Extent3d) {
+        if self.src_copy.is_some() && self.current_size == Some(size) {
+            return;
+        }
+
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("sugarloaf::distortion src copy"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: ctx.format,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.src_copy = Some((texture, view));
+        self.current_size = Some(size);
+    }
+
+    /// Whether the current params produce any distortion.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.current_params.distortion_type != DISTORTION_NONE
     }
 
     /// Render the distortion pass. Copies src_texture, then
     /// draws a full-screen triangle with distorted UV sampling
     /// back to dst_texture.
     pub fn render(
-        &self,
+        &mut self,
         ctx: &Context,
         encoder: &mut wgpu::CommandEncoder,
         src_texture: &wgpu::Texture,
         dst_texture: &wgpu::Texture,
     ) {
-        if self.current_params.distortion_type == DISTORTION_NONE {
+        self.advance_animation(&ctx.queue);
+        if !self.is_active() {
+            return;
+        }
+
+        // The high precision intermediate already keeps reads and
+        // writes on different textures
+        if self.hdr.is_some() {
+            self.render_from(ctx, encoder, src_texture, dst_texture);
             return;
         }
 
-        // Copy src to a temporary texture (can't read and
+        // Copy src to a separate texture (can't read and
         // write the same texture in one pass)
-        let src_copy = ctx.device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("sugarloaf::distortion src copy"),
-            size: src_texture.size(),
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: ctx.format,
-            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &[],
-        });
+        let size = src_texture.size();
+        self.ensure_src_copy(ctx, size);
+        let Some((src_copy, src_view)) = &self.src_copy else {
+            return;
+        };
 
         encoder.copy_texture_to_texture(
             src_texture.as_image_copy(),
             src_copy.as_image_copy(),
-            src_texture.size(),
+            size,
+        );
+
+        self.draw(ctx, encoder, self.distort_stage(), src_view, dst_texture);
+    }
+
+    #[inline]
+    fn distort_stage(&self) -> Stage<'_> {
+        Stage {
+            pipeline: &self.pipeline,
+            sampler: &self.sampler,
+            params_bind_group: &self.params_bind_group,
+        }
+    }
+
+    /// Draw the distortion sampling from `src_texture` without copying it
+    /// first. `src_texture` must not be `dst_texture` unless the high
+    /// precision intermediate is in use.
+    pub fn render_from(
+        &mut self,
+        ctx: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        src_texture: &wgpu::Texture,
+        dst_texture: &wgpu::Texture,
+    ) {
+        if !self.is_active() {
+            return;
+        }
+
+        let src_view = src_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        if let Some(hdr) = self.hdr.as_mut() {
+            hdr.ensure_texture(ctx, dst_texture.size());
+        }
+        let Some(hdr) = &self.hdr else {
+            self.draw(ctx, encoder, self.distort_stage(), &src_view, dst_texture);
+            return;
+        };
+        let Some((intermediate, intermediate_view)) = &hdr.texture else {
+            return;
+        };
+
+        self.draw(
+            ctx,
+            encoder,
+            Stage {
+                pipeline: &hdr.pipeline,
+                ..self.distort_stage()
+            },
+            &src_view,
+            intermediate,
+        );
+        self.draw(
+            ctx,
+            encoder,
+            Stage {
+                pipeline: &self.pipeline,
+                sampler: &hdr.resolve_sampler,
+                params_bind_group: &hdr.resolve_params_bind_group,
+            },
+            intermediate_view,
+            dst_texture,
         );
+    }
 
-        let src_view = src_copy.create_view(&wgpu::TextureViewDescriptor::default());
+    /// One full-screen draw of `stage` sampling `src_view` into
+    /// `dst_texture`.
+    fn draw(
+        &self,
+        ctx: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        stage: Stage<'_>,
+        src_view: &wgpu::TextureView,
+        dst_texture: &wgpu::Texture,
+    ) {
         let dst_view = dst_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         let texture_bind_group =
@@ -214,11 +764,11 @@ impl DistortionBrush {
                 entries: &[
                     wgpu::BindGroupEntry {
                         binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&src_view),
+                        resource: wgpu::BindingResource::TextureView(src_view),
                     },
                     wgpu::BindGroupEntry {
                         binding: 1,
-                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                        resource: wgpu::BindingResource::Sampler(stage.sampler),
                     },
                 ],
             });
@@ -240,10 +790,208 @@ impl DistortionBrush {
             multiview_mask: None,
         });
 
-        pass.set_pipeline(&self.pipeline);
+        pass.set_pipeline(stage.pipeline);
         pass.set_bind_group(0, &texture_bind_group, &[]);
-        pass.set_bind_group(1, &self.params_bind_group, &[]);
+        pass.set_bind_group(1, stage.params_bind_group, &[]);
         // Full-screen triangle: 3 vertices, 1 instance
         pass.draw(0..3, 0..1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampler_descriptor_filter() {
+        let linear = sampler_descriptor(DISTORTION_EDGE_BLACK, DISTORTION_FILTER_LINEAR);
+        assert_eq!(linear.mag_filter, wgpu::FilterMode::Linear);
+        assert_eq!(linear.min_filter, wgpu::FilterMode::Linear);
+
+        let nearest =
+            sampler_descriptor(DISTORTION_EDGE_BLACK, DISTORTION_FILTER_NEAREST);
+        assert_eq!(nearest.mag_filter, wgpu::FilterMode::Nearest);
+        assert_eq!(nearest.min_filter, wgpu::FilterMode::Nearest);
+    }
+
+    #[test]
+    fn test_params_layout_matches_shader() {
+        // 32 bytes of base params, three vec2 offsets and the vignette radii
+        assert_eq!(mem::size_of::<DistortionParams>(), 64);
+        assert_eq!(mem::size_of::<DistortionParams>() % 16, 0);
+    }
+
+    #[test]
+    fn test_chromatic_aberration_offsets() {
+        let params = DistortionParams::default().with_chromatic_aberration(0.005);
+        assert_eq!(params.red_offset, [0.005, 0.0]);
+        assert_eq!(params.green_offset, [0.0, 0.0]);
+        assert_eq!(params.blue_offset, [-0.005, 0.0]);
+
+        // Other effects don't shift channels
+        let params = DistortionParams::default();
+        assert_eq!(params.red_offset, params.blue_offset);
+    }
+
+    #[test]
+    fn test_vignette_radius() {
+        let params = DistortionParams::default().with_vignette(0.2, 0.9);
+        assert_eq!(params.vignette_radius, [0.2, 0.9]);
+
+        // Inverted or equal radii keep a non-empty fade
+        let params = DistortionParams::default().with_vignette(0.6, 0.3);
+        assert!(params.vignette_radius[1] > params.vignette_radius[0]);
+        let params = DistortionParams::default().with_vignette(-1.0, 0.0);
+        assert_eq!(params.vignette_radius[0], 0.0);
+        assert!(params.vignette_radius[1] > 0.0);
+    }
+
+    #[test]
+    fn test_intermediate_format_per_colorspace() {
+        let surface = wgpu::TextureFormat::Bgra8Unorm;
+        let auto = |colorspace| {
+            intermediate_format(colorspace, surface, DISTORTION_PRECISION_AUTO, true)
+        };
+        assert_eq!(auto(Colorspace::Srgb), surface);
+        assert_eq!(auto(Colorspace::DisplayP3), HDR_INTERMEDIATE_FORMAT);
+        assert_eq!(auto(Colorspace::Rec2020), HDR_INTERMEDIATE_FORMAT);
+
+        for colorspace in [Colorspace::Srgb, Colorspace::DisplayP3, Colorspace::Rec2020] {
+            assert_eq!(
+                intermediate_format(
+                    colorspace,
+                    surface,
+                    DISTORTION_PRECISION_STANDARD,
+                    true
+                ),
+                surface
+            );
+            assert_eq!(
+                intermediate_format(colorspace, surface, DISTORTION_PRECISION_HIGH, true),
+                HDR_INTERMEDIATE_FORMAT
+            );
+            // Without support everything falls back to the surface format
+            for precision in [DISTORTION_PRECISION_AUTO, DISTORTION_PRECISION_HIGH] {
+                assert_eq!(
+                    intermediate_format(colorspace, surface, precision, false),
+                    surface
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_intermediate_format_on_float_surface() {
+        assert_eq!(
+            intermediate_format(
+                Colorspace::Rec2020,
+                HDR_INTERMEDIATE_FORMAT,
+                DISTORTION_PRECISION_HIGH,
+                true
+            ),
+            HDR_INTERMEDIATE_FORMAT
+        );
+    }
+
+    #[test]
+    fn test_sampler_descriptor_edge_fill() {
+        let mirror = sampler_descriptor(DISTORTION_EDGE_MIRROR, DISTORTION_FILTER_LINEAR);
+        assert_eq!(mirror.address_mode_u, wgpu::AddressMode::MirrorRepeat);
+        assert_eq!(mirror.address_mode_v, wgpu::AddressMode::MirrorRepeat);
+
+        let clamp = sampler_descriptor(DISTORTION_EDGE_CLAMP, DISTORTION_FILTER_NEAREST);
+        assert_eq!(clamp.address_mode_u, wgpu::AddressMode::ClampToEdge);
+    }
+
+    #[test]
+    fn test_params_lerp() {
+        let start = DistortionParams::default();
+        let target = DistortionParams {
+            distortion_type: DISTORTION_BARREL,
+            strength: 0.4,
+            center: [0.3, 0.7],
+            edge_fill: DISTORTION_EDGE_MIRROR,
+            ..DistortionParams::default()
+        };
+
+        let half = start.lerp(&target, 0.5);
+        assert!((half.strength - 0.2).abs() < f32::EPSILON);
+        assert!((half.center[0] - 0.4).abs() < 1e-6);
+        assert!((half.center[1] - 0.6).abs() < 1e-6);
+        // Discrete fields don't blend
+        assert_eq!(half.distortion_type, DISTORTION_BARREL);
+        assert_eq!(half.edge_fill, DISTORTION_EDGE_MIRROR);
+
+        assert_eq!(start.lerp(&target, 0.0).strength, 0.0);
+        assert_eq!(start.lerp(&target, 2.0).strength, 0.4);
+    }
+
+    #[test]
+    fn test_animation_progress() {
+        let start_instant = Instant::now();
+        let animation = DistortionAnimation {
+            start_params: DistortionParams::default(),
+            target_params: DistortionParams {
+                strength: 1.0,
+                ..DistortionParams::default()
+            },
+            start_instant,
+            duration: Duration::from_millis(200),
+        };
+        assert_eq!(animation.progress(start_instant), 0.0);
+        let quarter = animation.progress(start_instant + Duration::from_millis(50));
+        assert!((quarter - 0.25).abs() < 1e-6);
+        assert_eq!(
+            animation.progress(start_instant + Duration::from_millis(400)),
+            1.0
+        );
+        assert_eq!(animation.params_at(1.0).strength, 1.0);
+    }
+
+    /// Strength of every frame of a 200ms animation rendered with `clock`
+    fn recorded_strengths(mut clock: TimeSource) -> Vec<f32> {
+        let animation = DistortionAnimation {
+            start_params: DistortionParams::default(),
+            target_params: DistortionParams {
+                strength: 0.8,
+                ..DistortionParams::default()
+            },
+            start_instant: clock.now(),
+            duration: Duration::from_millis(200),
+        };
+        let mut strengths = Vec::new();
+        loop {
+            let t = animation.progress(clock.next_frame());
+            strengths.push(animation.params_at(t).strength);
+            if t >= 1.0 {
+                return strengths;
+            }
+        }
+    }
+
+    #[test]
+    fn test_fixed_step_animation_is_reproducible() {
+        let step = Duration::from_millis(50);
+        let first = recorded_strengths(TimeSource::fixed_step(step));
+        let second = recorded_strengths(TimeSource::fixed_step(step));
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 4);
+        assert!((first[0] - 0.2).abs() < 1e-6);
+        assert_eq!(first[3], 0.8);
+    }
+
+    #[test]
+    fn test_manual_time_source_moves_only_on_advance() {
+        let mut clock = TimeSource::manual();
+        let start = clock.now();
+        assert_eq!(clock.next_frame(), start);
+
+        clock.advance(Duration::from_millis(30));
+        assert_eq!(clock.now(), start + Duration::from_millis(30));
+
+        // The real clock ignores manual steps
+        let mut real = TimeSource::Real;
+        real.advance(Duration::from_secs(3600));
+        assert!(real.now() < Instant::now() + Duration::from_secs(60));
+    }
+}
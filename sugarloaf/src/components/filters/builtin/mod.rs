@@ -46,28 +46,18 @@ impl From<std::io::Error> for LoadError {
     }
 }
 
-const NEWPIXIECRT_ACCUMULATE: &[u8] = resource!("./newpixiecrt/accumulate.slang");
-const NEWPIXIECRT_BLUR_HORIZ: &[u8] = resource!("./newpixiecrt/blur_horiz.slang");
-const NEWPIXIECRT_BLUR_VERT: &[u8] = resource!("./newpixiecrt/blur_vert.slang");
-const NEWPIXIECRT_CRTFRAME: &[u8] = resource!("./newpixiecrt/crtframe.png");
-const NEWPIXIECRT_NEWPIXIECRT: &[u8] = resource!("./newpixiecrt/newpixie-crt.slang");
-const NEWPIXIECRT_NEWPIXIECRTP: &[u8] = resource!("./newpixiecrt/newpixie-crt.slangp");
-
-pub fn newpixiecrt() -> Result<ShaderPreset, LoadError> {
-    let dir_path = Path::new("/tmp/newpixiecrt");
+/// Write the bundled preset files to `/tmp/<dir_name>` so that relative
+/// paths inside the `.slangp` resolve, then parse the preset.
+fn load_preset(
+    dir_name: &str,
+    files: &[(&str, &[u8])],
+    preset: &str,
+) -> Result<ShaderPreset, LoadError> {
+    let dir_path = Path::new("/tmp").join(dir_name);
     if !dir_path.exists() {
-        fs::create_dir_all(dir_path)?;
+        fs::create_dir_all(&dir_path)?;
     }
 
-    let files = vec![
-        ("accumulate.slang", NEWPIXIECRT_ACCUMULATE),
-        ("blur_horiz.slang", NEWPIXIECRT_BLUR_HORIZ),
-        ("blur_vert.slang", NEWPIXIECRT_BLUR_VERT),
-        ("crtframe.png", NEWPIXIECRT_CRTFRAME),
-        ("newpixie-crt.slang", NEWPIXIECRT_NEWPIXIECRT),
-        ("newpixie-crt.slangp", NEWPIXIECRT_NEWPIXIECRTP),
-    ];
-
     // Create files in the directory
     for (filename, content) in files {
         let file_path = dir_path.join(filename);
@@ -75,15 +65,34 @@ pub fn newpixiecrt() -> Result<ShaderPreset, LoadError> {
         file.write_all(content)?;
     }
 
-    match ShaderPreset::try_parse(
-        dir_path.join("newpixie-crt.slangp"),
-        ShaderFeatures::NONE,
-    ) {
+    match ShaderPreset::try_parse(dir_path.join(preset), ShaderFeatures::NONE) {
         Ok(preset) => Ok(preset),
         Err(err) => Err(LoadError::ParseError(err)),
     }
 }
 
+const NEWPIXIECRT_ACCUMULATE: &[u8] = resource!("./newpixiecrt/accumulate.slang");
+const NEWPIXIECRT_BLUR_HORIZ: &[u8] = resource!("./newpixiecrt/blur_horiz.slang");
+const NEWPIXIECRT_BLUR_VERT: &[u8] = resource!("./newpixiecrt/blur_vert.slang");
+const NEWPIXIECRT_CRTFRAME: &[u8] = resource!("./newpixiecrt/crtframe.png");
+const NEWPIXIECRT_NEWPIXIECRT: &[u8] = resource!("./newpixiecrt/newpixie-crt.slang");
+const NEWPIXIECRT_NEWPIXIECRTP: &[u8] = resource!("./newpixiecrt/newpixie-crt.slangp");
+
+pub fn newpixiecrt() -> Result<ShaderPreset, LoadError> {
+    load_preset(
+        "newpixiecrt",
+        &[
+            ("accumulate.slang", NEWPIXIECRT_ACCUMULATE),
+            ("blur_horiz.slang", NEWPIXIECRT_BLUR_HORIZ),
+            ("blur_vert.slang", NEWPIXIECRT_BLUR_VERT),
+            ("crtframe.png", NEWPIXIECRT_CRTFRAME),
+            ("newpixie-crt.slang", NEWPIXIECRT_NEWPIXIECRT),
+            ("newpixie-crt.slangp", NEWPIXIECRT_NEWPIXIECRTP),
+        ],
+        "newpixie-crt.slangp",
+    )
+}
+
 const FUBAXVR_CHROMATIC: &[u8] = resource!("./fubax_vr/Chromatic.slang");
 const FUBAXVR_FILMIC_SHARPEN: &[u8] = resource!("./fubax_vr/FilmicSharpen.slang");
 const FUBAXVR_FUBAXVRP: &[u8] = resource!("./fubax_vr/fubax_vr.slangp");
@@ -96,33 +105,61 @@ const FUBAXVR_VR: &[u8] = resource!("./fubax_vr/VR.slang");
 const FUBAXVR_VR_NOSE: &[u8] = resource!("./fubax_vr/VR_nose.slang");
 
 pub fn fubaxvr() -> Result<ShaderPreset, LoadError> {
-    let dir_path = Path::new("/tmp/fubax_vr");
-    if !dir_path.exists() {
-        fs::create_dir_all(dir_path)?;
-    }
+    load_preset(
+        "fubax_vr",
+        &[
+            ("Chromatic.slang", FUBAXVR_CHROMATIC),
+            ("FilmicSharpen.slang", FUBAXVR_FILMIC_SHARPEN),
+            ("fubax_vr.slangp", FUBAXVR_FUBAXVRP),
+            ("fubax_vr_params.inc", FUBAXVR_FUBAXVR_PARAMS),
+            ("fubax_vr_shared_funcs.inc", FUBAXVR_FUBAXVR_SHARED_FUNCS),
+            ("nose.png", FUBAXVR_NOSE),
+            ("stock.slang", FUBAXVR_STOCK),
+            ("VR.slang", FUBAXVR_VR),
+            ("VR_nose.slang", FUBAXVR_VR_NOSE),
+        ],
+        "fubax_vr.slangp",
+    )
+}
 
-    let files = vec![
-        ("Chromatic.slang", FUBAXVR_CHROMATIC),
-        ("FilmicSharpen.slang", FUBAXVR_FILMIC_SHARPEN),
-        ("fubax_vr.slangp", FUBAXVR_FUBAXVRP),
-        ("fubax_vr_params.inc", FUBAXVR_FUBAXVR_PARAMS),
-        ("fubax_vr_shared_funcs.inc", FUBAXVR_FUBAXVR_SHARED_FUNCS),
-        ("nose.png", FUBAXVR_NOSE),
-        ("stock.slang", FUBAXVR_STOCK),
-        ("VR.slang", FUBAXVR_VR),
-        ("VR_nose.slang", FUBAXVR_VR_NOSE),
-    ];
+const CRTGEOM_CRTGEOM: &[u8] = resource!("./crt_geom/crt-geom.slang");
+const CRTGEOM_CRTGEOMP: &[u8] = resource!("./crt_geom/crt-geom.slangp");
+
+pub fn crtgeom() -> Result<ShaderPreset, LoadError> {
+    load_preset(
+        "crt_geom",
+        &[
+            ("crt-geom.slang", CRTGEOM_CRTGEOM),
+            ("crt-geom.slangp", CRTGEOM_CRTGEOMP),
+        ],
+        "crt-geom.slangp",
+    )
+}
 
-    // Create files in the directory
-    for (filename, content) in files {
-        let file_path = dir_path.join(filename);
-        let mut file = fs::File::create(file_path)?;
-        file.write_all(content)?;
-    }
+const LCDGRID_LCDGRID: &[u8] = resource!("./lcd_grid/lcd-grid.slang");
+const LCDGRID_LCDGRIDP: &[u8] = resource!("./lcd_grid/lcd-grid.slangp");
+
+pub fn lcdgrid() -> Result<ShaderPreset, LoadError> {
+    load_preset(
+        "lcd_grid",
+        &[
+            ("lcd-grid.slang", LCDGRID_LCDGRID),
+            ("lcd-grid.slangp", LCDGRID_LCDGRIDP),
+        ],
+        "lcd-grid.slangp",
+    )
+}
 
-    match ShaderPreset::try_parse(dir_path.join("fubax_vr.slangp"), ShaderFeatures::NONE)
-    {
-        Ok(preset) => Ok(preset),
-        Err(err) => Err(LoadError::ParseError(err)),
-    }
+const SCANLINES_SCANLINES: &[u8] = resource!("./scanlines/scanlines.slang");
+const SCANLINES_SCANLINESP: &[u8] = resource!("./scanlines/scanlines.slangp");
+
+pub fn scanlines() -> Result<ShaderPreset, LoadError> {
+    load_preset(
+        "scanlines",
+        &[
+            ("scanlines.slang", SCANLINES_SCANLINES),
+            ("scanlines.slangp", SCANLINES_SCANLINESP),
+        ],
+        "scanlines.slangp",
+    )
 }
@@ -3,12 +3,43 @@ mod runtime;
 
 use crate::context::Context;
 use librashader_common::{Size, Viewport};
-use librashader_presets::ShaderFeatures;
+use librashader_presets::{ShaderFeatures, ShaderPreset};
 use std::borrow::Cow;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::Arc;
 
 pub type Filter = String;
 
+/// Names of the filters bundled with sugarloaf, usable in place of a
+/// `.slangp` path (matched case-insensitively).
+const BUILTIN_FILTERS: &[&str] = &[
+    "newpixiecrt",
+    "fubax_vr",
+    "crt-geom",
+    "lcd-grid",
+    "scanlines",
+];
+
+/// List the builtin filter names, e.g. to enumerate them in a config UI.
+#[inline]
+pub fn available_builtin_filters() -> &'static [&'static str] {
+    BUILTIN_FILTERS
+}
+
+/// Resolve a builtin filter name to the function building its preset.
+fn builtin_preset(
+    name: &str,
+) -> Option<fn() -> Result<ShaderPreset, builtin::LoadError>> {
+    match name {
+        "newpixiecrt" => Some(builtin::newpixiecrt),
+        "fubax_vr" => Some(builtin::fubaxvr),
+        "crt-geom" => Some(builtin::crtgeom),
+        "lcd-grid" => Some(builtin::lcdgrid),
+        "scanlines" => Some(builtin::scanlines),
+        _ => None,
+    }
+}
+
 /// Resources for restoring the alpha channel after filter passes.
 /// RetroArch shaders output alpha = 1.0, destroying window transparency.
 /// This pipeline composites filtered RGB with the original pre-filter alpha.
@@ -16,10 +47,27 @@ struct AlphaRestore {
     pipeline: wgpu::RenderPipeline,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    /// Whether RGB is multiplied by the restored alpha
+    premultiplied: bool,
 }
 
 impl AlphaRestore {
+    /// Straight alpha: the filtered RGB is written as is.
     fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        Self::with_mode(device, format, false)
+    }
+
+    /// Premultiplied alpha: the filtered RGB is multiplied by the restored
+    /// alpha, for compositors expecting `rgba = color * alpha`.
+    fn new_premultiplied(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        Self::with_mode(device, format, true)
+    }
+
+    fn with_mode(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        premultiplied: bool,
+    ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Alpha Restore Shader"),
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!(
@@ -85,7 +133,10 @@ impl AlphaRestore {
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
                 entry_point: Some("fs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &[("premultiplied", f64::from(u8::from(premultiplied)))],
+                    ..Default::default()
+                },
                 targets: &[Some(wgpu::ColorTargetState {
                     format,
                     blend: None,
@@ -113,6 +164,231 @@ impl AlphaRestore {
             pipeline,
             bind_group_layout,
             sampler,
+            premultiplied,
+        }
+    }
+}
+
+/// GPU time one filter took in the last profiled frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterFrameStat {
+    /// Filter as configured, or `alpha-restore` for the alpha restore pass
+    pub filter_name: String,
+    pub gpu_ns: u64,
+}
+
+/// Name reported for the alpha restore pass in `FilterFrameStat`.
+pub const ALPHA_RESTORE_STAT_NAME: &str = "alpha-restore";
+
+/// Features needed to write timestamps between filter chains.
+const TIMER_FEATURES: wgpu::Features = wgpu::Features::TIMESTAMP_QUERY
+    .union(wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS);
+
+/// Timestamp queries around each filter chain. Query 0 is written
+/// before the first filter, query `i + 1` after filter `i` and the last
+/// one after the alpha restore pass.
+struct FilterTimer {
+    device: wgpu::Device,
+    query_set: wgpu::QuerySet,
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    capacity: u32,
+    /// Nanoseconds per timestamp tick
+    period: f32,
+    /// Queries copied to `readback_buffer` by the last `render_from`,
+    /// zero until a frame has been profiled
+    written: u32,
+}
+
+impl FilterTimer {
+    fn new(ctx: &Context, filters_count: usize) -> Option<Self> {
+        if !ctx.device.features().contains(TIMER_FEATURES) {
+            return None;
+        }
+
+        let capacity = filters_count as u32 + 2;
+        let size = u64::from(capacity) * u64::from(wgpu::QUERY_SIZE);
+        let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some("Filter Timestamps"),
+            ty: wgpu::QueryType::Timestamp,
+            count: capacity,
+        });
+        let resolve_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Filter Timestamps Resolve"),
+            size,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Filter Timestamps Readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            device: ctx.device.clone(),
+            query_set,
+            resolve_buffer,
+            readback_buffer,
+            capacity,
+            period: ctx.queue.get_timestamp_period(),
+            written: 0,
+        })
+    }
+
+    #[inline]
+    fn write(&self, encoder: &mut wgpu::CommandEncoder, index: u32) {
+        if index < self.capacity {
+            encoder.write_timestamp(&self.query_set, index);
+        }
+    }
+
+    /// Copy the first `count` timestamps of this frame to the readback
+    /// buffer, read by `read` once the frame was submitted.
+    fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder, count: u32) {
+        let count = count.min(self.capacity);
+        let size = u64::from(count) * u64::from(wgpu::QUERY_SIZE);
+        encoder.resolve_query_set(&self.query_set, 0..count, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.readback_buffer,
+            0,
+            size,
+        );
+        self.written = count;
+    }
+
+    /// Block until the last resolved timestamps are readable and return them.
+    fn read(&self) -> Option<Vec<u64>> {
+        if self.written == 0 {
+            return None;
+        }
+
+        let size = u64::from(self.written) * u64::from(wgpu::QUERY_SIZE);
+        let slice = self.readback_buffer.slice(0..size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        if let Err(err) = self.device.poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        }) {
+            tracing::warn!("Failed to wait for filter timestamps: {err}");
+        }
+
+        let timestamps = match receiver.recv() {
+            Ok(Ok(())) => {
+                let data = slice.get_mapped_range();
+                let timestamps = data
+                    .chunks_exact(wgpu::QUERY_SIZE as usize)
+                    .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap_or_default()))
+                    .collect();
+                drop(data);
+                Some(timestamps)
+            }
+            _ => None,
+        };
+        self.readback_buffer.unmap();
+        timestamps
+    }
+}
+
+/// Turn consecutive timestamps into one stat per filter, plus one for
+/// the alpha restore pass when its timestamp is present.
+fn frame_stats(
+    filter_names: &[String],
+    timestamps: &[u64],
+    period: f32,
+) -> Vec<FilterFrameStat> {
+    let names = filter_names
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(ALPHA_RESTORE_STAT_NAME));
+    names
+        .zip(timestamps.windows(2))
+        .map(|(name, pair)| FilterFrameStat {
+            filter_name: name.to_string(),
+            gpu_ns: (pair[1].saturating_sub(pair[0]) as f64 * f64::from(period)) as u64,
+        })
+        .collect()
+}
+
+/// Load one configured filter, a builtin name or a `.slangp` path.
+fn load_filter(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    filter: &str,
+) -> Option<runtime::FilterChain> {
+    let configured_filter = filter.to_lowercase();
+    match builtin_preset(configured_filter.as_str()) {
+        Some(builtin_filter) => {
+            tracing::debug!("Loading builtin filter {}", configured_filter);
+
+            let shader_preset = builtin_filter()
+                .map_err(|e| {
+                    tracing::error!(
+                        "Failed to build shader preset from builtin filter {}: {}",
+                        configured_filter,
+                        e
+                    )
+                })
+                .ok()?;
+            runtime::FilterChain::load_from_preset(shader_preset, device, queue, None)
+                .map_err(|e| {
+                    tracing::error!(
+                        "Failed to load builtin filter {}: {}",
+                        configured_filter,
+                        e
+                    )
+                })
+                .ok()
+        }
+        None => {
+            tracing::debug!("Loading filter {}", filter);
+
+            runtime::FilterChain::load_from_path(
+                filter,
+                ShaderFeatures::NONE,
+                device,
+                queue,
+                None,
+            )
+            .map_err(|e| tracing::error!("Failed to load filter {}: {}", filter, e))
+            .ok()
+        }
+    }
+}
+
+/// A filter chain loaded on the loader thread.
+struct LoadedFilter {
+    name: String,
+    chain: runtime::FilterChain,
+}
+
+/// Load `filters` in order, sending each chain as soon as it's ready.
+/// Filters that fail to load are skipped. Stops early once the receiver
+/// is dropped, i.e. the filters were replaced in the meantime.
+fn load_filters(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    filters: Vec<Filter>,
+    sender: Sender<LoadedFilter>,
+) {
+    for filter in filters {
+        let Some(chain) = load_filter(device, queue, &filter) else {
+            continue;
+        };
+        if sender
+            .send(LoadedFilter {
+                name: filter,
+                chain,
+            })
+            .is_err()
+        {
+            return;
         }
     }
 }
@@ -121,16 +397,41 @@ impl AlphaRestore {
 #[derive(Default)]
 pub struct FiltersBrush {
     filter_chains: Vec<crate::components::filters::runtime::FilterChain>,
+    /// Configured name of each loaded chain, parallel to `filter_chains`
+    filter_names: Vec<String>,
     filter_intermediates: Vec<Arc<wgpu::Texture>>,
     framecount: usize,
     alpha_restore: Option<AlphaRestore>,
+    restore_alpha: bool,
+    /// Restore alpha as premultiplied colors
+    premultiplied_alpha: bool,
+    timer: Option<FilterTimer>,
+    /// Chains arriving from the loader thread, `None` once it finished
+    pending: Option<Receiver<LoadedFilter>>,
+    /// Chains received so far, applied together once loading finished
+    staged: Vec<LoadedFilter>,
 }
 
 impl FiltersBrush {
-    #[inline]
-    pub fn update_filters(&mut self, ctx: &Context, filters: &[Filter]) {
+    /// Start loading the given filter chains on a background thread and
+    /// return right away; `poll_pending` applies them once all are loaded.
+    /// When `restore_alpha` is false the alpha restore pass is skipped and
+    /// the chain renders straight to the destination texture, which is
+    /// cheaper for fully opaque windows.
+    pub fn update_filters(
+        &mut self,
+        ctx: &Context,
+        filters: &[Filter],
+        restore_alpha: bool,
+    ) {
         self.filter_chains.clear();
+        self.filter_names.clear();
         self.filter_intermediates.clear();
+        self.timer = None;
+        // Dropping the receiver stops a loader still working on the old list
+        self.pending = None;
+        self.staged.clear();
+        self.restore_alpha = restore_alpha;
 
         if filters.is_empty() {
             self.alpha_restore = None;
@@ -147,56 +448,93 @@ impl FiltersBrush {
             return;
         }
 
-        for filter in filters {
-            let configured_filter = filter.to_lowercase();
-            match configured_filter.as_str() {
-                "newpixiecrt" | "fubax_vr" => {
-                    tracing::debug!("Loading builtin filter {}", configured_filter);
-
-                    let builtin_filter = match configured_filter.as_str() {
-                        "newpixiecrt" => builtin::newpixiecrt,
-                        "fubax_vr" => builtin::fubaxvr,
-                        _ => {
-                            continue;
-                        }
-                    };
-
-                    match builtin_filter() {
-                        Ok(shader_preset) => {
-                            match crate::components::filters::runtime::FilterChain::load_from_preset(
-                                shader_preset,
-                                &ctx.device,
-                                &ctx.queue,
-                                None,
-                            ) {
-                                Ok(f) => self.filter_chains.push(f),
-                                Err(e) => tracing::error!("Failed to load builtin filter {}: {}", configured_filter, e),
-                            }
-                        },
-                        Err(e) => {
-                            tracing::error!("Failed to build shader preset from builtin filter {}: {}", configured_filter, e)
-                        },
-                    }
-                }
-                _ => {
-                    tracing::debug!("Loading filter {}", filter);
-
-                    match crate::components::filters::runtime::FilterChain::load_from_path(
-                        filter,
-                        ShaderFeatures::NONE,
-                        &ctx.device,
-                        &ctx.queue,
-                        None,
-                    ) {
-                        Ok(f) => self.filter_chains.push(f),
-                        Err(e) => {
-                            tracing::error!("Failed to load filter {}: {}", filter, e)
-                        }
-                    }
-                }
+        let (sender, receiver) = mpsc::channel();
+        let device = ctx.device.clone();
+        let queue = ctx.queue.clone();
+        let filters = filters.to_vec();
+        let spawned = std::thread::Builder::new()
+            .name("filter loader".to_string())
+            .spawn(move || load_filters(&device, &queue, filters, sender));
+        match spawned {
+            Ok(_) => self.pending = Some(receiver),
+            Err(e) => tracing::error!("Failed to start loading filters: {e}"),
+        }
+    }
+
+    /// Collect the filter chains loaded since the last call and apply
+    /// them all once the loader is done. Until then there is nothing to
+    /// apply and rendering leaves the frame as is, so a partial chain is
+    /// never shown.
+    pub fn poll_pending(&mut self, ctx: &Context) {
+        let Some(receiver) = &self.pending else {
+            return;
+        };
+
+        loop {
+            match receiver.try_recv() {
+                Ok(filter) => self.staged.push(filter),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => break,
             }
         }
 
+        self.pending = None;
+        if self.staged.is_empty() {
+            return;
+        }
+        for filter in self.staged.drain(..) {
+            self.filter_chains.push(filter.chain);
+            self.filter_names.push(filter.name);
+        }
+        self.prepare_chains(ctx);
+    }
+
+    /// Whether filters are still being loaded in the background.
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Write premultiplied colors from the alpha restore pass. The pass is
+    /// rebuilt on the next frame when the mode changed.
+    pub fn set_premultiplied_alpha(&mut self, premultiplied: bool) {
+        self.premultiplied_alpha = premultiplied;
+        if self
+            .alpha_restore
+            .as_ref()
+            .is_some_and(|restore| restore.premultiplied != premultiplied)
+        {
+            self.alpha_restore = None;
+        }
+    }
+
+    /// Create the alpha restore pass for the current mode, unless it is
+    /// disabled, exists already or there are no chains to restore after.
+    fn ensure_alpha_restore(&mut self, ctx: &Context) {
+        if !self.restore_alpha {
+            self.alpha_restore = None;
+            return;
+        }
+
+        if self.alpha_restore.is_none() && !self.filter_chains.is_empty() {
+            self.alpha_restore = Some(if self.premultiplied_alpha {
+                AlphaRestore::new_premultiplied(&ctx.device, ctx.format)
+            } else {
+                AlphaRestore::new(&ctx.device, ctx.format)
+            });
+        }
+    }
+
+    /// Create the timer, intermediate textures and alpha restore pass
+    /// for the loaded chains.
+    fn prepare_chains(&mut self, ctx: &Context) {
+        self.timer = None;
+        self.filter_intermediates.clear();
+
+        if !self.filter_chains.is_empty() {
+            self.timer = FilterTimer::new(ctx, self.filter_chains.len());
+        }
+
         self.filter_intermediates.reserve(self.filter_chains.len());
 
         // If we have an odd number of filters, the last filter can be
@@ -233,9 +571,32 @@ impl FiltersBrush {
         }
 
         // Initialize alpha restore pipeline for transparent background support
-        if self.alpha_restore.is_none() && !self.filter_chains.is_empty() {
-            self.alpha_restore = Some(AlphaRestore::new(&ctx.device, ctx.format));
-        }
+        self.ensure_alpha_restore(ctx);
+    }
+
+    /// Whether any filter chain is loaded.
+    #[inline]
+    pub fn has_filters(&self) -> bool {
+        !self.filter_chains.is_empty()
+    }
+
+    /// GPU time of each filter in the last frame submitted before this
+    /// call, with a trailing `alpha-restore` entry when that pass ran.
+    /// `None` when the device has no timestamp queries or nothing was
+    /// rendered yet. Blocks until the GPU finished that frame, so call it
+    /// after submitting, not between encoding and submission.
+    pub fn last_frame_stats(&self) -> Option<Vec<FilterFrameStat>> {
+        let timer = self.timer.as_ref()?;
+        let timestamps = timer.read()?;
+        Some(frame_stats(&self.filter_names, &timestamps, timer.period))
+    }
+
+    /// Whether the surface allows the texture copies filters rely on.
+    #[inline]
+    pub fn is_supported(ctx: &Context) -> bool {
+        let usage_caps = ctx.surface_caps().usages;
+        usage_caps.contains(wgpu::TextureUsages::COPY_SRC)
+            && usage_caps.contains(wgpu::TextureUsages::COPY_DST)
     }
 
     /// Render the filters on top of the src_texture to dst_texture.
@@ -250,16 +611,8 @@ impl FiltersBrush {
         src_texture: &wgpu::Texture,
         dst_texture: &wgpu::Texture,
     ) {
-        let filters_count = self.filter_chains.len();
-        if filters_count == 0 {
-            return;
-        }
-
-        let usage_caps = ctx.surface_caps().usages;
-
-        if !usage_caps.contains(wgpu::TextureUsages::COPY_SRC)
-            || !usage_caps.contains(wgpu::TextureUsages::COPY_DST)
-        {
+        self.poll_pending(ctx);
+        if self.filter_chains.is_empty() || !Self::is_supported(ctx) {
             return;
         }
 
@@ -292,6 +645,25 @@ impl FiltersBrush {
             new_src_texture
         };
 
+        self.render_from(ctx, encoder, src_texture, dst_texture);
+    }
+
+    /// Same as `render` but reads from a texture the caller already copied,
+    /// so chained post-processing stages can share a single copy.
+    /// `src_texture` must not be `dst_texture`.
+    pub fn render_from(
+        &mut self,
+        ctx: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        src_texture: Arc<wgpu::Texture>,
+        dst_texture: &wgpu::Texture,
+    ) {
+        let filters_count = self.filter_chains.len();
+        if filters_count == 0 {
+            return;
+        }
+        self.ensure_alpha_restore(ctx);
+
         // When alpha restore is active, the filter chain renders to an
         // intermediate texture instead of directly to dst_texture.
         // The alpha restore pass then composites filtered RGB + original alpha
@@ -322,6 +694,10 @@ impl FiltersBrush {
         let effective_dst: &wgpu::Texture =
             filter_output_texture.as_deref().unwrap_or(dst_texture);
 
+        if let Some(timer) = &self.timer {
+            timer.write(encoder, 0);
+        }
+
         let view_size = Size::new(ctx.size.width as u32, ctx.size.height as u32);
         for (idx, filter) in self.filter_chains.iter_mut().enumerate() {
             let filter_src_texture: Arc<wgpu::Texture>;
@@ -366,7 +742,12 @@ impl FiltersBrush {
             ) {
                 tracing::error!("Filter rendering failed: {err}");
             }
+
+            if let Some(timer) = &self.timer {
+                timer.write(encoder, idx as u32 + 1);
+            }
         }
+        let mut timestamps = filters_count as u32 + 1;
 
         // Alpha restore pass: combine filtered RGB with original alpha
         // to preserve window transparency through the filter pipeline.
@@ -419,6 +800,131 @@ impl FiltersBrush {
             pass.set_pipeline(&alpha_restore.pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
             pass.draw(0..3, 0..1);
+            drop(pass);
+
+            if let Some(timer) = &self.timer {
+                timer.write(encoder, timestamps);
+                timestamps += 1;
+            }
+        }
+
+        if let Some(timer) = &mut self.timer {
+            timer.resolve(encoder, timestamps);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_builtin_filters_resolve() {
+        for name in available_builtin_filters() {
+            assert!(builtin_preset(name).is_some(), "{name} is not registered");
+        }
+        assert!(builtin_preset("not-a-filter").is_none());
+    }
+
+    /// `None` when no GPU is available (e.g. CI without a software adapter).
+    fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = futures::executor::block_on(instance.request_adapter(
+            &wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::LowPower,
+                compatible_surface: None,
+                force_fallback_adapter: false,
+            },
+        ))
+        .ok()?;
+        let device = futures::executor::block_on(
+            adapter.request_device(&wgpu::DeviceDescriptor::default()),
+        )
+        .unwrap();
+        Some(device)
+    }
+
+    #[test]
+    fn test_builtin_presets_load_on_headless_device() {
+        let Some((device, queue)) = headless_device() else {
+            return;
+        };
+
+        for name in available_builtin_filters() {
+            let preset = builtin_preset(name).unwrap()().unwrap();
+            if let Err(err) =
+                runtime::FilterChain::load_from_preset(preset, &device, &queue, None)
+            {
+                panic!("Failed to load builtin filter {name}: {err}");
+            }
         }
     }
+
+    #[test]
+    fn test_alpha_restore_modes_build_on_headless_device() {
+        let Some((device, _queue)) = headless_device() else {
+            return;
+        };
+
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        assert!(!AlphaRestore::new(&device, format).premultiplied);
+        assert!(AlphaRestore::new_premultiplied(&device, format).premultiplied);
+    }
+
+    #[test]
+    fn test_load_filters_sends_chains_in_order() {
+        let Some((device, queue)) = headless_device() else {
+            return;
+        };
+
+        let filters = vec![
+            "scanlines".to_string(),
+            "/not/a/filter.slangp".to_string(),
+            "CRT-Geom".to_string(),
+        ];
+        let (sender, receiver) = mpsc::channel();
+        let loader = std::thread::spawn(move || {
+            load_filters(&device, &queue, filters, sender);
+        });
+        loader.join().unwrap();
+
+        // The missing file is skipped, names keep their configured case
+        let names: Vec<String> = receiver.iter().map(|filter| filter.name).collect();
+        assert_eq!(names, vec!["scanlines".to_string(), "CRT-Geom".to_string()]);
+    }
+
+    #[test]
+    fn test_frame_stats_per_filter() {
+        let names = vec!["crt-geom".to_string(), "scanlines".to_string()];
+        // Period of 2ns per tick
+        let stats = frame_stats(&names, &[100, 150, 400], 2.0);
+        assert_eq!(
+            stats,
+            vec![
+                FilterFrameStat {
+                    filter_name: "crt-geom".to_string(),
+                    gpu_ns: 100,
+                },
+                FilterFrameStat {
+                    filter_name: "scanlines".to_string(),
+                    gpu_ns: 500,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frame_stats_with_alpha_restore() {
+        let names = vec!["scanlines".to_string()];
+        let stats = frame_stats(&names, &[0, 10, 13], 1.0);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1].filter_name, ALPHA_RESTORE_STAT_NAME);
+        assert_eq!(stats[1].gpu_ns, 3);
+
+        // Timestamps going backwards never underflow
+        let stats = frame_stats(&names, &[10, 5], 1.0);
+        assert_eq!(stats[0].gpu_ns, 0);
+
+        assert!(frame_stats(&names, &[], 1.0).is_empty());
+    }
 }
@@ -1,6 +1,8 @@
 // Alpha restore shader for transparent background support with filters.
 // Composites the filtered RGB output with the original pre-filter alpha channel.
 // RetroArch shaders destroy alpha (output a=1.0), so this pass restores it.
+// With `premultiplied` set to 1 the RGB is also multiplied by the restored
+// alpha, for compositors that expect premultiplied colors.
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -35,9 +37,15 @@ var original_texture: texture_2d<f32>;
 @group(0) @binding(2)
 var tex_sampler: sampler;
 
+// Pipeline constant: 0 = straight alpha, 1 = premultiplied alpha
+override premultiplied: u32 = 0u;
+
 @fragment
 fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
     let filtered = textureSample(filtered_texture, tex_sampler, vertex.tex_coords);
     let original = textureSample(original_texture, tex_sampler, vertex.tex_coords);
+    if premultiplied == 1u {
+        return vec4<f32>(filtered.rgb * original.a, original.a);
+    }
     return vec4<f32>(filtered.rgb, original.a);
 }
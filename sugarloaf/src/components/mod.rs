@@ -2,5 +2,7 @@ pub mod core;
 pub mod distortion;
 pub mod filters;
 pub mod layer;
+pub mod post_process;
 pub mod quad;
 pub mod rich_text;
+pub mod vignette;
@@ -0,0 +1,266 @@
+// Post-processing chain: RetroArch filters, then distortion, then vignette.
+//
+// Each brush used to copy the frame on its own before sampling it. The chain
+// copies the frame once into a ping-pong pair of textures and lets every stage
+// read from one side while writing to the other (or straight to `dst` for the
+// last stage).
+
+use crate::components::distortion::{DistortionBrush, DistortionParams, TimeSource};
+use crate::components::filters::{Filter, FiltersBrush};
+use crate::components::vignette::{VignetteBrush, VignetteParams};
+use crate::context::Context;
+use std::sync::Arc;
+
+struct PingPong {
+    size: wgpu::Extent3d,
+    format: wgpu::TextureFormat,
+    textures: [Arc<wgpu::Texture>; 2],
+}
+
+impl PingPong {
+    fn new(ctx: &Context, size: wgpu::Extent3d, format: wgpu::TextureFormat) -> Self {
+        let create = |label: &'static str| {
+            Arc::new(ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::COPY_SRC
+                    | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[format],
+            }))
+        };
+
+        Self {
+            size,
+            format,
+            textures: [
+                create("PostProcess Ping Texture"),
+                create("PostProcess Pong Texture"),
+            ],
+        }
+    }
+
+    #[inline]
+    fn matches(&self, size: wgpu::Extent3d, format: wgpu::TextureFormat) -> bool {
+        self.size == size && self.format == format
+    }
+}
+
+/// Owns the post-processing brushes and runs them in a fixed
+/// order: filters first, then distortion, then vignette.
+#[derive(Default)]
+pub struct PostProcessChain {
+    filters: Option<FiltersBrush>,
+    distortion: Option<DistortionBrush>,
+    vignette: Option<VignetteBrush>,
+    ping_pong: Option<PingPong>,
+    /// Given to the distortion brush, also when it is created later
+    time_source: TimeSource,
+    /// Given to the filters brush, also when it is created later
+    premultiplied_alpha: bool,
+}
+
+/// Where a stage writes: the other ping-pong texture, or `dst` for the
+/// last stage.
+#[inline]
+fn stage_target<'a>(
+    textures: &'a [Arc<wgpu::Texture>; 2],
+    current: usize,
+    remaining: usize,
+    dst: &'a wgpu::Texture,
+) -> &'a wgpu::Texture {
+    if remaining == 0 {
+        dst
+    } else {
+        &textures[1 - current]
+    }
+}
+
+impl PostProcessChain {
+    /// Whether any stage would touch the frame.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.filters.as_ref().is_some_and(|f| f.has_filters())
+            || self.distortion.as_ref().is_some_and(|d| d.is_active())
+            || self.vignette.as_ref().is_some_and(|v| v.is_active())
+    }
+
+    /// Whether the distortion is still easing towards new params.
+    #[inline]
+    pub fn is_animating(&self) -> bool {
+        self.distortion.as_ref().is_some_and(|d| d.is_animating())
+    }
+
+    /// Whether filters are still being loaded in the background.
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        self.filters.as_ref().is_some_and(|f| f.is_loading())
+    }
+
+    /// Filters load in the background; until they arrive the frame is
+    /// left as is.
+    pub fn update_filters(
+        &mut self,
+        ctx: &Context,
+        filters: &[Filter],
+        restore_alpha: bool,
+    ) {
+        if filters.is_empty() {
+            self.filters = None;
+        } else {
+            let brush = self.filters.get_or_insert_with(FiltersBrush::default);
+            brush.set_premultiplied_alpha(self.premultiplied_alpha);
+            brush.update_filters(ctx, filters, restore_alpha);
+        }
+        self.release_unused_textures();
+    }
+
+    /// Restore alpha after the filters as premultiplied colors.
+    pub fn set_premultiplied_alpha(&mut self, premultiplied: bool) {
+        self.premultiplied_alpha = premultiplied;
+        if let Some(filters) = self.filters.as_mut() {
+            filters.set_premultiplied_alpha(premultiplied);
+        }
+    }
+
+    /// Pass `distortion_type = 0` (DISTORTION_NONE) to disable.
+    pub fn update_distortion(&mut self, ctx: &Context, params: DistortionParams) {
+        self.animate_distortion(ctx, params, 0);
+    }
+
+    /// Like `update_distortion`, easing into `params` over `duration_ms`.
+    /// Disabling is always immediate.
+    pub fn animate_distortion(
+        &mut self,
+        ctx: &Context,
+        params: DistortionParams,
+        duration_ms: u32,
+    ) {
+        use crate::components::distortion::DISTORTION_NONE;
+        if params.distortion_type == DISTORTION_NONE {
+            self.distortion = None;
+        } else {
+            let time_source = self.time_source;
+            let brush = self.distortion.get_or_insert_with(|| {
+                let mut brush = DistortionBrush::new(ctx);
+                brush.set_time_source(time_source);
+                brush
+            });
+            brush.prepare(ctx, &params);
+            brush.animate_to(&ctx.queue, params, duration_ms);
+        }
+        self.release_unused_textures();
+    }
+
+    /// Clock the distortion animation reads, see [`TimeSource`].
+    pub fn set_time_source(&mut self, time_source: TimeSource) {
+        self.time_source = time_source;
+        if let Some(distortion) = self.distortion.as_mut() {
+            distortion.set_time_source(time_source);
+        }
+    }
+
+    /// Move a manual or fixed step time source forward by `by`.
+    pub fn advance_time(&mut self, by: std::time::Duration) {
+        self.time_source.advance(by);
+        if let Some(distortion) = self.distortion.as_mut() {
+            distortion.advance_time(by);
+        }
+    }
+
+    /// Pass `intensity = 0.0` to disable.
+    pub fn update_vignette(&mut self, ctx: &Context, params: VignetteParams) {
+        if params.intensity <= 0.0 {
+            self.vignette = None;
+        } else {
+            let brush = self.vignette.get_or_insert_with(|| VignetteBrush::new(ctx));
+            brush.update_params(&ctx.queue, params);
+        }
+        self.release_unused_textures();
+    }
+
+    #[inline]
+    fn release_unused_textures(&mut self) {
+        if !self.is_active() {
+            self.ping_pong = None;
+        }
+    }
+
+    /// Apply filters, distortion and vignette from `src` into `dst`. `src`
+    /// and `dst` may be the same texture; the frame is copied exactly once.
+    pub fn render(
+        &mut self,
+        ctx: &Context,
+        encoder: &mut wgpu::CommandEncoder,
+        src: &wgpu::Texture,
+        dst: &wgpu::Texture,
+    ) {
+        if let Some(filters) = self.filters.as_mut() {
+            filters.poll_pending(ctx);
+        }
+        if let Some(distortion) = self.distortion.as_mut() {
+            distortion.advance_animation(&ctx.queue);
+        }
+
+        let filters = self.filters.as_mut().filter(|f| f.has_filters());
+        let distortion = self.distortion.as_mut().filter(|d| d.is_active());
+        let vignette = self.vignette.as_ref().filter(|v| v.is_active());
+
+        let mut remaining = usize::from(filters.is_some())
+            + usize::from(distortion.is_some())
+            + usize::from(vignette.is_some());
+        if remaining == 0 {
+            return;
+        }
+
+        if !FiltersBrush::is_supported(ctx) {
+            return;
+        }
+
+        let size = src.size();
+        let format = src.format();
+        if !self
+            .ping_pong
+            .as_ref()
+            .is_some_and(|p| p.matches(size, format))
+        {
+            self.ping_pong = Some(PingPong::new(ctx, size, format));
+        }
+        let Some(ping_pong) = self.ping_pong.as_ref() else {
+            return;
+        };
+        let textures = &ping_pong.textures;
+        let mut current = 0;
+
+        encoder.copy_texture_to_texture(
+            src.as_image_copy(),
+            textures[current].as_image_copy(),
+            size,
+        );
+
+        if let Some(filters) = filters {
+            remaining -= 1;
+            let target = stage_target(textures, current, remaining, dst);
+            filters.render_from(ctx, encoder, textures[current].clone(), target);
+            current = 1 - current;
+        }
+
+        if let Some(distortion) = distortion {
+            remaining -= 1;
+            let target = stage_target(textures, current, remaining, dst);
+            distortion.render_from(ctx, encoder, &textures[current], target);
+            current = 1 - current;
+        }
+
+        if let Some(vignette) = vignette {
+            remaining -= 1;
+            let target = stage_target(textures, current, remaining, dst);
+            vignette.render_from(ctx, encoder, &textures[current], target);
+        }
+    }
+}
@@ -44,6 +44,10 @@ pub struct Quad {
 
     /// The shadow blur radius of the [`Quad`].
     pub shadow_blur_radius: f32,
+
+    /// Bottom color of a vertical gradient: the fill fades from `color`
+    /// at the top to this at the bottom. Transparent disables it.
+    pub gradient_color: [f32; 4],
 }
 
 #[derive(Debug)]
@@ -165,6 +169,8 @@ impl QuadBrush {
                                 7 => Float32x2,
                                 // Shadow blur radius
                                 8 => Float32,
+                                // Gradient color
+                                9 => Float32x4,
                             ),
                         }],
                     },
@@ -59,6 +59,7 @@ struct SolidVertexInput {
     @location(6) shadow_color: vec4<f32>,
     @location(7) shadow_offset: vec2<f32>,
     @location(8) shadow_blur_radius: f32,
+    @location(9) gradient_color: vec4<f32>,
 }
 
 struct SolidVertexOutput {
@@ -72,6 +73,7 @@ struct SolidVertexOutput {
     @location(6) shadow_color: vec4<f16>,
     @location(7) shadow_offset: vec2<f16>,
     @location(8) shadow_blur_radius: f16,
+    @location(9) gradient_color: vec4<f16>,
 }
 
 @vertex
@@ -115,6 +117,7 @@ fn composed_quad_vs_main(input: SolidVertexInput) -> SolidVertexOutput {
     out.shadow_color = vec4<f16>(input.shadow_color);
     out.shadow_offset = vec2<f16>(input.shadow_offset * globals.scale);
     out.shadow_blur_radius = f16(input.shadow_blur_radius * globals.scale);
+    out.gradient_color = vec4<f16>(input.gradient_color);
 
     return out;
 }
@@ -123,7 +126,13 @@ fn composed_quad_vs_main(input: SolidVertexInput) -> SolidVertexOutput {
 fn composed_quad_fs_main(
     input: SolidVertexOutput
 ) -> @location(0) vec4<f32> {
-    var mixed_color: vec4<f16> = input.color;
+    var fill_color: vec4<f16> = input.color;
+    if (input.gradient_color.a > f16(0.0)) {
+        var t: f16 = f16(clamp((input.position.y - f32(input.pos.y)) / max(f32(input.scale.y), 1.0), 0.0, 1.0));
+        fill_color = mix(input.color, input.gradient_color, vec4<f16>(t, t, t, t));
+    }
+
+    var mixed_color: vec4<f16> = fill_color;
 
     var border_radius = select_border_radius(
         vec4<f32>(input.border_radius),
@@ -147,7 +156,7 @@ fn composed_quad_fs_main(
             internal_distance
         ));
 
-        mixed_color = mix(input.color, input.border_color, vec4<f16>(border_mix, border_mix, border_mix, border_mix));
+        mixed_color = mix(fill_color, input.border_color, vec4<f16>(border_mix, border_mix, border_mix, border_mix));
     }
 
     var dist: f32 = distance_alg(
@@ -57,6 +57,7 @@ struct SolidVertexInput {
     @location(6) shadow_color: vec4<f32>,
     @location(7) shadow_offset: vec2<f32>,
     @location(8) shadow_blur_radius: f32,
+    @location(9) gradient_color: vec4<f32>,
 }
 
 struct SolidVertexOutput {
@@ -70,6 +71,7 @@ struct SolidVertexOutput {
     @location(6) shadow_color: vec4<f32>,
     @location(7) shadow_offset: vec2<f32>,
     @location(8) shadow_blur_radius: f32,
+    @location(9) gradient_color: vec4<f32>,
 }
 
 @vertex
@@ -113,6 +115,7 @@ fn composed_quad_vs_main(input: SolidVertexInput) -> SolidVertexOutput {
     out.shadow_color = input.shadow_color;
     out.shadow_offset = input.shadow_offset * globals.scale;
     out.shadow_blur_radius = input.shadow_blur_radius * globals.scale;
+    out.gradient_color = input.gradient_color;
 
     return out;
 }
@@ -121,7 +124,13 @@ fn composed_quad_vs_main(input: SolidVertexInput) -> SolidVertexOutput {
 fn composed_quad_fs_main(
     input: SolidVertexOutput
 ) -> @location(0) vec4<f32> {
-    var mixed_color: vec4<f32> = input.color;
+    var fill_color: vec4<f32> = input.color;
+    if (input.gradient_color.a > 0.0) {
+        var t: f32 = clamp((input.position.y - input.pos.y) / max(input.scale.y, 1.0), 0.0, 1.0);
+        fill_color = mix(input.color, input.gradient_color, vec4<f32>(t, t, t, t));
+    }
+
+    var mixed_color: vec4<f32> = fill_color;
 
     var border_radius = select_border_radius(
         input.border_radius,
@@ -145,7 +154,7 @@ fn composed_quad_fs_main(
             internal_distance
         );
 
-        mixed_color = mix(input.color, input.border_color, vec4<f32>(border_mix, border_mix, border_mix, border_mix));
+        mixed_color = mix(fill_color, input.border_color, vec4<f32>(border_mix, border_mix, border_mix, border_mix));
     }
 
     var dist: f32 = distance_alg(
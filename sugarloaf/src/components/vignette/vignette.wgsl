@@ -0,0 +1,56 @@
+struct VignetteParams {
+    intensity: f32,
+    radius: f32,
+    smoothness: f32,
+    _padding: f32,
+    color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) tex_coords: vec2<f32>,
+}
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var tex_sampler: sampler;
+@group(1) @binding(0) var<uniform> params: VignetteParams;
+
+// Full-screen triangle (same pattern as blit.wgsl)
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32
+) -> VertexOutput {
+    var out: VertexOutput;
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    let tc = vec2<f32>(f32(x) * 2.0, f32(y) * 2.0);
+    out.position = vec4<f32>(
+        tc.x * 2.0 - 1.0,
+        1.0 - tc.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.tex_coords = tc;
+    return out;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let src = textureSample(src_texture, tex_sampler, input.tex_coords);
+
+    // Distance from the center, 1.0 at the corners
+    let dist = distance(input.tex_coords, vec2<f32>(0.5, 0.5)) * 1.41421356;
+
+    // 0 inside the radius, fading to 1 over `smoothness`
+    // (smoothstep is undefined when both edges are equal)
+    let smoothness = max(params.smoothness, 0.0001);
+    let edge = smoothstep(
+        params.radius - smoothness, params.radius, dist,
+    );
+    let amount = clamp(edge * params.intensity * params.color.a, 0.0, 1.0);
+
+    // Multiplicative tint keeps alpha untouched, so transparent
+    // backgrounds stay transparent
+    let tint = mix(vec3<f32>(1.0), params.color.rgb, amount);
+    return vec4<f32>(src.rgb * tint, src.a);
+}
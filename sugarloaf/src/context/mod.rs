@@ -12,6 +12,9 @@ pub struct Context<'a> {
     pub adapter_info: wgpu::AdapterInfo,
     surface_caps: wgpu::SurfaceCapabilities,
     pub supports_f16: bool,
+    /// Whether `Rgba16Float` can be rendered to and filtered, needed for
+    /// high precision post-processing intermediates
+    pub supports_hdr_intermediate: bool,
     pub colorspace: Colorspace,
     pub max_texture_dimension_2d: u32,
 }
@@ -164,7 +167,13 @@ impl Context<'_> {
         );
 
         let (device, queue, supports_f16) = {
-            let base_features = wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER;
+            // Timestamp queries are only used to profile filters, so
+            // request them when the adapter has them without requiring them
+            let timestamp_features = adapter.features()
+                & (wgpu::Features::TIMESTAMP_QUERY
+                    | wgpu::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS);
+            let base_features =
+                wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER | timestamp_features;
             let base_f16_features = base_features | wgpu::Features::SHADER_F16;
 
             let device_configs = [(base_f16_features, true), (base_features, false)];
@@ -240,7 +249,16 @@ impl Context<'_> {
 
         let max_texture_dimension_2d = device.limits().max_texture_dimension_2d;
 
+        let hdr_features =
+            adapter.get_texture_format_features(wgpu::TextureFormat::Rgba16Float);
+        let supports_hdr_intermediate = hdr_features.allowed_usages.contains(
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        ) && hdr_features
+            .flags
+            .contains(wgpu::TextureFormatFeatureFlags::FILTERABLE);
+
         tracing::info!("F16 shader support: {}", supports_f16);
+        tracing::info!("HDR intermediate support: {}", supports_hdr_intermediate);
         tracing::info!("Configured colorspace: {:?}", renderer_config.colorspace);
         tracing::info!("Surface format: {:?}", format);
         tracing::info!("Max texture dimension 2D: {}", max_texture_dimension_2d);
@@ -259,6 +277,7 @@ impl Context<'_> {
             adapter_info,
             surface_caps,
             supports_f16,
+            supports_hdr_intermediate,
             colorspace: renderer_config.colorspace,
             max_texture_dimension_2d,
         }
@@ -20,10 +20,15 @@ pub use crate::sugarloaf::{
     SugarloafWindowSize, SugarloafWithErrors,
 };
 pub use components::distortion::{
-    DistortionParams, DISTORTION_BARREL, DISTORTION_NONE, DISTORTION_PERSPECTIVE,
+    DistortionParams, TimeSource, DISTORTION_BARREL, DISTORTION_CHROMATIC_ABERRATION,
+    DISTORTION_EDGE_BLACK, DISTORTION_EDGE_CLAMP, DISTORTION_EDGE_MIRROR,
+    DISTORTION_FILTER_LINEAR, DISTORTION_FILTER_NEAREST, DISTORTION_FISHEYE,
+    DISTORTION_NONE, DISTORTION_PERSPECTIVE, DISTORTION_PRECISION_AUTO,
+    DISTORTION_PRECISION_HIGH, DISTORTION_PRECISION_STANDARD, DISTORTION_VIGNETTE,
 };
-pub use components::filters::Filter;
+pub use components::filters::{available_builtin_filters, Filter, FilterFrameStat};
 pub use components::quad::Quad;
+pub use components::vignette::VignetteParams;
 pub use layout::{
     Content, FragmentStyle, FragmentStyleDecoration, SugarDimensions, UnderlineInfo,
     UnderlineShape,
@@ -3,11 +3,13 @@ pub mod primitives;
 pub mod state;
 
 use crate::components::core::{image::Handle, shapes::Rectangle};
-use crate::components::distortion::{DistortionBrush, DistortionParams};
-use crate::components::filters::{Filter, FiltersBrush};
+use crate::components::distortion::{DistortionParams, TimeSource};
+use crate::components::filters::Filter;
 use crate::components::layer::{self, LayerBrush};
+use crate::components::post_process::PostProcessChain;
 use crate::components::quad::QuadBrush;
 use crate::components::rich_text::RichTextBrush;
+use crate::components::vignette::VignetteParams;
 use crate::font::{fonts::SugarloafFont, FontLibrary};
 use crate::layout::{RichTextLayout, RootStyle};
 use crate::sugarloaf::graphics::{BottomLayer, Graphics};
@@ -31,8 +33,7 @@ pub struct Sugarloaf<'a> {
     pub background_color: Option<wgpu::Color>,
     pub background_image: Option<ImageProperties>,
     pub graphics: Graphics,
-    filters_brush: Option<FiltersBrush>,
-    distortion_brush: Option<DistortionBrush>,
+    post_process: PostProcessChain,
 }
 
 #[derive(Debug)]
@@ -161,8 +162,7 @@ impl Sugarloaf<'_> {
             background_image: None,
             rich_text_brush,
             graphics: Graphics::default(),
-            filters_brush: None,
-            distortion_brush: None,
+            post_process: PostProcessChain::default(),
         };
 
         Ok(instance)
@@ -234,37 +234,66 @@ impl Sugarloaf<'_> {
         self.state.set_rich_text_line_height(rt_id, line_height);
     }
 
+    /// Restore alpha after the filters as premultiplied colors, for
+    /// compositors expecting `rgba = color * alpha`.
     #[inline]
-    pub fn update_filters(&mut self, filters: &[Filter]) {
-        if filters.is_empty() {
-            self.filters_brush = None;
-        } else {
-            if self.filters_brush.is_none() {
-                self.filters_brush = Some(FiltersBrush::default());
-            }
-            if let Some(ref mut brush) = self.filters_brush {
-                brush.update_filters(&self.ctx, filters);
-            }
-        }
+    pub fn set_premultiplied_alpha(&mut self, premultiplied: bool) {
+        self.post_process.set_premultiplied_alpha(premultiplied);
+    }
+
+    #[inline]
+    pub fn update_filters(&mut self, filters: &[Filter], restore_alpha: bool) {
+        self.post_process
+            .update_filters(&self.ctx, filters, restore_alpha);
+    }
+
+    /// Whether filters passed to `update_filters` are still loading. They
+    /// show up on the first render after they're ready, so keep rendering
+    /// until this is false.
+    #[inline]
+    pub fn has_pending_filters(&self) -> bool {
+        self.post_process.is_loading()
     }
 
     /// Enable or disable distortion with the given parameters.
     /// Pass `distortion_type = 0` (DISTORTION_NONE) to disable.
     #[inline]
     pub fn update_distortion(&mut self, params: DistortionParams) {
-        use crate::components::distortion::DISTORTION_NONE;
-        if params.distortion_type == DISTORTION_NONE {
-            self.distortion_brush = None;
-            return;
-        }
+        self.post_process.update_distortion(&self.ctx, params);
+    }
 
-        if self.distortion_brush.is_none() {
-            self.distortion_brush = Some(DistortionBrush::new(&self.ctx));
-        }
+    /// Like `update_distortion`, easing into `params` over `duration_ms`.
+    #[inline]
+    pub fn animate_distortion(&mut self, params: DistortionParams, duration_ms: u32) {
+        self.post_process
+            .animate_distortion(&self.ctx, params, duration_ms);
+    }
 
-        if let Some(ref mut brush) = self.distortion_brush {
-            brush.update_params(&self.ctx.queue, params);
-        }
+    /// Clock the distortion animation reads. A manual or fixed step
+    /// source makes recorded frames reproducible.
+    #[inline]
+    pub fn set_distortion_time_source(&mut self, time_source: TimeSource) {
+        self.post_process.set_time_source(time_source);
+    }
+
+    /// Move a manual or fixed step distortion clock forward by `by`.
+    #[inline]
+    pub fn advance_distortion_time(&mut self, by: std::time::Duration) {
+        self.post_process.advance_time(by);
+    }
+
+    /// Whether the distortion is still easing in. Its frames only advance
+    /// on render, so keep rendering until this is false.
+    #[inline]
+    pub fn is_distortion_animating(&self) -> bool {
+        self.post_process.is_animating()
+    }
+
+    /// Enable or disable the vignette with the given parameters.
+    /// Pass `intensity = 0.0` to disable.
+    #[inline]
+    pub fn update_vignette(&mut self, params: VignetteParams) {
+        self.post_process.update_vignette(&self.ctx, params);
     }
 
     #[inline]
@@ -291,6 +320,36 @@ impl Sugarloaf<'_> {
         self
     }
 
+    /// Fill the window with RGBA `pixels`, stretched to the window size.
+    #[inline]
+    pub fn set_background_pixels(
+        &mut self,
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    ) -> &mut Self {
+        self.graphics.bottom_layer = Some(BottomLayer {
+            should_fit: true,
+            data: types::Raster {
+                handle: Handle::from_pixels(width, height, pixels),
+                bounds: Rectangle {
+                    width: self.ctx.size.width,
+                    height: self.ctx.size.height,
+                    x: 0.0,
+                    y: 0.0,
+                },
+            },
+        });
+        self
+    }
+
+    /// Remove the background image or pixels.
+    #[inline]
+    pub fn clear_background_layer(&mut self) -> &mut Self {
+        self.graphics.bottom_layer = None;
+        self
+    }
+
     #[inline]
     pub fn create_rich_text(&mut self) -> usize {
         self.state.create_rich_text()
@@ -528,23 +587,13 @@ impl Sugarloaf<'_> {
                     self.graphics.clear_top_layer();
                 }
 
-                if let Some(ref distortion_brush) = self.distortion_brush {
-                    distortion_brush.render(
-                        &self.ctx,
-                        &mut encoder,
-                        &frame.texture,
-                        &frame.texture,
-                    );
-                }
-
-                if let Some(ref mut filters_brush) = self.filters_brush {
-                    filters_brush.render(
-                        &self.ctx,
-                        &mut encoder,
-                        &frame.texture,
-                        &frame.texture,
-                    );
-                }
+                // Filters, distortion and vignette run in order, sharing one frame copy.
+                self.post_process.render(
+                    &self.ctx,
+                    &mut encoder,
+                    &frame.texture,
+                    &frame.texture,
+                );
                 self.ctx.queue.submit(Some(encoder.finish()));
                 frame.present();
             }
@@ -385,6 +385,15 @@ impl ShellUser {
     }
 }
 
+/// Login shell of the current user, from `$SHELL` or the passwd entry.
+/// This is what an empty shell program spawns.
+pub fn login_shell() -> Option<String> {
+    ShellUser::from_env()
+        .ok()
+        .map(|user| user.shell)
+        .filter(|shell| !shell.is_empty())
+}
+
 ///
 /// Creates a pseudoterminal using spawn.
 ///
@@ -910,11 +919,19 @@ pub fn tty_ptsname(fd: libc::c_int) -> Result<String, String> {
     Ok(str_buf)
 }
 
-pub fn foreground_process_name(main_fd: RawFd, shell_pid: u32) -> String {
-    let mut pid = unsafe { libc::tcgetpgrp(main_fd) };
+/// Process group of the terminal's foreground job, the shell when it
+/// can't be queried.
+pub fn foreground_pid(main_fd: RawFd, shell_pid: u32) -> u32 {
+    let pid = unsafe { libc::tcgetpgrp(main_fd) };
     if pid < 0 {
-        pid = shell_pid as libc::pid_t;
+        shell_pid
+    } else {
+        pid as u32
     }
+}
+
+pub fn foreground_process_name(main_fd: RawFd, shell_pid: u32) -> String {
+    let pid = foreground_pid(main_fd, shell_pid) as libc::pid_t;
 
     #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
     let comm_path = format!("/proc/{pid}/comm");
@@ -940,10 +957,7 @@ pub fn foreground_process_path(
     main_fd: RawFd,
     shell_pid: u32,
 ) -> Result<PathBuf, Box<dyn std::error::Error>> {
-    let mut pid = unsafe { libc::tcgetpgrp(main_fd) };
-    if pid < 0 {
-        pid = shell_pid as libc::pid_t;
-    }
+    let pid = foreground_pid(main_fd, shell_pid) as libc::pid_t;
 
     #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
     let link_path = format!("/proc/{pid}/cwd");